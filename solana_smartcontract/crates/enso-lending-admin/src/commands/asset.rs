@@ -0,0 +1,146 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use enso_lending_client::{args, instructions, pda, states::Asset};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{json, runner::Runner};
+
+#[derive(Subcommand)]
+pub enum AssetCommand {
+    /// Register a mint as a lend and/or collateral asset
+    Init(InitAsset),
+    /// Edit an asset, omitted values are left unchanged
+    Edit(EditAsset),
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    List,
+}
+
+#[derive(Args)]
+pub struct InitAsset {
+    #[arg(long)]
+    mint: Pubkey,
+    #[arg(long)]
+    name: String,
+    /// Pyth price update account
+    #[arg(long)]
+    price_feed_account: Pubkey,
+    #[arg(long)]
+    price_feed_id: String,
+    #[arg(long)]
+    max_price_age_seconds: u64,
+    #[arg(long)]
+    is_lend: bool,
+    #[arg(long)]
+    is_collateral: bool,
+    /// Token address on the foreign chain for cross-chain collateral
+    #[arg(long)]
+    token_address: Option<String>,
+    #[arg(long, default_value_t = 1)]
+    chain_id: u16,
+    /// Protocol wide collateral cap, 0 for no cap
+    #[arg(long, default_value_t = 0)]
+    max_collateral_amount: u64,
+    /// Protocol wide borrow cap, 0 for no cap
+    #[arg(long, default_value_t = 0)]
+    max_borrow_amount: u64,
+    #[arg(long)]
+    isolated: bool,
+    #[arg(long = "isolated-lend-mint")]
+    isolated_lend_mints: Vec<Pubkey>,
+    #[arg(long, default_value_t = 0)]
+    isolated_debt_ceiling: u64,
+}
+
+#[derive(Args)]
+pub struct EditAsset {
+    #[arg(long)]
+    mint: Pubkey,
+    #[arg(long)]
+    name: Option<String>,
+    /// Required together with --price-feed-id
+    #[arg(long, requires = "price_feed_id")]
+    price_feed_account: Option<Pubkey>,
+    #[arg(long, requires = "price_feed_account")]
+    price_feed_id: Option<String>,
+    #[arg(long)]
+    max_price_age_seconds: Option<u64>,
+    #[arg(long)]
+    is_lend: Option<bool>,
+    #[arg(long)]
+    is_collateral: Option<bool>,
+    #[arg(long)]
+    token_address: Option<String>,
+    #[arg(long)]
+    max_collateral_amount: Option<u64>,
+    #[arg(long)]
+    max_borrow_amount: Option<u64>,
+    #[arg(long)]
+    isolated: Option<bool>,
+    /// Replaces the whole isolated lend mint list
+    #[arg(long = "isolated-lend-mint")]
+    isolated_lend_mints: Option<Vec<Pubkey>>,
+    #[arg(long)]
+    isolated_debt_ceiling: Option<u64>,
+}
+
+impl AssetCommand {
+    pub fn run(self, runner: &Runner) -> Result<()> {
+        match self {
+            Self::Init(asset) => runner.execute(&[instructions::init_asset(
+                asset.mint,
+                asset.price_feed_account,
+                args::InitAsset {
+                    name: asset.name,
+                    is_lend: asset.is_lend,
+                    is_collateral: asset.is_collateral,
+                    price_feed_id: asset.price_feed_id,
+                    max_price_age_seconds: asset.max_price_age_seconds,
+                    token_address: asset.token_address,
+                    chain_id: asset.chain_id,
+                    max_collateral_amount: asset.max_collateral_amount,
+                    max_borrow_amount: asset.max_borrow_amount,
+                    isolated: asset.isolated,
+                    isolated_lend_mints: asset.isolated_lend_mints,
+                    isolated_debt_ceiling: asset.isolated_debt_ceiling,
+                },
+            )]),
+            Self::Edit(asset) => runner.execute(&[instructions::edit_asset(
+                asset.mint,
+                asset.price_feed_account,
+                args::EditAsset {
+                    name: asset.name,
+                    is_lend: asset.is_lend,
+                    is_collateral: asset.is_collateral,
+                    price_feed_id: asset.price_feed_id,
+                    max_price_age_seconds: asset.max_price_age_seconds,
+                    token_address: asset.token_address,
+                    max_collateral_amount: asset.max_collateral_amount,
+                    max_borrow_amount: asset.max_borrow_amount,
+                    isolated: asset.isolated,
+                    isolated_lend_mints: asset.isolated_lend_mints,
+                    isolated_debt_ceiling: asset.isolated_debt_ceiling,
+                },
+            )]),
+            Self::Show { mint } => {
+                let address = pda::asset(&mint).0;
+                json::print(&json::keyed(&address, json::asset(&runner.fetch(&address)?)));
+
+                Ok(())
+            }
+            Self::List => {
+                let assets: Vec<_> = runner
+                    .rpc()
+                    .get_accounts::<Asset>()?
+                    .iter()
+                    .map(|(address, asset)| json::keyed(address, json::asset(asset)))
+                    .collect();
+                json::print(&assets.into());
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use enso_lending_client::EnsoAccount;
+use serde_json::{json, Value};
+
+use crate::{json, runner::Runner};
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// File to write, stdout when omitted
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+impl ExportArgs {
+    /// Every program account grouped by type, accounts with an unknown or outdated layout are
+    /// listed by address under `undecoded`.
+    pub fn run(self, runner: &Runner) -> Result<()> {
+        let slot = runner.rpc().get_slot()?;
+        let mut accounts: BTreeMap<&'static str, Vec<Value>> = BTreeMap::new();
+        let mut undecoded = Vec::new();
+
+        for (address, data) in runner.rpc().get_program_accounts(&[])? {
+            match EnsoAccount::decode(&data) {
+                Ok(account) => accounts
+                    .entry(account.name())
+                    .or_default()
+                    .push(json::keyed(&address, json::enso_account(&account))),
+                Err(_) => undecoded.push(address.to_string()),
+            }
+        }
+
+        let export = json!({
+            "program_id": enso_lending_client::ID.to_string(),
+            "slot": slot,
+            "accounts": accounts,
+            "undecoded": undecoded,
+        });
+        let encoded = serde_json::to_string_pretty(&export)?;
+
+        match self.file {
+            Some(path) => fs::write(&path, encoded).with_context(|| format!("failed to write {}", path.display())),
+            None => {
+                println!("{encoded}");
+
+                Ok(())
+            }
+        }
+    }
+}
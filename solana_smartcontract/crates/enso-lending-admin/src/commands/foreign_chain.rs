@@ -0,0 +1,54 @@
+use anyhow::Result;
+use clap::Subcommand;
+use enso_lending_client::{args, instructions, pda, states::ForeignChain};
+
+use crate::{json, runner::Runner};
+
+#[derive(Subcommand)]
+pub enum ForeignChainCommand {
+    /// Register the enso contract and Wormhole emitter of a foreign chain
+    Init {
+        /// Wormhole chain id
+        #[arg(long)]
+        chain_id: u16,
+        #[arg(long)]
+        chain_address: String,
+        #[arg(long)]
+        emitter_address: String,
+    },
+    Show {
+        #[arg(long)]
+        chain_id: u16,
+    },
+    List,
+}
+
+impl ForeignChainCommand {
+    pub fn run(self, runner: &Runner) -> Result<()> {
+        match self {
+            Self::Init { chain_id, chain_address, emitter_address } => {
+                runner.execute(&[instructions::init_foreign_chain_account(
+                    runner.authority(),
+                    args::InitForeignChainAccount { chain_id, chain_address, emitter_address },
+                )])
+            }
+            Self::Show { chain_id } => {
+                let address = pda::foreign_chain(chain_id).0;
+                json::print(&json::keyed(&address, json::foreign_chain(&runner.fetch(&address)?)));
+
+                Ok(())
+            }
+            Self::List => {
+                let chains: Vec<_> = runner
+                    .rpc()
+                    .get_accounts::<ForeignChain>()?
+                    .iter()
+                    .map(|(address, chain)| json::keyed(address, json::foreign_chain(chain)))
+                    .collect();
+                json::print(&chains.into());
+
+                Ok(())
+            }
+        }
+    }
+}
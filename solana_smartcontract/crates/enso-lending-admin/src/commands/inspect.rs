@@ -0,0 +1,135 @@
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use enso_lending_client::{
+    common::{LendOfferStatus, LoanOfferStatus},
+    pda,
+    states::{LendOfferAccount, LoanOfferAccount},
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{json, runner::Runner};
+
+const LOAN_OFFER_STATUSES: [LoanOfferStatus; 7] = [
+    LoanOfferStatus::Matched,
+    LoanOfferStatus::FundTransferred,
+    LoanOfferStatus::Repay,
+    LoanOfferStatus::BorrowerPaid,
+    LoanOfferStatus::Liquidating,
+    LoanOfferStatus::Liquidated,
+    LoanOfferStatus::Finished,
+];
+
+const LEND_OFFER_STATUSES: [LendOfferStatus; 4] = [
+    LendOfferStatus::Created,
+    LendOfferStatus::Canceling,
+    LendOfferStatus::Canceled,
+    LendOfferStatus::Loaned,
+];
+
+fn parse_status<T: Copy + std::fmt::Debug>(statuses: &[T], value: &str) -> Result<T> {
+    statuses
+        .iter()
+        .find(|status| format!("{status:?}").eq_ignore_ascii_case(value))
+        .copied()
+        .ok_or_else(|| anyhow!("unknown status {value}, expected one of {statuses:?}"))
+}
+
+fn parse_loan_offer_status(value: &str) -> Result<LoanOfferStatus> {
+    parse_status(&LOAN_OFFER_STATUSES, value)
+}
+
+fn parse_lend_offer_status(value: &str) -> Result<LendOfferStatus> {
+    parse_status(&LEND_OFFER_STATUSES, value)
+}
+
+#[derive(Subcommand)]
+pub enum LoanCommand {
+    List {
+        #[arg(long, value_parser = parse_loan_offer_status)]
+        status: Option<LoanOfferStatus>,
+        #[arg(long)]
+        borrower: Option<Pubkey>,
+        #[arg(long)]
+        lender: Option<Pubkey>,
+        #[arg(long)]
+        tier_id: Option<String>,
+    },
+    Show {
+        #[arg(long)]
+        borrower: Pubkey,
+        #[arg(long)]
+        offer_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LendOfferCommand {
+    List {
+        #[arg(long, value_parser = parse_lend_offer_status)]
+        status: Option<LendOfferStatus>,
+        #[arg(long)]
+        lender: Option<Pubkey>,
+    },
+    Show {
+        #[arg(long)]
+        lender: Pubkey,
+        #[arg(long)]
+        offer_id: String,
+    },
+}
+
+impl LoanCommand {
+    pub fn run(self, runner: &Runner) -> Result<()> {
+        match self {
+            // Note: status follows variable length strings in the account, so it is filtered here
+            // rather than with a getProgramAccounts memcmp
+            Self::List { status, borrower, lender, tier_id } => {
+                let loans: Vec<_> = runner
+                    .rpc()
+                    .get_accounts::<LoanOfferAccount>()?
+                    .iter()
+                    .filter(|(_, loan)| status.is_none_or(|status| loan.status == status))
+                    .filter(|(_, loan)| borrower.is_none_or(|borrower| loan.borrower == borrower))
+                    .filter(|(_, loan)| lender.is_none_or(|lender| loan.lender == lender))
+                    .filter(|(_, loan)| tier_id.as_ref().is_none_or(|tier_id| &loan.tier_id == tier_id))
+                    .map(|(address, loan)| json::keyed(address, json::loan_offer(loan)))
+                    .collect();
+                json::print(&loans.into());
+
+                Ok(())
+            }
+            Self::Show { borrower, offer_id } => {
+                let address = pda::loan_offer(&borrower, &offer_id).0;
+                json::print(&json::keyed(&address, json::loan_offer(&runner.fetch(&address)?)));
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl LendOfferCommand {
+    pub fn run(self, runner: &Runner) -> Result<()> {
+        match self {
+            Self::List { status, lender } => {
+                let offers: Vec<_> = runner
+                    .rpc()
+                    .get_accounts::<LendOfferAccount>()?
+                    .iter()
+                    .filter(|(_, offer)| status.is_none_or(|status| offer.status == status))
+                    .filter(|(_, offer)| lender.is_none_or(|lender| offer.lender == lender))
+                    .map(|(address, offer)| json::keyed(address, json::lend_offer(offer)))
+                    .collect();
+                json::print(&offers.into());
+
+                Ok(())
+            }
+            Self::Show { lender, offer_id } => {
+                let address = pda::lend_offer(&lender, &offer_id).0;
+                json::print(&json::keyed(&address, json::lend_offer(&runner.fetch(&address)?)));
+
+                Ok(())
+            }
+        }
+    }
+}
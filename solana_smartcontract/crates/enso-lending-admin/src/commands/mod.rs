@@ -0,0 +1,15 @@
+pub mod tier;
+
+pub mod asset;
+
+pub mod treasury;
+
+pub mod foreign_chain;
+
+pub mod wormhole;
+
+pub mod inspect;
+
+pub mod export;
+
+pub mod transaction;
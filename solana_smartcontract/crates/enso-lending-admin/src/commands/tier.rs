@@ -0,0 +1,160 @@
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+use enso_lending_client::{args, instructions, pda, states::SettingAccount};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{json, runner::Runner};
+
+#[derive(Subcommand)]
+pub enum TierCommand {
+    /// Create a tier setting account
+    Init(InitTier),
+    /// Edit a tier, omitted values are left unchanged
+    Edit(EditTier),
+    /// Close a tier setting account
+    Close {
+        #[arg(long)]
+        tier_id: String,
+    },
+    Show {
+        #[arg(long)]
+        tier_id: String,
+    },
+    List,
+}
+
+#[derive(Args)]
+pub struct InitTier {
+    #[arg(long)]
+    tier_id: String,
+    /// Hot wallet receiving lend offer principal
+    #[arg(long)]
+    receiver: Pubkey,
+    /// Fixed lend amount in whole tokens, used when --max-amount is 0
+    #[arg(long)]
+    amount: u64,
+    #[arg(long)]
+    duration: u64,
+    #[arg(long)]
+    lender_fee_percent: f64,
+    #[arg(long)]
+    borrower_fee_percent: f64,
+    #[arg(long)]
+    lend_mint: Pubkey,
+    #[arg(long = "collateral-mint")]
+    collateral_mints: Vec<Pubkey>,
+    #[arg(long, default_value_t = 0)]
+    min_amount: u64,
+    #[arg(long, default_value_t = 0)]
+    max_amount: u64,
+    #[arg(long, default_value_t = 0)]
+    amount_step: u64,
+    #[arg(long, default_value_t = 0)]
+    min_duration: u64,
+    #[arg(long, default_value_t = 0)]
+    max_duration: u64,
+}
+
+#[derive(Args)]
+pub struct EditTier {
+    #[arg(long)]
+    tier_id: String,
+    /// New hot wallet, defaults to the current one
+    #[arg(long)]
+    receiver: Option<Pubkey>,
+    #[arg(long)]
+    amount: Option<u64>,
+    #[arg(long)]
+    duration: Option<u64>,
+    #[arg(long)]
+    lender_fee_percent: Option<f64>,
+    #[arg(long)]
+    borrower_fee_percent: Option<f64>,
+    #[arg(long)]
+    lend_mint: Option<Pubkey>,
+    /// Replaces the whole collateral mint list
+    #[arg(long = "collateral-mint")]
+    collateral_mints: Option<Vec<Pubkey>>,
+    #[arg(long)]
+    min_amount: Option<u64>,
+    #[arg(long)]
+    max_amount: Option<u64>,
+    #[arg(long)]
+    amount_step: Option<u64>,
+    #[arg(long)]
+    min_duration: Option<u64>,
+    #[arg(long)]
+    max_duration: Option<u64>,
+}
+
+impl TierCommand {
+    pub fn run(self, runner: &Runner) -> Result<()> {
+        match self {
+            Self::Init(tier) => runner.execute(&[instructions::init_setting_account(
+                runner.authority(),
+                tier.receiver,
+                args::InitSettingAccount {
+                    tier_id: tier.tier_id,
+                    amount: tier.amount,
+                    duration: tier.duration,
+                    lender_fee_percent: tier.lender_fee_percent,
+                    borrower_fee_percent: tier.borrower_fee_percent,
+                    lend_mint_asset: tier.lend_mint,
+                    collateral_mint_assets: tier.collateral_mints,
+                    min_amount: tier.min_amount,
+                    max_amount: tier.max_amount,
+                    amount_step: tier.amount_step,
+                    min_duration: tier.min_duration,
+                    max_duration: tier.max_duration,
+                },
+            )]),
+            Self::Edit(tier) => {
+                let receiver = match tier.receiver {
+                    Some(receiver) => receiver,
+                    None if runner.is_offline() => bail!("--receiver is required with --sign-only"),
+                    None => runner.fetch::<SettingAccount>(&pda::setting_account(&tier.tier_id).0)?.receiver,
+                };
+
+                runner.execute(&[instructions::edit_setting_account(
+                    runner.authority(),
+                    receiver,
+                    args::EditSettingAccount {
+                        _tier_id: tier.tier_id,
+                        amount: tier.amount,
+                        duration: tier.duration,
+                        lender_fee_percent: tier.lender_fee_percent,
+                        borrower_fee_percent: tier.borrower_fee_percent,
+                        lend_mint_asset: tier.lend_mint,
+                        collateral_mint_assets: tier.collateral_mints,
+                        min_amount: tier.min_amount,
+                        max_amount: tier.max_amount,
+                        amount_step: tier.amount_step,
+                        min_duration: tier.min_duration,
+                        max_duration: tier.max_duration,
+                    },
+                )])
+            }
+            Self::Close { tier_id } => runner.execute(&[instructions::close_setting_account(
+                runner.authority(),
+                args::CloseSettingAccount { tier_id },
+            )]),
+            Self::Show { tier_id } => {
+                let address = pda::setting_account(&tier_id).0;
+                json::print(&json::keyed(&address, json::setting(&runner.fetch(&address)?)));
+
+                Ok(())
+            }
+            Self::List => {
+                let tiers: Vec<_> = runner
+                    .rpc()
+                    .get_accounts::<SettingAccount>()?
+                    .iter()
+                    .map(|(address, tier)| json::keyed(address, json::setting(tier)))
+                    .collect();
+                json::print(&tiers.into());
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use enso_lending_client::rpc::{decode_transaction, encode_transaction};
+use solana_sdk::transaction::Transaction;
+
+use crate::runner::{missing_signers, Runner};
+
+#[derive(Subcommand)]
+pub enum TransactionCommand {
+    /// Add the --keypair signature to a transaction file, in place
+    Sign { file: PathBuf },
+    /// Print the fee payer, signers and instructions of a transaction file
+    Inspect { file: PathBuf },
+    /// Simulate a transaction file
+    Simulate { file: PathBuf },
+    /// Send a fully signed transaction file
+    Submit { file: PathBuf },
+}
+
+fn read(file: &PathBuf) -> Result<Transaction> {
+    let encoded = fs::read_to_string(file).with_context(|| format!("failed to read {}", file.display()))?;
+
+    Ok(decode_transaction(&encoded)?)
+}
+
+impl TransactionCommand {
+    pub fn run(self, runner: &Runner) -> Result<()> {
+        match self {
+            Self::Sign { file } => {
+                let Some(keypair) = runner.keypair() else {
+                    bail!("sign requires --keypair");
+                };
+                let mut transaction = read(&file)?;
+                let blockhash = transaction.message.recent_blockhash;
+                transaction.try_partial_sign(&[keypair], blockhash)?;
+                fs::write(&file, format!("{}\n", encode_transaction(&transaction)?))?;
+
+                let missing = missing_signers(&transaction);
+                if missing.is_empty() {
+                    println!("{} is fully signed", file.display());
+                } else {
+                    println!("{} still needs {missing:?}", file.display());
+                }
+
+                Ok(())
+            }
+            Self::Inspect { file } => {
+                let transaction = read(&file)?;
+                let message = &transaction.message;
+
+                println!("fee payer: {}", message.account_keys[0]);
+                println!("recent blockhash: {}", message.recent_blockhash);
+                println!("missing signatures: {:?}", missing_signers(&transaction));
+                for instruction in &message.instructions {
+                    println!(
+                        "instruction: program {} with {} accounts, {} bytes of data",
+                        message.account_keys[instruction.program_id_index as usize],
+                        instruction.accounts.len(),
+                        instruction.data.len()
+                    );
+                }
+
+                Ok(())
+            }
+            Self::Simulate { file } => runner.simulate(&read(&file)?),
+            Self::Submit { file } => {
+                let transaction = read(&file)?;
+                let missing = missing_signers(&transaction);
+                if !missing.is_empty() {
+                    bail!("transaction still needs {missing:?}");
+                }
+
+                println!("{}", runner.send_and_confirm(&transaction)?);
+
+                Ok(())
+            }
+        }
+    }
+}
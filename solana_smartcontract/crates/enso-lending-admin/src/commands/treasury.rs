@@ -0,0 +1,66 @@
+use anyhow::Result;
+use clap::Subcommand;
+use enso_lending_client::{args, instructions, pda, states::Treasury};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{json, runner::Runner};
+
+#[derive(Subcommand)]
+pub enum TreasuryCommand {
+    /// Create the fee treasury of a lend mint and its token account
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    /// Withdraw collected fees of a lend mint
+    Withdraw {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Token account receiving the fees, defaults to the authority ATA
+        #[arg(long)]
+        receiver: Option<Pubkey>,
+    },
+    /// Show the fees a treasury collected by source and its token account
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    List,
+}
+
+impl TreasuryCommand {
+    pub fn run(self, runner: &Runner) -> Result<()> {
+        match self {
+            Self::Init { mint } => runner.execute(&[instructions::init_treasury(mint)]),
+            Self::Withdraw { mint, amount, receiver } => {
+                let receiver = receiver.unwrap_or_else(|| pda::ata(&runner.authority(), &mint));
+
+                runner.execute(&[instructions::withdraw_treasury(mint, receiver, args::WithdrawTreasury { amount })])
+            }
+            Self::Show { mint } => {
+                let address = pda::treasury(&mint).0;
+
+                json::print(&json!({
+                    "treasury": json::keyed(&address, json::treasury(&runner.fetch(&address)?)),
+                    "treasury_ata": pda::treasury_ata(&mint).to_string(),
+                }));
+
+                Ok(())
+            }
+            Self::List => {
+                let treasuries: Vec<_> = runner
+                    .rpc()
+                    .get_accounts::<Treasury>()?
+                    .iter()
+                    .map(|(address, treasury)| json::keyed(address, json::treasury(treasury)))
+                    .collect();
+                json::print(&treasuries.into());
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use clap::Subcommand;
+use enso_lending_client::{instructions, pda, states::WormholeConfig};
+use serde_json::json;
+
+use crate::{json, runner::Runner};
+
+#[derive(Subcommand)]
+pub enum WormholeCommand {
+    /// Create the Wormhole config and emitter accounts
+    Init,
+    /// Show the Wormhole config, emitter and next message sequence
+    Show,
+}
+
+impl WormholeCommand {
+    pub fn run(self, runner: &Runner) -> Result<()> {
+        match self {
+            Self::Init => runner.execute(&[instructions::init_wormhole()]),
+            Self::Show => {
+                let config = pda::wormhole_config().0;
+                let emitter = pda::wormhole_emitter().0;
+
+                json::print(&json!({
+                    "config": json::keyed(&config, json::wormhole_config(&runner.fetch::<WormholeConfig>(&config)?)),
+                    "emitter": emitter.to_string(),
+                    "next_sequence": runner.rpc().get_next_wormhole_sequence()?,
+                }));
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! JSON views of program accounts, the program types do not implement serde.
+
+use enso_lending_client::{
+    states::{
+        Asset, ForeignChain, LendOfferAccount, LoanOfferAccount, LoanPositionListing, SettingAccount, Treasury,
+        VaultAuthority, WormholeConfig, WormholeEmitter,
+    },
+    EnsoAccount,
+};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+fn pubkeys(keys: &[Pubkey]) -> Vec<String> {
+    keys.iter().map(Pubkey::to_string).collect()
+}
+
+pub fn loan_offer(account: &LoanOfferAccount) -> Value {
+    json!({
+        "offer_id": account.offer_id,
+        "tier_id": account.tier_id,
+        "lend_offer_id": account.lend_offer_id,
+        "status": format!("{:?}", account.status),
+        "borrower": account.borrower.to_string(),
+        "lender": account.lender.to_string(),
+        "lend_mint_token": account.lend_mint_token.to_string(),
+        "borrow_amount": account.borrow_amount,
+        "collateral_mint_token": account.collateral_mint_token.to_string(),
+        "collateral_amount": account.collateral_amount,
+        "request_withdraw_amount": account.request_withdraw_amount,
+        "interest": account.interest,
+        "lender_fee_percent": account.lender_fee_percent,
+        "borrower_fee_percent": account.borrower_fee_percent,
+        "duration": account.duration,
+        "started_at": account.started_at,
+        "liquidating_at": account.liquidating_at,
+        "liquidating_price": account.liquidating_price,
+        "liquidated_tx": account.liquidated_tx,
+        "liquidated_price": account.liquidated_price,
+        "position_mint": account.position_mint.map(|mint| mint.to_string()),
+        "rent_payer": account.rent_payer.to_string(),
+        "version": account.version,
+    })
+}
+
+pub fn lend_offer(account: &LendOfferAccount) -> Value {
+    json!({
+        "offer_id": account.offer_id,
+        "status": format!("{:?}", account.status),
+        "lender": account.lender.to_string(),
+        "lend_mint_token": account.lend_mint_token.to_string(),
+        "amount": account.amount,
+        "interest": account.interest,
+        "lender_fee_percent": account.lender_fee_percent,
+        "duration": account.duration,
+        "expires_at": account.expires_at,
+        "crank_tip": account.crank_tip,
+        "tier_id": account.tier_id,
+        "version": account.version,
+    })
+}
+
+pub fn asset(account: &Asset) -> Value {
+    json!({
+        "name": account.name,
+        "token_mint": account.token_mint.to_string(),
+        "decimals": account.decimals,
+        "is_lend": account.is_lend,
+        "is_collateral": account.is_collateral,
+        "price_feed_id": account.price_feed_id,
+        "price_feed_account": account.price_feed_account.to_string(),
+        "max_price_age_seconds": account.max_price_age_seconds,
+        "token_address": account.token_address,
+        "chain_id": account.chain_id,
+        "total_collateral_amount": account.total_collateral_amount,
+        "max_collateral_amount": account.max_collateral_amount,
+        "total_borrow_amount": account.total_borrow_amount,
+        "max_borrow_amount": account.max_borrow_amount,
+        "isolated": account.isolated,
+        "isolated_lend_mints": pubkeys(&account.isolated_lend_mints),
+        "isolated_debt_ceiling": account.isolated_debt_ceiling,
+        "total_isolated_debt": account.total_isolated_debt,
+        "version": account.version,
+    })
+}
+
+pub fn setting(account: &SettingAccount) -> Value {
+    json!({
+        "tier_id": account.tier_id,
+        "owner": account.owner.to_string(),
+        "receiver": account.receiver.to_string(),
+        "amount": account.amount,
+        "duration": account.duration,
+        "lender_fee_percent": account.lender_fee_percent,
+        "borrower_fee_percent": account.borrower_fee_percent,
+        "lend_mint_asset": account.lend_mint_asset.to_string(),
+        "collateral_mint_assets": pubkeys(&account.collateral_mint_assets),
+        "min_amount": account.min_amount,
+        "max_amount": account.max_amount,
+        "amount_step": account.amount_step,
+        "min_duration": account.min_duration,
+        "max_duration": account.max_duration,
+        "version": account.version,
+    })
+}
+
+pub fn vault_authority(account: &VaultAuthority) -> Value {
+    json!({ "initializer": account.initializer.to_string() })
+}
+
+pub fn foreign_chain(account: &ForeignChain) -> Value {
+    json!({
+        "chain_id": account.chain_id,
+        "chain_address": account.chain_address,
+        "emitter_address": account.emitter_address,
+    })
+}
+
+pub fn wormhole_config(account: &WormholeConfig) -> Value {
+    json!({
+        "owner": account.owner.to_string(),
+        "bridge": account.wormhole.bridge.to_string(),
+        "fee_collector": account.wormhole.fee_collector.to_string(),
+        "sequence": account.wormhole.sequence.to_string(),
+        "batch_id": account.batch_id,
+        "finality": account.finality,
+    })
+}
+
+pub fn wormhole_emitter(account: &WormholeEmitter) -> Value {
+    json!({ "bump": account.bump })
+}
+
+pub fn loan_position_listing(account: &LoanPositionListing) -> Value {
+    json!({
+        "seller": account.seller.to_string(),
+        "loan_offer": account.loan_offer.to_string(),
+        "position_mint": account.position_mint.to_string(),
+        "lend_mint_token": account.lend_mint_token.to_string(),
+        "price": account.price,
+        "listed_at": account.listed_at,
+    })
+}
+
+pub fn treasury(account: &Treasury) -> Value {
+    json!({
+        "token_mint": account.token_mint.to_string(),
+        "total_lender_fee": account.total_lender_fee,
+        "total_borrower_fee": account.total_borrower_fee,
+        "total_withdrawn": account.total_withdrawn,
+    })
+}
+
+pub fn enso_account(account: &EnsoAccount) -> Value {
+    match account {
+        EnsoAccount::LoanOffer(account) => loan_offer(account),
+        EnsoAccount::LendOffer(account) => lend_offer(account),
+        EnsoAccount::Asset(account) => asset(account),
+        EnsoAccount::Setting(account) => setting(account),
+        EnsoAccount::VaultAuthority(account) => vault_authority(account),
+        EnsoAccount::ForeignChain(account) => foreign_chain(account),
+        EnsoAccount::WormholeConfig(account) => wormhole_config(account),
+        EnsoAccount::WormholeEmitter(account) => wormhole_emitter(account),
+        EnsoAccount::LoanPositionListing(account) => loan_position_listing(account),
+        EnsoAccount::Treasury(account) => treasury(account),
+    }
+}
+
+/// Pretty JSON `{ "address": ..., ...fields }` of a keyed account.
+pub fn keyed(pubkey: &Pubkey, mut value: Value) -> Value {
+    if let Value::Object(fields) = &mut value {
+        fields.insert("address".to_string(), json!(pubkey.to_string()));
+    }
+
+    value
+}
+
+pub fn print(value: &Value) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap_or_default());
+}
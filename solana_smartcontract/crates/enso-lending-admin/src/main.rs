@@ -0,0 +1,71 @@
+//! Operator CLI for the enso-lending program: tier, asset, fee treasury, foreign chain and
+//! Wormhole management, account inspection and JSON export.
+
+mod commands;
+mod json;
+mod runner;
+
+use clap::{Parser, Subcommand};
+
+use commands::{
+    asset::AssetCommand, export::ExportArgs, foreign_chain::ForeignChainCommand, inspect::LendOfferCommand,
+    inspect::LoanCommand, tier::TierCommand, transaction::TransactionCommand, treasury::TreasuryCommand,
+    wormhole::WormholeCommand,
+};
+use runner::{GlobalArgs, Runner};
+
+#[derive(Parser)]
+#[command(name = "enso-lending-admin", version, about)]
+struct Cli {
+    #[command(flatten)]
+    global: GlobalArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage tier setting accounts
+    #[command(subcommand)]
+    Tier(TierCommand),
+    /// Manage asset accounts
+    #[command(subcommand)]
+    Asset(AssetCommand),
+    /// Manage the fee treasuries of lend mints
+    #[command(subcommand)]
+    Treasury(TreasuryCommand),
+    /// Manage registered foreign chains
+    #[command(subcommand)]
+    ForeignChain(ForeignChainCommand),
+    /// Manage the Wormhole config and emitter
+    #[command(subcommand)]
+    Wormhole(WormholeCommand),
+    /// List and inspect loans
+    #[command(subcommand)]
+    Loan(LoanCommand),
+    /// List and inspect lend offers
+    #[command(subcommand)]
+    LendOffer(LendOfferCommand),
+    /// Export every program account as JSON
+    Export(ExportArgs),
+    /// Sign or submit transaction files written with --output
+    #[command(subcommand)]
+    Transaction(TransactionCommand),
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let runner = Runner::new(cli.global)?;
+
+    match cli.command {
+        Command::Tier(command) => command.run(&runner),
+        Command::Asset(command) => command.run(&runner),
+        Command::Treasury(command) => command.run(&runner),
+        Command::ForeignChain(command) => command.run(&runner),
+        Command::Wormhole(command) => command.run(&runner),
+        Command::Loan(command) => command.run(&runner),
+        Command::LendOffer(command) => command.run(&runner),
+        Command::Export(args) => args.run(&runner),
+        Command::Transaction(command) => command.run(&runner),
+    }
+}
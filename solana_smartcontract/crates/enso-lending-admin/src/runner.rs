@@ -0,0 +1,200 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Args;
+use enso_lending_client::{
+    decode, pda,
+    rpc::{encode_transaction, RpcClient, SignatureStatus, DEFAULT_COMMITMENT},
+};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Args)]
+pub struct GlobalArgs {
+    /// JSON-RPC endpoint
+    #[arg(long, short = 'u', global = true, env = "ENSO_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+    #[arg(long, global = true, default_value = DEFAULT_COMMITMENT)]
+    commitment: String,
+    /// Keypair signing and paying for transactions
+    #[arg(long, short = 'k', global = true, env = "ENSO_KEYPAIR")]
+    keypair: Option<PathBuf>,
+    /// Authority and fee payer when no keypair is given, defaults to the operate system key
+    #[arg(long, global = true)]
+    authority: Option<Pubkey>,
+    /// Simulate the transaction and print its logs instead of sending it
+    #[arg(long, global = true)]
+    dry_run: bool,
+    /// Sign without any RPC call, requires --blockhash
+    #[arg(long, global = true, requires = "blockhash")]
+    sign_only: bool,
+    /// Recent blockhash used instead of fetching the latest one
+    #[arg(long, global = true)]
+    blockhash: Option<Hash>,
+    /// Write the transaction, base64 encoded, to this file instead of sending it
+    #[arg(long, short = 'o', global = true)]
+    output: Option<PathBuf>,
+}
+
+pub struct Runner {
+    rpc: RpcClient,
+    keypair: Option<Keypair>,
+    authority: Pubkey,
+    dry_run: bool,
+    sign_only: bool,
+    blockhash: Option<Hash>,
+    output: Option<PathBuf>,
+}
+
+impl Runner {
+    pub fn new(args: GlobalArgs) -> Result<Self> {
+        let keypair = args
+            .keypair
+            .map(|path| {
+                read_keypair_file(&path).map_err(|err| anyhow!("failed to read keypair {}: {err}", path.display()))
+            })
+            .transpose()?;
+        let authority = match (&keypair, args.authority) {
+            (Some(keypair), Some(authority)) if keypair.pubkey() != authority => {
+                bail!("--authority {authority} does not match the keypair {}", keypair.pubkey())
+            }
+            (Some(keypair), _) => keypair.pubkey(),
+            (None, Some(authority)) => authority,
+            (None, None) => pda::operate_system(),
+        };
+
+        Ok(Self {
+            rpc: RpcClient::with_commitment(args.url, args.commitment),
+            keypair,
+            authority,
+            dry_run: args.dry_run,
+            sign_only: args.sign_only,
+            blockhash: args.blockhash,
+            output: args.output,
+        })
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    pub fn authority(&self) -> Pubkey {
+        self.authority
+    }
+
+    pub fn keypair(&self) -> Option<&Keypair> {
+        self.keypair.as_ref()
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.sign_only
+    }
+
+    pub fn fetch<T: AccountDeserialize>(&self, pubkey: &Pubkey) -> Result<T> {
+        let data = self.rpc.get_account_data(pubkey)?.ok_or_else(|| anyhow!("account {pubkey} not found"))?;
+
+        Ok(decode(&data)?)
+    }
+
+    /// Runs `instructions` in one transaction paid by the authority, honouring --dry-run,
+    /// --sign-only and --output.
+    pub fn execute(&self, instructions: &[Instruction]) -> Result<()> {
+        let mut transaction = Transaction::new_unsigned(Message::new(instructions, Some(&self.authority)));
+
+        if self.dry_run {
+            return self.simulate(&transaction);
+        }
+
+        let blockhash = match self.blockhash {
+            Some(blockhash) => blockhash,
+            None => self.rpc.get_latest_blockhash()?,
+        };
+        transaction.message.recent_blockhash = blockhash;
+
+        if let Some(keypair) = &self.keypair {
+            transaction.try_partial_sign(&[keypair], blockhash)?;
+        }
+
+        if self.sign_only || self.output.is_some() {
+            return self.write(&transaction);
+        }
+
+        if !transaction.is_signed() {
+            bail!("transaction needs a signature from {}, pass --keypair or --output", self.authority);
+        }
+
+        let signature = self.send_and_confirm(&transaction)?;
+        println!("{signature}");
+
+        Ok(())
+    }
+
+    pub fn simulate(&self, transaction: &Transaction) -> Result<()> {
+        let simulation = self.rpc.simulate_transaction(transaction)?;
+
+        for log in &simulation.logs {
+            println!("{log}");
+        }
+
+        if let Some(units) = simulation.units_consumed {
+            println!("compute units consumed: {units}");
+        }
+
+        match simulation.err {
+            Some(err) => bail!("simulation failed: {err}"),
+            None => Ok(()),
+        }
+    }
+
+    pub fn write(&self, transaction: &Transaction) -> Result<()> {
+        let encoded = encode_transaction(transaction)?;
+        let missing: Vec<String> = missing_signers(transaction).iter().map(Pubkey::to_string).collect();
+
+        match &self.output {
+            Some(path) => {
+                fs::write(path, format!("{encoded}\n")).with_context(|| format!("failed to write {}", path.display()))?;
+                println!("wrote {}", path.display());
+            }
+            None => println!("{encoded}"),
+        }
+
+        if !missing.is_empty() {
+            eprintln!("missing signatures: {}", missing.join(", "));
+        }
+
+        Ok(())
+    }
+
+    pub fn send_and_confirm(&self, transaction: &Transaction) -> Result<Signature> {
+        let signature = self.rpc.send_transaction(transaction)?;
+
+        match self.rpc.confirm_transaction(&signature, CONFIRM_TIMEOUT)? {
+            SignatureStatus::Confirmed => Ok(signature),
+            SignatureStatus::Failed(err) => bail!("transaction {signature} failed: {err}"),
+            SignatureStatus::Pending => {
+                bail!("transaction {signature} was not confirmed after {}s", CONFIRM_TIMEOUT.as_secs())
+            }
+        }
+    }
+}
+
+pub fn missing_signers(transaction: &Transaction) -> Vec<Pubkey> {
+    let required = transaction.message.header.num_required_signatures as usize;
+
+    transaction.message.account_keys[..required]
+        .iter()
+        .zip(&transaction.signatures)
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(pubkey, _)| *pubkey)
+        .collect()
+}
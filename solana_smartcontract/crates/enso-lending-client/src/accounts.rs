@@ -0,0 +1,55 @@
+use anchor_lang::{AccountDeserialize, Discriminator};
+use enso_lending::states::{
+    Asset, ForeignChain, LendOfferAccount, LoanOfferAccount, LoanPositionListing, SettingAccount, Treasury,
+    VaultAuthority, WormholeConfig, WormholeEmitter,
+};
+
+use crate::error::{split_discriminator, ClientError, Result};
+
+/// Decodes a single account type, checking its discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    let mut data = data;
+    Ok(T::try_deserialize(&mut data)?)
+}
+
+macro_rules! enso_accounts {
+    ($($variant:ident($account:ident)),* $(,)?) => {
+        /// Any account owned by the enso-lending program.
+        pub enum EnsoAccount {
+            $($variant($account),)*
+        }
+
+        impl EnsoAccount {
+            pub fn decode(data: &[u8]) -> Result<Self> {
+                let (discriminator, _) = split_discriminator(data)?;
+
+                $(
+                    if discriminator == $account::DISCRIMINATOR {
+                        return Ok(Self::$variant(decode(data)?));
+                    }
+                )*
+
+                Err(ClientError::UnknownAccountDiscriminator(discriminator))
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(_) => stringify!($account),)*
+                }
+            }
+        }
+    };
+}
+
+enso_accounts! {
+    LoanOffer(LoanOfferAccount),
+    LendOffer(LendOfferAccount),
+    Asset(Asset),
+    Setting(SettingAccount),
+    VaultAuthority(VaultAuthority),
+    ForeignChain(ForeignChain),
+    WormholeConfig(WormholeConfig),
+    WormholeEmitter(WormholeEmitter),
+    LoanPositionListing(LoanPositionListing),
+    Treasury(Treasury),
+}
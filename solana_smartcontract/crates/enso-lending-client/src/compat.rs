@@ -0,0 +1,675 @@
+//! Events emitted before the event header existed, schema version 0. The program no longer
+//! defines them, they are kept here so older transactions stay decodable.
+
+use anchor_lang::{
+    prelude::{borsh, Pubkey},
+    solana_program::hash::hash,
+    AnchorDeserialize,
+};
+use enso_lending::common::{LendOfferStatus, LoanOfferStatus};
+
+use crate::error::{split_discriminator, ClientError, Result};
+
+/// Anchor event discriminator, the first 8 bytes of `sha256("event:<name>")`.
+fn discriminator(name: &str) -> [u8; 8] {
+    hash(format!("event:{name}").as_bytes()).to_bytes()[..8].try_into().unwrap()
+}
+
+trait LegacyLayout: Sized {
+    fn decode(body: &[u8]) -> Result<Self>;
+}
+
+macro_rules! legacy_events {
+    ($($event:ident),* $(,)?) => {
+        /// Event of the enso-lending program from before the event header.
+        #[derive(Clone, Debug)]
+        pub enum LegacyEvent {
+            $($event($event),)*
+        }
+
+        impl LegacyEvent {
+            /// Decodes the discriminator prefixed bytes of a `Program data:` log.
+            pub fn decode(data: &[u8]) -> Result<Self> {
+                let (event_discriminator, body) = split_discriminator(data)?;
+
+                $(
+                    if event_discriminator == discriminator(stringify!($event)) {
+                        return Ok(Self::$event(LegacyLayout::decode(body)?));
+                    }
+                )*
+
+                Err(ClientError::UnknownEventDiscriminator(event_discriminator))
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$event(_) => stringify!($event),)*
+                }
+            }
+        }
+    };
+}
+
+macro_rules! borsh_layouts {
+    ($($event:ident),* $(,)?) => {
+        $(
+            impl LegacyLayout for $event {
+                fn decode(mut body: &[u8]) -> Result<Self> {
+                    Ok(Self::deserialize(&mut body)?)
+                }
+            }
+        )*
+    };
+}
+
+legacy_events! {
+    InitSettingAccountEvent,
+    EditSettingAccountEvent,
+    CloseSettingAccountEvent,
+    InitAssetEvent,
+    EditAssetEvent,
+    CreateLendOfferEvent,
+    EditLendOfferEvent,
+    LendOfferCancelRequestEvent,
+    LendOfferCanceledEvent,
+    LendOfferReclaimedEvent,
+    LoanOfferCreateRequestEvent,
+    LoanOfferUpdateEvent,
+    WithdrawCollateralEvent,
+    SwapCollateralEvent,
+    UnhealthyRatioDetectedEvent,
+    LoanOfferExpiredEvent,
+    DepositCollateralLoanOfferEvent,
+    RepayLoanOfferEvent,
+    LiquidatingCollateralEvent,
+    LiquidatedCollateralEvent,
+    SystemRepayLoanOfferEvent,
+    SystemFinishLoanOfferEvent,
+    SystemRevertEvent,
+    InitForeignEmitterEvent,
+    RequestCancelCollateralCrossChainEvent,
+    UpdateDepositCollateralCrossChainEvent,
+    UpdateWithdrawCollateralCrossChainEvent,
+    ListLoanPositionEvent,
+    CancelLoanPositionListingEvent,
+    BuyLoanPositionEvent,
+    SwapCollateralCrossChainEvent,
+    LendOfferClosedEvent,
+    LoanOfferClosedEvent,
+    AccountMigratedEvent,
+}
+
+borsh_layouts! {
+    InitSettingAccountEvent,
+    EditSettingAccountEvent,
+    CloseSettingAccountEvent,
+    InitAssetEvent,
+    EditAssetEvent,
+    CreateLendOfferEvent,
+    EditLendOfferEvent,
+    LendOfferCancelRequestEvent,
+    LendOfferCanceledEvent,
+    LendOfferReclaimedEvent,
+    LoanOfferCreateRequestEvent,
+    LoanOfferUpdateEvent,
+    WithdrawCollateralEvent,
+    SwapCollateralEvent,
+    UnhealthyRatioDetectedEvent,
+    LoanOfferExpiredEvent,
+    DepositCollateralLoanOfferEvent,
+    RepayLoanOfferEvent,
+    LiquidatedCollateralEvent,
+    SystemRepayLoanOfferEvent,
+    InitForeignEmitterEvent,
+    RequestCancelCollateralCrossChainEvent,
+    UpdateDepositCollateralCrossChainEvent,
+    UpdateWithdrawCollateralCrossChainEvent,
+    ListLoanPositionEvent,
+    CancelLoanPositionListingEvent,
+    BuyLoanPositionEvent,
+    SwapCollateralCrossChainEvent,
+    LendOfferClosedEvent,
+    LoanOfferClosedEvent,
+    AccountMigratedEvent,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct InitSettingAccountEvent {
+    pub amount: u64,
+    pub duration: u64,
+    pub owner: Pubkey,
+    pub receiver: Pubkey,
+    pub tier_id: String,
+    pub lender_fee_percent: f64,
+    pub borrower_fee_percent: f64,
+    pub lend_mint_asset: Pubkey,
+    pub collateral_mint_assets: Vec<Pubkey>,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub amount_step: u64,
+    pub min_duration: u64,
+    pub max_duration: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct EditSettingAccountEvent {
+    pub receiver: Pubkey,
+    pub tier_id: String,
+    pub amount: u64,
+    pub duration: u64,
+    pub lender_fee_percent: f64,
+    pub lend_mint_asset: Pubkey,
+    pub collateral_mint_assets: Vec<Pubkey>,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub amount_step: u64,
+    pub min_duration: u64,
+    pub max_duration: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct CloseSettingAccountEvent {
+    pub tier_id: String,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct InitAssetEvent {
+    pub token_mint: Pubkey,
+    pub max_price_age_seconds: u64,
+    pub decimals: u8,
+    pub is_collateral: bool,
+    pub is_lend: bool,
+    pub price_feed_id: String,
+    pub price_feed_account: Pubkey,
+    pub bump: u8,
+    pub name: String,
+    pub token_address: Option<String>,
+    pub chain_id: u16,
+    pub max_collateral_amount: u64,
+    pub max_borrow_amount: u64,
+    pub isolated: bool,
+    pub isolated_lend_mints: Vec<Pubkey>,
+    pub isolated_debt_ceiling: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct EditAssetEvent {
+    pub token_mint: Pubkey,
+    pub max_price_age_seconds: u64,
+    pub decimals: u8,
+    pub is_collateral: bool,
+    pub is_lend: bool,
+    pub price_feed_id: String,
+    pub price_feed_account: Pubkey,
+    pub bump: u8,
+    pub name: String,
+    pub total_collateral_amount: u64,
+    pub max_collateral_amount: u64,
+    pub total_borrow_amount: u64,
+    pub max_borrow_amount: u64,
+    pub isolated: bool,
+    pub isolated_lend_mints: Vec<Pubkey>,
+    pub isolated_debt_ceiling: u64,
+    pub total_isolated_debt: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct CreateLendOfferEvent {
+    pub lender: Pubkey,
+    pub interest: f64,
+    pub lender_fee_percent: f64,
+    pub duration: u64,
+    pub amount: u64,
+    pub offer_id: String,
+    pub tier_id: String,
+    pub expires_at: Option<i64>,
+    pub crank_tip: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct EditLendOfferEvent {
+    pub lender: Pubkey,
+    pub interest: f64,
+    pub lender_fee_percent: f64,
+    pub duration: u64,
+    pub amount: u64,
+    pub offer_id: String,
+    pub old_interest: f64,
+    pub old_duration: u64,
+    pub old_amount: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LendOfferCancelRequestEvent {
+    pub lender: Pubkey,
+    pub interest: f64,
+    pub lender_fee_percent: f64,
+    pub duration: u64,
+    pub amount: u64,
+    pub offer_id: String,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LendOfferCanceledEvent {
+    pub lender: Pubkey,
+    pub interest: f64,
+    pub lender_fee_percent: f64,
+    pub duration: u64,
+    pub amount: u64,
+    pub offer_id: String,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LendOfferReclaimedEvent {
+    pub lender: Pubkey,
+    pub cranker: Pubkey,
+    pub amount: u64,
+    pub crank_tip: u64,
+    pub offer_id: String,
+    pub expires_at: i64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LoanOfferCreateRequestEvent {
+    pub tier_id: String,
+    pub lend_offer_id: String,
+    pub interest: f64,
+    pub borrow_amount: u64,
+    pub lender_fee_percent: f64,
+    pub duration: u64,
+    pub lend_mint_token: Pubkey,
+    pub lender: Pubkey,
+    pub offer_id: String,
+    pub borrower: Pubkey,
+    pub collateral_mint_token: Pubkey,
+    pub collateral_amount: u64,
+    pub status: LoanOfferStatus,
+    pub borrower_fee_percent: f64,
+    pub started_at: i64,
+    pub position_mint: Option<Pubkey>,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LoanOfferUpdateEvent {
+    pub tier_id: String,
+    pub lend_offer_id: String,
+    pub interest: f64,
+    pub borrow_amount: u64,
+    pub lender_fee_percent: f64,
+    pub duration: u64,
+    pub lend_mint_token: Pubkey,
+    pub lender: Pubkey,
+    pub offer_id: String,
+    pub borrower: Pubkey,
+    pub collateral_mint_token: Pubkey,
+    pub collateral_amount: u64,
+    pub status: LoanOfferStatus,
+    pub borrower_fee_percent: f64,
+    pub started_at: i64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct WithdrawCollateralEvent {
+    pub borrower: Pubkey,
+    pub withdraw_amount: u64,
+    pub loan_offer_id: String,
+    pub collateral_amount: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SwapCollateralEvent {
+    pub borrower: Pubkey,
+    pub loan_offer_id: String,
+    pub old_collateral_mint_token: Pubkey,
+    pub old_collateral_amount: u64,
+    pub new_collateral_mint_token: Pubkey,
+    pub new_collateral_amount: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct UnhealthyRatioDetectedEvent{
+    pub borrower: Pubkey,
+    pub withdraw_amount: u64,
+    pub loan_offer_id: String,
+    pub collateral_amount: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LoanOfferExpiredEvent{
+    pub borrower: Pubkey,
+    pub withdraw_amount: u64,
+    pub loan_offer_id: String,
+    pub collateral_amount: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DepositCollateralLoanOfferEvent {
+    pub tier_id: String,
+    pub lend_offer_id: String,
+    pub interest: f64,
+    pub borrow_amount: u64,
+    pub lender_fee_percent: f64,
+    pub duration: u64,
+    pub lend_mint_token: Pubkey,
+    pub lender: Pubkey,
+    pub offer_id: String,
+    pub borrower: Pubkey,
+    pub collateral_mint_token: Pubkey,
+    pub collateral_amount: u64,
+    pub status: LoanOfferStatus,
+    pub borrower_fee_percent: f64,
+    pub started_at: i64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct RepayLoanOfferEvent {
+    pub borrower: Pubkey,
+    pub loan_offer_id: String,
+    pub repay_amount: u64,
+    pub borrower_fee_percent: f64,
+    pub status: LoanOfferStatus,
+}
+
+/// `borrower` and `collateral_amount` are `None` in events emitted before they were added.
+#[derive(Clone, Debug)]
+pub struct LiquidatingCollateralEvent {
+    pub offer_id: String,
+    pub borrower: Option<Pubkey>,
+    pub collateral_amount: Option<u64>,
+    pub liquidating_price: Option<f64>,
+    pub liquidating_at: Option<i64>,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LiquidatedCollateralEvent {
+    pub loan_offer_id: String,
+    pub liquidated_price: u64,
+    pub liquidated_tx: String,
+    pub system: Pubkey,
+    pub lender: Pubkey,
+    pub borrower: Pubkey,
+    pub remaining_fund_to_borrower: u64,
+    pub collateral_swapped_amount: u64,
+    pub status: LoanOfferStatus,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SystemRepayLoanOfferEvent {
+    pub lender: Pubkey,
+    pub borrower: Pubkey,
+    pub interest: f64,
+    pub loan_amount: u64,
+    pub collateral_amount: u64,
+    pub loan_offer_id: String,
+    pub tier_id: String,
+    pub status: LoanOfferStatus,
+}
+
+/// `waiting_interest` and `total_repay_to_lender` are `None` in events emitted before they
+/// were added.
+#[derive(Clone, Debug)]
+pub struct SystemFinishLoanOfferEvent {
+    pub system: Pubkey,
+    pub lender: Pubkey,
+    pub borrower: Pubkey,
+    pub interest: f64,
+    pub loan_amount: u64,
+    pub waiting_interest: Option<u64>,
+    pub total_repay_to_lender: Option<u64>,
+    pub loan_offer_id: String,
+    pub tier_id: String,
+    pub status: LoanOfferStatus,
+}
+
+/// `borrower` is `None` in events emitted before it was added.
+#[derive(Clone, Debug)]
+pub struct SystemRevertEvent {
+    pub offer_id: String,
+    pub borrower: Option<Pubkey>,
+    pub status: LoanOfferStatus,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct InitForeignEmitterEvent {
+    pub chain_id: u16,
+    pub chain_address: String,
+    pub emitter_address: String,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct RequestCancelCollateralCrossChainEvent {
+    pub borrower: Pubkey,
+    pub lend_offer_id: String,
+    pub target_chain: u16,
+    pub chain_address: String,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct UpdateDepositCollateralCrossChainEvent {
+    pub target_chain: u16,
+    pub chain_address: String,
+    pub loan_offer_id: String,
+    pub collateral_amount: u64,
+    pub collateral_address: String,
+    pub borrower: Pubkey,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct UpdateWithdrawCollateralCrossChainEvent {
+    pub target_chain: u16,
+    pub chain_address: String,
+    pub loan_offer_id: String,
+    pub withdraw_amount: u64,
+    pub remaining_collateral_amount: u64,
+    pub collateral_address: String,
+    pub borrower: Pubkey,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ListLoanPositionEvent {
+    pub seller: Pubkey,
+    pub loan_offer: Pubkey,
+    pub loan_offer_id: String,
+    pub position_mint: Pubkey,
+    pub lend_mint_token: Pubkey,
+    pub price: u64,
+    pub listed_at: i64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct CancelLoanPositionListingEvent {
+    pub seller: Pubkey,
+    pub loan_offer: Pubkey,
+    pub position_mint: Pubkey,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct BuyLoanPositionEvent {
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub loan_offer: Pubkey,
+    pub position_mint: Pubkey,
+    pub lend_mint_token: Pubkey,
+    pub price: u64,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SwapCollateralCrossChainEvent {
+    pub target_chain: u16,
+    pub chain_address: String,
+    pub loan_offer_id: String,
+    pub old_collateral_address: String,
+    pub old_collateral_amount: u64,
+    pub new_collateral_address: String,
+    pub new_collateral_amount: u64,
+    pub borrower: Pubkey,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LendOfferClosedEvent {
+    pub lender: Pubkey,
+    pub offer_id: String,
+    pub lend_mint_token: Pubkey,
+    pub amount: u64,
+    pub interest: f64,
+    pub duration: u64,
+    pub status: LendOfferStatus,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LoanOfferClosedEvent {
+    pub loan_offer_id: String,
+    pub lend_offer_id: String,
+    pub tier_id: String,
+    pub borrower: Pubkey,
+    pub lender: Pubkey,
+    pub rent_receiver: Pubkey,
+    pub lend_mint_token: Pubkey,
+    pub borrow_amount: u64,
+    pub collateral_mint_token: Pubkey,
+    pub collateral_amount: u64,
+    pub interest: f64,
+    pub started_at: i64,
+    pub status: LoanOfferStatus,
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct AccountMigratedEvent {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+// Liquidation start, finish and revert events gained fields shortly before the header was
+// introduced, both layouts are found on chain. The longer one is tried first, an exact length
+// match tells them apart.
+
+#[derive(AnchorDeserialize)]
+struct LiquidatingCollateralLayout {
+    offer_id: String,
+    borrower: Pubkey,
+    collateral_amount: u64,
+    liquidating_price: Option<f64>,
+    liquidating_at: Option<i64>,
+}
+
+#[derive(AnchorDeserialize)]
+struct LiquidatingCollateralInitialLayout {
+    offer_id: String,
+    liquidating_price: Option<f64>,
+    liquidating_at: Option<i64>,
+}
+
+impl LegacyLayout for LiquidatingCollateralEvent {
+    fn decode(body: &[u8]) -> Result<Self> {
+        if let Ok(event) = LiquidatingCollateralLayout::try_from_slice(body) {
+            return Ok(Self {
+                offer_id: event.offer_id,
+                borrower: Some(event.borrower),
+                collateral_amount: Some(event.collateral_amount),
+                liquidating_price: event.liquidating_price,
+                liquidating_at: event.liquidating_at,
+            });
+        }
+
+        let event = LiquidatingCollateralInitialLayout::try_from_slice(body)?;
+        Ok(Self {
+            offer_id: event.offer_id,
+            borrower: None,
+            collateral_amount: None,
+            liquidating_price: event.liquidating_price,
+            liquidating_at: event.liquidating_at,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct SystemFinishLoanOfferLayout {
+    system: Pubkey,
+    lender: Pubkey,
+    borrower: Pubkey,
+    interest: f64,
+    loan_amount: u64,
+    waiting_interest: u64,
+    total_repay_to_lender: u64,
+    loan_offer_id: String,
+    tier_id: String,
+    status: LoanOfferStatus,
+}
+
+#[derive(AnchorDeserialize)]
+struct SystemFinishLoanOfferInitialLayout {
+    system: Pubkey,
+    lender: Pubkey,
+    borrower: Pubkey,
+    interest: f64,
+    loan_amount: u64,
+    loan_offer_id: String,
+    tier_id: String,
+    status: LoanOfferStatus,
+}
+
+impl LegacyLayout for SystemFinishLoanOfferEvent {
+    fn decode(body: &[u8]) -> Result<Self> {
+        if let Ok(event) = SystemFinishLoanOfferLayout::try_from_slice(body) {
+            return Ok(Self {
+                system: event.system,
+                lender: event.lender,
+                borrower: event.borrower,
+                interest: event.interest,
+                loan_amount: event.loan_amount,
+                waiting_interest: Some(event.waiting_interest),
+                total_repay_to_lender: Some(event.total_repay_to_lender),
+                loan_offer_id: event.loan_offer_id,
+                tier_id: event.tier_id,
+                status: event.status,
+            });
+        }
+
+        let event = SystemFinishLoanOfferInitialLayout::try_from_slice(body)?;
+        Ok(Self {
+            system: event.system,
+            lender: event.lender,
+            borrower: event.borrower,
+            interest: event.interest,
+            loan_amount: event.loan_amount,
+            waiting_interest: None,
+            total_repay_to_lender: None,
+            loan_offer_id: event.loan_offer_id,
+            tier_id: event.tier_id,
+            status: event.status,
+        })
+    }
+}
+
+#[derive(AnchorDeserialize)]
+struct SystemRevertLayout {
+    offer_id: String,
+    borrower: Pubkey,
+    status: LoanOfferStatus,
+}
+
+#[derive(AnchorDeserialize)]
+struct SystemRevertInitialLayout {
+    offer_id: String,
+    status: LoanOfferStatus,
+}
+
+impl LegacyLayout for SystemRevertEvent {
+    fn decode(body: &[u8]) -> Result<Self> {
+        if let Ok(event) = SystemRevertLayout::try_from_slice(body) {
+            return Ok(Self {
+                offer_id: event.offer_id,
+                borrower: Some(event.borrower),
+                status: event.status,
+            });
+        }
+
+        let event = SystemRevertInitialLayout::try_from_slice(body)?;
+        Ok(Self {
+            offer_id: event.offer_id,
+            borrower: None,
+            status: event.status,
+        })
+    }
+}
@@ -0,0 +1,46 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("data is shorter than an 8 byte discriminator")]
+    MissingDiscriminator,
+    #[error("unknown account discriminator {0:?}")]
+    UnknownAccountDiscriminator([u8; 8]),
+    #[error("unknown event discriminator {0:?}")]
+    UnknownEventDiscriminator([u8; 8]),
+    #[error(transparent)]
+    Anchor(#[from] anchor_lang::error::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Base64(#[from] base64::DecodeError),
+    #[cfg(feature = "rpc")]
+    #[error("rpc error {code}: {message}")]
+    Rpc { code: i64, message: String },
+    #[cfg(feature = "rpc")]
+    #[error(transparent)]
+    Http(#[from] Box<ureq::Error>),
+    #[cfg(feature = "rpc")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[cfg(feature = "rpc")]
+    #[error(transparent)]
+    Bincode(#[from] bincode::Error),
+    #[cfg(feature = "rpc")]
+    #[error("account {0} not found")]
+    AccountNotFound(anchor_lang::prelude::Pubkey),
+    #[cfg(feature = "rpc")]
+    #[error("invalid {0} in rpc response")]
+    InvalidResponse(&'static str),
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+pub(crate) fn split_discriminator(data: &[u8]) -> Result<([u8; 8], &[u8])> {
+    if data.len() < 8 {
+        return Err(ClientError::MissingDiscriminator);
+    }
+
+    let (discriminator, rest) = data.split_at(8);
+    Ok((discriminator.try_into().unwrap(), rest))
+}
@@ -0,0 +1,163 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use enso_lending::common::event::*;
+
+use crate::{
+    compat::LegacyEvent,
+    error::{split_discriminator, ClientError, Result},
+};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+macro_rules! enso_events {
+    ($($event:ident),* $(,)?) => {
+        /// Any `#[event]` the enso-lending program emits, each starts with an [`EventHeader`].
+        pub enum EnsoEvent {
+            $($event($event),)*
+        }
+
+        impl EnsoEvent {
+            /// Decodes the discriminator prefixed bytes of a `Program data:` log.
+            pub fn decode(data: &[u8]) -> Result<Self> {
+                let (discriminator, mut body) = split_discriminator(data)?;
+
+                $(
+                    if discriminator == $event::DISCRIMINATOR {
+                        return Ok(Self::$event($event::deserialize(&mut body)?));
+                    }
+                )*
+
+                Err(ClientError::UnknownEventDiscriminator(discriminator))
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$event(_) => stringify!($event),)*
+                }
+            }
+
+            pub fn header(&self) -> &EventHeader {
+                match self {
+                    $(Self::$event(event) => &event.header,)*
+                }
+            }
+        }
+    };
+}
+
+enso_events! {
+    SettingAccountInitialized,
+    SettingAccountEdited,
+    SettingAccountClosed,
+    AssetInitialized,
+    AssetEdited,
+    VaultAuthorityInitialized,
+    TreasuryInitialized,
+    TreasuryFeesCollected,
+    TreasuryWithdrawn,
+    ForeignChainInitialized,
+    WormholeInitialized,
+    AccountMigrated,
+    LendOfferCreated,
+    LendOfferEdited,
+    LendOfferWithdrawn,
+    LendOfferCancelRequested,
+    LendOfferCanceled,
+    LendOfferReclaimed,
+    LendOfferClosed,
+    LoanCreated,
+    LoanUpdated,
+    CollateralDeposited,
+    CollateralWithdrawn,
+    CollateralSwapped,
+    LoanRepaid,
+    LoanRepaidToLender,
+    LiquidationStarted,
+    LiquidationFinished,
+    LiquidationSettled,
+    LoanFinished,
+    LoanStatusReverted,
+    LoanClosed,
+    CollateralVaultClosed,
+    LoanPositionListed,
+    LoanPositionListingCanceled,
+    LoanPositionBought,
+    CrossChainCancelRequested,
+    CrossChainCollateralDeposited,
+    CrossChainCollateralWithdrawn,
+    CrossChainCollateralSwapped,
+}
+
+/// Event of either schema, transactions sent before the header was introduced carry
+/// [`LegacyEvent`]s.
+pub enum ProgramEvent {
+    Current(EnsoEvent),
+    Legacy(LegacyEvent),
+}
+
+impl ProgramEvent {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        match EnsoEvent::decode(data) {
+            Err(ClientError::UnknownEventDiscriminator(_)) => LegacyEvent::decode(data).map(Self::Legacy),
+            event => event.map(Self::Current),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Current(event) => event.name(),
+            Self::Legacy(event) => event.name(),
+        }
+    }
+
+    /// Header schema version, 0 for legacy events.
+    pub fn schema_version(&self) -> u8 {
+        match self {
+            Self::Current(event) => event.header().schema_version,
+            Self::Legacy(_) => 0,
+        }
+    }
+}
+
+/// Decodes every event the enso-lending program emitted in a transaction's log messages.
+/// Data logged by other programs, including ones invoked through CPI, is skipped.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Result<Vec<ProgramEvent>> {
+    program_data(logs)?.iter().map(|data| ProgramEvent::decode(data)).collect()
+}
+
+/// Raw `Program data:` payloads logged by the enso-lending program, in emission order, for
+/// callers that keep events they cannot decode.
+pub fn program_data<S: AsRef<str>>(logs: &[S]) -> Result<Vec<Vec<u8>>> {
+    let program_id = enso_lending::ID.to_string();
+    let mut invocations: Vec<bool> = vec![];
+    let mut payloads = vec![];
+
+    for log in logs {
+        let log = log.as_ref();
+
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if invocations.last() == Some(&true) {
+                payloads.push(STANDARD.decode(data)?);
+            }
+            continue;
+        }
+
+        let Some(rest) = log.strip_prefix("Program ") else {
+            continue;
+        };
+        let mut parts = rest.split_whitespace();
+        let (Some(id), Some(action)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+
+        match action {
+            "invoke" => invocations.push(id == program_id),
+            "success" | "failed:" => {
+                invocations.pop();
+            }
+            _ => {}
+        }
+    }
+
+    Ok(payloads)
+}
@@ -0,0 +1,139 @@
+use anchor_lang::{prelude::Pubkey, solana_program::{instruction::Instruction, system_program, sysvar}};
+use anchor_spl::{associated_token, token};
+use enso_lending::{accounts, instruction};
+use wormhole_anchor_sdk::wormhole;
+
+use super::build;
+use crate::pda;
+
+pub fn init_setting_account(owner: Pubkey, receiver: Pubkey, args: instruction::InitSettingAccount) -> Instruction {
+    build(
+        accounts::InitSettingAccount {
+            owner,
+            receiver,
+            setting_account: pda::setting_account(&args.tier_id).0,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn edit_setting_account(owner: Pubkey, receiver: Pubkey, args: instruction::EditSettingAccount) -> Instruction {
+    build(
+        accounts::EditSettingAccount {
+            owner,
+            receiver,
+            setting_account: pda::setting_account(&args._tier_id).0,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn close_setting_account(owner: Pubkey, args: instruction::CloseSettingAccount) -> Instruction {
+    build(
+        accounts::CloseSettingAccount {
+            owner,
+            setting_account: pda::setting_account(&args.tier_id).0,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn init_asset(token_mint: Pubkey, price_feed_account: Pubkey, args: instruction::InitAsset) -> Instruction {
+    build(
+        accounts::InitAsset {
+            owner: pda::operate_system(),
+            token_mint,
+            asset: pda::asset(&token_mint).0,
+            price_feed_account,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// `price_feed_account` is required when `args.price_feed_id` changes the feed.
+pub fn edit_asset(token_mint: Pubkey, price_feed_account: Option<Pubkey>, args: instruction::EditAsset) -> Instruction {
+    build(
+        accounts::EditAsset {
+            owner: pda::operate_system(),
+            token_mint,
+            asset: pda::asset(&token_mint).0,
+            price_feed_account,
+        },
+        args,
+    )
+}
+
+pub fn init_vault_authority(borrower: Pubkey) -> Instruction {
+    build(
+        accounts::InitVaultAuthority {
+            borrower,
+            vault_authority: pda::vault_authority(&borrower).0,
+            system_program: system_program::ID,
+        },
+        instruction::InitVaultAuthority {},
+    )
+}
+
+pub fn init_treasury(token_mint: Pubkey) -> Instruction {
+    build(
+        accounts::InitTreasury {
+            owner: pda::operate_system(),
+            token_mint,
+            treasury: pda::treasury(&token_mint).0,
+            treasury_ata: pda::treasury_ata(&token_mint),
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::InitTreasury {},
+    )
+}
+
+/// `receiver_ata` is any token account of `token_mint`.
+pub fn withdraw_treasury(token_mint: Pubkey, receiver_ata: Pubkey, args: instruction::WithdrawTreasury) -> Instruction {
+    build(
+        accounts::WithdrawTreasury {
+            owner: pda::operate_system(),
+            token_mint,
+            treasury: pda::treasury(&token_mint).0,
+            treasury_ata: pda::treasury_ata(&token_mint),
+            receiver_ata,
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn init_foreign_chain_account(owner: Pubkey, args: instruction::InitForeignChainAccount) -> Instruction {
+    build(
+        accounts::InitForeignEmitter {
+            owner,
+            foreign_chain: pda::foreign_chain(args.chain_id).0,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn init_wormhole() -> Instruction {
+    build(
+        accounts::InitWormhole {
+            owner: pda::operate_system(),
+            wormhole_config: pda::wormhole_config().0,
+            wormhole_program: wormhole::program::ID,
+            wormhole_bridge: pda::wormhole_bridge().0,
+            wormhole_fee_collector: pda::wormhole_fee_collector().0,
+            wormhole_emitter: pda::wormhole_emitter().0,
+            wormhole_sequence: pda::wormhole_sequence().0,
+            wormhole_message: pda::wormhole_message(wormhole::INITIAL_SEQUENCE).0,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        },
+        instruction::InitWormhole {},
+    )
+}
@@ -0,0 +1,345 @@
+//! Cross-chain builders. `chain_id` is the Wormhole id of the chain holding the collateral and
+//! `sequence` the next value of the emitter sequence tracker for builders that post a message.
+
+use anchor_lang::{prelude::Pubkey, solana_program::{instruction::Instruction, system_program}};
+use anchor_spl::{associated_token, token};
+use enso_lending::{accounts, instruction};
+use wormhole_anchor_sdk::wormhole;
+
+use super::{build, LoanAssets, WormholeMessageAccounts};
+use crate::pda;
+
+pub struct CreateLoanOfferCrossChainParams {
+    pub signer: Pubkey,
+    pub borrower: Pubkey,
+    pub lender: Pubkey,
+    pub lend_mint: Pubkey,
+    pub collateral_mint: Pubkey,
+    /// Hot wallet of the tier, `SettingAccount::receiver`.
+    pub receiver: Pubkey,
+    pub lend_offer_escrowed: bool,
+    pub chain_id: u16,
+}
+
+pub fn create_loan_offer_cross_chain(
+    params: CreateLoanOfferCrossChainParams,
+    args: instruction::CreateLoanOfferCrossChain,
+) -> Instruction {
+    let CreateLoanOfferCrossChainParams {
+        signer,
+        borrower,
+        lender,
+        lend_mint,
+        collateral_mint,
+        receiver,
+        lend_offer_escrowed,
+        chain_id,
+    } = params;
+    let loan_offer = pda::loan_offer(&borrower, &args.loan_offer_id).0;
+    let lend_offer = pda::lend_offer(&lender, &args.lend_offer_id).0;
+    let position_mint = pda::loan_position_mint(&loan_offer).0;
+
+    build(
+        accounts::CreateLoanOfferCrossChain {
+            signer,
+            collateral_mint_asset: collateral_mint,
+            lend_mint_asset: lend_mint,
+            lend_asset: pda::asset(&lend_mint).0,
+            collateral_asset: pda::asset(&collateral_mint).0,
+            loan_offer,
+            lend_offer,
+            lender,
+            borrower,
+            setting_account: pda::setting_account(&args.tier_id).0,
+            posted: pda::posted_vaa(&args.vaa_hash).0,
+            foreign_chain: pda::foreign_chain(chain_id).0,
+            lend_offer_vault: lend_offer_escrowed.then(|| pda::ata(&lend_offer, &lend_mint)),
+            hot_wallet_ata_lend_asset: lend_offer_escrowed.then(|| pda::ata(&receiver, &lend_mint)),
+            position_mint,
+            lender_position_ata: pda::ata(&lender, &position_mint),
+            associated_token_program: associated_token::ID,
+            wormhole_program: wormhole::program::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn request_cancel_collateral_cross_chain(
+    borrower: Pubkey,
+    lender: Pubkey,
+    chain_id: u16,
+    sequence: u64,
+    args: instruction::RequestCancelCollateralCrossChain,
+) -> Instruction {
+    let wormhole = WormholeMessageAccounts::new(sequence);
+
+    build(
+        accounts::RequestCancelCollateralCrossChain {
+            borrower,
+            lender,
+            lend_offer: pda::lend_offer(&lender, &args.lend_offer_id).0,
+            posted: pda::posted_vaa(&args.vaa_hash).0,
+            foreign_chain: pda::foreign_chain(chain_id).0,
+            config: wormhole.config,
+            wormhole_program: wormhole.wormhole_program,
+            wormhole_bridge: wormhole.wormhole_bridge,
+            wormhole_fee_collector: wormhole.wormhole_fee_collector,
+            wormhole_emitter: wormhole.wormhole_emitter,
+            wormhole_sequence: wormhole.wormhole_sequence,
+            wormhole_message: wormhole.wormhole_message,
+            system_program: system_program::ID,
+            clock: wormhole.clock,
+            rent: wormhole.rent,
+        },
+        args,
+    )
+}
+
+pub fn request_cancel_loaned_cross_chain(
+    borrower: Pubkey,
+    lender: Pubkey,
+    chain_id: u16,
+    sequence: u64,
+    args: instruction::RequestCancelLoanedCrossChain,
+) -> Instruction {
+    let wormhole = WormholeMessageAccounts::new(sequence);
+
+    build(
+        accounts::RequestCancelLoanedCrossChain {
+            borrower,
+            lender,
+            lend_offer: pda::lend_offer(&lender, &args.lend_offer_id).0,
+            loan_offer: pda::loan_offer(&borrower, &args.loan_offer_id).0,
+            posted: pda::posted_vaa(&args.vaa_hash).0,
+            foreign_chain: pda::foreign_chain(chain_id).0,
+            config: wormhole.config,
+            wormhole_program: wormhole.wormhole_program,
+            wormhole_bridge: wormhole.wormhole_bridge,
+            wormhole_fee_collector: wormhole.wormhole_fee_collector,
+            wormhole_emitter: wormhole.wormhole_emitter,
+            wormhole_sequence: wormhole.wormhole_sequence,
+            wormhole_message: wormhole.wormhole_message,
+            system_program: system_program::ID,
+            clock: wormhole.clock,
+            rent: wormhole.rent,
+        },
+        args,
+    )
+}
+
+pub fn repay_loan_offer_cross_chain(
+    borrower: Pubkey,
+    lend_mint: Pubkey,
+    collateral_mint: Pubkey,
+    chain_id: u16,
+    sequence: u64,
+    args: instruction::RepayLoanOfferCrossChain,
+) -> Instruction {
+    let wormhole = WormholeMessageAccounts::new(sequence);
+
+    build(
+        accounts::RepayLoanOfferCrossChain {
+            borrower,
+            collateral_mint_asset: collateral_mint,
+            lend_mint_asset: lend_mint,
+            collateral_asset: pda::asset(&collateral_mint).0,
+            lend_asset: pda::asset(&lend_mint).0,
+            borrower_ata_lend_asset: pda::ata(&borrower, &lend_mint),
+            loan_offer: pda::loan_offer(&borrower, &args.loan_offer_id).0,
+            hot_wallet_ata_lend_asset: pda::ata(&pda::hot_wallet(), &lend_mint),
+            treasury: pda::treasury(&lend_mint).0,
+            treasury_ata: pda::treasury_ata(&lend_mint),
+            config: wormhole.config,
+            wormhole_bridge: wormhole.wormhole_bridge,
+            wormhole_fee_collector: wormhole.wormhole_fee_collector,
+            wormhole_emitter: wormhole.wormhole_emitter,
+            wormhole_sequence: wormhole.wormhole_sequence,
+            wormhole_message: wormhole.wormhole_message,
+            foreign_chain: pda::foreign_chain(chain_id).0,
+            wormhole_program: wormhole.wormhole_program,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            clock: wormhole.clock,
+            rent: wormhole.rent,
+        },
+        args,
+    )
+}
+
+pub fn update_deposit_collateral_cross_chain(
+    signer: Pubkey,
+    borrower: Pubkey,
+    collateral_mint: Pubkey,
+    chain_id: u16,
+    args: instruction::UpdateDepositCollateralCrossChain,
+) -> Instruction {
+    build(
+        accounts::UpdateDepositCollateralCrossChain {
+            signer,
+            borrower,
+            collateral_mint_asset: collateral_mint,
+            collateral_asset: pda::asset(&collateral_mint).0,
+            loan_offer: pda::loan_offer(&borrower, &args.loan_offer_id).0,
+            posted: pda::posted_vaa(&args.vaa_hash).0,
+            foreign_chain: pda::foreign_chain(chain_id).0,
+            wormhole_program: wormhole::program::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn update_withdraw_collateral_cross_chain(
+    signer: Pubkey,
+    borrower: Pubkey,
+    collateral_mint: Pubkey,
+    chain_id: u16,
+    args: instruction::UpdateWithdrawCollateralCrossChain,
+) -> Instruction {
+    build(
+        accounts::UpdateWithdrawCollateralCrossChain {
+            signer,
+            borrower,
+            collateral_mint_asset: collateral_mint,
+            collateral_asset: pda::asset(&collateral_mint).0,
+            loan_offer: pda::loan_offer(&borrower, &args.loan_offer_id).0,
+            posted: pda::posted_vaa(&args.vaa_hash).0,
+            foreign_chain: pda::foreign_chain(chain_id).0,
+            wormhole_program: wormhole::program::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn start_liquidate_health_loan_cross_chain(
+    borrower: Pubkey,
+    assets: &LoanAssets,
+    chain_id: u16,
+    sequence: u64,
+    args: instruction::StartLiquidateHealthLoanCrossChain,
+) -> Instruction {
+    let wormhole = WormholeMessageAccounts::new(sequence);
+
+    build(
+        accounts::StartLiquidateLoanHealthCrossChain {
+            system: pda::operate_system(),
+            borrower,
+            loan_offer: pda::loan_offer(&borrower, &args._loan_offer_id).0,
+            collateral_mint_asset: assets.collateral_mint,
+            lend_mint_asset: assets.lend_mint,
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&assets.collateral_mint).0,
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+            foreign_chain: pda::foreign_chain(chain_id).0,
+            config: wormhole.config,
+            wormhole_program: wormhole.wormhole_program,
+            wormhole_bridge: wormhole.wormhole_bridge,
+            wormhole_fee_collector: wormhole.wormhole_fee_collector,
+            wormhole_emitter: wormhole.wormhole_emitter,
+            wormhole_sequence: wormhole.wormhole_sequence,
+            wormhole_message: wormhole.wormhole_message,
+            system_program: system_program::ID,
+            clock: wormhole.clock,
+            rent: wormhole.rent,
+        },
+        args,
+    )
+}
+
+pub fn start_liquidate_expired_loan_cross_chain(
+    borrower: Pubkey,
+    collateral_mint: Pubkey,
+    chain_id: u16,
+    sequence: u64,
+    args: instruction::StartLiquidateExpiredLoanCrossChain,
+) -> Instruction {
+    let wormhole = WormholeMessageAccounts::new(sequence);
+
+    build(
+        accounts::StartLiquidateExpiredLoanCrossChain {
+            system: pda::operate_system(),
+            borrower,
+            collateral_mint_asset: collateral_mint,
+            collateral_asset: pda::asset(&collateral_mint).0,
+            loan_offer: pda::loan_offer(&borrower, &args._loan_offer_id).0,
+            foreign_chain: pda::foreign_chain(chain_id).0,
+            config: wormhole.config,
+            wormhole_program: wormhole.wormhole_program,
+            wormhole_bridge: wormhole.wormhole_bridge,
+            wormhole_fee_collector: wormhole.wormhole_fee_collector,
+            wormhole_emitter: wormhole.wormhole_emitter,
+            wormhole_sequence: wormhole.wormhole_sequence,
+            wormhole_message: wormhole.wormhole_message,
+            system_program: system_program::ID,
+            clock: wormhole.clock,
+            rent: wormhole.rent,
+        },
+        args,
+    )
+}
+
+pub struct SwapCollateralCrossChainParams {
+    pub signer: Pubkey,
+    pub borrower: Pubkey,
+    pub tier_id: String,
+    pub lend_mint: Pubkey,
+    pub lend_price_feed: Pubkey,
+    pub old_collateral_mint: Pubkey,
+    pub new_collateral_mint: Pubkey,
+    pub new_collateral_price_feed: Pubkey,
+    pub chain_id: u16,
+    pub sequence: u64,
+}
+
+pub fn swap_collateral_cross_chain(
+    params: SwapCollateralCrossChainParams,
+    args: instruction::SwapCollateralCrossChain,
+) -> Instruction {
+    let SwapCollateralCrossChainParams {
+        signer,
+        borrower,
+        tier_id,
+        lend_mint,
+        lend_price_feed,
+        old_collateral_mint,
+        new_collateral_mint,
+        new_collateral_price_feed,
+        chain_id,
+        sequence,
+    } = params;
+    let wormhole = WormholeMessageAccounts::new(sequence);
+
+    build(
+        accounts::SwapCollateralCrossChain {
+            signer,
+            borrower,
+            lend_mint_asset: lend_mint,
+            lend_asset: pda::asset(&lend_mint).0,
+            old_collateral_mint_asset: old_collateral_mint,
+            old_collateral_asset: pda::asset(&old_collateral_mint).0,
+            new_collateral_mint_asset: new_collateral_mint,
+            new_collateral_asset: pda::asset(&new_collateral_mint).0,
+            setting_account: pda::setting_account(&tier_id).0,
+            loan_offer: pda::loan_offer(&borrower, &args.loan_offer_id).0,
+            lend_price_feed_account: lend_price_feed,
+            new_collateral_price_feed_account: new_collateral_price_feed,
+            posted: pda::posted_vaa(&args.vaa_hash).0,
+            foreign_chain: pda::foreign_chain(chain_id).0,
+            config: wormhole.config,
+            wormhole_program: wormhole.wormhole_program,
+            wormhole_bridge: wormhole.wormhole_bridge,
+            wormhole_fee_collector: wormhole.wormhole_fee_collector,
+            wormhole_emitter: wormhole.wormhole_emitter,
+            wormhole_sequence: wormhole.wormhole_sequence,
+            wormhole_message: wormhole.wormhole_message,
+            system_program: system_program::ID,
+            clock: wormhole.clock,
+            rent: wormhole.rent,
+        },
+        args,
+    )
+}
@@ -0,0 +1,167 @@
+use anchor_lang::{prelude::Pubkey, solana_program::{instruction::Instruction, system_program}};
+use anchor_spl::{associated_token, token};
+use enso_lending::{accounts, instruction};
+
+use super::build;
+use crate::pda;
+
+/// `receiver` is the hot wallet of the tier, `SettingAccount::receiver`.
+pub fn create_lend_offer(
+    lender: Pubkey,
+    mint_asset: Pubkey,
+    receiver: Pubkey,
+    args: instruction::CreateLendOffer,
+) -> Instruction {
+    let lend_offer = pda::lend_offer(&lender, &args.offer_id).0;
+    let escrowed = args.expires_at.is_some();
+
+    build(
+        accounts::CreateLendOffer {
+            lender,
+            lend_asset: pda::asset(&mint_asset).0,
+            mint_asset,
+            lender_ata_asset: pda::ata(&lender, &mint_asset),
+            setting_account: pda::setting_account(&args._tier_id).0,
+            lend_offer,
+            hot_wallet_ata: pda::ata(&receiver, &mint_asset),
+            lend_offer_vault: escrowed.then(|| pda::ata(&lend_offer, &mint_asset)),
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// `escrowed` is whether the lend offer has an expiry and keeps its principal in a vault.
+pub fn edit_lend_offer(
+    lender: Pubkey,
+    mint_asset: Pubkey,
+    receiver: Pubkey,
+    escrowed: bool,
+    args: instruction::EditLendOffer,
+) -> Instruction {
+    let lend_offer = pda::lend_offer(&lender, &args._offer_id).0;
+
+    build(
+        accounts::EditLendOffer {
+            lender,
+            lend_offer,
+            setting_account: pda::setting_account(&args._tier_id).0,
+            mint_asset,
+            lender_ata_asset: pda::ata(&lender, &mint_asset),
+            hot_wallet_ata: (!escrowed).then(|| pda::ata(&receiver, &mint_asset)),
+            lend_offer_vault: escrowed.then(|| pda::ata(&lend_offer, &mint_asset)),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn cancel_lend_offer(
+    lender: Pubkey,
+    mint_asset: Pubkey,
+    escrowed: bool,
+    args: instruction::CancelLendOffer,
+) -> Instruction {
+    let lend_offer = pda::lend_offer(&lender, &args._offer_id).0;
+
+    build(
+        accounts::CancelLendOffer {
+            lender,
+            lend_offer,
+            mint_asset: escrowed.then_some(mint_asset),
+            lender_ata_asset: escrowed.then(|| pda::ata(&lender, &mint_asset)),
+            lend_offer_vault: escrowed.then(|| pda::ata(&lend_offer, &mint_asset)),
+            token_program: escrowed.then_some(token::ID),
+        },
+        args,
+    )
+}
+
+pub fn reclaim_expired_lend_offer(
+    cranker: Pubkey,
+    lender: Pubkey,
+    mint_asset: Pubkey,
+    args: instruction::ReclaimExpiredLendOffer,
+) -> Instruction {
+    let lend_offer = pda::lend_offer(&lender, &args._offer_id).0;
+
+    build(
+        accounts::ReclaimExpiredLendOffer {
+            cranker,
+            lender,
+            lender_ata_asset: pda::ata(&lender, &mint_asset),
+            mint_asset,
+            lend_offer,
+            lend_offer_vault: pda::ata(&lend_offer, &mint_asset),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn system_cancel_lend_offer(
+    system: Pubkey,
+    lender: Pubkey,
+    mint_asset: Pubkey,
+    args: instruction::SystemCancelLendOffer,
+) -> Instruction {
+    build(
+        accounts::SystemCancelLendOffer {
+            lender,
+            lender_ata_asset: pda::ata(&lender, &mint_asset),
+            mint_asset,
+            lend_offer: pda::lend_offer(&lender, &args._offer_id).0,
+            system,
+            system_ata: pda::ata(&system, &mint_asset),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn system_withdraw_lend_offer(
+    system: Pubkey,
+    lender: Pubkey,
+    mint_asset: Pubkey,
+    args: instruction::SystemWithdrawLendOffer,
+) -> Instruction {
+    build(
+        accounts::SystemWithdrawLendOffer {
+            lender,
+            lender_ata_asset: pda::ata(&lender, &mint_asset),
+            mint_asset,
+            lend_offer: pda::lend_offer(&lender, &args._offer_id).0,
+            system,
+            system_ata: pda::ata(&system, &mint_asset),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+/// `loan_offer` is the loan taken from the lend offer, required once the offer is loaned.
+pub fn close_lend_offer(
+    signer: Pubkey,
+    lender: Pubkey,
+    mint_asset: Pubkey,
+    escrowed: bool,
+    loan_offer: Option<Pubkey>,
+    args: instruction::CloseLendOffer,
+) -> Instruction {
+    let lend_offer = pda::lend_offer(&lender, &args.offer_id).0;
+
+    build(
+        accounts::CloseLendOffer {
+            signer,
+            lender,
+            lend_offer,
+            loan_offer,
+            lend_offer_vault: escrowed.then(|| pda::ata(&lend_offer, &mint_asset)),
+            token_program: escrowed.then_some(token::ID),
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
@@ -0,0 +1,198 @@
+use anchor_lang::{prelude::Pubkey, solana_program::{instruction::Instruction, system_program}};
+use anchor_spl::{associated_token, token::{self, spl_token::native_mint}};
+use enso_lending::{accounts, instruction};
+
+use super::{build, creditor_position, LoanAssets};
+use crate::pda;
+
+pub fn start_liquidate_loan_offer_health(
+    borrower: Pubkey,
+    assets: &LoanAssets,
+    args: instruction::StartLiquidateLoanOfferHealth,
+) -> Instruction {
+    build(
+        accounts::StartLiquidateLoanOfferHealth {
+            system: pda::operate_system(),
+            borrower,
+            loan_offer: pda::loan_offer(&borrower, &args._offer_id).0,
+            collateral_mint_asset: assets.collateral_mint,
+            lend_mint_asset: assets.lend_mint,
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&assets.collateral_mint).0,
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+            hot_wallet_ata_collateral_asset: pda::ata(&pda::hot_wallet(), &assets.collateral_mint),
+            vault_authority: pda::vault_authority(&borrower).0,
+            vault: pda::vault(&borrower, &assets.collateral_mint),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+/// `assets.collateral_mint` is ignored, the collateral is SOL.
+pub fn start_liquidate_loan_offer_native_health(
+    borrower: Pubkey,
+    assets: &LoanAssets,
+    args: instruction::StartLiquidateLoanOfferNativeHealth,
+) -> Instruction {
+    build(
+        accounts::StartLiquidateLoanOfferNativeHealth {
+            system: pda::operate_system(),
+            borrower,
+            loan_offer: pda::loan_offer(&borrower, &args._offer_id).0,
+            hot_wallet: pda::hot_wallet(),
+            collateral_mint_asset: native_mint::ID,
+            lend_mint_asset: assets.lend_mint,
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&native_mint::ID).0,
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+        },
+        args,
+    )
+}
+
+pub fn start_liquidate_loan_offer_expired(
+    borrower: Pubkey,
+    collateral_mint: Pubkey,
+    args: instruction::StartLiquidateLoanOfferExpired,
+) -> Instruction {
+    build(
+        accounts::StartLiquidateLoanOfferExpired {
+            system: pda::operate_system(),
+            borrower,
+            collateral_mint_asset: collateral_mint,
+            loan_offer: pda::loan_offer(&borrower, &args._offer_id).0,
+            hot_wallet_ata_collateral_asset: pda::ata(&pda::hot_wallet(), &collateral_mint),
+            vault_authority: pda::vault_authority(&borrower).0,
+            vault: pda::vault(&borrower, &collateral_mint),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn start_liquidate_loan_offer_native_expired(
+    borrower: Pubkey,
+    args: instruction::StartLiquidateLoanOfferNativeExpired,
+) -> Instruction {
+    build(
+        accounts::StartLiquidateLoanOfferNativeExpired {
+            system: pda::operate_system(),
+            borrower,
+            loan_offer: pda::loan_offer(&borrower, &args._offer_id).0,
+            hot_wallet: pda::hot_wallet(),
+        },
+        args,
+    )
+}
+
+/// `liquidator` pays the debt from its lend mint ATA, `creditor` holds the loan position token and
+/// `position_mint` is `LoanOfferAccount::position_mint`. `listed` is whether the creditor listed
+/// the position token for sale. `assets.collateral_mint` is ignored.
+pub fn liquidate_loan_offer_native(
+    liquidator: Pubkey,
+    borrower: Pubkey,
+    creditor: Pubkey,
+    position_mint: Option<Pubkey>,
+    listed: bool,
+    assets: &LoanAssets,
+    args: instruction::LiquidateLoanOfferNative,
+) -> Instruction {
+    let (lender_position_ata, listing) = creditor_position(&creditor, position_mint, listed);
+
+    build(
+        accounts::LiquidateLoanOfferNative {
+            liquidator,
+            liquidator_ata_asset: pda::ata(&liquidator, &assets.lend_mint),
+            borrower,
+            loan_offer: pda::loan_offer(&borrower, &args._offer_id).0,
+            lender: creditor,
+            lender_position_ata,
+            listing,
+            lender_ata_asset: pda::ata(&creditor, &assets.lend_mint),
+            treasury: pda::treasury(&assets.lend_mint).0,
+            treasury_ata: pda::treasury_ata(&assets.lend_mint),
+            lend_mint_asset: assets.lend_mint,
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&native_mint::ID).0,
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn finish_liquidate_contract(
+    borrower: Pubkey,
+    lend_mint: Pubkey,
+    collateral_mint: Pubkey,
+    args: instruction::FinishLiquidateContract,
+) -> Instruction {
+    let system = pda::operate_system();
+
+    build(
+        accounts::SystemLiquidateLoanOffer {
+            system,
+            system_ata: pda::ata(&system, &lend_mint),
+            mint_asset: lend_mint,
+            borrower,
+            borrower_ata_asset: pda::ata(&borrower, &lend_mint),
+            loan_offer: pda::loan_offer(&borrower, &args._loan_offer_id).0,
+            lend_asset: pda::asset(&lend_mint).0,
+            collateral_asset: pda::asset(&collateral_mint).0,
+            treasury: pda::treasury(&lend_mint).0,
+            treasury_ata: pda::treasury_ata(&lend_mint),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+/// `creditor` holds the loan position token, `position_mint` is `LoanOfferAccount::position_mint`.
+/// `listed` is whether the creditor listed the position token for sale.
+pub fn system_finish_loan_offer(
+    system: Pubkey,
+    borrower: Pubkey,
+    creditor: Pubkey,
+    lend_mint: Pubkey,
+    position_mint: Option<Pubkey>,
+    listed: bool,
+    args: instruction::SystemFinishLoanOffer,
+) -> Instruction {
+    let (lender_position_ata, listing) = creditor_position(&creditor, position_mint, listed);
+
+    build(
+        accounts::SystemFinishLoanOffer {
+            system,
+            system_ata: pda::ata(&system, &lend_mint),
+            mint_asset: lend_mint,
+            lender: creditor,
+            lender_position_ata,
+            listing,
+            lender_ata_asset: pda::ata(&creditor, &lend_mint),
+            borrower,
+            loan_offer: pda::loan_offer(&borrower, &args._loan_offer_id).0,
+            treasury: pda::treasury(&lend_mint).0,
+            treasury_ata: pda::treasury_ata(&lend_mint),
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn system_revert_status(system: Pubkey, borrower: Pubkey, args: instruction::SystemRevertStatus) -> Instruction {
+    build(
+        accounts::SystemRevertStatus {
+            system,
+            borrower,
+            loan_offer: pda::loan_offer(&borrower, &args._offer_id).0,
+        },
+        args,
+    )
+}
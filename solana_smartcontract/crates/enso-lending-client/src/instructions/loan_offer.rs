@@ -0,0 +1,357 @@
+use anchor_lang::{prelude::Pubkey, solana_program::{instruction::Instruction, system_program}};
+use anchor_spl::{associated_token, token::{self, spl_token::native_mint}};
+use enso_lending::{accounts, instruction};
+
+use super::{build, creditor_position, LoanAssets};
+use crate::pda;
+
+/// `receiver` is the tier hot wallet and `lend_offer_escrowed` whether the lend offer has an expiry.
+pub fn create_loan_offer(
+    borrower: Pubkey,
+    lender: Pubkey,
+    assets: &LoanAssets,
+    receiver: Pubkey,
+    lend_offer_escrowed: bool,
+    args: instruction::CreateLoanOffer,
+) -> Instruction {
+    let loan_offer = pda::loan_offer(&borrower, &args.offer_id).0;
+    let lend_offer = pda::lend_offer(&lender, &args.lend_offer_id).0;
+    let position_mint = pda::loan_position_mint(&loan_offer).0;
+
+    build(
+        accounts::CreateLoanOffer {
+            borrower,
+            collateral_mint_asset: assets.collateral_mint,
+            lend_mint_asset: assets.lend_mint,
+            borrower_ata_asset: pda::ata(&borrower, &assets.collateral_mint),
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&assets.collateral_mint).0,
+            loan_offer,
+            lend_offer,
+            vault_authority: pda::vault_authority(&borrower).0,
+            vault: pda::vault(&borrower, &assets.collateral_mint),
+            lend_offer_vault: lend_offer_escrowed.then(|| pda::ata(&lend_offer, &assets.lend_mint)),
+            hot_wallet_ata_lend_asset: lend_offer_escrowed.then(|| pda::ata(&receiver, &assets.lend_mint)),
+            lender,
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+            setting_account: pda::setting_account(&args.tier_id).0,
+            position_mint,
+            lender_position_ata: pda::ata(&lender, &position_mint),
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// Same as [`create_loan_offer`] with SOL collateral, `assets.collateral_mint` is ignored.
+pub fn create_loan_offer_native(
+    borrower: Pubkey,
+    lender: Pubkey,
+    assets: &LoanAssets,
+    receiver: Pubkey,
+    lend_offer_escrowed: bool,
+    args: instruction::CreateLoanOfferNative,
+) -> Instruction {
+    let loan_offer = pda::loan_offer(&borrower, &args.offer_id).0;
+    let lend_offer = pda::lend_offer(&lender, &args.lend_offer_id).0;
+    let position_mint = pda::loan_position_mint(&loan_offer).0;
+
+    build(
+        accounts::CreateLoanOfferNative {
+            borrower,
+            collateral_mint_asset: native_mint::ID,
+            lend_mint_asset: assets.lend_mint,
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&native_mint::ID).0,
+            loan_offer,
+            lender,
+            lend_offer,
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+            setting_account: pda::setting_account(&args.tier_id).0,
+            lend_offer_vault: lend_offer_escrowed.then(|| pda::ata(&lend_offer, &assets.lend_mint)),
+            hot_wallet_ata_lend_asset: lend_offer_escrowed.then(|| pda::ata(&receiver, &assets.lend_mint)),
+            position_mint,
+            lender_position_ata: pda::ata(&lender, &position_mint),
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn system_update_loan_offer(
+    system: Pubkey,
+    borrower: Pubkey,
+    lend_mint: Pubkey,
+    args: instruction::SystemUpdateLoanOffer,
+) -> Instruction {
+    build(
+        accounts::SystemUpdateLoanOffer {
+            borrower,
+            borrower_ata_asset: pda::ata(&borrower, &lend_mint),
+            mint_asset: lend_mint,
+            loan_offer: pda::loan_offer(&borrower, &args._offer_id).0,
+            system,
+            system_ata: pda::ata(&system, &lend_mint),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn deposit_collateral_loan_offer(
+    borrower: Pubkey,
+    collateral_mint: Pubkey,
+    args: instruction::DepositCollateralLoanOffer,
+) -> Instruction {
+    build(
+        accounts::DepositCollateralLoanOffer {
+            borrower,
+            collateral_mint_asset: collateral_mint,
+            collateral_asset: pda::asset(&collateral_mint).0,
+            borrower_ata_asset: pda::ata(&borrower, &collateral_mint),
+            loan_offer: pda::loan_offer(&borrower, &args._offer_id).0,
+            vault_authority: pda::vault_authority(&borrower).0,
+            vault: pda::vault(&borrower, &collateral_mint),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn deposit_collateral_loan_offer_native(
+    borrower: Pubkey,
+    args: instruction::DepositCollateralLoanOfferNative,
+) -> Instruction {
+    build(
+        accounts::DepositCollateralLoanOfferNative {
+            borrower,
+            loan_offer: pda::loan_offer(&borrower, &args._offer_id).0,
+            collateral_asset: pda::asset(&native_mint::ID).0,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn repay_loan_offer(
+    borrower: Pubkey,
+    lend_mint: Pubkey,
+    collateral_mint: Pubkey,
+    args: instruction::RepayLoanOffer,
+) -> Instruction {
+    build(
+        accounts::RepayLoanOffer {
+            borrower,
+            collateral_mint_asset: collateral_mint,
+            lend_mint_asset: lend_mint,
+            lend_asset: pda::asset(&lend_mint).0,
+            collateral_asset: pda::asset(&collateral_mint).0,
+            borrower_ata_lend_asset: pda::ata(&borrower, &lend_mint),
+            borrower_ata_collateral_asset: pda::ata(&borrower, &collateral_mint),
+            loan_offer: pda::loan_offer(&borrower, &args._loan_offer_id).0,
+            vault_authority: pda::vault_authority(&borrower).0,
+            vault: pda::vault(&borrower, &collateral_mint),
+            hot_wallet_ata_lend_asset: pda::ata(&pda::hot_wallet(), &lend_mint),
+            treasury: pda::treasury(&lend_mint).0,
+            treasury_ata: pda::treasury_ata(&lend_mint),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn repay_loan_offer_native(
+    borrower: Pubkey,
+    lend_mint: Pubkey,
+    args: instruction::RepayLoanOfferNative,
+) -> Instruction {
+    build(
+        accounts::RepayLoanOfferNative {
+            borrower,
+            lend_mint_asset: lend_mint,
+            lend_asset: pda::asset(&lend_mint).0,
+            collateral_asset: pda::asset(&native_mint::ID).0,
+            borrower_ata_lend_asset: pda::ata(&borrower, &lend_mint),
+            loan_offer: pda::loan_offer(&borrower, &args._loan_offer_id).0,
+            hot_wallet_ata_lend_asset: pda::ata(&pda::hot_wallet(), &lend_mint),
+            treasury: pda::treasury(&lend_mint).0,
+            treasury_ata: pda::treasury_ata(&lend_mint),
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// `creditor` holds the loan position token, `position_mint` is `LoanOfferAccount::position_mint`.
+/// `listed` is whether the creditor listed the position token for sale.
+pub fn repay_loan_offer_to_lender(
+    borrower: Pubkey,
+    creditor: Pubkey,
+    position_mint: Option<Pubkey>,
+    listed: bool,
+    lend_mint: Pubkey,
+    collateral_mint: Pubkey,
+    args: instruction::RepayLoanOfferToLender,
+) -> Instruction {
+    let (lender_position_ata, listing) = creditor_position(&creditor, position_mint, listed);
+
+    build(
+        accounts::RepayLoanOfferToLender {
+            borrower,
+            collateral_mint_asset: collateral_mint,
+            lend_mint_asset: lend_mint,
+            lend_asset: pda::asset(&lend_mint).0,
+            collateral_asset: pda::asset(&collateral_mint).0,
+            borrower_ata_lend_asset: pda::ata(&borrower, &lend_mint),
+            borrower_ata_collateral_asset: pda::ata(&borrower, &collateral_mint),
+            loan_offer: pda::loan_offer(&borrower, &args._loan_offer_id).0,
+            vault_authority: pda::vault_authority(&borrower).0,
+            vault: pda::vault(&borrower, &collateral_mint),
+            lender: creditor,
+            lender_position_ata,
+            listing,
+            lender_ata_asset: pda::ata(&creditor, &lend_mint),
+            treasury: pda::treasury(&lend_mint).0,
+            treasury_ata: pda::treasury_ata(&lend_mint),
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// `creditor` holds the loan position token, `position_mint` is `LoanOfferAccount::position_mint`.
+/// `listed` is whether the creditor listed the position token for sale.
+pub fn repay_loan_offer_native_to_lender(
+    borrower: Pubkey,
+    creditor: Pubkey,
+    position_mint: Option<Pubkey>,
+    listed: bool,
+    lend_mint: Pubkey,
+    args: instruction::RepayLoanOfferNativeToLender,
+) -> Instruction {
+    let (lender_position_ata, listing) = creditor_position(&creditor, position_mint, listed);
+
+    build(
+        accounts::RepayLoanOfferNativeToLender {
+            borrower,
+            lend_mint_asset: lend_mint,
+            lend_asset: pda::asset(&lend_mint).0,
+            collateral_asset: pda::asset(&native_mint::ID).0,
+            borrower_ata_lend_asset: pda::ata(&borrower, &lend_mint),
+            loan_offer: pda::loan_offer(&borrower, &args._loan_offer_id).0,
+            lender: creditor,
+            lender_position_ata,
+            listing,
+            lender_ata_asset: pda::ata(&creditor, &lend_mint),
+            treasury: pda::treasury(&lend_mint).0,
+            treasury_ata: pda::treasury_ata(&lend_mint),
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn withdraw_collateral_loan_offer(
+    borrower: Pubkey,
+    assets: &LoanAssets,
+    args: instruction::WithdrawCollateralLoanOffer,
+) -> Instruction {
+    build(
+        accounts::WithdrawCollateralLoanOffer {
+            borrower,
+            collateral_mint_asset: assets.collateral_mint,
+            lend_mint_asset: assets.lend_mint,
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&assets.collateral_mint).0,
+            borrower_ata_collateral_asset: pda::ata(&borrower, &assets.collateral_mint),
+            loan_offer: pda::loan_offer(&borrower, &args.loan_offer_id).0,
+            vault_authority: pda::vault_authority(&borrower).0,
+            vault: pda::vault(&borrower, &assets.collateral_mint),
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+/// `assets.collateral_mint` is ignored, the collateral is SOL.
+pub fn withdraw_collateral_loan_offer_native(
+    borrower: Pubkey,
+    assets: &LoanAssets,
+    args: instruction::WithdrawCollateralLoanOfferNative,
+) -> Instruction {
+    build(
+        accounts::WithdrawCollateralLoanOfferNative {
+            borrower,
+            collateral_mint_asset: native_mint::ID,
+            lend_mint_asset: assets.lend_mint,
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&native_mint::ID).0,
+            loan_offer: pda::loan_offer(&borrower, &args.loan_offer_id).0,
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+        },
+        args,
+    )
+}
+
+pub struct SwapCollateralParams {
+    pub borrower: Pubkey,
+    pub tier_id: String,
+    pub lend_mint: Pubkey,
+    pub lend_price_feed: Pubkey,
+    pub old_collateral_mint: Pubkey,
+    pub new_collateral_mint: Pubkey,
+    pub new_collateral_price_feed: Pubkey,
+}
+
+pub fn swap_collateral(params: SwapCollateralParams, args: instruction::SwapCollateral) -> Instruction {
+    let SwapCollateralParams {
+        borrower,
+        tier_id,
+        lend_mint,
+        lend_price_feed,
+        old_collateral_mint,
+        new_collateral_mint,
+        new_collateral_price_feed,
+    } = params;
+    let old_is_token = old_collateral_mint != native_mint::ID;
+    let new_is_token = new_collateral_mint != native_mint::ID;
+
+    build(
+        accounts::SwapCollateral {
+            borrower,
+            lend_mint_asset: lend_mint,
+            lend_asset: pda::asset(&lend_mint).0,
+            old_collateral_mint_asset: old_collateral_mint,
+            old_collateral_asset: pda::asset(&old_collateral_mint).0,
+            new_collateral_mint_asset: new_collateral_mint,
+            new_collateral_asset: pda::asset(&new_collateral_mint).0,
+            setting_account: pda::setting_account(&tier_id).0,
+            loan_offer: pda::loan_offer(&borrower, &args.loan_offer_id).0,
+            vault_authority: pda::vault_authority(&borrower).0,
+            old_vault: old_is_token.then(|| pda::vault(&borrower, &old_collateral_mint)),
+            borrower_ata_old_collateral_asset: old_is_token.then(|| pda::ata(&borrower, &old_collateral_mint)),
+            new_vault: new_is_token.then(|| pda::vault(&borrower, &new_collateral_mint)),
+            borrower_ata_new_collateral_asset: new_is_token.then(|| pda::ata(&borrower, &new_collateral_mint)),
+            lend_price_feed_account: lend_price_feed,
+            new_collateral_price_feed_account: new_collateral_price_feed,
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
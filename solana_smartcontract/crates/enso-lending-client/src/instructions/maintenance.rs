@@ -0,0 +1,63 @@
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::{instruction::Instruction, system_program},
+};
+use anchor_spl::token;
+use enso_lending::{accounts, instruction};
+
+use super::build;
+
+/// `rent_receiver` is `LoanOfferAccount::rent_receiver()`.
+/// `lend_offer` is the lend offer the loan was taken from, it has to be closed first.
+pub fn close_loan_offer(
+    signer: Pubkey,
+    rent_receiver: Pubkey,
+    loan_offer: Pubkey,
+    lend_offer: Pubkey,
+    args: instruction::CloseLoanOffer,
+) -> Instruction {
+    build(
+        accounts::CloseLoanOffer {
+            signer,
+            rent_receiver,
+            loan_offer,
+            lend_offer,
+        },
+        args,
+    )
+}
+
+/// `groups` are passed as writable remaining accounts: `[loan_offer, rent_receiver, lend_offer]`
+/// once the lend offer is closed, `[lend_offer, lender]` or `[lend_offer, lender, loan_offer]` for a
+/// loaned lend offer, followed by the lend offer vault for a lend offer with expiry, or
+/// `[vault, vault_authority, borrower]` for an empty collateral vault. List a loaned lend offer
+/// before its loan.
+pub fn batch_close_accounts(
+    cranker: Pubkey,
+    groups: &[Pubkey],
+    args: instruction::BatchCloseAccounts,
+) -> Instruction {
+    let mut instruction = build(
+        accounts::BatchCloseAccounts {
+            cranker,
+            token_program: Some(token::ID),
+        },
+        args,
+    );
+    instruction
+        .accounts
+        .extend(groups.iter().map(|account| AccountMeta::new(*account, false)));
+
+    instruction
+}
+
+pub fn migrate_account(payer: Pubkey, account: Pubkey, args: instruction::MigrateAccount) -> Instruction {
+    build(
+        accounts::MigrateAccount {
+            payer,
+            account,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
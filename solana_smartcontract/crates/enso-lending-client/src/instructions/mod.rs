@@ -0,0 +1,97 @@
+//! One builder per program instruction. Builders derive every PDA and ATA they can and take
+//! the arguments of the instruction as the anchor generated `enso_lending::instruction` struct.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, sysvar},
+    InstructionData, ToAccountMetas,
+};
+use wormhole_anchor_sdk::wormhole;
+
+use crate::pda;
+
+pub mod admin;
+pub use admin::*;
+
+pub mod lend_offer;
+pub use lend_offer::*;
+
+pub mod loan_offer;
+pub use loan_offer::*;
+
+pub mod liquidation;
+pub use liquidation::*;
+
+pub mod cross_chain;
+pub use cross_chain::*;
+
+pub mod position;
+pub use position::*;
+
+pub mod maintenance;
+pub use maintenance::*;
+
+pub mod view;
+pub use view::*;
+
+pub(crate) fn build(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: enso_lending::ID,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+/// Position token account of a creditor and the listing holding it, a listed position token
+/// sits in the listing's ATA while the seller stays the creditor.
+pub(crate) fn creditor_position(
+    creditor: &Pubkey,
+    position_mint: Option<Pubkey>,
+    listed: bool,
+) -> (Option<Pubkey>, Option<Pubkey>) {
+    let Some(position_mint) = position_mint else {
+        return (None, None);
+    };
+    let listing = listed.then(|| pda::loan_position_listing(&position_mint).0);
+
+    (Some(pda::ata(&listing.unwrap_or(*creditor), &position_mint)), listing)
+}
+
+/// Mints of a loan and the Pyth price update accounts configured on their `Asset`s.
+#[derive(Clone, Copy, Debug)]
+pub struct LoanAssets {
+    pub lend_mint: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub lend_price_feed: Pubkey,
+    pub collateral_price_feed: Pubkey,
+}
+
+/// Accounts used to post an outgoing Wormhole message, `sequence` is the next value of the
+/// emitter sequence tracker.
+pub struct WormholeMessageAccounts {
+    pub config: Pubkey,
+    pub wormhole_program: Pubkey,
+    pub wormhole_bridge: Pubkey,
+    pub wormhole_fee_collector: Pubkey,
+    pub wormhole_emitter: Pubkey,
+    pub wormhole_sequence: Pubkey,
+    pub wormhole_message: Pubkey,
+    pub clock: Pubkey,
+    pub rent: Pubkey,
+}
+
+impl WormholeMessageAccounts {
+    pub fn new(sequence: u64) -> Self {
+        Self {
+            config: pda::wormhole_config().0,
+            wormhole_program: wormhole::program::ID,
+            wormhole_bridge: pda::wormhole_bridge().0,
+            wormhole_fee_collector: pda::wormhole_fee_collector().0,
+            wormhole_emitter: pda::wormhole_emitter().0,
+            wormhole_sequence: pda::wormhole_sequence().0,
+            wormhole_message: pda::wormhole_message(sequence).0,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+        }
+    }
+}
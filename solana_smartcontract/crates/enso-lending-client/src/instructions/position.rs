@@ -0,0 +1,99 @@
+use anchor_lang::{prelude::Pubkey, solana_program::{instruction::Instruction, system_program}};
+use anchor_spl::{associated_token, token};
+use enso_lending::{accounts, instruction};
+
+use super::{build, LoanAssets};
+use crate::pda;
+
+pub fn list_loan_position(
+    seller: Pubkey,
+    loan_offer: Pubkey,
+    position_mint: Pubkey,
+    args: instruction::ListLoanPosition,
+) -> Instruction {
+    let listing = pda::loan_position_listing(&position_mint).0;
+
+    build(
+        accounts::ListLoanPosition {
+            seller,
+            loan_offer,
+            position_mint,
+            seller_position_ata: pda::ata(&seller, &position_mint),
+            listing,
+            listing_position_ata: pda::ata(&listing, &position_mint),
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn cancel_loan_position_listing(
+    seller: Pubkey,
+    position_mint: Pubkey,
+    args: instruction::CancelLoanPositionListing,
+) -> Instruction {
+    let listing = pda::loan_position_listing(&position_mint).0;
+
+    build(
+        accounts::CancelLoanPositionListing {
+            seller,
+            position_mint,
+            seller_position_ata: pda::ata(&seller, &position_mint),
+            listing,
+            listing_position_ata: pda::ata(&listing, &position_mint),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn buy_loan_position(
+    buyer: Pubkey,
+    seller: Pubkey,
+    loan_offer: Pubkey,
+    position_mint: Pubkey,
+    lend_mint: Pubkey,
+    args: instruction::BuyLoanPosition,
+) -> Instruction {
+    let listing = pda::loan_position_listing(&position_mint).0;
+
+    build(
+        accounts::BuyLoanPosition {
+            buyer,
+            seller,
+            loan_offer,
+            position_mint,
+            lend_mint_asset: lend_mint,
+            listing,
+            listing_position_ata: pda::ata(&listing, &position_mint),
+            buyer_position_ata: pda::ata(&buyer, &position_mint),
+            buyer_ata_asset: pda::ata(&buyer, &lend_mint),
+            seller_ata_asset: pda::ata(&seller, &lend_mint),
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn view_loan_position_listing(
+    loan_offer: Pubkey,
+    position_mint: Pubkey,
+    assets: &LoanAssets,
+    args: instruction::ViewLoanPositionListing,
+) -> Instruction {
+    build(
+        accounts::ViewLoanPositionListing {
+            listing: pda::loan_position_listing(&position_mint).0,
+            loan_offer,
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&assets.collateral_mint).0,
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+        },
+        args,
+    )
+}
@@ -0,0 +1,49 @@
+//! Builders for the read-only instructions, meant to be simulated. The returned value is the
+//! borsh encoded return data of the simulation.
+
+use anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction, InstructionData};
+use enso_lending::{accounts, instruction};
+
+use super::{build, LoanAssets};
+use crate::pda;
+
+fn view_loan_offer(loan_offer: Pubkey, assets: &LoanAssets, args: impl InstructionData) -> Instruction {
+    build(
+        accounts::ViewLoanOffer {
+            loan_offer,
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&assets.collateral_mint).0,
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+        },
+        args,
+    )
+}
+
+pub fn view_loan_health(loan_offer: Pubkey, assets: &LoanAssets) -> Instruction {
+    view_loan_offer(loan_offer, assets, instruction::ViewLoanHealth {})
+}
+
+pub fn quote_repay_loan_offer(loan_offer: Pubkey, assets: &LoanAssets) -> Instruction {
+    view_loan_offer(loan_offer, assets, instruction::QuoteRepayLoanOffer {})
+}
+
+pub fn view_max_withdrawable_collateral(loan_offer: Pubkey, assets: &LoanAssets) -> Instruction {
+    view_loan_offer(loan_offer, assets, instruction::ViewMaxWithdrawableCollateral {})
+}
+
+pub fn view_liquidation_price(loan_offer: Pubkey, assets: &LoanAssets) -> Instruction {
+    view_loan_offer(loan_offer, assets, instruction::ViewLiquidationPrice {})
+}
+
+pub fn quote_max_borrow(assets: &LoanAssets, args: instruction::QuoteMaxBorrow) -> Instruction {
+    build(
+        accounts::QuoteMaxBorrow {
+            lend_asset: pda::asset(&assets.lend_mint).0,
+            collateral_asset: pda::asset(&assets.collateral_mint).0,
+            lend_price_feed_account: assets.lend_price_feed,
+            collateral_price_feed_account: assets.collateral_price_feed,
+        },
+        args,
+    )
+}
@@ -0,0 +1,22 @@
+//! Rust client for the enso-lending program: PDA derivation, instruction builders and
+//! decoders for program accounts and events.
+
+pub mod error;
+pub use error::*;
+
+pub mod pda;
+
+pub mod accounts;
+pub use accounts::*;
+
+pub mod events;
+pub use events::*;
+
+pub mod compat;
+
+pub mod instructions;
+
+#[cfg(feature = "rpc")]
+pub mod rpc;
+
+pub use enso_lending::{common, instruction as args, risk, states, ID};
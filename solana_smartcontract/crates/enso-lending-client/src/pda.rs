@@ -0,0 +1,149 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use enso_lending::common::{
+    ASSET_SEED, ENSO_SEED, HOT_WALLET_PUBKEY, LEND_OFFER_ACCOUNT_SEED, LOAN_OFFER_ACCOUNT_SEED,
+    LOAN_POSITION_LISTING_SEED, LOAN_POSITION_MINT_SEED, OPERATE_SYSTEM_PUBKEY, SETTING_ACCOUNT_SEED,
+    TREASURY_SEED, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED, WORMHOLE_SENT_SEED,
+};
+use enso_lending::states::{WormholeConfig, WormholeEmitter};
+use wormhole_anchor_sdk::wormhole;
+
+pub fn hot_wallet() -> Pubkey {
+    Pubkey::from_str(HOT_WALLET_PUBKEY).unwrap()
+}
+
+pub fn operate_system() -> Pubkey {
+    Pubkey::from_str(OPERATE_SYSTEM_PUBKEY).unwrap()
+}
+
+pub fn ata(authority: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(authority, mint)
+}
+
+pub fn setting_account(tier_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ENSO_SEED, SETTING_ACCOUNT_SEED, tier_id.as_bytes(), enso_lending::ID.as_ref()],
+        &enso_lending::ID,
+    )
+}
+
+pub fn asset(token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ENSO_SEED, ASSET_SEED, token_mint.as_ref(), enso_lending::ID.as_ref()],
+        &enso_lending::ID,
+    )
+}
+
+pub fn lend_offer(lender: &Pubkey, offer_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ENSO_SEED,
+            LEND_OFFER_ACCOUNT_SEED,
+            lender.as_ref(),
+            offer_id.as_bytes(),
+            enso_lending::ID.as_ref(),
+        ],
+        &enso_lending::ID,
+    )
+}
+
+pub fn loan_offer(borrower: &Pubkey, offer_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ENSO_SEED,
+            LOAN_OFFER_ACCOUNT_SEED,
+            borrower.as_ref(),
+            offer_id.as_bytes(),
+            enso_lending::ID.as_ref(),
+        ],
+        &enso_lending::ID,
+    )
+}
+
+pub fn vault_authority(borrower: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ENSO_SEED,
+            borrower.as_ref(),
+            VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED,
+            enso_lending::ID.as_ref(),
+        ],
+        &enso_lending::ID,
+    )
+}
+
+/// Collateral vault of a borrower, the ATA of their vault authority.
+pub fn vault(borrower: &Pubkey, collateral_mint: &Pubkey) -> Pubkey {
+    ata(&vault_authority(borrower).0, collateral_mint)
+}
+
+pub fn treasury(token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ENSO_SEED, TREASURY_SEED, token_mint.as_ref(), enso_lending::ID.as_ref()],
+        &enso_lending::ID,
+    )
+}
+
+/// Token account protocol fees of a lend mint are paid into, the ATA of its treasury.
+pub fn treasury_ata(token_mint: &Pubkey) -> Pubkey {
+    ata(&treasury(token_mint).0, token_mint)
+}
+
+pub fn loan_position_mint(loan_offer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ENSO_SEED, LOAN_POSITION_MINT_SEED, loan_offer.as_ref(), enso_lending::ID.as_ref()],
+        &enso_lending::ID,
+    )
+}
+
+pub fn loan_position_listing(position_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ENSO_SEED, LOAN_POSITION_LISTING_SEED, position_mint.as_ref(), enso_lending::ID.as_ref()],
+        &enso_lending::ID,
+    )
+}
+
+pub fn foreign_chain(chain_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ENSO_SEED,
+            wormhole::SEED_PREFIX_EMITTER,
+            &chain_id.to_be_bytes(),
+            enso_lending::ID.as_ref(),
+        ],
+        &enso_lending::ID,
+    )
+}
+
+pub fn wormhole_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WormholeConfig::SEED_PREFIX], &enso_lending::ID)
+}
+
+pub fn wormhole_emitter() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WormholeEmitter::SEED_PREFIX], &enso_lending::ID)
+}
+
+pub fn wormhole_message(sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WORMHOLE_SENT_SEED, &sequence.to_le_bytes()[..]], &enso_lending::ID)
+}
+
+pub fn wormhole_bridge() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[wormhole::BridgeData::SEED_PREFIX], &wormhole::program::ID)
+}
+
+pub fn wormhole_fee_collector() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[wormhole::FeeCollector::SEED_PREFIX], &wormhole::program::ID)
+}
+
+pub fn wormhole_sequence() -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[wormhole::SequenceTracker::SEED_PREFIX, wormhole_emitter().0.as_ref()],
+        &wormhole::program::ID,
+    )
+}
+
+pub fn posted_vaa(vaa_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[wormhole::SEED_PREFIX_POSTED_VAA, vaa_hash], &wormhole::program::ID)
+}
@@ -0,0 +1,430 @@
+//! Blocking JSON-RPC client covering the calls our services make. `solana-client` is not used,
+//! its dependency tree conflicts with the `solana-program` and `ahash` pins of the program.
+
+use std::{str::FromStr, thread, time::{Duration, Instant}};
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use solana_sdk::{hash::Hash, signature::Signature, transaction::Transaction};
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    error::{ClientError, Result},
+    pda,
+};
+
+pub const DEFAULT_COMMITMENT: &str = "confirmed";
+
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub enum RpcFilter {
+    Memcmp { offset: usize, bytes: Vec<u8> },
+    DataSize(u64),
+}
+
+impl RpcFilter {
+    fn to_json(&self) -> Value {
+        match self {
+            Self::Memcmp { offset, bytes } => json!({
+                "memcmp": { "offset": offset, "bytes": STANDARD.encode(bytes), "encoding": "base64" }
+            }),
+            Self::DataSize(size) => json!({ "dataSize": size }),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SimulationResult {
+    /// Transaction error as returned by the node, `None` when the simulation succeeded.
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    pub return_data: Option<Vec<u8>>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct WithContext<T> {
+    value: T,
+}
+
+#[derive(Deserialize)]
+struct UiAccount {
+    data: (String, String),
+}
+
+#[derive(Deserialize)]
+struct KeyedAccount {
+    pubkey: String,
+    account: UiAccount,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiSimulation {
+    err: Option<Value>,
+    logs: Option<Vec<String>>,
+    units_consumed: Option<u64>,
+    return_data: Option<UiReturnData>,
+}
+
+#[derive(Deserialize)]
+struct UiReturnData {
+    data: (String, String),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiSignatureStatus {
+    err: Option<Value>,
+    confirmation_status: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiPrioritizationFee {
+    prioritization_fee: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiSignatureInfo {
+    signature: String,
+    slot: u64,
+    err: Option<Value>,
+    block_time: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiTransaction {
+    slot: u64,
+    block_time: Option<i64>,
+    meta: Option<UiTransactionMeta>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiTransactionMeta {
+    err: Option<Value>,
+    log_messages: Option<Vec<String>>,
+}
+
+/// Entry of `getSignaturesForAddress`, newest first.
+#[derive(Debug)]
+pub struct SignatureInfo {
+    pub signature: Signature,
+    pub slot: u64,
+    /// Transaction error, `None` when it succeeded.
+    pub err: Option<Value>,
+    pub block_time: Option<i64>,
+}
+
+/// Outcome and log messages of a landed transaction.
+#[derive(Debug)]
+pub struct TransactionLogs {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+}
+
+/// Confirmation state of a sent transaction.
+#[derive(Debug)]
+pub enum SignatureStatus {
+    Pending,
+    Failed(Value),
+    Confirmed,
+}
+
+pub struct RpcClient {
+    url: String,
+    commitment: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_commitment(url, DEFAULT_COMMITMENT)
+    }
+
+    pub fn with_commitment(url: impl Into<String>, commitment: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            commitment: commitment.into(),
+            agent: ureq::Agent::new(),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: RpcResponse<T> = self
+            .agent
+            .post(&self.url)
+            .send_json(body)
+            .map_err(Box::new)?
+            .into_json()?;
+
+        if let Some(RpcError { code, message }) = response.error {
+            return Err(ClientError::Rpc { code, message });
+        }
+
+        response.result.ok_or(ClientError::InvalidResponse("result"))
+    }
+
+    /// Like `request`, for methods answering `null` when nothing is found.
+    pub fn request_optional<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<Option<T>> {
+        match self.request::<Option<T>>(method, params) {
+            Err(ClientError::InvalidResponse("result")) => Ok(None),
+            result => result,
+        }
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<Hash> {
+        #[derive(Deserialize)]
+        struct Blockhash {
+            blockhash: String,
+        }
+
+        let response: WithContext<Blockhash> =
+            self.request("getLatestBlockhash", json!([{ "commitment": self.commitment }]))?;
+        Hash::from_str(&response.value.blockhash).map_err(|_| ClientError::InvalidResponse("blockhash"))
+    }
+
+    pub fn get_slot(&self) -> Result<u64> {
+        self.request("getSlot", json!([{ "commitment": self.commitment }]))
+    }
+
+    pub fn get_account_data(&self, pubkey: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let response: WithContext<Option<UiAccount>> = self.request(
+            "getAccountInfo",
+            json!([pubkey.to_string(), { "encoding": "base64", "commitment": self.commitment }]),
+        )?;
+
+        response.value.map(|account| decode_data(&account.data)).transpose()
+    }
+
+    pub fn get_multiple_account_data(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        let keys: Vec<String> = pubkeys.iter().map(Pubkey::to_string).collect();
+        let response: WithContext<Vec<Option<UiAccount>>> = self.request(
+            "getMultipleAccounts",
+            json!([keys, { "encoding": "base64", "commitment": self.commitment }]),
+        )?;
+
+        response
+            .value
+            .into_iter()
+            .map(|account| account.map(|account| decode_data(&account.data)).transpose())
+            .collect()
+    }
+
+    /// Raw data of every enso-lending account matching `filters`.
+    pub fn get_program_accounts(&self, filters: &[RpcFilter]) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let filters: Vec<Value> = filters.iter().map(RpcFilter::to_json).collect();
+        let accounts: Vec<KeyedAccount> = self.request(
+            "getProgramAccounts",
+            json!([
+                enso_lending::ID.to_string(),
+                { "encoding": "base64", "commitment": self.commitment, "filters": filters }
+            ]),
+        )?;
+
+        accounts
+            .into_iter()
+            .map(|keyed| {
+                let pubkey = Pubkey::from_str(&keyed.pubkey).map_err(|_| ClientError::InvalidResponse("pubkey"))?;
+                Ok((pubkey, decode_data(&keyed.account.data)?))
+            })
+            .collect()
+    }
+
+    /// Every account of type `T`, selected by its discriminator. Accounts that fail to decode,
+    /// such as ones not yet migrated to the current layout, are skipped.
+    pub fn get_accounts<T: AccountDeserialize + Discriminator>(&self) -> Result<Vec<(Pubkey, T)>> {
+        let filter = RpcFilter::Memcmp {
+            offset: 0,
+            bytes: T::DISCRIMINATOR.to_vec(),
+        };
+
+        Ok(self
+            .get_program_accounts(&[filter])?
+            .into_iter()
+            .filter_map(|(pubkey, data)| crate::decode::<T>(&data).ok().map(|account| (pubkey, account)))
+            .collect())
+    }
+
+    /// Value the program derives the next Wormhole message account from, the `sequence`
+    /// argument of the cross-chain builders.
+    pub fn get_next_wormhole_sequence(&self) -> Result<u64> {
+        let sequence = pda::wormhole_sequence().0;
+        let data = self.get_account_data(&sequence)?.ok_or(ClientError::AccountNotFound(sequence))?;
+
+        Ok(crate::decode::<wormhole::SequenceTracker>(&data)?.next_value())
+    }
+
+    /// Simulates without checking signatures and with the latest blockhash, so unsigned and
+    /// offline built transactions can be simulated as well.
+    pub fn simulate_transaction(&self, transaction: &Transaction) -> Result<SimulationResult> {
+        let response: WithContext<UiSimulation> = self.request(
+            "simulateTransaction",
+            json!([
+                encode_transaction(transaction)?,
+                {
+                    "encoding": "base64",
+                    "commitment": self.commitment,
+                    "sigVerify": false,
+                    "replaceRecentBlockhash": true
+                }
+            ]),
+        )?;
+        let simulation = response.value;
+
+        Ok(SimulationResult {
+            err: simulation.err,
+            logs: simulation.logs.unwrap_or_default(),
+            units_consumed: simulation.units_consumed,
+            return_data: simulation.return_data.map(|data| decode_data(&data.data)).transpose()?,
+        })
+    }
+
+    pub fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let signature: String = self.request(
+            "sendTransaction",
+            json!([
+                encode_transaction(transaction)?,
+                { "encoding": "base64", "preflightCommitment": self.commitment }
+            ]),
+        )?;
+
+        Signature::from_str(&signature).map_err(|_| ClientError::InvalidResponse("signature"))
+    }
+
+    pub fn get_signature_status(&self, signature: &Signature) -> Result<SignatureStatus> {
+        let response: WithContext<Vec<Option<UiSignatureStatus>>> =
+            self.request("getSignatureStatuses", json!([[signature.to_string()]]))?;
+
+        Ok(match response.value.into_iter().next().flatten() {
+            None => SignatureStatus::Pending,
+            Some(UiSignatureStatus { err: Some(err), .. }) => SignatureStatus::Failed(err),
+            Some(UiSignatureStatus { confirmation_status, .. }) => match confirmation_status.as_deref() {
+                Some("confirmed") | Some("finalized") => SignatureStatus::Confirmed,
+                _ => SignatureStatus::Pending,
+            },
+        })
+    }
+
+    /// Polls `signature` until it is confirmed or failed, `Pending` once `timeout` elapses.
+    pub fn confirm_transaction(&self, signature: &Signature, timeout: Duration) -> Result<SignatureStatus> {
+        let started = Instant::now();
+
+        loop {
+            let status = self.get_signature_status(signature)?;
+            if !matches!(status, SignatureStatus::Pending) || started.elapsed() >= timeout {
+                return Ok(status);
+            }
+
+            thread::sleep(CONFIRM_POLL_INTERVAL);
+        }
+    }
+
+    /// Signatures of transactions referencing `address`, newest first, older than `before` and
+    /// newer than `until` when given.
+    pub fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&Signature>,
+        until: Option<&Signature>,
+        limit: usize,
+    ) -> Result<Vec<SignatureInfo>> {
+        let mut config = json!({ "commitment": self.commitment, "limit": limit });
+        if let Some(before) = before {
+            config["before"] = json!(before.to_string());
+        }
+        if let Some(until) = until {
+            config["until"] = json!(until.to_string());
+        }
+
+        let infos: Vec<UiSignatureInfo> =
+            self.request("getSignaturesForAddress", json!([address.to_string(), config]))?;
+
+        infos
+            .into_iter()
+            .map(|info| {
+                Ok(SignatureInfo {
+                    signature: Signature::from_str(&info.signature)
+                        .map_err(|_| ClientError::InvalidResponse("signature"))?,
+                    slot: info.slot,
+                    err: info.err,
+                    block_time: info.block_time,
+                })
+            })
+            .collect()
+    }
+
+    /// `None` while the transaction is unknown to the node at the client commitment.
+    pub fn get_transaction_logs(&self, signature: &Signature) -> Result<Option<TransactionLogs>> {
+        let transaction: Option<UiTransaction> = self.request_optional(
+            "getTransaction",
+            json!([
+                signature.to_string(),
+                { "encoding": "json", "commitment": self.commitment, "maxSupportedTransactionVersion": 0 }
+            ]),
+        )?;
+
+        Ok(transaction.map(|transaction| {
+            let meta = transaction.meta.unwrap_or(UiTransactionMeta {
+                err: None,
+                log_messages: None,
+            });
+
+            TransactionLogs {
+                slot: transaction.slot,
+                block_time: transaction.block_time,
+                err: meta.err,
+                logs: meta.log_messages.unwrap_or_default(),
+            }
+        }))
+    }
+
+    /// Prioritization fees in micro-lamports per compute unit paid in recent slots by
+    /// transactions writing to `accounts`.
+    pub fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> Result<Vec<u64>> {
+        let keys: Vec<String> = accounts.iter().map(Pubkey::to_string).collect();
+        let fees: Vec<UiPrioritizationFee> = self.request("getRecentPrioritizationFees", json!([keys]))?;
+
+        Ok(fees.into_iter().map(|fee| fee.prioritization_fee).collect())
+    }
+}
+
+pub fn encode_transaction(transaction: &Transaction) -> Result<String> {
+    Ok(STANDARD.encode(bincode::serialize(transaction)?))
+}
+
+pub fn decode_transaction(encoded: &str) -> Result<Transaction> {
+    Ok(bincode::deserialize(&STANDARD.decode(encoded.trim())?)?)
+}
+
+fn decode_data((data, encoding): &(String, String)) -> Result<Vec<u8>> {
+    if encoding != "base64" {
+        return Err(ClientError::InvalidResponse("account encoding"));
+    }
+
+    Ok(STANDARD.decode(data)?)
+}
@@ -0,0 +1,119 @@
+//! Events logged before the event header, decoded from their raw `Program data:` bytes.
+
+use anchor_lang::{prelude::Pubkey, solana_program::hash::hash, AnchorSerialize};
+use enso_lending_client::{
+    common::LoanOfferStatus,
+    compat::LegacyEvent,
+    ClientError, ProgramEvent,
+};
+
+fn event_data(name: &str, body: impl AnchorSerialize) -> Vec<u8> {
+    let mut data = hash(format!("event:{name}").as_bytes()).to_bytes()[..8].to_vec();
+    data.extend(body.try_to_vec().unwrap());
+    data
+}
+
+#[test]
+fn decodes_borsh_layout_events() {
+    let borrower = Pubkey::new_unique();
+    let data = event_data(
+        "RepayLoanOfferEvent",
+        (borrower, "loan".to_string(), 1_000_u64, 1.5_f64, LoanOfferStatus::BorrowerPaid),
+    );
+
+    let LegacyEvent::RepayLoanOfferEvent(event) = LegacyEvent::decode(&data).unwrap() else {
+        panic!("decoded another event");
+    };
+    assert_eq!(event.borrower, borrower);
+    assert_eq!(event.loan_offer_id, "loan");
+    assert_eq!(event.repay_amount, 1_000);
+    assert_eq!(event.status, LoanOfferStatus::BorrowerPaid);
+}
+
+#[test]
+fn liquidation_start_decodes_both_layouts() {
+    let borrower = Pubkey::new_unique();
+    let current = event_data(
+        "LiquidatingCollateralEvent",
+        ("loan".to_string(), borrower, 15_u64, Some(70.0_f64), Some(1_700_000_000_i64)),
+    );
+    let initial = event_data(
+        "LiquidatingCollateralEvent",
+        ("loan".to_string(), Some(70.0_f64), Some(1_700_000_000_i64)),
+    );
+
+    let LegacyEvent::LiquidatingCollateralEvent(event) = LegacyEvent::decode(&current).unwrap() else {
+        panic!("decoded another event");
+    };
+    assert_eq!(event.borrower, Some(borrower));
+    assert_eq!(event.collateral_amount, Some(15));
+    assert_eq!(event.liquidating_price, Some(70.0));
+
+    let LegacyEvent::LiquidatingCollateralEvent(event) = LegacyEvent::decode(&initial).unwrap() else {
+        panic!("decoded another event");
+    };
+    assert_eq!(event.offer_id, "loan");
+    assert_eq!(event.borrower, None);
+    assert_eq!(event.collateral_amount, None);
+    assert_eq!(event.liquidating_at, Some(1_700_000_000));
+}
+
+#[test]
+fn system_finish_decodes_both_layouts() {
+    let [system, lender, borrower] = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let head = (system, lender, borrower, 5.0_f64, 1_000_u64);
+    let tail = ("loan".to_string(), "tier".to_string(), LoanOfferStatus::Finished);
+    let current = event_data("SystemFinishLoanOfferEvent", (head, 10_u64, 1_010_u64, tail.clone()));
+    let initial = event_data("SystemFinishLoanOfferEvent", (head, tail));
+
+    let LegacyEvent::SystemFinishLoanOfferEvent(event) = LegacyEvent::decode(&current).unwrap() else {
+        panic!("decoded another event");
+    };
+    assert_eq!(event.waiting_interest, Some(10));
+    assert_eq!(event.total_repay_to_lender, Some(1_010));
+    assert_eq!(event.tier_id, "tier");
+
+    let LegacyEvent::SystemFinishLoanOfferEvent(event) = LegacyEvent::decode(&initial).unwrap() else {
+        panic!("decoded another event");
+    };
+    assert_eq!(event.lender, lender);
+    assert_eq!(event.waiting_interest, None);
+    assert_eq!(event.total_repay_to_lender, None);
+    assert_eq!(event.status, LoanOfferStatus::Finished);
+}
+
+#[test]
+fn system_revert_decodes_both_layouts() {
+    let borrower = Pubkey::new_unique();
+    let current = event_data("SystemRevertEvent", ("loan".to_string(), borrower, LoanOfferStatus::FundTransferred));
+    let initial = event_data("SystemRevertEvent", ("loan".to_string(), LoanOfferStatus::FundTransferred));
+
+    let LegacyEvent::SystemRevertEvent(event) = LegacyEvent::decode(&current).unwrap() else {
+        panic!("decoded another event");
+    };
+    assert_eq!(event.borrower, Some(borrower));
+
+    let LegacyEvent::SystemRevertEvent(event) = LegacyEvent::decode(&initial).unwrap() else {
+        panic!("decoded another event");
+    };
+    assert_eq!(event.borrower, None);
+    assert_eq!(event.status, LoanOfferStatus::FundTransferred);
+}
+
+#[test]
+fn program_events_fall_back_to_legacy_schema() {
+    let data = event_data("CloseSettingAccountEvent", "tier".to_string());
+
+    let ProgramEvent::Legacy(LegacyEvent::CloseSettingAccountEvent(event)) = ProgramEvent::decode(&data).unwrap() else {
+        panic!("decoded another event");
+    };
+    assert_eq!(event.tier_id, "tier");
+}
+
+#[test]
+fn rejects_unknown_and_truncated_data() {
+    let data = event_data("NeverEmittedEvent", 0_u8);
+    assert!(matches!(LegacyEvent::decode(&data), Err(ClientError::UnknownEventDiscriminator(_))));
+    assert!(matches!(ProgramEvent::decode(&data), Err(ClientError::UnknownEventDiscriminator(_))));
+    assert!(matches!(LegacyEvent::decode(&data[..4]), Err(ClientError::MissingDiscriminator)));
+}
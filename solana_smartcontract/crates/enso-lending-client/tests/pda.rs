@@ -0,0 +1,76 @@
+//! PDAs derived by the client against seeds spelled out as the program's `seeds` constraints.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use enso_lending_client::{pda, ID};
+
+fn program_address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &ID)
+}
+
+#[test]
+fn offer_accounts_are_keyed_by_owner_and_offer_id() {
+    let owner = Pubkey::new_unique();
+
+    assert_eq!(
+        pda::lend_offer(&owner, "offer"),
+        program_address(&[b"enso", b"lend_offer", owner.as_ref(), b"offer", ID.as_ref()])
+    );
+    assert_eq!(
+        pda::loan_offer(&owner, "offer"),
+        program_address(&[b"enso", b"loan_offer", owner.as_ref(), b"offer", ID.as_ref()])
+    );
+    assert_ne!(pda::lend_offer(&owner, "offer").0, pda::loan_offer(&owner, "offer").0);
+    assert_ne!(pda::lend_offer(&owner, "offer").0, pda::lend_offer(&owner, "other").0);
+}
+
+#[test]
+fn configuration_accounts_match_program_seeds() {
+    let mint = Pubkey::new_unique();
+
+    assert_eq!(
+        pda::setting_account("tier"),
+        program_address(&[b"enso", b"setting_account", b"tier", ID.as_ref()])
+    );
+    assert_eq!(pda::asset(&mint), program_address(&[b"enso", b"asset", mint.as_ref(), ID.as_ref()]));
+    assert_eq!(pda::treasury(&mint), program_address(&[b"enso", b"treasury", mint.as_ref(), ID.as_ref()]));
+}
+
+#[test]
+fn vaults_are_associated_token_accounts_of_their_authority() {
+    let borrower = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+
+    let vault_authority = pda::vault_authority(&borrower);
+    assert_eq!(
+        vault_authority,
+        program_address(&[b"enso", borrower.as_ref(), b"vault_authority_loan_offer", ID.as_ref()])
+    );
+    assert_eq!(pda::vault(&borrower, &mint), get_associated_token_address(&vault_authority.0, &mint));
+    assert_eq!(pda::treasury_ata(&mint), get_associated_token_address(&pda::treasury(&mint).0, &mint));
+}
+
+#[test]
+fn position_accounts_chain_from_the_loan() {
+    let loan_offer = Pubkey::new_unique();
+
+    let position_mint = pda::loan_position_mint(&loan_offer);
+    assert_eq!(
+        position_mint,
+        program_address(&[b"enso", b"loan_position_mint", loan_offer.as_ref(), ID.as_ref()])
+    );
+    assert_eq!(
+        pda::loan_position_listing(&position_mint.0),
+        program_address(&[b"enso", b"loan_position_listing", position_mint.0.as_ref(), ID.as_ref()])
+    );
+}
+
+#[test]
+fn wormhole_accounts_encode_chain_and_sequence() {
+    assert_eq!(
+        pda::foreign_chain(21),
+        program_address(&[b"enso", b"emitter", &[0, 21], ID.as_ref()])
+    );
+    assert_eq!(pda::wormhole_message(1), program_address(&[b"sent", &[1, 0, 0, 0, 0, 0, 0, 0]]));
+    assert_ne!(pda::wormhole_message(1).0, pda::wormhole_message(2).0);
+}
@@ -0,0 +1,14 @@
+use enso_lending_client::ClientError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IndexerError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("invalid {0} stored in the database")]
+    InvalidRow(&'static str),
+}
+
+pub type Result<T> = std::result::Result<T, IndexerError>;
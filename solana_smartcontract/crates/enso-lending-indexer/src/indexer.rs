@@ -0,0 +1,160 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+use enso_lending_client::{
+    decode, program_data,
+    rpc::{RpcClient, SignatureInfo},
+    states::{LendOfferAccount, LoanOfferAccount},
+    ProgramEvent, ID,
+};
+
+use crate::{
+    error::Result,
+    replay::{diff, replay, ChainState, Divergence},
+    store::{IndexedTransaction, Store, StoredEvent},
+    transition::{transitions, SubjectKind},
+};
+
+/// Largest page `getSignaturesForAddress` returns.
+const SIGNATURE_PAGE: usize = 1000;
+/// `getMultipleAccounts` accepts at most 100 keys per call.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+pub struct Indexer {
+    rpc: RpcClient,
+    store: Store,
+}
+
+impl Indexer {
+    pub fn new(rpc: RpcClient, store: Store) -> Self {
+        Self { rpc, store }
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Indexes every program transaction newer than the last indexed one, returns how many
+    /// were added.
+    pub fn sync(&mut self) -> Result<usize> {
+        let until = self.store.latest_signature()?;
+        let mut before = None;
+        let mut pending: Vec<SignatureInfo> = vec![];
+
+        loop {
+            let page = self
+                .rpc
+                .get_signatures_for_address(&ID, before.as_ref(), until.as_ref(), SIGNATURE_PAGE)?;
+            let last_page = page.len() < SIGNATURE_PAGE;
+            before = page.last().map(|info| info.signature);
+            pending.extend(page);
+
+            if last_page {
+                break;
+            }
+        }
+
+        let count = pending.len();
+        for info in pending.into_iter().rev() {
+            let transaction = self.fetch_transaction(info)?;
+            self.store.insert_transaction(&transaction)?;
+        }
+
+        Ok(count)
+    }
+
+    /// Replays every indexed account, compares it with the chain and records the divergences.
+    pub fn verify(&mut self) -> Result<Vec<Divergence>> {
+        let subjects = self.store.subjects()?;
+        let mut divergences = vec![];
+
+        for chunk in subjects.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let addresses: Vec<Pubkey> = chunk.iter().map(|(_, subject)| *subject).collect();
+            let accounts = self.rpc.get_multiple_account_data(&addresses)?;
+
+            for ((kind, subject), data) in chunk.iter().zip(accounts) {
+                let Some(chain) = chain_state(*kind, subject, data.as_deref()) else {
+                    continue;
+                };
+                let replayed = replay(&self.store.timeline(subject)?);
+                divergences.extend(diff(*kind, *subject, &replayed, &chain));
+            }
+        }
+
+        let checked_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or_default();
+        self.store.record_divergences(&divergences, checked_at)?;
+
+        for divergence in &divergences {
+            log::warn!(
+                "{} {} diverges on {}: replayed {}, on chain {}",
+                divergence.kind,
+                divergence.subject,
+                divergence.field,
+                divergence.replayed,
+                divergence.on_chain
+            );
+        }
+
+        Ok(divergences)
+    }
+
+    /// Failed transactions are kept, without events, so the next sync resumes after them.
+    fn fetch_transaction(&self, info: SignatureInfo) -> Result<IndexedTransaction> {
+        let mut transaction = IndexedTransaction {
+            signature: info.signature,
+            slot: info.slot,
+            block_time: info.block_time,
+            failed: info.err.is_some(),
+            events: vec![],
+        };
+        if transaction.failed {
+            return Ok(transaction);
+        }
+
+        let Some(logs) = self.rpc.get_transaction_logs(&info.signature)? else {
+            log::warn!("transaction {} not found, indexed without events", info.signature);
+            return Ok(transaction);
+        };
+        transaction.block_time = logs.block_time.or(transaction.block_time);
+
+        for data in program_data(&logs.logs)? {
+            let event = match ProgramEvent::decode(&data) {
+                Ok(event) => StoredEvent {
+                    name: Some(event.name()),
+                    transitions: transitions(&event),
+                    data,
+                },
+                Err(err) => {
+                    log::warn!("undecodable event in {}: {err}", info.signature);
+                    StoredEvent {
+                        name: None,
+                        transitions: vec![],
+                        data,
+                    }
+                }
+            };
+            transaction.events.push(event);
+        }
+
+        Ok(transaction)
+    }
+}
+
+/// `None` when the account exists but cannot be decoded, such as one awaiting migration.
+fn chain_state(kind: SubjectKind, subject: &Pubkey, data: Option<&[u8]>) -> Option<ChainState> {
+    let Some(data) = data else {
+        return Some(ChainState::missing());
+    };
+
+    let chain = match kind {
+        SubjectKind::Loan => decode::<LoanOfferAccount>(data).map(|account| ChainState::loan(&account)),
+        SubjectKind::LendOffer => decode::<LendOfferAccount>(data).map(|account| ChainState::lend_offer(&account)),
+    };
+
+    chain
+        .map_err(|err| log::warn!("skipping {kind} {subject}, failed to decode: {err}"))
+        .ok()
+}
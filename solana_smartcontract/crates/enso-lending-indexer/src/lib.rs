@@ -0,0 +1,18 @@
+//! Indexer rebuilding the history of enso-lending loans and lend offers from the events in
+//! transaction logs. Events and the per account timeline they imply are stored in SQLite, and
+//! the replayed state is checked against the accounts on chain.
+
+pub mod error;
+pub use error::*;
+
+pub mod transition;
+pub use transition::*;
+
+pub mod replay;
+pub use replay::*;
+
+pub mod store;
+pub use store::*;
+
+pub mod indexer;
+pub use indexer::*;
@@ -0,0 +1,113 @@
+//! Folds a timeline into the account state it implies and compares it with the chain.
+
+use anchor_lang::prelude::Pubkey;
+use enso_lending_client::states::{LendOfferAccount, LoanOfferAccount};
+
+use crate::{
+    store::TimelineEntry,
+    transition::{PartialState, SubjectKind},
+};
+
+/// Field of an account whose replayed value differs from the one on chain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Divergence {
+    pub kind: SubjectKind,
+    pub subject: Pubkey,
+    pub field: &'static str,
+    pub replayed: String,
+    pub on_chain: String,
+}
+
+/// Account fields as of the last entry. Fields no event set stay `None`, which happens when
+/// indexing started after the account was created.
+pub fn replay(timeline: &[TimelineEntry]) -> PartialState {
+    timeline.iter().fold(PartialState::default(), |mut state, entry| {
+        state.apply(&entry.state);
+        state
+    })
+}
+
+/// On chain view of a loan or lend offer, `None` fields are not compared.
+pub struct ChainState {
+    pub exists: bool,
+    pub state: PartialState,
+}
+
+impl ChainState {
+    pub fn missing() -> Self {
+        Self {
+            exists: false,
+            state: PartialState::default(),
+        }
+    }
+
+    pub fn loan(account: &LoanOfferAccount) -> Self {
+        Self {
+            exists: true,
+            state: PartialState {
+                status: Some(format!("{:?}", account.status)),
+                amount: Some(account.borrow_amount),
+                collateral_mint: Some(account.collateral_mint_token),
+                collateral_amount: Some(account.collateral_amount),
+                closed: Some(false),
+            },
+        }
+    }
+
+    pub fn lend_offer(account: &LendOfferAccount) -> Self {
+        Self {
+            exists: true,
+            state: PartialState {
+                status: Some(format!("{:?}", account.status)),
+                amount: Some(account.amount),
+                collateral_mint: None,
+                collateral_amount: None,
+                closed: Some(false),
+            },
+        }
+    }
+}
+
+pub fn diff(kind: SubjectKind, subject: Pubkey, replayed: &PartialState, chain: &ChainState) -> Vec<Divergence> {
+    let divergence = |field, replayed: String, on_chain: String| Divergence {
+        kind,
+        subject,
+        field,
+        replayed,
+        on_chain,
+    };
+    let closed = replayed.closed == Some(true);
+
+    if closed || !chain.exists {
+        return if closed == chain.exists {
+            vec![divergence("exists", (!closed).to_string(), chain.exists.to_string())]
+        } else {
+            vec![]
+        };
+    }
+
+    let mut divergences = vec![];
+    let mut compare = |field, replayed: Option<String>, on_chain: Option<String>| {
+        if let (Some(replayed), Some(on_chain)) = (replayed, on_chain) {
+            if replayed != on_chain {
+                divergences.push(divergence(field, replayed, on_chain));
+            }
+        }
+    };
+    let on_chain = &chain.state;
+
+    compare("status", replayed.status.clone(), on_chain.status.clone());
+    compare("amount", replayed.amount.map(|v| v.to_string()), on_chain.amount.map(|v| v.to_string()));
+    compare(
+        "collateral_mint",
+        replayed.collateral_mint.map(|v| v.to_string()),
+        on_chain.collateral_mint.map(|v| v.to_string()),
+    );
+    compare(
+        "collateral_amount",
+        replayed.collateral_amount.map(|v| v.to_string()),
+        on_chain.collateral_amount.map(|v| v.to_string()),
+    );
+
+    divergences
+}
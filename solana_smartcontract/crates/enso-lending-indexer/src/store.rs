@@ -0,0 +1,264 @@
+//! SQLite storage of indexed transactions, events, per account transitions and divergences.
+
+use std::{path::Path, str::FromStr};
+
+use anchor_lang::prelude::Pubkey;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use solana_sdk::signature::Signature;
+
+use crate::{
+    error::{IndexerError, Result},
+    replay::Divergence,
+    transition::{PartialState, SubjectKind, Transition},
+};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    signature TEXT NOT NULL UNIQUE,
+    slot INTEGER NOT NULL,
+    block_time INTEGER,
+    failed INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_id INTEGER NOT NULL REFERENCES transactions(id),
+    event_index INTEGER NOT NULL,
+    name TEXT,
+    data BLOB NOT NULL
+);
+CREATE TABLE IF NOT EXISTS transitions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_id INTEGER NOT NULL REFERENCES events(id),
+    subject_kind TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    status TEXT,
+    amount INTEGER,
+    collateral_mint TEXT,
+    collateral_amount INTEGER,
+    closed INTEGER
+);
+CREATE INDEX IF NOT EXISTS transitions_subject ON transitions(subject);
+CREATE TABLE IF NOT EXISTS divergences (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    subject_kind TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    field TEXT NOT NULL,
+    replayed TEXT NOT NULL,
+    on_chain TEXT NOT NULL,
+    checked_at INTEGER NOT NULL
+);
+";
+
+/// Program event as logged, `name` is `None` when the event could not be decoded.
+pub struct StoredEvent {
+    pub name: Option<&'static str>,
+    pub data: Vec<u8>,
+    pub transitions: Vec<Transition>,
+}
+
+pub struct IndexedTransaction {
+    pub signature: Signature,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub failed: bool,
+    pub events: Vec<StoredEvent>,
+}
+
+/// One transition of an account timeline.
+#[derive(Clone, Debug)]
+pub struct TimelineEntry {
+    pub signature: Signature,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub event_index: u32,
+    pub event: Option<String>,
+    pub state: PartialState,
+}
+
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+
+        Ok(Self { connection })
+    }
+
+    /// Most recent indexed transaction, where the next sync resumes from.
+    pub fn latest_signature(&self) -> Result<Option<Signature>> {
+        self.connection
+            .query_row("SELECT signature FROM transactions ORDER BY id DESC LIMIT 1", [], |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()?
+            .map(|signature| parse_signature(&signature))
+            .transpose()
+    }
+
+    /// Stores a transaction with its events and transitions. Transactions must be inserted
+    /// oldest first, timelines follow insertion order.
+    pub fn insert_transaction(&mut self, transaction: &IndexedTransaction) -> Result<()> {
+        let db = self.connection.transaction()?;
+
+        db.execute(
+            "INSERT INTO transactions (signature, slot, block_time, failed) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                transaction.signature.to_string(),
+                transaction.slot,
+                transaction.block_time,
+                transaction.failed
+            ],
+        )?;
+        let transaction_id = db.last_insert_rowid();
+
+        for (index, event) in transaction.events.iter().enumerate() {
+            db.execute(
+                "INSERT INTO events (transaction_id, event_index, name, data) VALUES (?1, ?2, ?3, ?4)",
+                params![transaction_id, index as u32, event.name, event.data],
+            )?;
+            let event_id = db.last_insert_rowid();
+
+            for transition in &event.transitions {
+                let state = &transition.state;
+                db.execute(
+                    "INSERT INTO transitions (event_id, subject_kind, subject, status, amount, collateral_mint, \
+                     collateral_amount, closed) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        event_id,
+                        transition.kind.as_str(),
+                        transition.subject.to_string(),
+                        state.status,
+                        state.amount,
+                        state.collateral_mint.map(|mint| mint.to_string()),
+                        state.collateral_amount,
+                        state.closed
+                    ],
+                )?;
+            }
+        }
+
+        db.commit()?;
+
+        Ok(())
+    }
+
+    /// Every loan and lend offer with at least one transition.
+    pub fn subjects(&self) -> Result<Vec<(SubjectKind, Pubkey)>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT DISTINCT subject_kind, subject FROM transitions ORDER BY subject_kind, subject")?;
+        let rows = statement.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+
+        rows.map(|row| {
+            let (kind, subject) = row?;
+            Ok((
+                SubjectKind::from_str(&kind).map_err(|_| IndexerError::InvalidRow("subject kind"))?,
+                parse_pubkey(&subject)?,
+            ))
+        })
+        .collect()
+    }
+
+    /// Transitions of an account, oldest first.
+    pub fn timeline(&self, subject: &Pubkey) -> Result<Vec<TimelineEntry>> {
+        let mut statement = self.connection.prepare(
+            "SELECT t.signature, t.slot, t.block_time, e.event_index, e.name, r.status, r.amount, \
+             r.collateral_mint, r.collateral_amount, r.closed \
+             FROM transitions r JOIN events e ON e.id = r.event_id JOIN transactions t ON t.id = e.transaction_id \
+             WHERE r.subject = ?1 ORDER BY r.id",
+        )?;
+        let rows = statement.query_map([subject.to_string()], timeline_row)?;
+
+        rows.map(|row| {
+            let (signature, slot, block_time, event_index, event, mut state, collateral_mint) = row?;
+            state.collateral_mint = collateral_mint.map(|mint| parse_pubkey(&mint)).transpose()?;
+
+            Ok(TimelineEntry {
+                signature: parse_signature(&signature)?,
+                slot,
+                block_time,
+                event_index,
+                event,
+                state,
+            })
+        })
+        .collect()
+    }
+
+    /// Raw data of events that could not be decoded when indexed, with their signature.
+    pub fn undecoded_events(&self) -> Result<Vec<(Signature, Vec<u8>)>> {
+        let mut statement = self.connection.prepare(
+            "SELECT t.signature, e.data FROM events e JOIN transactions t ON t.id = e.transaction_id \
+             WHERE e.name IS NULL ORDER BY e.id",
+        )?;
+        let rows = statement.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?)))?;
+
+        rows.map(|row| {
+            let (signature, data) = row?;
+            Ok((parse_signature(&signature)?, data))
+        })
+        .collect()
+    }
+
+    pub fn record_divergences(&mut self, divergences: &[Divergence], checked_at: i64) -> Result<()> {
+        let db = self.connection.transaction()?;
+
+        for divergence in divergences {
+            db.execute(
+                "INSERT INTO divergences (subject_kind, subject, field, replayed, on_chain, checked_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    divergence.kind.as_str(),
+                    divergence.subject.to_string(),
+                    divergence.field,
+                    divergence.replayed,
+                    divergence.on_chain,
+                    checked_at
+                ],
+            )?;
+        }
+
+        db.commit()?;
+
+        Ok(())
+    }
+}
+
+type TimelineRow = (String, u64, Option<i64>, u32, Option<String>, PartialState, Option<String>);
+
+fn timeline_row(row: &Row) -> rusqlite::Result<TimelineRow> {
+    Ok((
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        row.get(3)?,
+        row.get(4)?,
+        PartialState {
+            status: row.get(5)?,
+            amount: row.get(6)?,
+            collateral_mint: None,
+            collateral_amount: row.get(8)?,
+            closed: row.get(9)?,
+        },
+        row.get(7)?,
+    ))
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| IndexerError::InvalidRow("pubkey"))
+}
+
+fn parse_signature(value: &str) -> Result<Signature> {
+    Signature::from_str(value).map_err(|_| IndexerError::InvalidRow("signature"))
+}
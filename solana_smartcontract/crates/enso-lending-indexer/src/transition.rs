@@ -0,0 +1,509 @@
+//! What each program event says about the loan or lend offer account it touched.
+
+use std::{fmt, str::FromStr};
+
+use anchor_lang::prelude::Pubkey;
+use enso_lending_client::{
+    common::{event::EventStatus, LendOfferStatus, LoanOfferStatus},
+    compat::LegacyEvent,
+    pda, EnsoEvent, ProgramEvent,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SubjectKind {
+    Loan,
+    LendOffer,
+}
+
+impl SubjectKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Loan => "loan",
+            Self::LendOffer => "lend_offer",
+        }
+    }
+}
+
+impl fmt::Display for SubjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SubjectKind {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "loan" => Ok(Self::Loan),
+            "lend_offer" => Ok(Self::LendOffer),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Account fields an event sets, `None` for the ones it says nothing about. Statuses are the
+/// `Debug` names of `LoanOfferStatus` and `LendOfferStatus`. `amount` is the borrow amount of
+/// a loan and the lend amount of a lend offer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartialState {
+    pub status: Option<String>,
+    pub amount: Option<u64>,
+    pub collateral_mint: Option<Pubkey>,
+    pub collateral_amount: Option<u64>,
+    pub closed: Option<bool>,
+}
+
+impl PartialState {
+    /// Overwrites the fields `other` sets.
+    pub fn apply(&mut self, other: &PartialState) {
+        if other.status.is_some() {
+            self.status.clone_from(&other.status);
+        }
+        self.amount = other.amount.or(self.amount);
+        self.collateral_mint = other.collateral_mint.or(self.collateral_mint);
+        self.collateral_amount = other.collateral_amount.or(self.collateral_amount);
+        self.closed = other.closed.or(self.closed);
+    }
+}
+
+/// Effect of one event on one account.
+#[derive(Clone, Debug)]
+pub struct Transition {
+    pub kind: SubjectKind,
+    pub subject: Pubkey,
+    pub state: PartialState,
+}
+
+fn loan_status(status: LoanOfferStatus) -> Option<String> {
+    Some(format!("{status:?}"))
+}
+
+fn lend_status(status: LendOfferStatus) -> Option<String> {
+    Some(format!("{status:?}"))
+}
+
+fn status_name(status: &EventStatus) -> String {
+    match status {
+        EventStatus::LoanOffer(status) => format!("{status:?}"),
+        EventStatus::LendOffer(status) => format!("{status:?}"),
+    }
+}
+
+fn loan(borrower: &Pubkey, offer_id: &str, state: PartialState) -> Transition {
+    Transition {
+        kind: SubjectKind::Loan,
+        subject: pda::loan_offer(borrower, offer_id).0,
+        state,
+    }
+}
+
+fn lend_offer(lender: &Pubkey, offer_id: &str, state: PartialState) -> Transition {
+    Transition {
+        kind: SubjectKind::LendOffer,
+        subject: pda::lend_offer(lender, offer_id).0,
+        state,
+    }
+}
+
+/// Loan and lend offer accounts touched by `event`. Configuration events (settings, assets,
+/// foreign chains, migrations) touch none of them.
+pub fn transitions(event: &ProgramEvent) -> Vec<Transition> {
+    match event {
+        ProgramEvent::Current(event) => current_transitions(event),
+        ProgramEvent::Legacy(event) => legacy_transitions(event),
+    }
+}
+
+/// The header names the account and its new status, the event adds the amounts it changed.
+fn current_transitions(event: &EnsoEvent) -> Vec<Transition> {
+    let header = event.header();
+    let state = PartialState {
+        status: header.new_status.as_ref().map(status_name),
+        ..Default::default()
+    };
+    let subject = |kind, state| Transition {
+        kind,
+        subject: header.subject,
+        state,
+    };
+
+    match event {
+        EnsoEvent::LendOfferCreated(event) => vec![subject(
+            SubjectKind::LendOffer,
+            PartialState {
+                amount: Some(event.amount),
+                closed: Some(false),
+                ..state
+            },
+        )],
+        EnsoEvent::LendOfferEdited(event) => vec![subject(
+            SubjectKind::LendOffer,
+            PartialState {
+                amount: Some(event.amount),
+                ..state
+            },
+        )],
+        EnsoEvent::LendOfferWithdrawn(_)
+        | EnsoEvent::LendOfferCancelRequested(_)
+        | EnsoEvent::LendOfferCanceled(_)
+        | EnsoEvent::LendOfferReclaimed(_) => vec![subject(SubjectKind::LendOffer, state)],
+        EnsoEvent::LendOfferClosed(_) => vec![subject(
+            SubjectKind::LendOffer,
+            PartialState {
+                closed: Some(true),
+                ..state
+            },
+        )],
+        EnsoEvent::LoanCreated(event) => vec![
+            subject(
+                SubjectKind::Loan,
+                PartialState {
+                    amount: Some(event.borrow_amount),
+                    collateral_mint: Some(event.collateral_mint_token),
+                    collateral_amount: Some(event.collateral_amount),
+                    closed: Some(false),
+                    ..state
+                },
+            ),
+            lend_offer(
+                &event.lender,
+                &event.lend_offer_id,
+                PartialState {
+                    status: lend_status(LendOfferStatus::Loaned),
+                    ..Default::default()
+                },
+            ),
+        ],
+        EnsoEvent::LoanUpdated(event) => vec![subject(
+            SubjectKind::Loan,
+            PartialState {
+                amount: Some(event.borrow_amount),
+                collateral_mint: Some(event.collateral_mint_token),
+                collateral_amount: Some(event.collateral_amount),
+                ..state
+            },
+        )],
+        EnsoEvent::CollateralDeposited(event) => vec![subject(
+            SubjectKind::Loan,
+            PartialState {
+                collateral_mint: Some(event.collateral_mint_token),
+                collateral_amount: Some(event.collateral_amount),
+                ..state
+            },
+        )],
+        EnsoEvent::CollateralWithdrawn(event) => vec![subject(
+            SubjectKind::Loan,
+            PartialState {
+                collateral_mint: Some(event.collateral_mint_token),
+                collateral_amount: Some(event.collateral_amount),
+                ..state
+            },
+        )],
+        EnsoEvent::CollateralSwapped(event) => vec![subject(
+            SubjectKind::Loan,
+            PartialState {
+                collateral_mint: Some(event.new_collateral_mint_token),
+                collateral_amount: Some(event.new_collateral_amount),
+                ..state
+            },
+        )],
+        EnsoEvent::CrossChainCollateralDeposited(event) => vec![subject(
+            SubjectKind::Loan,
+            PartialState {
+                collateral_amount: Some(event.collateral_amount),
+                ..state
+            },
+        )],
+        EnsoEvent::CrossChainCollateralWithdrawn(event) => vec![subject(
+            SubjectKind::Loan,
+            PartialState {
+                collateral_amount: Some(event.remaining_collateral_amount),
+                ..state
+            },
+        )],
+        EnsoEvent::CrossChainCollateralSwapped(event) => vec![subject(
+            SubjectKind::Loan,
+            PartialState {
+                collateral_amount: Some(event.new_collateral_amount),
+                ..state
+            },
+        )],
+        EnsoEvent::LiquidationSettled(_) => vec![subject(
+            SubjectKind::Loan,
+            PartialState {
+                collateral_amount: Some(0),
+                ..state
+            },
+        )],
+        EnsoEvent::LoanRepaid(_)
+        | EnsoEvent::LoanRepaidToLender(_)
+        | EnsoEvent::LiquidationStarted(_)
+        | EnsoEvent::LiquidationFinished(_)
+        | EnsoEvent::LoanFinished(_)
+        | EnsoEvent::LoanStatusReverted(_)
+        | EnsoEvent::LoanPositionListed(_)
+        | EnsoEvent::LoanPositionListingCanceled(_)
+        | EnsoEvent::LoanPositionBought(_) => vec![subject(SubjectKind::Loan, state)],
+        EnsoEvent::LoanClosed(_) => vec![subject(
+            SubjectKind::Loan,
+            PartialState {
+                closed: Some(true),
+                ..state
+            },
+        )],
+        EnsoEvent::SettingAccountInitialized(_)
+        | EnsoEvent::SettingAccountEdited(_)
+        | EnsoEvent::SettingAccountClosed(_)
+        | EnsoEvent::AssetInitialized(_)
+        | EnsoEvent::AssetEdited(_)
+        | EnsoEvent::VaultAuthorityInitialized(_)
+        | EnsoEvent::CollateralVaultClosed(_)
+        | EnsoEvent::TreasuryInitialized(_)
+        | EnsoEvent::TreasuryFeesCollected(_)
+        | EnsoEvent::TreasuryWithdrawn(_)
+        | EnsoEvent::ForeignChainInitialized(_)
+        | EnsoEvent::WormholeInitialized(_)
+        | EnsoEvent::AccountMigrated(_)
+        | EnsoEvent::CrossChainCancelRequested(_) => vec![],
+    }
+}
+
+/// Events from before the header carry the borrower or lender instead of the account address,
+/// which is derived from it and the offer ID.
+fn legacy_transitions(event: &LegacyEvent) -> Vec<Transition> {
+    match event {
+        LegacyEvent::CreateLendOfferEvent(event) => vec![lend_offer(
+            &event.lender,
+            &event.offer_id,
+            PartialState {
+                status: lend_status(LendOfferStatus::Created),
+                amount: Some(event.amount),
+                closed: Some(false),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::EditLendOfferEvent(event) => vec![lend_offer(
+            &event.lender,
+            &event.offer_id,
+            PartialState {
+                amount: Some(event.amount),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::LendOfferCancelRequestEvent(event) => vec![lend_offer(
+            &event.lender,
+            &event.offer_id,
+            PartialState {
+                status: lend_status(LendOfferStatus::Canceling),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::LendOfferCanceledEvent(event) => vec![lend_offer(
+            &event.lender,
+            &event.offer_id,
+            PartialState {
+                status: lend_status(LendOfferStatus::Canceled),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::LendOfferReclaimedEvent(event) => vec![lend_offer(
+            &event.lender,
+            &event.offer_id,
+            PartialState {
+                status: lend_status(LendOfferStatus::Canceled),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::LendOfferClosedEvent(event) => vec![lend_offer(
+            &event.lender,
+            &event.offer_id,
+            PartialState {
+                closed: Some(true),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::LoanOfferCreateRequestEvent(event) => vec![
+            loan(
+                &event.borrower,
+                &event.offer_id,
+                PartialState {
+                    status: loan_status(event.status),
+                    amount: Some(event.borrow_amount),
+                    collateral_mint: Some(event.collateral_mint_token),
+                    collateral_amount: Some(event.collateral_amount),
+                    closed: Some(false),
+                },
+            ),
+            lend_offer(
+                &event.lender,
+                &event.lend_offer_id,
+                PartialState {
+                    status: lend_status(LendOfferStatus::Loaned),
+                    ..Default::default()
+                },
+            ),
+        ],
+        LegacyEvent::LoanOfferUpdateEvent(event) => vec![loan(
+            &event.borrower,
+            &event.offer_id,
+            PartialState {
+                status: loan_status(event.status),
+                amount: Some(event.borrow_amount),
+                collateral_mint: Some(event.collateral_mint_token),
+                collateral_amount: Some(event.collateral_amount),
+                closed: None,
+            },
+        )],
+        LegacyEvent::DepositCollateralLoanOfferEvent(event) => vec![loan(
+            &event.borrower,
+            &event.offer_id,
+            PartialState {
+                status: loan_status(event.status),
+                amount: Some(event.borrow_amount),
+                collateral_mint: Some(event.collateral_mint_token),
+                collateral_amount: Some(event.collateral_amount),
+                closed: None,
+            },
+        )],
+        LegacyEvent::WithdrawCollateralEvent(event) => vec![loan(
+            &event.borrower,
+            &event.loan_offer_id,
+            PartialState {
+                collateral_amount: Some(event.collateral_amount),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::SwapCollateralEvent(event) => vec![loan(
+            &event.borrower,
+            &event.loan_offer_id,
+            PartialState {
+                collateral_mint: Some(event.new_collateral_mint_token),
+                collateral_amount: Some(event.new_collateral_amount),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::SwapCollateralCrossChainEvent(event) => vec![loan(
+            &event.borrower,
+            &event.loan_offer_id,
+            PartialState {
+                collateral_amount: Some(event.new_collateral_amount),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::UpdateDepositCollateralCrossChainEvent(event) => vec![loan(
+            &event.borrower,
+            &event.loan_offer_id,
+            PartialState {
+                collateral_amount: Some(event.collateral_amount),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::UpdateWithdrawCollateralCrossChainEvent(event) => vec![loan(
+            &event.borrower,
+            &event.loan_offer_id,
+            PartialState {
+                collateral_amount: Some(event.remaining_collateral_amount),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::RepayLoanOfferEvent(event) => vec![loan(
+            &event.borrower,
+            &event.loan_offer_id,
+            PartialState {
+                status: loan_status(event.status),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::SystemRepayLoanOfferEvent(event) => vec![loan(
+            &event.borrower,
+            &event.loan_offer_id,
+            PartialState {
+                status: loan_status(event.status),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::LiquidatingCollateralEvent(event) => event
+            .borrower
+            .map(|borrower| {
+                loan(
+                    &borrower,
+                    &event.offer_id,
+                    PartialState {
+                        status: loan_status(LoanOfferStatus::Liquidating),
+                        collateral_amount: event.collateral_amount,
+                        ..Default::default()
+                    },
+                )
+            })
+            .into_iter()
+            .collect(),
+        LegacyEvent::LiquidatedCollateralEvent(event) => vec![loan(
+            &event.borrower,
+            &event.loan_offer_id,
+            PartialState {
+                status: loan_status(event.status),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::SystemFinishLoanOfferEvent(event) => vec![loan(
+            &event.borrower,
+            &event.loan_offer_id,
+            PartialState {
+                status: loan_status(event.status),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::SystemRevertEvent(event) => event
+            .borrower
+            .map(|borrower| {
+                loan(
+                    &borrower,
+                    &event.offer_id,
+                    PartialState {
+                        status: loan_status(event.status),
+                        ..Default::default()
+                    },
+                )
+            })
+            .into_iter()
+            .collect(),
+        LegacyEvent::LoanOfferClosedEvent(event) => vec![loan(
+            &event.borrower,
+            &event.loan_offer_id,
+            PartialState {
+                closed: Some(true),
+                ..Default::default()
+            },
+        )],
+        LegacyEvent::UnhealthyRatioDetectedEvent(event) => {
+            vec![loan(&event.borrower, &event.loan_offer_id, PartialState::default())]
+        }
+        LegacyEvent::LoanOfferExpiredEvent(event) => {
+            vec![loan(&event.borrower, &event.loan_offer_id, PartialState::default())]
+        }
+        LegacyEvent::ListLoanPositionEvent(event) => vec![Transition {
+            kind: SubjectKind::Loan,
+            subject: event.loan_offer,
+            state: PartialState::default(),
+        }],
+        LegacyEvent::CancelLoanPositionListingEvent(event) => vec![Transition {
+            kind: SubjectKind::Loan,
+            subject: event.loan_offer,
+            state: PartialState::default(),
+        }],
+        LegacyEvent::BuyLoanPositionEvent(event) => vec![Transition {
+            kind: SubjectKind::Loan,
+            subject: event.loan_offer,
+            state: PartialState::default(),
+        }],
+        LegacyEvent::InitSettingAccountEvent(_)
+        | LegacyEvent::EditSettingAccountEvent(_)
+        | LegacyEvent::CloseSettingAccountEvent(_)
+        | LegacyEvent::InitAssetEvent(_)
+        | LegacyEvent::EditAssetEvent(_)
+        | LegacyEvent::InitForeignEmitterEvent(_)
+        | LegacyEvent::RequestCancelCollateralCrossChainEvent(_)
+        | LegacyEvent::AccountMigratedEvent(_) => vec![],
+    }
+}
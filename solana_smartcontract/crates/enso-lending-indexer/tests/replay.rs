@@ -0,0 +1,302 @@
+//! Events decoded from their `Program data:` bytes, folded into a timeline and compared with
+//! the state on chain.
+
+use anchor_lang::{prelude::Pubkey, solana_program::hash::hash, AnchorSerialize, Event};
+use enso_lending_client::{
+    common::{event::*, LendOfferStatus, LoanOfferStatus},
+    pda, ProgramEvent,
+};
+use enso_lending_indexer::{diff, replay, transitions, ChainState, PartialState, SubjectKind, TimelineEntry, Transition};
+use solana_sdk::signature::Signature;
+
+const LEND_OFFER_ID: &str = "lend";
+const LOAN_ID: &str = "loan";
+
+struct Accounts {
+    lender: Pubkey,
+    borrower: Pubkey,
+    collateral_mint: Pubkey,
+    lend_offer: Pubkey,
+    loan: Pubkey,
+}
+
+impl Accounts {
+    fn new() -> Self {
+        let [lender, borrower] = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        Self {
+            lender,
+            borrower,
+            collateral_mint: Pubkey::new_unique(),
+            lend_offer: pda::lend_offer(&lender, LEND_OFFER_ID).0,
+            loan: pda::loan_offer(&borrower, LOAN_ID).0,
+        }
+    }
+}
+
+fn header(subject: Pubkey, id: &str, actor: Pubkey, new_status: Option<EventStatus>) -> EventHeader {
+    EventHeader {
+        schema_version: EVENT_SCHEMA_VERSION,
+        slot: 0,
+        timestamp: 0,
+        subject,
+        id: id.to_string(),
+        actor,
+        old_status: None,
+        new_status,
+    }
+}
+
+fn decode(event: impl Event) -> ProgramEvent {
+    ProgramEvent::decode(&event.data()).unwrap()
+}
+
+fn legacy_event_data(name: &str, body: impl AnchorSerialize) -> Vec<u8> {
+    let mut data = hash(format!("event:{name}").as_bytes()).to_bytes()[..8].to_vec();
+    data.extend(body.try_to_vec().unwrap());
+    data
+}
+
+fn lend_offer_created(accounts: &Accounts, amount: u64) -> ProgramEvent {
+    decode(LendOfferCreated {
+        header: header(
+            accounts.lend_offer,
+            LEND_OFFER_ID,
+            accounts.lender,
+            Some(LendOfferStatus::Created.into()),
+        ),
+        tier_id: "tier".to_string(),
+        lend_mint_token: Pubkey::new_unique(),
+        amount,
+        interest: 5.0,
+        lender_fee_percent: 0.0,
+        duration: 86_400,
+        expires_at: None,
+        crank_tip: 0,
+    })
+}
+
+fn loan_created(accounts: &Accounts, borrow_amount: u64, collateral_amount: u64) -> ProgramEvent {
+    decode(LoanCreated {
+        header: header(
+            accounts.loan,
+            LOAN_ID,
+            accounts.borrower,
+            Some(LoanOfferStatus::Matched.into()),
+        ),
+        tier_id: "tier".to_string(),
+        lend_offer_id: LEND_OFFER_ID.to_string(),
+        borrower: accounts.borrower,
+        lender: accounts.lender,
+        lend_mint_token: Pubkey::new_unique(),
+        borrow_amount,
+        collateral_mint_token: accounts.collateral_mint,
+        collateral_amount,
+        interest: 5.0,
+        lender_fee_percent: 0.0,
+        borrower_fee_percent: 0.0,
+        duration: 86_400,
+        started_at: 0,
+        position_mint: None,
+    })
+}
+
+fn collateral_deposited(accounts: &Accounts, deposited_amount: u64, collateral_amount: u64) -> ProgramEvent {
+    decode(CollateralDeposited {
+        header: header(
+            accounts.loan,
+            LOAN_ID,
+            accounts.borrower,
+            Some(LoanOfferStatus::Matched.into()),
+        ),
+        borrower: accounts.borrower,
+        collateral_mint_token: accounts.collateral_mint,
+        deposited_amount,
+        collateral_amount,
+    })
+}
+
+fn loan_closed(accounts: &Accounts) -> ProgramEvent {
+    decode(LoanClosed {
+        header: header(accounts.loan, LOAN_ID, accounts.borrower, None),
+        lend_offer_id: LEND_OFFER_ID.to_string(),
+        tier_id: "tier".to_string(),
+        borrower: accounts.borrower,
+        original_lender: accounts.lender,
+        rent_receiver: accounts.borrower,
+        lend_mint_token: Pubkey::new_unique(),
+        borrow_amount: 0,
+        collateral_mint_token: accounts.collateral_mint,
+        collateral_amount: 0,
+        interest: 5.0,
+        started_at: 0,
+    })
+}
+
+/// Timeline of `subject` across `events`, in event order.
+fn timeline(events: &[ProgramEvent], kind: SubjectKind, subject: Pubkey) -> Vec<TimelineEntry> {
+    events
+        .iter()
+        .flat_map(|event| transitions(event).into_iter().map(move |transition| (event, transition)))
+        .enumerate()
+        .filter(|(_, (_, transition))| transition.kind == kind && transition.subject == subject)
+        .map(|(index, (event, Transition { state, .. }))| TimelineEntry {
+            signature: Signature::default(),
+            slot: index as u64,
+            block_time: None,
+            event_index: index as u32,
+            event: Some(event.name().to_string()),
+            state,
+        })
+        .collect()
+}
+
+fn loan_on_chain(accounts: &Accounts, status: LoanOfferStatus, amount: u64, collateral_amount: u64) -> ChainState {
+    ChainState {
+        exists: true,
+        state: PartialState {
+            status: Some(format!("{status:?}")),
+            amount: Some(amount),
+            collateral_mint: Some(accounts.collateral_mint),
+            collateral_amount: Some(collateral_amount),
+            closed: Some(false),
+        },
+    }
+}
+
+#[test]
+fn loan_created_also_marks_the_lend_offer_loaned() {
+    let accounts = Accounts::new();
+
+    let transitions = transitions(&loan_created(&accounts, 1_000, 10));
+
+    assert_eq!(transitions.len(), 2);
+    assert_eq!(transitions[0].kind, SubjectKind::Loan);
+    assert_eq!(transitions[0].subject, accounts.loan);
+    assert_eq!(transitions[0].state.status.as_deref(), Some("Matched"));
+    assert_eq!(transitions[0].state.amount, Some(1_000));
+    assert_eq!(transitions[0].state.collateral_amount, Some(10));
+    assert_eq!(transitions[1].kind, SubjectKind::LendOffer);
+    assert_eq!(transitions[1].subject, accounts.lend_offer);
+    assert_eq!(transitions[1].state.status.as_deref(), Some("Loaned"));
+    assert_eq!(transitions[1].state.amount, None);
+}
+
+#[test]
+fn replay_keeps_the_latest_value_of_each_field() {
+    let accounts = Accounts::new();
+    let events = [
+        lend_offer_created(&accounts, 1_000),
+        loan_created(&accounts, 1_000, 10),
+        collateral_deposited(&accounts, 5, 15),
+    ];
+
+    let loan = replay(&timeline(&events, SubjectKind::Loan, accounts.loan));
+    assert_eq!(loan, PartialState {
+        status: Some("Matched".to_string()),
+        amount: Some(1_000),
+        collateral_mint: Some(accounts.collateral_mint),
+        collateral_amount: Some(15),
+        closed: Some(false),
+    });
+
+    let lend_offer = replay(&timeline(&events, SubjectKind::LendOffer, accounts.lend_offer));
+    assert_eq!(lend_offer.status.as_deref(), Some("Loaned"));
+    assert_eq!(lend_offer.amount, Some(1_000));
+}
+
+#[test]
+fn matching_chain_state_has_no_divergence() {
+    let accounts = Accounts::new();
+    let events = [loan_created(&accounts, 1_000, 10), collateral_deposited(&accounts, 5, 15)];
+    let replayed = replay(&timeline(&events, SubjectKind::Loan, accounts.loan));
+
+    let chain = loan_on_chain(&accounts, LoanOfferStatus::Matched, 1_000, 15);
+
+    assert!(diff(SubjectKind::Loan, accounts.loan, &replayed, &chain).is_empty());
+}
+
+#[test]
+fn reports_fields_that_differ_from_the_chain() {
+    let accounts = Accounts::new();
+    let events = [loan_created(&accounts, 1_000, 10)];
+    let replayed = replay(&timeline(&events, SubjectKind::Loan, accounts.loan));
+
+    let chain = loan_on_chain(&accounts, LoanOfferStatus::FundTransferred, 1_000, 15);
+    let divergences = diff(SubjectKind::Loan, accounts.loan, &replayed, &chain);
+
+    let fields: Vec<_> = divergences.iter().map(|divergence| divergence.field).collect();
+    assert_eq!(fields, ["status", "collateral_amount"]);
+    assert_eq!(divergences[0].replayed, "Matched");
+    assert_eq!(divergences[0].on_chain, "FundTransferred");
+    assert_eq!(divergences[1].replayed, "10");
+    assert_eq!(divergences[1].on_chain, "15");
+}
+
+#[test]
+fn fields_no_event_set_are_not_compared() {
+    let accounts = Accounts::new();
+    // Indexing started after the lend offer was created, only the loan marks it loaned
+    let events = [loan_created(&accounts, 1_000, 10)];
+    let replayed = replay(&timeline(&events, SubjectKind::LendOffer, accounts.lend_offer));
+
+    let chain = ChainState {
+        exists: true,
+        state: PartialState {
+            status: Some("Loaned".to_string()),
+            amount: Some(2_000),
+            closed: Some(false),
+            ..Default::default()
+        },
+    };
+
+    assert!(diff(SubjectKind::LendOffer, accounts.lend_offer, &replayed, &chain).is_empty());
+}
+
+#[test]
+fn closed_account_diverges_only_while_it_still_exists() {
+    let accounts = Accounts::new();
+    let events = [loan_created(&accounts, 1_000, 10), loan_closed(&accounts)];
+    let replayed = replay(&timeline(&events, SubjectKind::Loan, accounts.loan));
+    assert_eq!(replayed.closed, Some(true));
+
+    assert!(diff(SubjectKind::Loan, accounts.loan, &replayed, &ChainState::missing()).is_empty());
+
+    let chain = loan_on_chain(&accounts, LoanOfferStatus::Finished, 1_000, 0);
+    let divergences = diff(SubjectKind::Loan, accounts.loan, &replayed, &chain);
+    assert_eq!(divergences.len(), 1);
+    assert_eq!(divergences[0].field, "exists");
+    assert_eq!(divergences[0].replayed, "false");
+    assert_eq!(divergences[0].on_chain, "true");
+}
+
+#[test]
+fn open_account_missing_on_chain_diverges() {
+    let accounts = Accounts::new();
+    let events = [loan_created(&accounts, 1_000, 10)];
+    let replayed = replay(&timeline(&events, SubjectKind::Loan, accounts.loan));
+
+    let divergences = diff(SubjectKind::Loan, accounts.loan, &replayed, &ChainState::missing());
+
+    assert_eq!(divergences.len(), 1);
+    assert_eq!(divergences[0].field, "exists");
+    assert_eq!(divergences[0].replayed, "true");
+    assert_eq!(divergences[0].on_chain, "false");
+}
+
+#[test]
+fn legacy_events_resolve_the_loan_from_the_borrower() {
+    let accounts = Accounts::new();
+    let repaid = ProgramEvent::decode(&legacy_event_data(
+        "RepayLoanOfferEvent",
+        (accounts.borrower, LOAN_ID.to_string(), 1_000_u64, 5.0_f64, LoanOfferStatus::BorrowerPaid),
+    ))
+    .unwrap();
+    let events = [loan_created(&accounts, 1_000, 10), repaid];
+
+    let replayed = replay(&timeline(&events, SubjectKind::Loan, accounts.loan));
+    assert_eq!(replayed.status.as_deref(), Some("BorrowerPaid"));
+
+    let chain = loan_on_chain(&accounts, LoanOfferStatus::BorrowerPaid, 1_000, 10);
+    assert!(diff(SubjectKind::Loan, accounts.loan, &replayed, &chain).is_empty());
+}
@@ -0,0 +1,81 @@
+//! Maps a liquidation candidate onto the start liquidation instruction for its collateral.
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Result};
+use enso_lending_client::{args, instructions, instructions::LoanAssets, rpc::RpcClient};
+use solana_sdk::instruction::Instruction;
+
+use crate::{
+    prices::PricedAsset,
+    scanner::{Candidate, CollateralKind, Trigger},
+};
+
+pub fn build_instruction(
+    rpc: &RpcClient,
+    candidate: &Candidate,
+    assets: &HashMap<Pubkey, PricedAsset>,
+) -> Result<Instruction> {
+    let loan_offer = &candidate.loan_offer;
+    let borrower = loan_offer.borrower;
+    let collateral_mint = loan_offer.collateral_mint_token;
+    let offer_id = loan_offer.offer_id.clone();
+
+    Ok(match (candidate.kind, candidate.trigger) {
+        (CollateralKind::Token, Trigger::Expired) => instructions::start_liquidate_loan_offer_expired(
+            borrower,
+            collateral_mint,
+            args::StartLiquidateLoanOfferExpired { _offer_id: offer_id },
+        ),
+        (CollateralKind::Native, Trigger::Expired) => instructions::start_liquidate_loan_offer_native_expired(
+            borrower,
+            args::StartLiquidateLoanOfferNativeExpired { _offer_id: offer_id },
+        ),
+        (CollateralKind::CrossChain { chain_id }, Trigger::Expired) => {
+            instructions::start_liquidate_expired_loan_cross_chain(
+                borrower,
+                collateral_mint,
+                chain_id,
+                rpc.get_next_wormhole_sequence()?,
+                args::StartLiquidateExpiredLoanCrossChain { _loan_offer_id: offer_id },
+            )
+        }
+        (CollateralKind::Token, Trigger::Unhealthy) => instructions::start_liquidate_loan_offer_health(
+            borrower,
+            &loan_assets(candidate, assets)?,
+            args::StartLiquidateLoanOfferHealth { _offer_id: offer_id },
+        ),
+        (CollateralKind::Native, Trigger::Unhealthy) => instructions::start_liquidate_loan_offer_native_health(
+            borrower,
+            &loan_assets(candidate, assets)?,
+            args::StartLiquidateLoanOfferNativeHealth { _offer_id: offer_id },
+        ),
+        (CollateralKind::CrossChain { chain_id }, Trigger::Unhealthy) => {
+            instructions::start_liquidate_health_loan_cross_chain(
+                borrower,
+                &loan_assets(candidate, assets)?,
+                chain_id,
+                rpc.get_next_wormhole_sequence()?,
+                args::StartLiquidateHealthLoanCrossChain { _loan_offer_id: offer_id },
+            )
+        }
+    })
+}
+
+fn loan_assets(candidate: &Candidate, assets: &HashMap<Pubkey, PricedAsset>) -> Result<LoanAssets> {
+    let price_feed = |mint: &Pubkey| {
+        assets
+            .get(mint)
+            .map(|priced| priced.asset.price_feed_account)
+            .ok_or_else(|| anyhow!("no asset for mint {mint}"))
+    };
+    let loan_offer = &candidate.loan_offer;
+
+    Ok(LoanAssets {
+        lend_mint: loan_offer.lend_mint_token,
+        collateral_mint: loan_offer.collateral_mint_token,
+        lend_price_feed: price_feed(&loan_offer.lend_mint_token)?,
+        collateral_price_feed: price_feed(&loan_offer.collateral_mint_token)?,
+    })
+}
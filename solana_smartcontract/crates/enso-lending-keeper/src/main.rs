@@ -0,0 +1,183 @@
+//! Liquidation keeper for the enso-lending program: scans live loans, checks their health
+//! against the Pyth price accounts the program reads and starts the liquidation of expired
+//! and unhealthy loans with the operate system key.
+
+mod liquidation;
+mod metrics;
+mod mock_price;
+mod prices;
+mod scanner;
+mod sender;
+
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, bail, Result};
+use clap::{Args, Parser, Subcommand};
+use enso_lending_client::{
+    pda,
+    rpc::{RpcClient, DEFAULT_COMMITMENT},
+};
+use solana_sdk::{signature::read_keypair_file, signer::Signer};
+
+use metrics::Metrics;
+use mock_price::MockPriceArgs;
+use sender::{Sender, SenderConfig};
+
+#[derive(Parser)]
+#[command(name = "enso-lending-keeper", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Scan loans and liquidate expired and unhealthy ones
+    Run(RunArgs),
+    /// Write a Pyth price update account for solana-test-validator
+    MockPrice(MockPriceArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// JSON-RPC endpoint
+    #[arg(long, short = 'u', env = "ENSO_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+    #[arg(long, default_value = DEFAULT_COMMITMENT)]
+    commitment: String,
+    /// Operate system keypair, the only signer allowed to start liquidations
+    #[arg(long, short = 'k', env = "ENSO_KEYPAIR")]
+    keypair: PathBuf,
+    /// Seconds between two scans
+    #[arg(long, default_value_t = 10)]
+    interval_secs: u64,
+    /// Address serving Prometheus metrics
+    #[arg(long, default_value = "127.0.0.1:9464")]
+    metrics_addr: SocketAddr,
+    /// Retries of a liquidation after transient failures
+    #[arg(long, default_value_t = 3)]
+    max_retries: u32,
+    /// Percentile of recent priority fees paid on the loan accounts
+    #[arg(long, default_value_t = 75, value_parser = clap::value_parser!(u8).range(0..=100))]
+    priority_fee_percentile: u8,
+    /// Highest priority fee in micro-lamports per compute unit
+    #[arg(long, default_value_t = 1_000_000)]
+    max_priority_fee: u64,
+    #[arg(long, default_value_t = 200_000)]
+    compute_unit_limit: u32,
+    /// Seconds a loan is skipped after a liquidation attempt
+    #[arg(long, default_value_t = 60)]
+    cooldown_secs: u64,
+    /// Simulate liquidations instead of sending them
+    #[arg(long)]
+    dry_run: bool,
+    /// Exit after a single scan
+    #[arg(long)]
+    once: bool,
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    match Cli::parse().command {
+        Command::Run(args) => run(args),
+        Command::MockPrice(args) => args.run(),
+    }
+}
+
+fn run(args: RunArgs) -> Result<()> {
+    let keypair = read_keypair_file(&args.keypair)
+        .map_err(|err| anyhow!("failed to read keypair {}: {err}", args.keypair.display()))?;
+    if keypair.pubkey() != pda::operate_system() {
+        bail!("keypair {} is not the operate system key {}", keypair.pubkey(), pda::operate_system());
+    }
+
+    let rpc = RpcClient::with_commitment(args.url, args.commitment);
+    let metrics = Arc::new(Metrics::default());
+    metrics.clone().serve(args.metrics_addr)?;
+    log::info!("serving metrics on {}", args.metrics_addr);
+
+    let mut sender = Sender::new(
+        keypair,
+        SenderConfig {
+            max_retries: args.max_retries,
+            priority_fee_percentile: args.priority_fee_percentile,
+            max_priority_fee: args.max_priority_fee,
+            compute_unit_limit: args.compute_unit_limit,
+            cooldown: Duration::from_secs(args.cooldown_secs),
+            dry_run: args.dry_run,
+        },
+    );
+    let interval = Duration::from_secs(args.interval_secs);
+
+    loop {
+        let started = Instant::now();
+
+        if let Err(err) = scan_and_liquidate(&rpc, &mut sender, &metrics) {
+            Metrics::inc(&metrics.scan_errors);
+            log::error!("scan failed: {err:#}");
+        }
+        Metrics::set(&metrics.scan_duration_ms, started.elapsed().as_millis() as u64);
+
+        if args.once {
+            return Ok(());
+        }
+
+        thread::sleep(interval.saturating_sub(started.elapsed()));
+    }
+}
+
+fn scan_and_liquidate(rpc: &RpcClient, sender: &mut Sender, metrics: &Metrics) -> Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let assets = prices::fetch_priced_assets(rpc, now)?;
+    let report = scanner::scan(rpc, &assets, now)?;
+
+    Metrics::inc(&metrics.scans);
+    Metrics::set(&metrics.loans_scanned, report.loans_scanned);
+    Metrics::set(&metrics.loans_expired, report.count(scanner::Trigger::Expired));
+    Metrics::set(&metrics.loans_unhealthy, report.count(scanner::Trigger::Unhealthy));
+    Metrics::set_f64(&metrics.min_health_ratio, report.min_health_ratio.unwrap_or(f64::NAN));
+    metrics
+        .price_errors
+        .fetch_add(report.price_errors, std::sync::atomic::Ordering::Relaxed);
+    Metrics::set(&metrics.last_scan_timestamp, now as u64);
+    log::info!(
+        "scanned {} loans, {} to liquidate",
+        report.loans_scanned,
+        report.candidates.len()
+    );
+
+    for candidate in &report.candidates {
+        if sender.is_cooling_down(&candidate.address) {
+            continue;
+        }
+
+        log::info!(
+            "liquidating {} ({:?}, {:?}, health ratio {:?})",
+            candidate.address,
+            candidate.trigger,
+            candidate.kind,
+            candidate.health_ratio
+        );
+
+        match sender.send(rpc, candidate.address, || liquidation::build_instruction(rpc, candidate, &assets)) {
+            Ok(Some(signature)) => {
+                Metrics::inc(&metrics.liquidations_submitted);
+                log::info!("liquidation of {} confirmed: {signature}", candidate.address);
+            }
+            Ok(None) => log::info!("liquidation of {} simulated", candidate.address),
+            Err(err) => {
+                Metrics::inc(&metrics.liquidations_failed);
+                log::error!("liquidation of {} failed: {err:#}", candidate.address);
+            }
+        }
+    }
+
+    Ok(())
+}
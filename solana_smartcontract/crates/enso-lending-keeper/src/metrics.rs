@@ -0,0 +1,108 @@
+//! Prometheus metrics served in the text exposition format.
+
+use std::{
+    fmt::Write,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use anyhow::{anyhow, Result};
+use tiny_http::{Header, Response, Server};
+
+#[derive(Default)]
+pub struct Metrics {
+    pub scans: AtomicU64,
+    pub scan_errors: AtomicU64,
+    pub loans_scanned: AtomicU64,
+    pub loans_expired: AtomicU64,
+    pub loans_unhealthy: AtomicU64,
+    pub price_errors: AtomicU64,
+    pub liquidations_submitted: AtomicU64,
+    pub liquidations_failed: AtomicU64,
+    pub last_scan_timestamp: AtomicU64,
+    pub scan_duration_ms: AtomicU64,
+    /// Lowest health ratio of live loans in the last scan, stored as `f64` bits.
+    pub min_health_ratio: AtomicU64,
+}
+
+impl Metrics {
+    pub fn inc(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn set(gauge: &AtomicU64, value: u64) {
+        gauge.store(value, Ordering::Relaxed);
+    }
+
+    pub fn set_f64(gauge: &AtomicU64, value: f64) {
+        gauge.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn render(&self) -> String {
+        let metrics: [(&str, &str, &str, f64); 11] = [
+            ("enso_keeper_scans_total", "counter", "Completed scans", self.get(&self.scans)),
+            ("enso_keeper_scan_errors_total", "counter", "Scans aborted by an error", self.get(&self.scan_errors)),
+            ("enso_keeper_loans_scanned", "gauge", "Live loans in the last scan", self.get(&self.loans_scanned)),
+            ("enso_keeper_loans_expired", "gauge", "Expired loans in the last scan", self.get(&self.loans_expired)),
+            ("enso_keeper_loans_unhealthy", "gauge", "Unhealthy loans in the last scan", self.get(&self.loans_unhealthy)),
+            ("enso_keeper_price_errors_total", "counter", "Stale or invalid price updates", self.get(&self.price_errors)),
+            (
+                "enso_keeper_liquidations_submitted_total",
+                "counter",
+                "Confirmed liquidation transactions",
+                self.get(&self.liquidations_submitted),
+            ),
+            (
+                "enso_keeper_liquidations_failed_total",
+                "counter",
+                "Liquidations that failed after every retry",
+                self.get(&self.liquidations_failed),
+            ),
+            (
+                "enso_keeper_last_scan_timestamp_seconds",
+                "gauge",
+                "Unix time of the last completed scan",
+                self.get(&self.last_scan_timestamp),
+            ),
+            ("enso_keeper_scan_duration_ms", "gauge", "Duration of the last scan", self.get(&self.scan_duration_ms)),
+            (
+                "enso_keeper_min_health_ratio",
+                "gauge",
+                "Lowest health ratio of live loans in the last scan",
+                f64::from_bits(self.min_health_ratio.load(Ordering::Relaxed)),
+            ),
+        ];
+
+        let mut output = String::new();
+        for (name, kind, help, value) in metrics {
+            let _ = writeln!(output, "# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}");
+        }
+
+        output
+    }
+
+    fn get(&self, counter: &AtomicU64) -> f64 {
+        counter.load(Ordering::Relaxed) as f64
+    }
+
+    /// Serves `render()` on every request from a background thread.
+    pub fn serve(self: Arc<Self>, address: SocketAddr) -> Result<()> {
+        let server = Server::http(address).map_err(|err| anyhow!("failed to bind metrics on {address}: {err}"))?;
+        let content_type = Header::from_bytes("Content-Type", "text/plain; version=0.0.4").unwrap();
+
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let response = Response::from_string(self.render()).with_header(content_type.clone());
+                if let Err(err) = request.respond(response) {
+                    log::warn!("failed to answer metrics request: {err}");
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
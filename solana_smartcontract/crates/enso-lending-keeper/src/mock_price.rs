@@ -0,0 +1,91 @@
+//! `solana-test-validator --account` files holding Pyth price updates, so liquidations can be
+//! exercised against a local validator.
+
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anchor_lang::{prelude::Pubkey, AccountSerialize};
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::Args;
+use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2, VerificationLevel};
+use pythnet_sdk::messages::PriceFeedMessage;
+use serde_json::json;
+
+/// Rent exempt balance is not checked for accounts loaded at genesis, one SOL is plenty.
+const MOCK_LAMPORTS: u64 = 1_000_000_000;
+
+#[derive(Args)]
+pub struct MockPriceArgs {
+    /// Address of the price update account, the `price_feed_account` of the asset
+    #[arg(long)]
+    address: Pubkey,
+    /// Hex encoded Pyth feed id, the `price_feed_id` of the asset
+    #[arg(long)]
+    feed_id: String,
+    /// Price in base units of the exponent, 150 USD with exponent -8 is 15000000000
+    #[arg(long, allow_hyphen_values = true)]
+    price: i64,
+    #[arg(long, allow_hyphen_values = true, default_value_t = -8)]
+    exponent: i32,
+    #[arg(long, default_value_t = 0)]
+    conf: u64,
+    /// Unix publish time, defaults to now
+    #[arg(long)]
+    publish_time: Option<i64>,
+    /// File to write, printed to stdout when omitted
+    #[arg(long, short = 'o')]
+    output: Option<PathBuf>,
+}
+
+impl MockPriceArgs {
+    pub fn run(self) -> Result<()> {
+        let publish_time = match self.publish_time {
+            Some(publish_time) => publish_time,
+            None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64,
+        };
+        let feed_id = get_feed_id_from_hex(&self.feed_id).map_err(|err| anyhow!("invalid feed id: {err}"))?;
+
+        let price_update = PriceUpdateV2 {
+            write_authority: Pubkey::default(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage {
+                feed_id,
+                price: self.price,
+                conf: self.conf,
+                exponent: self.exponent,
+                publish_time,
+                prev_publish_time: publish_time,
+                ema_price: self.price,
+                ema_conf: self.conf,
+            },
+            posted_slot: 0,
+        };
+        let mut data = Vec::with_capacity(PriceUpdateV2::LEN);
+        price_update.try_serialize(&mut data)?;
+        data.resize(PriceUpdateV2::LEN, 0);
+
+        let account = json!({
+            "pubkey": self.address.to_string(),
+            "account": {
+                "lamports": MOCK_LAMPORTS,
+                "data": [STANDARD.encode(&data), "base64"],
+                "owner": pyth_solana_receiver_sdk::ID.to_string(),
+                "executable": false,
+                "rentEpoch": 0,
+                "space": data.len(),
+            },
+        });
+        let account = serde_json::to_string_pretty(&account)?;
+
+        match &self.output {
+            Some(path) => fs::write(path, account).with_context(|| format!("failed to write {}", path.display()))?,
+            None => println!("{account}"),
+        }
+
+        Ok(())
+    }
+}
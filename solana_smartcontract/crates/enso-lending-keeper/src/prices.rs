@@ -0,0 +1,63 @@
+//! Asset configuration and USD prices read from the Pyth price update accounts the program uses.
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::{Clock, Pubkey};
+use anyhow::{anyhow, Result};
+use enso_lending_client::{decode, risk, rpc::RpcClient, states::Asset};
+use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+
+/// `getMultipleAccounts` accepts at most 100 keys per call.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+pub struct PricedAsset {
+    pub asset: Asset,
+    /// `None` when the price update is missing, stale or for another feed.
+    pub price: Option<f64>,
+}
+
+/// Every `Asset` keyed by token mint with its current price, applying the same staleness check
+/// as the program.
+pub fn fetch_priced_assets(rpc: &RpcClient, now: i64) -> Result<HashMap<Pubkey, PricedAsset>> {
+    let assets: Vec<Asset> = rpc.get_accounts::<Asset>()?.into_iter().map(|(_, asset)| asset).collect();
+    let feeds: Vec<Pubkey> = assets.iter().map(|asset| asset.price_feed_account).collect();
+
+    let mut feed_data = Vec::with_capacity(feeds.len());
+    for chunk in feeds.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        feed_data.extend(rpc.get_multiple_account_data(chunk)?);
+    }
+
+    let clock = Clock {
+        unix_timestamp: now,
+        ..Default::default()
+    };
+
+    Ok(assets
+        .into_iter()
+        .zip(feed_data)
+        .map(|(asset, data)| {
+            let price = data
+                .ok_or_else(|| anyhow!("price feed account {} not found", asset.price_feed_account))
+                .and_then(|data| get_price(&asset, &data, &clock));
+            let price = match price {
+                Ok(price) => Some(price),
+                Err(err) => {
+                    log::warn!("no price for {} ({}): {err}", asset.name, asset.token_mint);
+                    None
+                }
+            };
+
+            (asset.token_mint, PricedAsset { asset, price })
+        })
+        .collect())
+}
+
+fn get_price(asset: &Asset, data: &[u8], clock: &Clock) -> Result<f64> {
+    let price_update = decode::<PriceUpdateV2>(data)?;
+    let feed_id = get_feed_id_from_hex(&asset.price_feed_id).map_err(|err| anyhow!("invalid feed id: {err}"))?;
+    let price = price_update
+        .get_price_no_older_than(clock, asset.max_price_age_seconds, &feed_id)
+        .map_err(|err| anyhow!("{err}"))?;
+
+    Ok(risk::to_ui_price(price.price, price.exponent))
+}
@@ -0,0 +1,132 @@
+//! Finds live loans that can be liquidated, either expired or below the borrow health ratio.
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token::spl_token::native_mint;
+use anyhow::Result;
+use enso_lending_client::{
+    common::{LoanOfferStatus, MIN_BORROW_HEALTH_RATIO},
+    risk::{self, PricedLoanParams},
+    rpc::RpcClient,
+    states::LoanOfferAccount,
+};
+use wormhole_anchor_sdk::wormhole;
+
+use crate::prices::PricedAsset;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trigger {
+    Expired,
+    Unhealthy,
+}
+
+/// Which liquidation instruction family the loan collateral needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollateralKind {
+    Token,
+    Native,
+    CrossChain { chain_id: u16 },
+}
+
+pub struct Candidate {
+    pub address: Pubkey,
+    pub loan_offer: LoanOfferAccount,
+    pub trigger: Trigger,
+    pub kind: CollateralKind,
+    pub health_ratio: Option<f64>,
+}
+
+#[derive(Default)]
+pub struct ScanReport {
+    pub loans_scanned: u64,
+    pub price_errors: u64,
+    pub min_health_ratio: Option<f64>,
+    pub candidates: Vec<Candidate>,
+}
+
+impl ScanReport {
+    pub fn count(&self, trigger: Trigger) -> u64 {
+        self.candidates.iter().filter(|candidate| candidate.trigger == trigger).count() as u64
+    }
+}
+
+/// Note: `status` follows the variable length `String` fields of `LoanOfferAccount`, so it
+/// has no fixed offset to filter on with `getProgramAccounts`. Loans are selected by their
+/// discriminator and filtered on status here.
+pub fn scan(rpc: &RpcClient, assets: &HashMap<Pubkey, PricedAsset>, now: i64) -> Result<ScanReport> {
+    let mut report = ScanReport::default();
+
+    for (address, loan_offer) in rpc.get_accounts::<LoanOfferAccount>()? {
+        if loan_offer.status != LoanOfferStatus::FundTransferred {
+            continue;
+        }
+        report.loans_scanned += 1;
+
+        let Some(collateral) = assets.get(&loan_offer.collateral_mint_token) else {
+            log::warn!("loan {address} uses unknown collateral {}", loan_offer.collateral_mint_token);
+            continue;
+        };
+        let kind = collateral_kind(&loan_offer, collateral);
+
+        let health_ratio = match health_ratio(&loan_offer, assets) {
+            Some(ratio) => {
+                report.min_health_ratio = Some(report.min_health_ratio.map_or(ratio, |min| min.min(ratio)));
+                Some(ratio)
+            }
+            None => {
+                report.price_errors += 1;
+                None
+            }
+        };
+
+        let trigger = if is_expired(&loan_offer, now) {
+            Trigger::Expired
+        } else if health_ratio.is_some_and(|ratio| ratio < MIN_BORROW_HEALTH_RATIO) {
+            Trigger::Unhealthy
+        } else {
+            continue;
+        };
+
+        report.candidates.push(Candidate {
+            address,
+            loan_offer,
+            trigger,
+            kind,
+            health_ratio,
+        });
+    }
+
+    Ok(report)
+}
+
+/// Same rule as the program, the loan can be liquidated from `started_at + duration` on.
+pub fn is_expired(loan_offer: &LoanOfferAccount, now: i64) -> bool {
+    now >= loan_offer.started_at + loan_offer.duration as i64
+}
+
+fn collateral_kind(loan_offer: &LoanOfferAccount, collateral: &PricedAsset) -> CollateralKind {
+    if collateral.asset.chain_id != wormhole::CHAIN_ID_SOLANA {
+        CollateralKind::CrossChain {
+            chain_id: collateral.asset.chain_id,
+        }
+    } else if loan_offer.collateral_mint_token == native_mint::ID {
+        CollateralKind::Native
+    } else {
+        CollateralKind::Token
+    }
+}
+
+fn health_ratio(loan_offer: &LoanOfferAccount, assets: &HashMap<Pubkey, PricedAsset>) -> Option<f64> {
+    let collateral = assets.get(&loan_offer.collateral_mint_token)?;
+    let lend = assets.get(&loan_offer.lend_mint_token)?;
+
+    Some(risk::get_health_ratio(&PricedLoanParams {
+        collateral_amount: loan_offer.collateral_amount,
+        collateral_decimals: collateral.asset.decimals,
+        collateral_price: collateral.price?,
+        lend_amount: loan_offer.borrow_amount,
+        lend_decimals: lend.asset.decimals,
+        lend_price: lend.price?,
+    }))
+}
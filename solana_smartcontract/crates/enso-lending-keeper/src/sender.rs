@@ -0,0 +1,162 @@
+//! Sends liquidation transactions with a priority fee, retrying transient failures.
+
+use std::{
+    collections::HashMap,
+    fmt, thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Result};
+use enso_lending_client::{
+    rpc::{RpcClient, SignatureStatus},
+    ClientError,
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+/// JSON-RPC error code of a `sendTransaction` preflight simulation failure.
+const PREFLIGHT_FAILURE: i64 = -32002;
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+pub struct SenderConfig {
+    pub max_retries: u32,
+    pub priority_fee_percentile: u8,
+    pub max_priority_fee: u64,
+    pub compute_unit_limit: u32,
+    /// Loans are skipped for this long after an attempt, confirmed or not.
+    pub cooldown: Duration,
+    pub dry_run: bool,
+}
+
+pub struct Sender {
+    keypair: Keypair,
+    config: SenderConfig,
+    cooldowns: HashMap<Pubkey, Instant>,
+}
+
+impl Sender {
+    pub fn new(keypair: Keypair, config: SenderConfig) -> Self {
+        Self {
+            keypair,
+            config,
+            cooldowns: HashMap::new(),
+        }
+    }
+
+    pub fn is_cooling_down(&self, loan_offer: &Pubkey) -> bool {
+        self.cooldowns
+            .get(loan_offer)
+            .is_some_and(|attempted_at| attempted_at.elapsed() < self.config.cooldown)
+    }
+
+    /// Sends the instruction returned by `build`, rebuilt on every attempt so cross-chain
+    /// liquidations pick up the current Wormhole sequence. `None` on a dry run.
+    pub fn send(
+        &mut self,
+        rpc: &RpcClient,
+        loan_offer: Pubkey,
+        build: impl Fn() -> Result<Instruction>,
+    ) -> Result<Option<Signature>> {
+        self.cooldowns.insert(loan_offer, Instant::now());
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            match self.try_send(rpc, build()?) {
+                Ok(signature) => return Ok(signature),
+                Err(err) if attempt > self.config.max_retries || !is_retryable(&err) => return Err(err),
+                Err(err) => {
+                    log::warn!("liquidation of {loan_offer} failed on attempt {attempt}, retrying: {err:#}");
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+
+    fn try_send(&self, rpc: &RpcClient, instruction: Instruction) -> Result<Option<Signature>> {
+        let priority_fee = self.priority_fee(rpc, &instruction)?;
+        let instructions = [
+            ComputeBudgetInstruction::set_compute_unit_limit(self.config.compute_unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+            instruction,
+        ];
+        let payer = self.keypair.pubkey();
+        let mut transaction = Transaction::new_unsigned(Message::new(&instructions, Some(&payer)));
+
+        if self.config.dry_run {
+            let simulation = rpc.simulate_transaction(&transaction)?;
+            for log in &simulation.logs {
+                log::debug!("{log}");
+            }
+            if let Some(err) = simulation.err {
+                return Err(Rejected(format!("simulation failed: {err}")).into());
+            }
+
+            return Ok(None);
+        }
+
+        transaction.try_sign(&[&self.keypair], rpc.get_latest_blockhash()?)?;
+        let signature = rpc.send_transaction(&transaction)?;
+
+        match rpc.confirm_transaction(&signature, CONFIRM_TIMEOUT)? {
+            SignatureStatus::Confirmed => Ok(Some(signature)),
+            SignatureStatus::Failed(err) => Err(Rejected(format!("transaction {signature} failed: {err}")).into()),
+            SignatureStatus::Pending => bail!("transaction {signature} was not confirmed"),
+        }
+    }
+
+    /// Configured percentile of recent fees paid on the writable accounts, capped at
+    /// `max_priority_fee` micro-lamports per compute unit.
+    fn priority_fee(&self, rpc: &RpcClient, instruction: &Instruction) -> Result<u64> {
+        let writable: Vec<Pubkey> = instruction
+            .accounts
+            .iter()
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
+            .collect();
+        let mut fees = rpc.get_recent_prioritization_fees(&writable)?;
+        if fees.is_empty() {
+            return Ok(0);
+        }
+
+        fees.sort_unstable();
+        let index = (fees.len() - 1) * self.config.priority_fee_percentile.min(100) as usize / 100;
+
+        Ok(fees[index].min(self.config.max_priority_fee))
+    }
+}
+
+/// The program rejected the liquidation, sending it again would fail the same way.
+#[derive(Debug)]
+struct Rejected(String);
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Rejected {}
+
+/// Program errors, from preflight or on chain, will fail again, anything else may be transient.
+fn is_retryable(err: &anyhow::Error) -> bool {
+    if err.is::<Rejected>() {
+        return false;
+    }
+
+    !matches!(
+        err.downcast_ref::<ClientError>(),
+        Some(ClientError::Rpc { code: PREFLIGHT_FAILURE, .. })
+    )
+}
@@ -25,28 +25,42 @@ pub const ASSET_SEED: &[u8] = b"asset";
 pub const VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED: &[u8] = b"vault_authority_loan_offer";
 pub const LEND_OFFER_ACCOUNT_SEED: &[u8] = b"lend_offer";
 pub const LOAN_OFFER_ACCOUNT_SEED: &[u8] = b"loan_offer";
+pub const LOAN_POSITION_MINT_SEED: &[u8] = b"loan_position_mint";
+pub const LOAN_POSITION_LISTING_SEED: &[u8] = b"loan_position_listing";
+pub const TREASURY_SEED: &[u8] = b"treasury";
 pub const WORMHOLE_SENT_SEED: &[u8; 4] = b"sent";
 
-#[cfg(feature = "staging")]
-pub const OPERATE_SYSTEM_PUBKEY: &str = "sysvYFEXhxW7FP32Ha15BBGWBEfMq1e1ScvFq61u5mG";
-#[cfg(not(feature = "staging"))]
-pub const OPERATE_SYSTEM_PUBKEY: &str = "opty8HWBKX3wW8c9qMPkmB4xnrCpMWWmQwqq7yGzmr4";
-#[cfg(feature = "staging")]
-pub const HOT_WALLET_PUBKEY: &str = "hotbEp8jbFUwfAGTUtLupGXE2JtrfZENLgRcSQsYk56";
-#[cfg(not(feature = "staging"))]
-pub const HOT_WALLET_PUBKEY: &str = "Hot7zcvBTa3NybAnKrKtjcW1yJcoDWao39ZAoBn4mfPu";
+cfg_if::cfg_if! {
+  if #[cfg(feature = "test-keys")] {
+    // Note: Keypairs from the 32 byte seeds "enso-lending operate system test" and
+    // "enso-lending hot wallet test key", only for the in-process test suite
+    pub const OPERATE_SYSTEM_PUBKEY: &str = "46mbfV5fWWwdDUu5Ke1XNXriHQFBztEKXj3Enisre5ZC";
+    pub const HOT_WALLET_PUBKEY: &str = "An2t4DhtinqVjFVeqvpGmTdzJGF3eEZTo9KGWvu2h4U4";
+  } else if #[cfg(feature = "staging")] {
+    pub const OPERATE_SYSTEM_PUBKEY: &str = "sysvYFEXhxW7FP32Ha15BBGWBEfMq1e1ScvFq61u5mG";
+    pub const HOT_WALLET_PUBKEY: &str = "hotbEp8jbFUwfAGTUtLupGXE2JtrfZENLgRcSQsYk56";
+  } else {
+    pub const OPERATE_SYSTEM_PUBKEY: &str = "opty8HWBKX3wW8c9qMPkmB4xnrCpMWWmQwqq7yGzmr4";
+    pub const HOT_WALLET_PUBKEY: &str = "Hot7zcvBTa3NybAnKrKtjcW1yJcoDWao39ZAoBn4mfPu";
+  }
+}
 
 #[cfg(feature = "dev")]
 pub const MIN_BORROW_HEALTH_RATIO: f64 = 1.1;
 #[cfg(not(feature = "dev"))]
 pub const MIN_BORROW_HEALTH_RATIO: f64 = 1.2;
 
-pub const DISCRIMINATOR: u8 = 0;
-
 pub const HEX_MIN_WIDTH: u8 = 16;
 
 pub const MAX_ALLOWED_INTEREST: f64 = 200.0;
 
+// Note: Extra collateral value, over the debt repaid, a keeper receives when settling a liquidation
+pub const LIQUIDATION_BONUS_PERCENT: f64 = 5.0;
+
+pub const MAX_ISOLATED_LEND_MINTS: usize = 5;
+pub const MAX_TIER_COLLATERAL_MINTS: usize = 10;
+pub const ISOLATED_DEBT_DECIMALS: u8 = 6;
+
 pub const POSTED_TIMESTAMP_THRESHOLD: u32 = 30 * 60;
 
 pub const CREATE_LOAN_OFFER_CROSS_CHAIN_FUNCTION: &str = "create_loan_offer_cross_chain";
@@ -56,4 +70,7 @@ pub const UPDATE_WITHDRAW_COLLATERAL_CROSS_CHAIN_FUNCTION: &str = "update_withdr
 pub const START_LIQUIDATE_HEALTH_LOAN_CROSS_CHAIN: &str = "start_liquidate_health_loan_cross_chain";
 pub const START_LIQUIDATE_EXPIRED_LOAN_CROSS_CHAIN: &str = "start_liquidate_expired_loan_cross_chain";
 
+pub const SWAP_COLLATERAL_CROSS_CHAIN_FUNCTION: &str = "swap_collateral_cross_chain";
+pub const RELEASE_SWAPPED_COLLATERAL_FUNCTION: &str = "release_swapped_collateral";
+
 pub const REFUND_COLLATERAL_CROSS_CHAIN_FUNCTION: &str = "refund_collateral_to_repaid_borrower";
\ No newline at end of file
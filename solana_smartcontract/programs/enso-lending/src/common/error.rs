@@ -6,6 +6,38 @@ pub enum SettingAccountError {
     InvalidTierId,   
     #[msg("Invalid owner account")]
     InvalidOwner, 
+    #[msg("Lend mint is not allowed in this tier")]
+    LendMintNotAllowed,
+    #[msg("Collateral mint is not allowed in this tier")]
+    CollateralMintNotAllowed,
+    #[msg("Too many collateral mints")]
+    TooManyCollateralMints,
+    #[msg("Min amount must not be greater than max amount")]
+    InvalidAmountRange,
+    #[msg("Min duration must not be greater than max duration")]
+    InvalidDurationRange,
+}
+
+#[error_code]
+pub enum AssetError {
+    #[msg("Too many isolated lend mints")]
+    TooManyIsolatedLendMints,
+}
+
+#[error_code]
+pub enum AccountVersionError {
+    #[msg("Account is not at the current version, migrate it first")]
+    OutdatedAccountVersion,
+    #[msg("Account version is newer than this program supports")]
+    UnsupportedAccountVersion,
+    #[msg("Account type can not be migrated")]
+    UnknownAccountType,
+}
+
+#[error_code]
+pub enum StatusTransitionError {
+    #[msg("Current status does not allow this instruction")]
+    InvalidTransition,
 }
 
 #[error_code]
@@ -29,7 +61,25 @@ pub enum LendOfferError {
     #[msg("Interest over limit")]
     InterestOverLimit,
     #[msg("Invalid signer")]
-    InvalidSigner
+    InvalidSigner,
+    #[msg("Duration is not allowed in this tier")]
+    InvalidDuration,
+    #[msg("Lend offer expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Lend offer vault is required for lend offer with expiry")]
+    MissingLendOfferVault,
+    #[msg("Lend offer is not expired")]
+    LendOfferNotExpired,
+    #[msg("Only lend offer with expiry can withdraw principal, cancel the offer instead")]
+    WithdrawRequiresEscrow,
+    #[msg("Lend offer vault is not empty")]
+    LendOfferVaultNotEmpty,
+    #[msg("Lend offer vault is only created for lend offer with expiry")]
+    UnexpectedLendOfferVault,
+    #[msg("Tier id not match the tier of the lend offer")]
+    TierIdNotMatch,
+    #[msg("Lend offer has no recorded tier, only its interest can be edited")]
+    UnknownLendOfferTier,
 }
 
 #[error_code]
@@ -102,6 +152,36 @@ pub enum LoanOfferError {
     InvalidChainId,
     #[msg("Withdraw amount not match")]
     WithdrawAmountNotMatch,
+    #[msg("Collateral cap of asset exceeded")]
+    CollateralCapExceeded,
+    #[msg("Borrow cap of asset exceeded")]
+    BorrowCapExceeded,
+    #[msg("Lend mint is not allowed for isolated collateral")]
+    LendMintNotAllowedForIsolatedCollateral,
+    #[msg("Debt ceiling of isolated collateral exceeded")]
+    IsolatedDebtCeilingExceeded,
+    #[msg("Lend offer expired")]
+    LendOfferExpired,
+    #[msg("Lend offer vault and hot wallet are required for lend offer with expiry")]
+    MissingLendOfferVault,
+    #[msg("Can not create loan cause lend amount updated")]
+    CanNotCreateLoanCauseLendAmountUpdated,
+    #[msg("Can not create loan cause lend duration updated")]
+    CanNotCreateLoanCauseLendDurationUpdated,
+    #[msg("Can not create loan cause borrower fee updated")]
+    CanNotCreateLoanCauseBorrowerFeeUpdated,
+    #[msg("Can not create loan cause lender fee updated")]
+    CanNotCreateLoanCauseLenderFeeUpdated,
+    #[msg("New collateral mint asset must differ from current one")]
+    SameCollateralMintAsset,
+    #[msg("Collateral token accounts are required for SPL collateral")]
+    MissingCollateralTokenAccount,
+    #[msg("Rent receiver is not the payer of loan offer")]
+    InvalidRentReceiver,
+    #[msg("Collateral vault is not empty")]
+    CollateralVaultNotEmpty,
+    #[msg("Lend offer of the loan is not closed")]
+    LendOfferNotClosed,
 }
 
 #[error_code]
@@ -123,7 +203,33 @@ pub enum RepayOfferError {
     #[msg("Loan offer not belong to lender")]
     InvalidLender,
     #[msg("Invalid repay lender time")]
-    TimeUnmetException
+    TimeUnmetException,
+    #[msg("Invalid position token account of loan offer")]
+    InvalidPositionTokenAccount,
+}
+
+#[error_code]
+pub enum LoanPositionListingError {
+    #[msg("Loan offer status is invalid")]
+    InvalidOfferStatus,
+    #[msg("Position mint does not belong to loan offer")]
+    InvalidPositionMint,
+    #[msg("Invalid position token account")]
+    InvalidPositionTokenAccount,
+    #[msg("Listing price must be greater than 0")]
+    InvalidPrice,
+    #[msg("Listing price updated")]
+    PriceUpdated,
+    #[msg("Listing not belong to seller")]
+    InvalidSeller,
+    #[msg("Invalid mint asset")]
+    InvalidMintAsset,
+    #[msg("Invalid loan offer")]
+    InvalidLoanOffer,
+    #[msg("Invalid asset account")]
+    InvalidAssetAccount,
+    #[msg("Invalid price feed account")]
+    InvalidPriceFeedAccount,
 }
 
 #[error_code]
@@ -142,6 +248,8 @@ pub enum LiquidateOfferError {
     InvalidMintAsset,
     #[msg("Invalid operator system account")]
     InvalidSystem,
+    #[msg("Loan collateral is not native SOL")]
+    NotNativeCollateral,
 }
 
 #[error_code]
@@ -183,5 +291,15 @@ pub enum ParseVaaError {
     #[msg("Invalid Lend amount")]
     InvalidLendAmount,
     #[msg("Invalid remaining collateral amount")]
-    InvalidRemainingCollateralAmount
-}
\ No newline at end of file
+    InvalidRemainingCollateralAmount,
+    #[msg("Invalid expected loan terms")]
+    InvalidExpectedLoanTerms,
+}
+
+#[error_code]
+pub enum TreasuryError {
+    #[msg("Withdraw amount must be greater than 0")]
+    InvalidAmount,
+    #[msg("Not enough treasury balance")]
+    NotEnoughAmount,
+}
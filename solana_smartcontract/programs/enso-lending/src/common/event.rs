@@ -1,37 +1,122 @@
 use anchor_lang::{
     event,
-    prelude::{borsh, AnchorDeserialize, AnchorSerialize, Pubkey},
+    prelude::{borsh, AnchorDeserialize, AnchorSerialize, Clock, Pubkey, Result, SolanaSysvar},
 };
 
-use crate::LoanOfferStatus;
+use crate::{LendOfferStatus, LoanOfferStatus};
+
+/// Version of the event layout, carried by every event header. Events emitted before the
+/// header existed are version 0 and keep their old names.
+pub const EVENT_SCHEMA_VERSION: u8 = 1;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum EventStatus {
+    LoanOffer(LoanOfferStatus),
+    LendOffer(LendOfferStatus),
+}
+
+impl From<LoanOfferStatus> for EventStatus {
+    fn from(status: LoanOfferStatus) -> Self {
+        Self::LoanOffer(status)
+    }
+}
+
+impl From<LendOfferStatus> for EventStatus {
+    fn from(status: LendOfferStatus) -> Self {
+        Self::LendOffer(status)
+    }
+}
+
+/// Common first field of every event.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct EventHeader {
+    pub schema_version: u8,
+    pub slot: u64,
+    pub timestamp: i64,
+    /// Account the event is about: the loan offer, lend offer, setting, asset or foreign chain.
+    pub subject: Pubkey,
+    /// Offer ID of a loan or lend offer, tier ID of a setting account, asset name or chain ID.
+    /// Empty when the instruction does not load the account, such as migrations.
+    pub id: String,
+    /// Signer of the instruction.
+    pub actor: Pubkey,
+    /// Status before the instruction, `None` when the account did not exist or has no status.
+    pub old_status: Option<EventStatus>,
+    /// Status after the instruction, `None` when the account was closed or has no status.
+    pub new_status: Option<EventStatus>,
+}
+
+impl EventHeader {
+    pub fn new(subject: Pubkey, id: impl Into<String>, actor: Pubkey) -> Result<Self> {
+        let clock = Clock::get()?;
+
+        Ok(Self {
+            schema_version: EVENT_SCHEMA_VERSION,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+            subject,
+            id: id.into(),
+            actor,
+            old_status: None,
+            new_status: None,
+        })
+    }
+
+    pub fn with_status<S: Into<EventStatus>>(mut self, old_status: Option<S>, new_status: Option<S>) -> Self {
+        self.old_status = old_status.map(Into::into);
+        self.new_status = new_status.map(Into::into);
+        self
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LiquidationReason {
+    Unhealthy,
+    Expired,
+}
 
 #[event]
-pub struct InitSettingAccountEvent {
+pub struct SettingAccountInitialized {
+    pub header: EventHeader,
     pub amount: u64,
     pub duration: u64,
-    pub owner: Pubkey,
     pub receiver: Pubkey,
-    pub tier_id: String,
     pub lender_fee_percent: f64,
-    pub borrower_fee_percent: f64
+    pub borrower_fee_percent: f64,
+    pub lend_mint_asset: Pubkey,
+    pub collateral_mint_assets: Vec<Pubkey>,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub amount_step: u64,
+    pub min_duration: u64,
+    pub max_duration: u64,
 }
 
 #[event]
-pub struct EditSettingAccountEvent {
-    pub receiver: Pubkey,
-    pub tier_id: String,
+pub struct SettingAccountEdited {
+    pub header: EventHeader,
     pub amount: u64,
     pub duration: u64,
+    pub receiver: Pubkey,
     pub lender_fee_percent: f64,
+    pub borrower_fee_percent: f64,
+    pub lend_mint_asset: Pubkey,
+    pub collateral_mint_assets: Vec<Pubkey>,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub amount_step: u64,
+    pub min_duration: u64,
+    pub max_duration: u64,
 }
 
 #[event]
-pub struct CloseSettingAccountEvent {
-    pub tier_id: String,
+pub struct SettingAccountClosed {
+    pub header: EventHeader,
 }
 
 #[event]
-pub struct InitAssetEvent {
+pub struct AssetInitialized {
+    pub header: EventHeader,
     pub token_mint: Pubkey,
     pub max_price_age_seconds: u64,
     pub decimals: u8,
@@ -39,14 +124,18 @@ pub struct InitAssetEvent {
     pub is_lend: bool,
     pub price_feed_id: String,
     pub price_feed_account: Pubkey,
-    pub bump: u8,
-    pub name: String,
     pub token_address: Option<String>,
     pub chain_id: u16,
+    pub max_collateral_amount: u64,
+    pub max_borrow_amount: u64,
+    pub isolated: bool,
+    pub isolated_lend_mints: Vec<Pubkey>,
+    pub isolated_debt_ceiling: u64,
 }
 
 #[event]
-pub struct EditAssetEvent {
+pub struct AssetEdited {
+    pub header: EventHeader,
     pub token_mint: Pubkey,
     pub max_price_age_seconds: u64,
     pub decimals: u8,
@@ -54,223 +143,383 @@ pub struct EditAssetEvent {
     pub is_lend: bool,
     pub price_feed_id: String,
     pub price_feed_account: Pubkey,
-    pub bump: u8,
-    pub name: String
+    pub total_collateral_amount: u64,
+    pub max_collateral_amount: u64,
+    pub total_borrow_amount: u64,
+    pub max_borrow_amount: u64,
+    pub isolated: bool,
+    pub isolated_lend_mints: Vec<Pubkey>,
+    pub isolated_debt_ceiling: u64,
+    pub total_isolated_debt: u64,
 }
 
 #[event]
-pub struct CreateLendOfferEvent {
-    pub lender: Pubkey,
+pub struct VaultAuthorityInitialized {
+    pub header: EventHeader,
+}
+
+#[event]
+pub struct TreasuryInitialized {
+    pub header: EventHeader,
+    pub token_mint: Pubkey,
+    /// Token account of the treasury fees are paid into.
+    pub treasury_ata: Pubkey,
+}
+
+/// Fees a loan instruction paid into the treasury of its lend mint.
+#[event]
+pub struct TreasuryFeesCollected {
+    pub header: EventHeader,
+    pub token_mint: Pubkey,
+    pub loan_offer: Pubkey,
+    pub lender_fee: u64,
+    pub borrower_fee: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub header: EventHeader,
+    pub token_mint: Pubkey,
+    /// Token account the withdrawn fees were sent to.
+    pub receiver_ata: Pubkey,
+    pub amount: u64,
+    pub remaining_amount: u64,
+}
+
+#[event]
+pub struct ForeignChainInitialized {
+    pub header: EventHeader,
+    pub chain_id: u16,
+    pub chain_address: String,
+    pub emitter_address: String,
+}
+
+#[event]
+pub struct WormholeInitialized {
+    pub header: EventHeader,
+    pub emitter: Pubkey,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub header: EventHeader,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct LendOfferCreated {
+    pub header: EventHeader,
+    pub tier_id: String,
+    pub lend_mint_token: Pubkey,
+    pub amount: u64,
     pub interest: f64,
     pub lender_fee_percent: f64,
     pub duration: u64,
+    pub expires_at: Option<i64>,
+    pub crank_tip: u64,
+}
+
+#[event]
+pub struct LendOfferEdited {
+    pub header: EventHeader,
     pub amount: u64,
-    pub offer_id: String,
-    pub tier_id: String,
+    pub interest: f64,
+    pub duration: u64,
+    pub old_amount: u64,
+    pub old_interest: f64,
+    pub old_duration: u64,
+    /// Principal waiting for the operator to pay it back from the hot wallet, see `LendOfferWithdrawn`.
+    pub pending_withdraw_amount: u64,
 }
 
 #[event]
-pub struct EditLendOfferEvent {
-    pub lender: Pubkey,
+pub struct LendOfferWithdrawn {
+    pub header: EventHeader,
+    pub lend_mint_token: Pubkey,
+    pub amount: u64,
+    pub withdrawn_amount: u64,
+}
+
+/// Lend offers created before tiers were recorded have no tier ID, the lend mint, amount and duration identify their tier.
+#[event]
+pub struct LendOfferCancelRequested {
+    pub header: EventHeader,
+    pub lend_mint_token: Pubkey,
+    pub amount: u64,
     pub interest: f64,
     pub lender_fee_percent: f64,
     pub duration: u64,
-    pub amount: u64,
-    pub offer_id: String,
+    /// Principal refunded by this instruction, only escrowed offers are refunded right away.
+    pub refunded_amount: u64,
 }
 
 #[event]
-pub struct LendOfferCancelRequestEvent {
-    pub lender: Pubkey,
+pub struct LendOfferCanceled {
+    pub header: EventHeader,
+    pub lend_mint_token: Pubkey,
+    pub amount: u64,
     pub interest: f64,
     pub lender_fee_percent: f64,
     pub duration: u64,
+    pub refunded_amount: u64,
+}
+
+#[event]
+pub struct LendOfferReclaimed {
+    pub header: EventHeader,
+    pub lender: Pubkey,
+    pub lend_mint_token: Pubkey,
     pub amount: u64,
-    pub offer_id: String,
+    pub crank_tip: u64,
+    pub expires_at: i64,
 }
 
 #[event]
-pub struct LendOfferCanceledEvent {
+pub struct LendOfferClosed {
+    pub header: EventHeader,
     pub lender: Pubkey,
+    pub lend_mint_token: Pubkey,
+    pub amount: u64,
     pub interest: f64,
-    pub lender_fee_percent: f64,
     pub duration: u64,
-    pub amount: u64,
-    pub offer_id: String,
 }
 
 #[event]
-pub struct LoanOfferCreateRequestEvent {
+pub struct LoanCreated {
+    pub header: EventHeader,
     pub tier_id: String,
     pub lend_offer_id: String,
-    pub interest: f64,
-    pub borrow_amount: u64,
-    pub lender_fee_percent: f64,
-    pub duration: u64,
-    pub lend_mint_token: Pubkey,
-    pub lender: Pubkey,
-    pub offer_id: String,
     pub borrower: Pubkey,
+    pub lender: Pubkey,
+    pub lend_mint_token: Pubkey,
+    pub borrow_amount: u64,
     pub collateral_mint_token: Pubkey,
     pub collateral_amount: u64,
-    pub status: LoanOfferStatus,
+    pub interest: f64,
+    pub lender_fee_percent: f64,
     pub borrower_fee_percent: f64,
+    pub duration: u64,
     pub started_at: i64,
+    pub position_mint: Option<Pubkey>,
 }
 
 #[event]
-pub struct LoanOfferUpdateEvent {
-    pub tier_id: String,
-    pub lend_offer_id: String,
-    pub interest: f64,
-    pub borrow_amount: u64,
-    pub lender_fee_percent: f64,
-    pub duration: u64,
-    pub lend_mint_token: Pubkey,
-    pub lender: Pubkey,
-    pub offer_id: String,
+pub struct LoanUpdated {
+    pub header: EventHeader,
     pub borrower: Pubkey,
+    pub borrow_amount: u64,
     pub collateral_mint_token: Pubkey,
     pub collateral_amount: u64,
-    pub status: LoanOfferStatus,
-    pub borrower_fee_percent: f64,
     pub started_at: i64,
 }
 
 #[event]
-pub struct WithdrawCollateralEvent {
+pub struct CollateralDeposited {
+    pub header: EventHeader,
     pub borrower: Pubkey,
-    pub withdraw_amount: u64,
-    pub loan_offer_id: String,
+    pub collateral_mint_token: Pubkey,
+    pub deposited_amount: u64,
     pub collateral_amount: u64,
 }
 
 #[event]
-pub struct UnhealthyRatioDetectedEvent  {
+pub struct CollateralWithdrawn {
+    pub header: EventHeader,
     pub borrower: Pubkey,
+    pub collateral_mint_token: Pubkey,
     pub withdraw_amount: u64,
-    pub loan_offer_id: String,
     pub collateral_amount: u64,
 }
 
 #[event]
-pub struct LoanOfferExpiredEvent  {
+pub struct CollateralSwapped {
+    pub header: EventHeader,
     pub borrower: Pubkey,
-    pub withdraw_amount: u64,
-    pub loan_offer_id: String,
-    pub collateral_amount: u64,
+    pub old_collateral_mint_token: Pubkey,
+    pub old_collateral_amount: u64,
+    pub new_collateral_mint_token: Pubkey,
+    pub new_collateral_amount: u64,
 }
 
 #[event]
-pub struct DepositCollateralLoanOfferEvent {
+pub struct LoanRepaid {
+    pub header: EventHeader,
+    pub borrower: Pubkey,
+    /// Lender the loan was created with, the creditor is whoever holds the position token.
+    pub original_lender: Pubkey,
     pub tier_id: String,
-    pub lend_offer_id: String,
-    pub interest: f64,
     pub borrow_amount: u64,
-    pub lender_fee_percent: f64,
-    pub duration: u64,
-    pub lend_mint_token: Pubkey,
-    pub lender: Pubkey,
-    pub offer_id: String,
-    pub borrower: Pubkey,
-    pub collateral_mint_token: Pubkey,
+    pub interest: f64,
+    /// Collateral released to the borrower.
     pub collateral_amount: u64,
-    pub status: LoanOfferStatus,
-    pub borrower_fee_percent: f64,
-    pub started_at: i64,
 }
 
+/// Repayment paid to the creditor and the treasury in the same instruction, finishing the loan.
 #[event]
-pub struct RepayLoanOfferEvent {
-  pub borrower: Pubkey,
-  pub loan_offer_id: String,
-  pub repay_amount: u64,
-  pub borrower_fee_percent: f64,
-  pub status: LoanOfferStatus,
+pub struct LoanRepaidToLender {
+    pub header: EventHeader,
+    pub borrower: Pubkey,
+    /// Creditor of the loan, credited with `lender_amount`.
+    pub lender: Pubkey,
+    pub tier_id: String,
+    pub borrow_amount: u64,
+    pub interest: f64,
+    /// Lend mint amount the borrower paid, `lender_amount` plus `fee_amount`.
+    pub repaid_amount: u64,
+    pub lender_amount: u64,
+    /// Lender and borrower fees paid to the treasury.
+    pub fee_amount: u64,
+    /// Collateral released to the borrower.
+    pub collateral_amount: u64,
 }
 
 #[event]
-pub struct LiquidatingCollateralEvent {
-    pub offer_id: String,
+pub struct LiquidationStarted {
+    pub header: EventHeader,
+    pub borrower: Pubkey,
+    pub reason: LiquidationReason,
+    pub collateral_mint_token: Pubkey,
+    pub collateral_amount: u64,
+    pub borrow_amount: u64,
     pub liquidating_price: Option<f64>,
-    pub liquidating_at: Option<i64>,
 }
 
 #[event]
-pub struct LiquidatedCollateralEvent {
-    pub loan_offer_id: String,
+pub struct LiquidationFinished {
+    pub header: EventHeader,
+    pub borrower: Pubkey,
+    /// Lender the loan was created with, the creditor is whoever holds the position token.
+    pub original_lender: Pubkey,
+    pub collateral_swapped_amount: u64,
     pub liquidated_price: u64,
     pub liquidated_tx: String,
-    pub system: Pubkey,
-    pub lender: Pubkey,
-    pub borrower: Pubkey,
     pub remaining_fund_to_borrower: u64,
-    pub collateral_swapped_amount: u64,
-    pub status: LoanOfferStatus,
 }
 
+/// Liquidation of a native SOL loan settled by a keeper in a single instruction.
 #[event]
-pub struct SystemRepayLoanOfferEvent {
+pub struct LiquidationSettled {
+    pub header: EventHeader,
+    pub borrower: Pubkey,
+    /// Creditor of the loan, credited with `lender_amount`.
     pub lender: Pubkey,
+    pub liquidator: Pubkey,
+    pub reason: LiquidationReason,
+    /// Lend mint amount the liquidator paid, `lender_amount` plus `fee_amount`.
+    pub repaid_amount: u64,
+    pub lender_amount: u64,
+    pub fee_amount: u64,
+    pub collateral_price: f64,
+    /// Lamports of collateral the liquidator received, bonus included.
+    pub seized_collateral_amount: u64,
+    pub remaining_collateral_to_borrower: u64,
+}
+
+#[event]
+pub struct LoanFinished {
+    pub header: EventHeader,
     pub borrower: Pubkey,
+    pub lender: Pubkey,
+    pub tier_id: String,
     pub interest: f64,
     pub loan_amount: u64,
-    pub collateral_amount: u64,
-    pub loan_offer_id: String,
-    pub tier_id: String,
-    pub status: LoanOfferStatus,
+    pub waiting_interest: u64,
+    pub total_repay_to_lender: u64,
 }
 
 #[event]
-pub struct SystemFinishLoanOfferEvent {
-    pub system: Pubkey,
-    pub lender: Pubkey,
+pub struct LoanStatusReverted {
+    pub header: EventHeader,
     pub borrower: Pubkey,
-    pub interest: f64,
-    pub loan_amount: u64,
-    pub loan_offer_id: String,
+}
+
+#[event]
+pub struct LoanClosed {
+    pub header: EventHeader,
+    pub lend_offer_id: String,
     pub tier_id: String,
-    pub status: LoanOfferStatus,
+    pub borrower: Pubkey,
+    /// Lender the loan was created with, the creditor is whoever holds the position token.
+    pub original_lender: Pubkey,
+    pub rent_receiver: Pubkey,
+    pub lend_mint_token: Pubkey,
+    pub borrow_amount: u64,
+    pub collateral_mint_token: Pubkey,
+    pub collateral_amount: u64,
+    pub interest: f64,
+    pub started_at: i64,
 }
 
 #[event]
-pub struct SystemRevertEvent {
-    pub offer_id: String,
-    pub status: LoanOfferStatus,
+pub struct CollateralVaultClosed {
+    pub header: EventHeader,
+    pub borrower: Pubkey,
+    pub collateral_mint_token: Pubkey,
 }
 
 #[event]
-pub struct InitForeignEmitterEvent {
-    pub chain_id: u16,
+pub struct LoanPositionListed {
+    pub header: EventHeader,
+    pub position_mint: Pubkey,
+    pub lend_mint_token: Pubkey,
+    pub price: u64,
+    pub listed_at: i64,
+}
+
+#[event]
+pub struct LoanPositionListingCanceled {
+    pub header: EventHeader,
+    pub position_mint: Pubkey,
+}
+
+#[event]
+pub struct LoanPositionBought {
+    pub header: EventHeader,
+    pub seller: Pubkey,
+    pub position_mint: Pubkey,
+    pub lend_mint_token: Pubkey,
+    pub price: u64,
+}
+
+/// Both cancellation requests of a cross-chain loan, before or after it was funded. The
+/// subject is the borrower, no loan account exists yet when the collateral is canceled.
+#[event]
+pub struct CrossChainCancelRequested {
+    pub header: EventHeader,
+    pub target_chain: u16,
     pub chain_address: String,
-    pub emitter_address: String
 }
 
 #[event]
-pub struct RequestCancelCollateralCrossChainEvent {
+pub struct CrossChainCollateralDeposited {
+    pub header: EventHeader,
     pub borrower: Pubkey,
-    pub lend_offer_id: String,
     pub target_chain: u16,
     pub chain_address: String,
+    pub collateral_address: String,
+    pub collateral_amount: u64,
 }
 
 #[event]
-pub struct UpdateDepositCollateralCrossChainEvent {
-  pub target_chain: u16,
-  pub chain_address: String,
-  pub loan_offer_id: String,
-  pub collateral_amount: u64,
-  pub collateral_address: String,
-  pub borrower: Pubkey,
+pub struct CrossChainCollateralWithdrawn {
+    pub header: EventHeader,
+    pub borrower: Pubkey,
+    pub target_chain: u16,
+    pub chain_address: String,
+    pub collateral_address: String,
+    pub withdraw_amount: u64,
+    pub remaining_collateral_amount: u64,
 }
 
 #[event]
-pub struct UpdateWithdrawCollateralCrossChainEvent {
-  pub target_chain: u16,
-  pub chain_address: String,
-  pub loan_offer_id: String,
-  pub withdraw_amount: u64,
-  pub remaining_collateral_amount: u64,
-  pub collateral_address: String,
-  pub borrower: Pubkey,
-}
\ No newline at end of file
+pub struct CrossChainCollateralSwapped {
+    pub header: EventHeader,
+    pub borrower: Pubkey,
+    pub target_chain: u16,
+    pub chain_address: String,
+    pub old_collateral_address: String,
+    pub old_collateral_amount: u64,
+    pub new_collateral_address: String,
+    pub new_collateral_amount: u64,
+}
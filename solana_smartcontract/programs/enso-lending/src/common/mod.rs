@@ -4,3 +4,5 @@ pub mod error;
 pub use error::*;
 pub mod constant;
 pub use constant::*;
+pub mod state_machine;
+pub use state_machine::*;
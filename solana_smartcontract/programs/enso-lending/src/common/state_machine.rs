@@ -0,0 +1,116 @@
+use std::fmt::Debug;
+
+use anchor_lang::prelude::*;
+
+use crate::{LendOfferStatus, LoanOfferStatus, StatusTransitionError};
+
+/// Status of an account whose instructions are allowed by a transition table.
+pub trait Status: Copy + PartialEq + Debug + 'static {
+    type Event: Copy + PartialEq + Debug + 'static;
+
+    /// Status an event is allowed from and the status it leads to, events that keep the status
+    /// lead back to the same one.
+    const TRANSITIONS: &'static [(Self, Self::Event, Self)];
+
+    fn next(self, event: Self::Event) -> Option<Self> {
+        Self::TRANSITIONS
+            .iter()
+            .find(|(from, on, _)| *from == self && *on == event)
+            .map(|(_, _, to)| *to)
+    }
+
+    fn can_transition_to(self, next: Self) -> bool {
+        next != self && Self::TRANSITIONS.iter().any(|(from, _, to)| *from == self && *to == next)
+    }
+
+    fn is_final(self) -> bool {
+        !Self::TRANSITIONS.iter().any(|(from, _, to)| *from == self && *to != self)
+    }
+}
+
+/// Status after `event`, or `StatusTransitionError::InvalidTransition` carrying the attempted
+/// status and event as its compared values when `from` does not allow it.
+pub fn transition<S: Status>(from: S, event: S::Event) -> Result<S> {
+    from.next(event).ok_or_else(|| {
+        error!(StatusTransitionError::InvalidTransition).with_values((format!("{:?}", from), format!("{:?}", event)))
+    })
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LoanOfferEvent {
+    Fund,
+    DepositCollateral,
+    WithdrawCollateral,
+    SyncCrossChainCollateral,
+    SwapCollateral,
+    TradePosition,
+    Repay,
+    RepayToLender,
+    StartLiquidation,
+    SettleLiquidation,
+    RevertLiquidation,
+    Liquidate,
+    Finish,
+    Close,
+}
+
+impl Status for LoanOfferStatus {
+    type Event = LoanOfferEvent;
+
+    // Note: Repay is never entered
+    const TRANSITIONS: &'static [(LoanOfferStatus, LoanOfferEvent, LoanOfferStatus)] = &[
+        (LoanOfferStatus::Matched, LoanOfferEvent::Fund, LoanOfferStatus::FundTransferred),
+        (LoanOfferStatus::Matched, LoanOfferEvent::DepositCollateral, LoanOfferStatus::Matched),
+        (LoanOfferStatus::FundTransferred, LoanOfferEvent::DepositCollateral, LoanOfferStatus::FundTransferred),
+        (LoanOfferStatus::FundTransferred, LoanOfferEvent::WithdrawCollateral, LoanOfferStatus::FundTransferred),
+        (LoanOfferStatus::Matched, LoanOfferEvent::SyncCrossChainCollateral, LoanOfferStatus::Matched),
+        (LoanOfferStatus::FundTransferred, LoanOfferEvent::SyncCrossChainCollateral, LoanOfferStatus::FundTransferred),
+        (LoanOfferStatus::FundTransferred, LoanOfferEvent::SwapCollateral, LoanOfferStatus::FundTransferred),
+        (LoanOfferStatus::FundTransferred, LoanOfferEvent::TradePosition, LoanOfferStatus::FundTransferred),
+        (LoanOfferStatus::FundTransferred, LoanOfferEvent::Repay, LoanOfferStatus::BorrowerPaid),
+        (LoanOfferStatus::FundTransferred, LoanOfferEvent::RepayToLender, LoanOfferStatus::Finished),
+        (LoanOfferStatus::FundTransferred, LoanOfferEvent::StartLiquidation, LoanOfferStatus::Liquidating),
+        (LoanOfferStatus::FundTransferred, LoanOfferEvent::SettleLiquidation, LoanOfferStatus::Finished),
+        (LoanOfferStatus::Liquidating, LoanOfferEvent::RevertLiquidation, LoanOfferStatus::FundTransferred),
+        (LoanOfferStatus::Liquidating, LoanOfferEvent::Liquidate, LoanOfferStatus::Liquidated),
+        (LoanOfferStatus::BorrowerPaid, LoanOfferEvent::Finish, LoanOfferStatus::Finished),
+        (LoanOfferStatus::Liquidated, LoanOfferEvent::Finish, LoanOfferStatus::Finished),
+        (LoanOfferStatus::Finished, LoanOfferEvent::Close, LoanOfferStatus::Finished),
+    ];
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LendOfferEvent {
+    Edit,
+    Take,
+    Cancel,
+    CancelEscrowed,
+    ConfirmCancel,
+    Reclaim,
+    RefundCrossChainCollateral,
+    RequestCancelLoaned,
+    ConfirmWithdraw,
+    Close,
+}
+
+impl Status for LendOfferStatus {
+    type Event = LendOfferEvent;
+
+    const TRANSITIONS: &'static [(LendOfferStatus, LendOfferEvent, LendOfferStatus)] = &[
+        (LendOfferStatus::Created, LendOfferEvent::Edit, LendOfferStatus::Created),
+        (LendOfferStatus::Created, LendOfferEvent::Take, LendOfferStatus::Loaned),
+        (LendOfferStatus::Created, LendOfferEvent::Cancel, LendOfferStatus::Canceling),
+        (LendOfferStatus::Created, LendOfferEvent::CancelEscrowed, LendOfferStatus::Canceled),
+        (LendOfferStatus::Canceling, LendOfferEvent::ConfirmCancel, LendOfferStatus::Canceled),
+        (LendOfferStatus::Created, LendOfferEvent::Reclaim, LendOfferStatus::Canceled),
+        (LendOfferStatus::Created, LendOfferEvent::RefundCrossChainCollateral, LendOfferStatus::Created),
+        (LendOfferStatus::Canceling, LendOfferEvent::RefundCrossChainCollateral, LendOfferStatus::Canceling),
+        (LendOfferStatus::Canceled, LendOfferEvent::RefundCrossChainCollateral, LendOfferStatus::Canceled),
+        (LendOfferStatus::Loaned, LendOfferEvent::RequestCancelLoaned, LendOfferStatus::Loaned),
+        (LendOfferStatus::Created, LendOfferEvent::ConfirmWithdraw, LendOfferStatus::Created),
+        (LendOfferStatus::Canceling, LendOfferEvent::ConfirmWithdraw, LendOfferStatus::Canceling),
+        (LendOfferStatus::Loaned, LendOfferEvent::ConfirmWithdraw, LendOfferStatus::Loaned),
+        (LendOfferStatus::Canceled, LendOfferEvent::Close, LendOfferStatus::Canceled),
+        (LendOfferStatus::Loaned, LendOfferEvent::Close, LendOfferStatus::Loaned),
+    ];
+}
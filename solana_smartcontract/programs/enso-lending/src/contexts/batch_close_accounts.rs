@@ -0,0 +1,244 @@
+use anchor_lang::{prelude::*, AccountsClose, Discriminator};
+use anchor_spl::{
+  associated_token::get_associated_token_address,
+  token::{self, close_account, CloseAccount, Token, TokenAccount},
+};
+
+use crate::{
+  common::error::{AccountVersionError, LendOfferError, LoanOfferError},
+  states::{lend_offer::LendOfferAccount, loan_offer::LoanOfferAccount},
+  transition, CollateralVaultClosed, EventHeader, LendOfferClosed, LendOfferEvent, LendOfferStatus, LoanClosed, LoanOfferEvent,
+  LoanOfferStatus, VaultAuthority, ENSO_SEED, LEND_OFFER_ACCOUNT_SEED, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED
+};
+
+#[derive(Accounts)]
+pub struct BatchCloseAccounts<'info> {
+  pub cranker: Signer<'info>,
+  /// Required to close lend offer vaults and collateral vaults
+  pub token_program: Option<Program<'info, Token>>,
+}
+
+impl<'info> BatchCloseAccounts<'info> {
+  // Note: remaining_accounts are read as groups, the first account's owner and discriminator decide the group:
+  // - loan offer: [loan_offer, rent_receiver, lend_offer], the lend offer has to be closed already
+  // - canceled lend offer: [lend_offer, lender], followed by its lend_offer_vault when the offer has expiry
+  // - loaned lend offer: [lend_offer, lender, loan_offer], followed by its lend_offer_vault when the offer has expiry,
+  //   list it before the loan offer group
+  // - empty collateral vault: [vault, vault_authority, borrower]
+  pub fn batch_close_accounts(&self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+    let mut index = 0;
+
+    while index < remaining_accounts.len() {
+      let account = &remaining_accounts[index];
+      if account.owner == &token::ID {
+        index += self.close_collateral_vault(remaining_accounts, index)?;
+        continue;
+      }
+
+      let data = account.try_borrow_data()?;
+      let discriminator: [u8; 8] = data.get(..8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+      drop(data);
+
+      index += if discriminator == LoanOfferAccount::DISCRIMINATOR {
+        Self::close_loan_offer(remaining_accounts, index, self.cranker.key())?
+      } else if discriminator == LendOfferAccount::DISCRIMINATOR {
+        self.close_lend_offer(remaining_accounts, index)?
+      } else {
+        return err!(ErrorCode::AccountDiscriminatorMismatch);
+      };
+    }
+
+    Ok(())
+  }
+
+  fn close_loan_offer(remaining_accounts: &'info [AccountInfo<'info>], index: usize, cranker: Pubkey) -> Result<usize> {
+    let loan_offer: Account<'info, LoanOfferAccount> = Account::try_from(&remaining_accounts[index])?;
+    let rent_receiver = remaining_accounts.get(index + 1).ok_or(ErrorCode::AccountNotEnoughKeys)?;
+    let lend_offer = remaining_accounts.get(index + 2).ok_or(ErrorCode::AccountNotEnoughKeys)?;
+
+    if !loan_offer.is_current_version() {
+      return err!(AccountVersionError::OutdatedAccountVersion);
+    }
+
+    transition(loan_offer.status, LoanOfferEvent::Close)?;
+
+    if rent_receiver.key() != loan_offer.rent_receiver() {
+      return err!(LoanOfferError::InvalidRentReceiver);
+    }
+
+    if lend_offer.key() != loan_offer.lend_offer_address() {
+      return err!(ErrorCode::ConstraintAddress);
+    }
+
+    if !lend_offer.data_is_empty() {
+      return err!(LoanOfferError::LendOfferNotClosed);
+    }
+
+    emit!(LoanClosed {
+      header: EventHeader::new(loan_offer.key(), loan_offer.offer_id.clone(), cranker)?
+        .with_status(Some(loan_offer.status), None::<LoanOfferStatus>),
+      lend_offer_id: loan_offer.lend_offer_id.clone(),
+      tier_id: loan_offer.tier_id.clone(),
+      borrower: loan_offer.borrower,
+      original_lender: loan_offer.lender,
+      rent_receiver: rent_receiver.key(),
+      lend_mint_token: loan_offer.lend_mint_token,
+      borrow_amount: loan_offer.borrow_amount,
+      collateral_mint_token: loan_offer.collateral_mint_token,
+      collateral_amount: loan_offer.collateral_amount,
+      interest: loan_offer.interest,
+      started_at: loan_offer.started_at,
+    });
+
+    loan_offer.close(rent_receiver.clone())?;
+
+    Ok(3)
+  }
+
+  fn close_lend_offer(&self, remaining_accounts: &'info [AccountInfo<'info>], index: usize) -> Result<usize> {
+    let lend_offer: Account<'info, LendOfferAccount> = Account::try_from(&remaining_accounts[index])?;
+    let lender = remaining_accounts.get(index + 1).ok_or(ErrorCode::AccountNotEnoughKeys)?;
+
+    if !lend_offer.is_current_version() {
+      return err!(AccountVersionError::OutdatedAccountVersion);
+    }
+
+    if lender.key() != lend_offer.lender {
+      return err!(LendOfferError::InvalidLender);
+    }
+
+    let (loan_offer, mut group_size) = if lend_offer.status == LendOfferStatus::Loaned {
+      let loan_offer_info = remaining_accounts.get(index + 2).ok_or(ErrorCode::AccountNotEnoughKeys)?;
+      let loan_offer: Account<'info, LoanOfferAccount> = Account::try_from(loan_offer_info)?;
+      if !loan_offer.is_current_version() {
+        return err!(AccountVersionError::OutdatedAccountVersion);
+      }
+      (Some(loan_offer), 3)
+    } else {
+      (None, 2)
+    };
+
+    transition(lend_offer.status, LendOfferEvent::Close)?;
+    if !lend_offer.is_closable(loan_offer.as_deref()) {
+      return err!(LendOfferError::InvalidOfferStatus);
+    }
+
+    if lend_offer.is_escrowed() {
+      let lend_offer_vault = remaining_accounts.get(index + group_size).ok_or(ErrorCode::AccountNotEnoughKeys)?;
+      self.close_lend_offer_vault(&lend_offer, lend_offer_vault, lender)?;
+      group_size += 1;
+    }
+
+    emit!(LendOfferClosed {
+      header: EventHeader::new(lend_offer.key(), lend_offer.offer_id.clone(), self.cranker.key())?
+        .with_status(Some(lend_offer.status), None::<LendOfferStatus>),
+      lender: lend_offer.lender,
+      lend_mint_token: lend_offer.lend_mint_token,
+      amount: lend_offer.amount,
+      interest: lend_offer.interest,
+      duration: lend_offer.duration,
+    });
+
+    lend_offer.close(lender.clone())?;
+
+    Ok(group_size)
+  }
+
+  fn close_lend_offer_vault(
+    &self,
+    lend_offer: &Account<'info, LendOfferAccount>,
+    lend_offer_vault: &'info AccountInfo<'info>,
+    lender: &'info AccountInfo<'info>,
+  ) -> Result<()> {
+    let vault: Account<'info, TokenAccount> = Account::try_from(lend_offer_vault)?;
+    if vault.key() != get_associated_token_address(&lend_offer.key(), &lend_offer.lend_mint_token) {
+      return err!(ErrorCode::AccountNotAssociatedTokenAccount);
+    }
+
+    if vault.amount != 0 {
+      return err!(LendOfferError::LendOfferVaultNotEmpty);
+    }
+
+    let program_id = crate::ID.key();
+    let signer: &[&[&[u8]]] = &[&[
+      ENSO_SEED.as_ref(),
+      LEND_OFFER_ACCOUNT_SEED.as_ref(),
+      lend_offer.lender.as_ref(),
+      lend_offer.offer_id.as_bytes(),
+      program_id.as_ref(),
+      &[lend_offer.bump]
+    ]];
+
+    close_account(CpiContext::new_with_signer(
+      self.token_program()?,
+      CloseAccount {
+        account: lend_offer_vault.clone(),
+        destination: lender.clone(),
+        authority: lend_offer.to_account_info(),
+      },
+      signer
+    ))
+  }
+
+  // Note: The vault is shared by every loan of the borrower with this collateral mint, it is only empty once
+  // none of them holds collateral in it, and loans taken later re-create it
+  fn close_collateral_vault(&self, remaining_accounts: &'info [AccountInfo<'info>], index: usize) -> Result<usize> {
+    let vault: Account<'info, TokenAccount> = Account::try_from(&remaining_accounts[index])?;
+    let vault_authority_info = remaining_accounts.get(index + 1).ok_or(ErrorCode::AccountNotEnoughKeys)?;
+    let borrower = remaining_accounts.get(index + 2).ok_or(ErrorCode::AccountNotEnoughKeys)?;
+    let vault_authority: Account<'info, VaultAuthority> = Account::try_from(vault_authority_info)?;
+
+    if vault_authority.initializer != borrower.key() {
+      return err!(LoanOfferError::InvalidInitializerVaultAuthority);
+    }
+
+    let borrower_pub_key = borrower.key();
+    let program_id = crate::ID.key();
+    let seeds: &[&[u8]] = &[
+      ENSO_SEED.as_ref(),
+      borrower_pub_key.as_ref(),
+      VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED.as_ref(),
+      program_id.as_ref(),
+      &[vault_authority.bump]
+    ];
+
+    if Pubkey::create_program_address(seeds, &crate::ID).ok() != Some(vault_authority.key()) {
+      return err!(ErrorCode::ConstraintSeeds);
+    }
+
+    if vault.key() != get_associated_token_address(&vault_authority.key(), &vault.mint) {
+      return err!(ErrorCode::AccountNotAssociatedTokenAccount);
+    }
+
+    if vault.amount != 0 {
+      return err!(LoanOfferError::CollateralVaultNotEmpty);
+    }
+
+    close_account(CpiContext::new_with_signer(
+      self.token_program()?,
+      CloseAccount {
+        account: vault.to_account_info(),
+        destination: borrower.clone(),
+        authority: vault_authority.to_account_info(),
+      },
+      &[seeds]
+    ))?;
+
+    emit!(CollateralVaultClosed {
+      header: EventHeader::new(vault.key(), String::new(), self.cranker.key())?,
+      borrower: borrower_pub_key,
+      collateral_mint_token: vault.mint,
+    });
+
+    Ok(3)
+  }
+
+  fn token_program(&self) -> Result<AccountInfo<'info>> {
+    self.token_program
+      .as_ref()
+      .map(|token_program| token_program.to_account_info())
+      .ok_or(error!(ErrorCode::AccountNotEnoughKeys))
+  }
+}
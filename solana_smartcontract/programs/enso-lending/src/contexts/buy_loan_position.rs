@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token::{close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+  common::{ENSO_SEED, LOAN_POSITION_LISTING_SEED},
+  EventHeader, LoanOfferAccount, LoanPositionBought, LoanPositionListing, LoanPositionListingError
+};
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
+
+#[derive(Accounts)]
+pub struct BuyLoanPosition<'info> {
+  #[account(mut)]
+  pub buyer: Signer<'info>,
+  #[account(
+    mut,
+    constraint = seller.key() == listing.seller @ LoanPositionListingError::InvalidSeller,
+  )]
+  pub seller: SystemAccount<'info>,
+  #[account(
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = loan_offer.key() == listing.loan_offer @ LoanPositionListingError::InvalidLoanOffer,
+  )]
+  pub loan_offer: Box<Account<'info, LoanOfferAccount>>,
+  #[account(
+    constraint = position_mint.key() == listing.position_mint @ LoanPositionListingError::InvalidPositionMint,
+  )]
+  pub position_mint: Box<Account<'info, Mint>>,
+  #[account(
+    constraint = lend_mint_asset.key() == listing.lend_mint_token @ LoanPositionListingError::InvalidMintAsset,
+  )]
+  pub lend_mint_asset: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    close = seller,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LOAN_POSITION_LISTING_SEED.as_ref(),
+      position_mint.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = listing.bump
+  )]
+  pub listing: Box<Account<'info, LoanPositionListing>>,
+  #[account(
+    mut,
+    associated_token::mint = position_mint,
+    associated_token::authority = listing
+  )]
+  pub listing_position_ata: Box<Account<'info, TokenAccount>>,
+  #[account(
+    init_if_needed,
+    payer = buyer,
+    associated_token::mint = position_mint,
+    associated_token::authority = buyer
+  )]
+  pub buyer_position_ata: Box<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = buyer
+  )]
+  pub buyer_ata_asset: Box<Account<'info, TokenAccount>>,
+  #[account(
+    init_if_needed,
+    payer = buyer,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = seller
+  )]
+  pub seller_ata_asset: Box<Account<'info, TokenAccount>>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuyLoanPosition<'info> {
+  pub fn buy_loan_position(&mut self, expected_price: u64) -> Result<()> {
+    transition(self.loan_offer.status, LoanOfferEvent::TradePosition)?;
+
+    if self.listing.price != expected_price {
+      return err!(LoanPositionListingError::PriceUpdated);
+    }
+
+    self.pay_seller()?;
+    self.transfer_position_token()?;
+
+    self.emit_event_buy_loan_position()?;
+
+    Ok(())
+  }
+
+  fn pay_seller(&self) -> Result<()> {
+    let ctx = CpiContext::new(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.buyer_ata_asset.to_account_info(),
+        mint: self.lend_mint_asset.to_account_info(),
+        to: self.seller_ata_asset.to_account_info(),
+        authority: self.buyer.to_account_info(),
+      }
+    );
+
+    transfer_checked(ctx, self.listing.price, self.lend_mint_asset.decimals)
+  }
+
+  fn transfer_position_token(&self) -> Result<()> {
+    let position_mint = self.position_mint.key();
+    let program_id = crate::ID.key();
+
+    let signer: &[&[&[u8]]] = &[&[
+      ENSO_SEED.as_ref(),
+      LOAN_POSITION_LISTING_SEED.as_ref(),
+      position_mint.as_ref(),
+      program_id.as_ref(),
+      &[self.listing.bump]
+    ]];
+
+    transfer_checked(
+      CpiContext::new_with_signer(
+        self.token_program.to_account_info(),
+        TransferChecked {
+          from: self.listing_position_ata.to_account_info(),
+          mint: self.position_mint.to_account_info(),
+          to: self.buyer_position_ata.to_account_info(),
+          authority: self.listing.to_account_info(),
+        },
+        signer
+      ),
+      self.listing_position_ata.amount,
+      self.position_mint.decimals,
+    )?;
+
+    close_account(CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      CloseAccount {
+        account: self.listing_position_ata.to_account_info(),
+        destination: self.seller.to_account_info(),
+        authority: self.listing.to_account_info(),
+      },
+      signer
+    ))
+  }
+
+  fn emit_event_buy_loan_position(&self) -> Result<()> {
+    emit!(LoanPositionBought {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.buyer.key())?
+        .with_status(Some(self.loan_offer.status), Some(self.loan_offer.status)),
+      seller: self.seller.key(),
+      position_mint: self.position_mint.key(),
+      lend_mint_token: self.lend_mint_asset.key(),
+      price: self.listing.price,
+    });
+
+    Ok(())
+  }
+}
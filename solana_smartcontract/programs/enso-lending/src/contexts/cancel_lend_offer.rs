@@ -1,13 +1,17 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
 use crate::{
   ENSO_SEED, LEND_OFFER_ACCOUNT_SEED,
   common::{
-    LendOfferCancelRequestEvent, 
+    EventHeader,
+    LendOfferCancelRequested,
     LendOfferStatus, 
     LendOfferError
   },
   states::lend_offer::LendOfferAccount
 };
+use crate::AccountVersionError;
+use crate::{transition, LendOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(offer_id: String)]
@@ -16,7 +20,7 @@ pub struct CancelLendOffer<'info> {
   pub lender: Signer<'info>,
   #[account(
     mut,
-    constraint = lend_offer.status == LendOfferStatus::Created @ LendOfferError::InvalidOfferStatus,
+    constraint = lend_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LEND_OFFER_ACCOUNT_SEED.as_ref(),  
@@ -27,28 +31,98 @@ pub struct CancelLendOffer<'info> {
     bump = lend_offer.bump
   )]
   pub lend_offer: Account<'info, LendOfferAccount>,
+  #[account(
+    constraint = mint_asset.key() == lend_offer.lend_mint_token @ LendOfferError::InvalidMintAsset,
+  )]
+  pub mint_asset: Option<Account<'info, Mint>>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = lender
+  )]
+  pub lender_ata_asset: Option<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = lend_offer
+  )]
+  pub lend_offer_vault: Option<Account<'info, TokenAccount>>,
+  pub token_program: Option<Program<'info, Token>>,
 }
 
 impl<'info> CancelLendOffer<'info> {
   pub fn cancel_lend_offer(&mut self) -> Result<()>  {
-    self.lend_offer.status = LendOfferStatus::Canceling;
+    let old_status = self.lend_offer.status;
+
+    // Escrowed principal is not held by the hot wallet, so it is refunded here instead of by the system
+    if self.lend_offer.is_escrowed() {
+      self.transfer_back_escrowed_lend_asset()?;
+      self.lend_offer.status = transition(self.lend_offer.status, LendOfferEvent::CancelEscrowed)?;
+
+      self.emit_event_cancel_lend_offer(old_status, self.lend_offer.amount)?;
+
+      return Ok(());
+    }
+
+    self.lend_offer.status = transition(self.lend_offer.status, LendOfferEvent::Cancel)?;
 
-    self.emit_event_cancel_lend_offer()?;
+    self.emit_event_cancel_lend_offer(old_status, 0)?;
 
     Ok(())
   }
 
-  fn emit_event_cancel_lend_offer(&mut self) -> Result<()> {
-    emit!(LendOfferCancelRequestEvent {
-      lender: self.lender.key(),
+  fn transfer_back_escrowed_lend_asset(&self) -> Result<()> {
+    let (Some(mint_asset), Some(lender_ata_asset), Some(lend_offer_vault), Some(token_program)) = (
+      &self.mint_asset,
+      &self.lender_ata_asset,
+      &self.lend_offer_vault,
+      &self.token_program
+    ) else {
+      return err!(LendOfferError::MissingLendOfferVault);
+    };
+
+    let lender_pub_key = self.lender.key();
+    let program_id = crate::ID.key();
+
+    let signer: &[&[&[u8]]] = &[&[
+      ENSO_SEED.as_ref(),
+      LEND_OFFER_ACCOUNT_SEED.as_ref(),
+      lender_pub_key.as_ref(),
+      self.lend_offer.offer_id.as_bytes(),
+      program_id.as_ref(),
+      &[self.lend_offer.bump]
+    ]];
+
+    let ctx = CpiContext::new_with_signer(
+      token_program.to_account_info(),
+      TransferChecked {
+        from: lend_offer_vault.to_account_info(),
+        mint: mint_asset.to_account_info(),
+        to: lender_ata_asset.to_account_info(),
+        authority: self.lend_offer.to_account_info(),
+      },
+      signer
+    );
+
+    transfer_checked(
+      ctx,
+      lend_offer_vault.amount,
+      mint_asset.decimals,
+    )
+  }
+
+  fn emit_event_cancel_lend_offer(&self, old_status: LendOfferStatus, refunded_amount: u64) -> Result<()> {
+    emit!(LendOfferCancelRequested {
+      header: EventHeader::new(self.lend_offer.key(), self.lend_offer.offer_id.clone(), self.lender.key())?
+        .with_status(Some(old_status), Some(self.lend_offer.status)),
+      lend_mint_token: self.lend_offer.lend_mint_token,
       amount: self.lend_offer.amount,
-      duration: self.lend_offer.duration,
       interest: self.lend_offer.interest,
       lender_fee_percent: self.lend_offer.lender_fee_percent,
-      offer_id: self.lend_offer.offer_id.clone()
+      duration: self.lend_offer.duration,
+      refunded_amount,
     });
 
-
     Ok(())
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{
+  common::{ENSO_SEED, LOAN_POSITION_LISTING_SEED},
+  EventHeader, LoanPositionListing, LoanPositionListingCanceled, LoanPositionListingError
+};
+
+#[derive(Accounts)]
+pub struct CancelLoanPositionListing<'info> {
+  #[account(mut)]
+  pub seller: Signer<'info>,
+  #[account(
+    constraint = position_mint.key() == listing.position_mint @ LoanPositionListingError::InvalidPositionMint,
+  )]
+  pub position_mint: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    associated_token::mint = position_mint,
+    associated_token::authority = seller
+  )]
+  pub seller_position_ata: Box<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    close = seller,
+    constraint = listing.seller == seller.key() @ LoanPositionListingError::InvalidSeller,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LOAN_POSITION_LISTING_SEED.as_ref(),
+      position_mint.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = listing.bump
+  )]
+  pub listing: Box<Account<'info, LoanPositionListing>>,
+  #[account(
+    mut,
+    associated_token::mint = position_mint,
+    associated_token::authority = listing
+  )]
+  pub listing_position_ata: Box<Account<'info, TokenAccount>>,
+  pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CancelLoanPositionListing<'info> {
+  pub fn cancel_loan_position_listing(&mut self) -> Result<()> {
+    self.withdraw_position_token()?;
+
+    self.emit_event_cancel_loan_position_listing()?;
+
+    Ok(())
+  }
+
+  fn withdraw_position_token(&self) -> Result<()> {
+    let position_mint = self.position_mint.key();
+    let program_id = crate::ID.key();
+
+    let signer: &[&[&[u8]]] = &[&[
+      ENSO_SEED.as_ref(),
+      LOAN_POSITION_LISTING_SEED.as_ref(),
+      position_mint.as_ref(),
+      program_id.as_ref(),
+      &[self.listing.bump]
+    ]];
+
+    transfer_checked(
+      CpiContext::new_with_signer(
+        self.token_program.to_account_info(),
+        TransferChecked {
+          from: self.listing_position_ata.to_account_info(),
+          mint: self.position_mint.to_account_info(),
+          to: self.seller_position_ata.to_account_info(),
+          authority: self.listing.to_account_info(),
+        },
+        signer
+      ),
+      self.listing_position_ata.amount,
+      self.position_mint.decimals,
+    )?;
+
+    close_account(CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      CloseAccount {
+        account: self.listing_position_ata.to_account_info(),
+        destination: self.seller.to_account_info(),
+        authority: self.listing.to_account_info(),
+      },
+      signer
+    ))
+  }
+
+  fn emit_event_cancel_loan_position_listing(&self) -> Result<()> {
+    emit!(LoanPositionListingCanceled {
+      header: EventHeader::new(self.listing.loan_offer, String::new(), self.seller.key())?,
+      position_mint: self.position_mint.key(),
+    });
+
+    Ok(())
+  }
+}
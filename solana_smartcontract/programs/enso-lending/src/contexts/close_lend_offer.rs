@@ -1,13 +1,17 @@
 use std::str::FromStr;
 
 use anchor_lang::prelude::*;
+use anchor_spl::token::{close_account, CloseAccount, Token, TokenAccount};
 use crate::{
   common::{
-    constant::{LendOfferStatus, OPERATE_SYSTEM_PUBKEY},
+    constant::{OPERATE_SYSTEM_PUBKEY, ENSO_SEED, LEND_OFFER_ACCOUNT_SEED},
     error::LendOfferError
   }, 
-  states::lend_offer::LendOfferAccount
+  states::{lend_offer::LendOfferAccount, loan_offer::LoanOfferAccount},
+  EventHeader, LendOfferClosed, LendOfferStatus
 };
+use crate::AccountVersionError;
+use crate::{transition, LendOfferEvent};
 
 #[derive(Accounts)]
 pub struct CloseLendOffer<'info> {
@@ -17,21 +21,37 @@ pub struct CloseLendOffer<'info> {
   )]
   pub signer: Signer<'info>,
   #[account(
+    mut,
     constraint = lender.key() == lend_offer.lender @ LendOfferError::InvalidLender
   )]
   ///CHECK: This account is used to check the validate of lend offer account
   pub lender: AccountInfo<'info>,
   #[account(
     mut,
+    constraint = lend_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     close = lender
   )]
   pub lend_offer: Account<'info, LendOfferAccount>,
+  /// Loan taken from the lend offer, required to close a loaned lend offer
+  #[account(
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+  )]
+  pub loan_offer: Option<Account<'info, LoanOfferAccount>>,
+  #[account(
+    mut,
+    constraint = lend_offer_vault.amount == 0 @ LendOfferError::LendOfferVaultNotEmpty,
+    associated_token::mint = lend_offer.lend_mint_token,
+    associated_token::authority = lend_offer
+  )]
+  pub lend_offer_vault: Option<Account<'info, TokenAccount>>,
+  pub token_program: Option<Program<'info, Token>>,
   pub system_program: Program<'info, System>,
 }
 
 impl<'info> CloseLendOffer<'info> {
   pub fn validate_lend_offer(&mut self, offer_id: String) -> Result<()> {
-    if self.lend_offer.status != LendOfferStatus::Canceled {
+    transition(self.lend_offer.status, LendOfferEvent::Close)?;
+    if !self.lend_offer.is_closable(self.loan_offer.as_deref()) {
       return err!(LendOfferError::InvalidOfferStatus);
     }
 
@@ -39,6 +59,52 @@ impl<'info> CloseLendOffer<'info> {
       return err!(LendOfferError::InvalidOfferId);
     }
 
+    self.close_lend_offer_vault()?;
+
+    self.emit_event_close_lend_offer()?;
+
+    Ok(())
+  }
+
+  fn close_lend_offer_vault(&self) -> Result<()> {
+    let (Some(lend_offer_vault), Some(token_program)) = (&self.lend_offer_vault, &self.token_program) else {
+      return Ok(());
+    };
+
+    let lender_pub_key = self.lender.key();
+    let program_id = crate::ID.key();
+
+    let signer: &[&[&[u8]]] = &[&[
+      ENSO_SEED.as_ref(),
+      LEND_OFFER_ACCOUNT_SEED.as_ref(),
+      lender_pub_key.as_ref(),
+      self.lend_offer.offer_id.as_bytes(),
+      program_id.as_ref(),
+      &[self.lend_offer.bump]
+    ]];
+
+    close_account(CpiContext::new_with_signer(
+      token_program.to_account_info(),
+      CloseAccount {
+        account: lend_offer_vault.to_account_info(),
+        destination: self.lender.to_account_info(),
+        authority: self.lend_offer.to_account_info(),
+      },
+      signer
+    ))
+  }
+
+  fn emit_event_close_lend_offer(&self) -> Result<()> {
+    emit!(LendOfferClosed {
+      header: EventHeader::new(self.lend_offer.key(), self.lend_offer.offer_id.clone(), self.signer.key())?
+        .with_status(Some(self.lend_offer.status), None::<LendOfferStatus>),
+      lender: self.lend_offer.lender,
+      lend_mint_token: self.lend_offer.lend_mint_token,
+      amount: self.lend_offer.amount,
+      interest: self.lend_offer.interest,
+      duration: self.lend_offer.duration,
+    });
+
     Ok(())
   }
-}
\ No newline at end of file
+}
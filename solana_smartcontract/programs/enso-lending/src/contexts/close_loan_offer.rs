@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+  common::error::LoanOfferError,
+  states::loan_offer::LoanOfferAccount,
+  EventHeader, LoanClosed, LoanOfferStatus
+};
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
+
+#[derive(Accounts)]
+pub struct CloseLoanOffer<'info> {
+  pub signer: Signer<'info>,
+  #[account(
+    mut,
+    constraint = rent_receiver.key() == loan_offer.rent_receiver() @ LoanOfferError::InvalidRentReceiver
+  )]
+  pub rent_receiver: SystemAccount<'info>,
+  #[account(
+    mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    close = rent_receiver,
+  )]
+  pub loan_offer: Account<'info, LoanOfferAccount>,
+  /// CHECK: Lend offer the loan was taken from, only its address and emptiness are read
+  #[account(address = loan_offer.lend_offer_address())]
+  pub lend_offer: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseLoanOffer<'info> {
+  pub fn close_loan_offer(&mut self) -> Result<()> {
+    transition(self.loan_offer.status, LoanOfferEvent::Close)?;
+
+    if !self.lend_offer.data_is_empty() {
+      return err!(LoanOfferError::LendOfferNotClosed);
+    }
+
+    emit!(LoanClosed {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.signer.key())?
+        .with_status(Some(self.loan_offer.status), None::<LoanOfferStatus>),
+      lend_offer_id: self.loan_offer.lend_offer_id.clone(),
+      tier_id: self.loan_offer.tier_id.clone(),
+      borrower: self.loan_offer.borrower,
+      original_lender: self.loan_offer.lender,
+      rent_receiver: self.rent_receiver.key(),
+      lend_mint_token: self.loan_offer.lend_mint_token,
+      borrow_amount: self.loan_offer.borrow_amount,
+      collateral_mint_token: self.loan_offer.collateral_mint_token,
+      collateral_amount: self.loan_offer.collateral_amount,
+      interest: self.loan_offer.interest,
+      started_at: self.loan_offer.started_at,
+    });
+
+    Ok(())
+  }
+}
@@ -3,8 +3,9 @@ use anchor_lang::__private::CLOSED_ACCOUNT_DISCRIMINATOR;
 use std::io::{Cursor, Write};
 use std::ops::DerefMut;
 
-use crate::common::{CloseSettingAccountEvent, constant::{ ENSO_SEED, SETTING_ACCOUNT_SEED }};
+use crate::common::{EventHeader, SettingAccountClosed, constant::{ ENSO_SEED, SETTING_ACCOUNT_SEED }};
 use crate::{SettingAccount, SettingAccountError};
+use crate::AccountVersionError;
 
 #[derive(Accounts)]
 #[instruction(tier_id: String)]
@@ -14,6 +15,7 @@ pub struct CloseSettingAccount<'info> {
   /// CHECK: This is the account used to make a seeds to create ata account for transfer asset from lender to how wallet
   #[account(
     mut,
+    constraint = setting_account.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     has_one = owner,
     constraint = setting_account.tier_id == tier_id @ SettingAccountError::InvalidTierId,
     seeds = [
@@ -56,7 +58,9 @@ impl<'info> CloseSettingAccount<'info> {
     &mut self,
     tier_id: String,
   ) -> Result<()> {
-    emit!(CloseSettingAccountEvent { tier_id });
+    emit!(SettingAccountClosed {
+      header: EventHeader::new(self.setting_account.key(), tier_id, self.owner.key())?,
+    });
 
 
     Ok(())
@@ -1,14 +1,19 @@
-use anchor_lang::prelude::*;
-use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+use anchor_lang::{prelude::*, solana_program::{program::invoke, system_instruction}};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
 
-use crate::{constant::{ENSO_SEED, LEND_OFFER_ACCOUNT_SEED, SETTING_ACCOUNT_SEED}, Asset, CreateLendOfferEvent, LendOfferAccount, LendOfferError, LendOfferStatus, SettingAccount, ASSET_SEED, DISCRIMINATOR, MAX_ALLOWED_INTEREST};
+use crate::{constant::{ENSO_SEED, LEND_OFFER_ACCOUNT_SEED, SETTING_ACCOUNT_SEED}, Asset, EventHeader, LendOfferAccount, LendOfferCreated, LendOfferError, LendOfferStatus, SettingAccount, ASSET_SEED, MAX_ALLOWED_INTEREST};
+use crate::AccountVersionError;
 
 #[derive(Accounts)]
-#[instruction(offer_id: String, tier_id: String, interest: f64)]
+#[instruction(offer_id: String, tier_id: String, interest: f64, amount: u64)]
 pub struct CreateLendOffer<'info> {
     #[account(mut)]
     pub lender: Signer<'info>,
     #[account(
+        constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
         seeds = [
             ENSO_SEED.as_ref(),
             ASSET_SEED.as_ref(),
@@ -24,12 +29,13 @@ pub struct CreateLendOffer<'info> {
     pub mint_asset: Account<'info, Mint>,
     #[account(
         mut,
-        constraint = lender_ata_asset.amount >= setting_account.amount @ LendOfferError::NotEnoughAmount,
+        constraint = lender_ata_asset.amount >= amount @ LendOfferError::NotEnoughAmount,
         associated_token::mint = mint_asset,
         associated_token::authority = lender
     )]
     pub lender_ata_asset: Account<'info, TokenAccount>,
     #[account(
+        constraint = setting_account.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
         seeds = [
             ENSO_SEED.as_ref(), 
             SETTING_ACCOUNT_SEED.as_ref(),
@@ -42,7 +48,7 @@ pub struct CreateLendOffer<'info> {
     #[account(
         init,
         payer = lender,
-        space = (DISCRIMINATOR as usize) + LendOfferAccount::INIT_SPACE,
+        space = 8 + LendOfferAccount::INIT_SPACE,
         seeds = [
             ENSO_SEED.as_ref(), 
             LEND_OFFER_ACCOUNT_SEED.as_ref(), 
@@ -59,6 +65,14 @@ pub struct CreateLendOffer<'info> {
         associated_token::authority = setting_account.receiver
     )]
     pub hot_wallet_ata: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = lender,
+        associated_token::mint = mint_asset,
+        associated_token::authority = lend_offer
+    )]
+    pub lend_offer_vault: Option<Box<Account<'info, TokenAccount>>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -69,6 +83,10 @@ impl<'info> CreateLendOffer<'info> {
         bumps: &CreateLendOfferBumps,
         offer_id: String,
         interest: f64,
+        amount: u64,
+        duration: u64,
+        expires_at: Option<i64>,
+        crank_tip: u64,
     ) -> Result<()> {
             if interest <= (0 as f64) {
                 return err!(LendOfferError::InterestGreaterThanZero);
@@ -78,14 +96,29 @@ impl<'info> CreateLendOffer<'info> {
                 return err!(LendOfferError::InterestOverLimit);
             }
 
-            let SettingAccount { 
-                mut amount, 
-                lender_fee_percent, 
-                duration, 
-                .. 
-            } = self.setting_account.clone().into_inner();
+            self.setting_account.validate_lend_mint(self.mint_asset.key())?;
+            self.setting_account.validate_lend_amount(amount, self.mint_asset.decimals)?;
+            self.setting_account.validate_duration(duration)?;
+
+            match expires_at {
+                Some(expires_at) => {
+                    if expires_at <= Clock::get()?.unix_timestamp {
+                        return err!(LendOfferError::InvalidExpiry);
+                    }
 
-            amount = amount * 10_u64.pow(self.mint_asset.decimals as u32);
+                    if self.lend_offer_vault.is_none() {
+                        return err!(LendOfferError::MissingLendOfferVault);
+                    }
+                }
+                // Note: The vault is created by init_if_needed, rejecting it keeps lenders from paying rent for an unused ATA
+                None => {
+                    if self.lend_offer_vault.is_some() {
+                        return err!(LendOfferError::UnexpectedLendOfferVault);
+                    }
+                }
+            }
+
+            let lender_fee_percent = self.setting_account.lender_fee_percent;
             
             self.lend_offer.set_inner(LendOfferAccount {
                 amount,
@@ -97,21 +130,32 @@ impl<'info> CreateLendOffer<'info> {
                 lend_mint_token: self.mint_asset.key(),
                 offer_id: offer_id.clone(),
                 status: LendOfferStatus::Created,
+                expires_at,
+                crank_tip: if expires_at.is_some() { crank_tip } else { 0 },
+                version: LendOfferAccount::VERSION,
+                tier_id: self.setting_account.tier_id.clone(),
+                pending_withdraw_amount: 0,
             });
 
             self.deposit(amount)?;
+            self.deposit_crank_tip()?;
             self.emit_event_create_lend_offer()?;
             
             Ok(())
     }
 
     fn deposit(&mut self, amount: u64) -> Result<()> {
+        let to = match &self.lend_offer_vault {
+            Some(lend_offer_vault) if self.lend_offer.is_escrowed() => lend_offer_vault.to_account_info(),
+            _ => self.hot_wallet_ata.to_account_info(),
+        };
+
         let ctx = CpiContext::new(
             self.token_program.to_account_info(), 
             TransferChecked {
                 from: self.lender_ata_asset.to_account_info(),
                 mint: self.mint_asset.to_account_info(),
-                to: self.hot_wallet_ata.to_account_info(),
+                to,
                 authority: self.lender.to_account_info(),
             }
         );
@@ -123,15 +167,39 @@ impl<'info> CreateLendOffer<'info> {
         )
     }
 
+    fn deposit_crank_tip(&mut self) -> Result<()> {
+        if self.lend_offer.crank_tip == 0 {
+            return Ok(());
+        }
+
+        invoke(
+            &system_instruction::transfer(
+                &self.lender.key(),
+                &self.lend_offer.key(),
+                self.lend_offer.crank_tip,
+            ),
+            &[
+                self.lender.to_account_info(),
+                self.lend_offer.to_account_info(),
+                self.system_program.to_account_info(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
     fn emit_event_create_lend_offer(&mut self) -> Result<()> {
-        emit!(CreateLendOfferEvent {
-            lender: self.lender.key(),
+        emit!(LendOfferCreated {
+            header: EventHeader::new(self.lend_offer.key(), self.lend_offer.offer_id.clone(), self.lender.key())?
+                .with_status(None, Some(self.lend_offer.status)),
+            tier_id: self.setting_account.tier_id.clone(),
+            lend_mint_token: self.lend_offer.lend_mint_token,
+            amount: self.lend_offer.amount,
             interest: self.lend_offer.interest,
             lender_fee_percent: self.lend_offer.lender_fee_percent,
-            amount: self.lend_offer.amount,
             duration: self.lend_offer.duration,
-            offer_id: self.lend_offer.offer_id.clone(),
-            tier_id: self.setting_account.tier_id.clone(),
+            expires_at: self.lend_offer.expires_at,
+            crank_tip: self.lend_offer.crank_tip,
         });
         
         Ok(())
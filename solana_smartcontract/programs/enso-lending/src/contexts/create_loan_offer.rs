@@ -8,8 +8,14 @@ use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 use crate::{
     common::{
       ASSET_SEED, ENSO_SEED, LEND_OFFER_ACCOUNT_SEED, LOAN_OFFER_ACCOUNT_SEED, SETTING_ACCOUNT_SEED
-    }, health_ratio::{self, HealthRatioParams}, Asset, LendOfferAccount, LendOfferStatus, LoanOfferAccount, LoanOfferCreateRequestEvent, LoanOfferError, LoanOfferStatus, SettingAccount, VaultAuthority, DISCRIMINATOR, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED
+    }, health_ratio::{self, HealthRatioParams}, Asset, ExpectedLoanTerms, LendOfferAccount, LendOfferError, EventHeader, LoanCreated, LoanOfferAccount, LoanOfferError, LoanOfferStatus, SettingAccount, VaultAuthority, LOAN_POSITION_MINT_SEED, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED
 };
+use crate::utils::{
+  lend_offer_vault::{self, ReleaseLendOfferVaultParams},
+  position::{self, MintPositionTokenParams},
+};
+use crate::AccountVersionError;
+use crate::{transition, LendOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(
@@ -37,6 +43,8 @@ pub struct CreateLoanOffer<'info> {
   )]
   pub borrower_ata_asset: Box<Account<'info, TokenAccount>>,
   #[account(
+    mut,
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = lend_asset.is_lend == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -48,6 +56,8 @@ pub struct CreateLoanOffer<'info> {
   )]
   pub lend_asset: Box<Account<'info, Asset>>,
   #[account(
+    mut,
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = collateral_asset.is_collateral == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -61,7 +71,7 @@ pub struct CreateLoanOffer<'info> {
   #[account(
     init,
     payer = borrower,
-    space = (DISCRIMINATOR as usize) + LoanOfferAccount::INIT_SPACE,
+    space = 8 + LoanOfferAccount::INIT_SPACE,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -74,7 +84,7 @@ pub struct CreateLoanOffer<'info> {
   pub loan_offer: Box<Account<'info, LoanOfferAccount>>,
   #[account(
     mut,
-    constraint = lend_offer.status == LendOfferStatus::Created @ LoanOfferError::LendOfferIsNotAvailable,
+    constraint = lend_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(), 
       LEND_OFFER_ACCOUNT_SEED.as_ref(), 
@@ -104,6 +114,18 @@ pub struct CreateLoanOffer<'info> {
     associated_token::authority = vault_authority
   )]
   pub vault: Box<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = lend_offer
+  )]
+  pub lend_offer_vault: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = setting_account.receiver
+  )]
+  pub hot_wallet_ata_lend_asset: Option<Box<Account<'info, TokenAccount>>>,
   pub lender: SystemAccount<'info>,
   #[account(
     constraint = lend_price_feed_account.key() == lend_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccount,
@@ -114,6 +136,8 @@ pub struct CreateLoanOffer<'info> {
   )]
   pub collateral_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
   #[account(
+    constraint = setting_account.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = lend_offer.tier_id.is_empty() || lend_offer.tier_id == setting_account.tier_id @ LendOfferError::TierIdNotMatch,
     seeds = [
       ENSO_SEED.as_ref(), 
       SETTING_ACCOUNT_SEED.as_ref(),
@@ -123,6 +147,27 @@ pub struct CreateLoanOffer<'info> {
     bump = setting_account.bump
   )]
   pub setting_account: Box<Account<'info, SettingAccount>>,
+  #[account(
+    init,
+    payer = borrower,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LOAN_POSITION_MINT_SEED.as_ref(),
+      loan_offer.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump,
+    mint::decimals = 0,
+    mint::authority = position_mint,
+  )]
+  pub position_mint: Box<Account<'info, Mint>>,
+  #[account(
+    init_if_needed,
+    payer = borrower,
+    associated_token::mint = position_mint,
+    associated_token::authority = lender
+  )]
+  pub lender_position_ata: Box<Account<'info, TokenAccount>>,
   pub associated_token_program: Program<'info, AssociatedToken>,
   pub token_program: Program<'info, Token>,
   pub system_program: Program<'info, System>,
@@ -135,10 +180,14 @@ impl<'info> CreateLoanOffer<'info> {
     lend_offer_id: String, 
     tier_id: String, 
     collateral_amount: u64,
-    interest: f64,
+    expected_terms: ExpectedLoanTerms,
     bumps: &CreateLoanOfferBumps
   ) -> Result<()> {
-    self.validate_lend_offer(interest)?;
+    let lend_offer_status = transition(self.lend_offer.status, LendOfferEvent::Take)?;
+
+    self.validate_lend_offer(&expected_terms)?;
+    self.setting_account.validate_lend_mint(self.lend_asset.token_mint)?;
+    self.setting_account.validate_collateral_mint(self.collateral_asset.token_mint)?;
     health_ratio::validate_health_ratio(HealthRatioParams {
       collateral_price_feed_account: &self.collateral_price_feed_account,
       collateral_amount,
@@ -152,9 +201,24 @@ impl<'info> CreateLoanOffer<'info> {
       lend_decimals: self.lend_asset.decimals,
     })?;
 
+    self.collateral_asset.add_collateral(collateral_amount)?;
+    self.lend_asset.add_borrow(self.lend_offer.amount)?;
+    self.collateral_asset.add_isolated_debt(
+      self.lend_asset.token_mint,
+      self.lend_offer.amount,
+      self.lend_asset.decimals,
+    )?;
+
     self.deposit_collateral(collateral_amount)?;
+    lend_offer_vault::release_lend_offer_vault(ReleaseLendOfferVaultParams {
+      token_program: self.token_program.to_account_info(),
+      lend_mint: &self.lend_mint_asset,
+      lend_offer: &self.lend_offer,
+      lend_offer_vault: self.lend_offer_vault.as_ref().map(|vault| vault.to_account_info()),
+      hot_wallet_ata: self.hot_wallet_ata_lend_asset.as_ref().map(|ata| ata.to_account_info()),
+    })?;
 
-    self.lend_offer.status = LendOfferStatus::Loaned;
+    self.lend_offer.status = lend_offer_status;
     self.loan_offer.set_inner(LoanOfferAccount {
       tier_id,
       borrow_amount: self.lend_offer.amount,
@@ -176,22 +240,36 @@ impl<'info> CreateLoanOffer<'info> {
       liquidating_price: None,
       liquidated_tx: None,
       liquidated_price: None,
-      request_withdraw_amount: None
+      request_withdraw_amount: None,
+      position_mint: Some(self.position_mint.key()),
+      rent_payer: self.borrower.key(),
+      version: LoanOfferAccount::VERSION,
     });
 
+    position::mint_position_token(MintPositionTokenParams {
+      token_program: self.token_program.to_account_info(),
+      position_mint: self.position_mint.to_account_info(),
+      lender_position_ata: self.lender_position_ata.to_account_info(),
+      loan_offer: &self.loan_offer.key(),
+      position_mint_bump: bumps.position_mint,
+    })?;
+
     self.emit_event_create_loan_offer()?;
 
     Ok(())
   }
 
-  fn validate_lend_offer(&self, interest: f64) -> Result<()> {
-    if self.lend_offer.interest != interest {
-      return err!(LoanOfferError::CanNotCreateLoanCauseLendInterestUpdated);
+  fn validate_lend_offer(&self, expected_terms: &ExpectedLoanTerms) -> Result<()> {
+    expected_terms.validate(&self.lend_offer, &self.setting_account)?;
+
+    if self.lend_offer.is_expired(Clock::get()?.unix_timestamp) {
+      return err!(LoanOfferError::LendOfferExpired);
     }
 
     Ok(())
   }
 
+
   fn deposit_collateral(&self, collateral_amount: u64) -> Result<()> {
     let cpi_context = CpiContext::new(self.token_program.to_account_info(), TransferChecked {
       from: self.borrower_ata_asset.to_account_info(),
@@ -208,22 +286,23 @@ impl<'info> CreateLoanOffer<'info> {
   }
 
   fn emit_event_create_loan_offer(&self) -> Result<()> {
-    emit!(LoanOfferCreateRequestEvent {
+    emit!(LoanCreated {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.borrower.key())?
+        .with_status(None, Some(self.loan_offer.status)),
       tier_id: self.loan_offer.tier_id.clone(),
       lend_offer_id: self.loan_offer.lend_offer_id.clone(),
-      interest: self.loan_offer.interest,
-      borrow_amount: self.loan_offer.borrow_amount,
-      lender_fee_percent: self.loan_offer.lender_fee_percent,
-      duration: self.loan_offer.duration,
-      lend_mint_token: self.loan_offer.lend_mint_token,
-      lender: self.loan_offer.lender,
-      offer_id: self.loan_offer.offer_id.clone(),
       borrower: self.loan_offer.borrower,
+      lender: self.loan_offer.lender,
+      lend_mint_token: self.loan_offer.lend_mint_token,
+      borrow_amount: self.loan_offer.borrow_amount,
       collateral_mint_token: self.loan_offer.collateral_mint_token,
       collateral_amount: self.loan_offer.collateral_amount,
-      status: self.loan_offer.status,
+      interest: self.loan_offer.interest,
+      lender_fee_percent: self.loan_offer.lender_fee_percent,
       borrower_fee_percent: self.loan_offer.borrower_fee_percent,
+      duration: self.loan_offer.duration,
       started_at: self.loan_offer.started_at,
+      position_mint: self.loan_offer.position_mint,
     });
     
     Ok(())
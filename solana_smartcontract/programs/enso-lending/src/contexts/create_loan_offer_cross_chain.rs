@@ -1,11 +1,20 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token::{Mint, Token, TokenAccount},
+};
 use wormhole_anchor_sdk::wormhole::{self, program::Wormhole};
 
 use crate::{
-  common::{ENSO_SEED, LEND_OFFER_ACCOUNT_SEED, SETTING_ACCOUNT_SEED}, Asset, ForeignChain, LendOfferAccount, LendOfferStatus, LoanOfferAccount, LoanOfferCreateRequestEvent, LoanOfferError, LoanOfferStatus, SettingAccount, WormholeMessage, ASSET_SEED, CREATE_LOAN_OFFER_CROSS_CHAIN_FUNCTION, DISCRIMINATOR, LOAN_OFFER_ACCOUNT_SEED, POSTED_TIMESTAMP_THRESHOLD
+  common::{ENSO_SEED, LEND_OFFER_ACCOUNT_SEED, SETTING_ACCOUNT_SEED}, Asset, ForeignChain, LendOfferAccount, LendOfferError, EventHeader, LoanCreated, LoanOfferAccount, LoanOfferError, LoanOfferStatus, SettingAccount, WormholeMessage, ASSET_SEED, CREATE_LOAN_OFFER_CROSS_CHAIN_FUNCTION, LOAN_OFFER_ACCOUNT_SEED, LOAN_POSITION_MINT_SEED, POSTED_TIMESTAMP_THRESHOLD
+};
+use crate::utils::{
+  lend_offer_vault::{self, ReleaseLendOfferVaultParams},
+  position::{self, MintPositionTokenParams},
+  vaa,
 };
-use crate::utils::vaa;
+use crate::AccountVersionError;
+use crate::{transition, LendOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(
@@ -26,6 +35,8 @@ pub struct CreateLoanOfferCrossChain<'info> {
   )]
   pub lend_mint_asset: Box<Account<'info, Mint>>,
   #[account(
+    mut,
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = lend_asset.is_lend == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
     ENSO_SEED.as_ref(),
@@ -37,6 +48,8 @@ pub struct CreateLoanOfferCrossChain<'info> {
   )]
   pub lend_asset: Box<Account<'info, Asset>>,
   #[account(
+    mut,
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = collateral_asset.is_collateral == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -50,7 +63,7 @@ pub struct CreateLoanOfferCrossChain<'info> {
   #[account(
     init,
     payer = signer,
-    space = (DISCRIMINATOR as usize) + LoanOfferAccount::INIT_SPACE,
+    space = 8 + LoanOfferAccount::INIT_SPACE,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -63,7 +76,7 @@ pub struct CreateLoanOfferCrossChain<'info> {
   pub loan_offer:Box<Account<'info, LoanOfferAccount>>,
     #[account(
       mut,
-      constraint = lend_offer.status == LendOfferStatus::Created @ LoanOfferError::LendOfferIsNotAvailable,
+      constraint = lend_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
       seeds = [
         ENSO_SEED.as_ref(), 
         LEND_OFFER_ACCOUNT_SEED.as_ref(), 
@@ -77,6 +90,8 @@ pub struct CreateLoanOfferCrossChain<'info> {
   pub lender: SystemAccount<'info>,
   pub borrower: SystemAccount<'info>,
   #[account(
+    constraint = setting_account.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = lend_offer.tier_id.is_empty() || lend_offer.tier_id == setting_account.tier_id @ LendOfferError::TierIdNotMatch,
     seeds = [
       ENSO_SEED.as_ref(), 
       SETTING_ACCOUNT_SEED.as_ref(),
@@ -101,7 +116,42 @@ pub struct CreateLoanOfferCrossChain<'info> {
     constraint = collateral_asset.chain_id == foreign_chain.chain_id @ LoanOfferError::InvalidChainId
   )]
   pub foreign_chain: Account<'info, ForeignChain>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = lend_offer
+  )]
+  pub lend_offer_vault: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = setting_account.receiver
+  )]
+  pub hot_wallet_ata_lend_asset: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    init,
+    payer = signer,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LOAN_POSITION_MINT_SEED.as_ref(),
+      loan_offer.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump,
+    mint::decimals = 0,
+    mint::authority = position_mint,
+  )]
+  pub position_mint: Box<Account<'info, Mint>>,
+  #[account(
+    init_if_needed,
+    payer = signer,
+    associated_token::mint = position_mint,
+    associated_token::authority = lender
+  )]
+  pub lender_position_ata: Box<Account<'info, TokenAccount>>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
   pub wormhole_program: Program<'info, Wormhole>,
+  pub token_program: Program<'info, Token>,
   pub system_program: Program<'info, System>,
 }
 
@@ -114,8 +164,14 @@ impl<'info> CreateLoanOfferCrossChain<'info> {
     lend_offer_id: String, 
     _vaa_hash: [u8; 32],
   ) -> Result<()> {
+    let lend_offer_status = transition(self.lend_offer.status, LendOfferEvent::Take)?;
+
     self.validate_posted_vaa()?;
 
+    if self.lend_offer.is_expired(Clock::get()?.unix_timestamp) {
+      return err!(LoanOfferError::LendOfferExpired);
+    }
+
     let posted_vaa = &self.posted.clone().into_inner();
     let WormholeMessage::Message { payload } = posted_vaa.data();
     let ( 
@@ -131,6 +187,10 @@ impl<'info> CreateLoanOfferCrossChain<'info> {
 
     ) = self.parse_create_loan_payload(&payload).unwrap();
 
+    if let Some(expected_terms) = vaa::parse_create_loan_expected_terms(&payload, lend_amount)? {
+      expected_terms.validate(&self.lend_offer, &self.setting_account)?;
+    }
+
     let _ = self.verify_payload_message_data(
       target_function, 
       posted_lend_offer_id, 
@@ -141,7 +201,26 @@ impl<'info> CreateLoanOfferCrossChain<'info> {
       collateral_address
     )?; 
 
-    self.lend_offer.status = LendOfferStatus::Loaned;
+    self.setting_account.validate_lend_mint(self.lend_asset.token_mint)?;
+    self.setting_account.validate_collateral_mint(self.collateral_asset.token_mint)?;
+
+    self.collateral_asset.add_collateral(collateral_amount)?;
+    self.lend_asset.add_borrow(self.lend_offer.amount)?;
+    self.collateral_asset.add_isolated_debt(
+      self.lend_asset.token_mint,
+      self.lend_offer.amount,
+      self.lend_asset.decimals,
+    )?;
+
+    lend_offer_vault::release_lend_offer_vault(ReleaseLendOfferVaultParams {
+      token_program: self.token_program.to_account_info(),
+      lend_mint: &self.lend_mint_asset,
+      lend_offer: &self.lend_offer,
+      lend_offer_vault: self.lend_offer_vault.as_ref().map(|vault| vault.to_account_info()),
+      hot_wallet_ata: self.hot_wallet_ata_lend_asset.as_ref().map(|ata| ata.to_account_info()),
+    })?;
+
+    self.lend_offer.status = lend_offer_status;
     self.loan_offer.set_inner(LoanOfferAccount {
       tier_id: tier_id.clone(),
       offer_id: loan_offer_id,
@@ -164,13 +243,25 @@ impl<'info> CreateLoanOfferCrossChain<'info> {
       liquidated_tx: None,
       liquidated_price: None,
       request_withdraw_amount: None,
+      position_mint: Some(self.position_mint.key()),
+      rent_payer: self.signer.key(),
+      version: LoanOfferAccount::VERSION,
     });
 
+    position::mint_position_token(MintPositionTokenParams {
+      token_program: self.token_program.to_account_info(),
+      position_mint: self.position_mint.to_account_info(),
+      lender_position_ata: self.lender_position_ata.to_account_info(),
+      loan_offer: &self.loan_offer.key(),
+      position_mint_bump: bumps.position_mint,
+    })?;
+
     self.emit_event_create_loan_offer_cross_chain()?;
 
     Ok(())
   }
 
+
   fn validate_posted_vaa(&self) -> Result<()> {
     let posted_chain_id = self.posted.meta.emitter_chain;
     let chain_id = self.foreign_chain.chain_id;
@@ -238,22 +329,23 @@ impl<'info> CreateLoanOfferCrossChain<'info> {
   fn emit_event_create_loan_offer_cross_chain(
     &self,
   ) -> Result<()> {
-    emit!(LoanOfferCreateRequestEvent {
-      offer_id: self.loan_offer.offer_id.clone(),
-      collateral_mint_token: self.collateral_mint_asset.key(),
+    emit!(LoanCreated {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.signer.key())?
+        .with_status(None, Some(self.loan_offer.status)),
       tier_id: self.loan_offer.tier_id.clone(),
       lend_offer_id: self.loan_offer.lend_offer_id.clone(),
-      interest: self.loan_offer.interest,
+      borrower: self.loan_offer.borrower,
+      lender: self.loan_offer.lender,
+      lend_mint_token: self.loan_offer.lend_mint_token,
       borrow_amount: self.loan_offer.borrow_amount,
-      lender_fee_percent: self.loan_offer.lender_fee_percent,
-      duration: self.loan_offer.duration,
-      lend_mint_token: self.lend_mint_asset.key(),
-      lender: self.lender.key(),
-      borrower: self.borrower.key(),
+      collateral_mint_token: self.loan_offer.collateral_mint_token,
       collateral_amount: self.loan_offer.collateral_amount,
-      status: self.loan_offer.status,
+      interest: self.loan_offer.interest,
+      lender_fee_percent: self.loan_offer.lender_fee_percent,
       borrower_fee_percent: self.loan_offer.borrower_fee_percent,
+      duration: self.loan_offer.duration,
       started_at: self.loan_offer.started_at,
+      position_mint: self.loan_offer.position_mint,
     });
 
     Ok(())
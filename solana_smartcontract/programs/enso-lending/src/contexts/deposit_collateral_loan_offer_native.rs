@@ -3,8 +3,10 @@ use std::ops::Add;
 use anchor_lang::{prelude::*, solana_program::{program::invoke_signed, system_instruction}};
 
 use crate::{
-  common::{ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED}, DepositCollateralLoanOfferEvent, LoanOfferAccount, LoanOfferError, LoanOfferStatus
+  common::{ASSET_SEED, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED}, Asset, CollateralDeposited, EventHeader, LoanOfferAccount
 };
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(
@@ -17,9 +19,7 @@ pub struct DepositCollateralLoanOfferNative<'info> {
   pub borrower: Signer<'info>,
   #[account(
     mut,
-    constraint = 
-      loan_offer.status == LoanOfferStatus::Matched || loan_offer.status == LoanOfferStatus::FundTransferred 
-      @ LoanOfferError::CanNotDepositCollateralToContractThatNotAvailable,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -30,38 +30,44 @@ pub struct DepositCollateralLoanOfferNative<'info> {
     bump = loan_offer.bump
   )]
   pub loan_offer: Account<'info, LoanOfferAccount>,
+  #[account(
+    mut,
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      loan_offer.collateral_mint_token.as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = collateral_asset.bump
+  )]
+  pub collateral_asset: Account<'info, Asset>,
   pub system_program: Program<'info, System>,
 }
 
 impl<'info> DepositCollateralLoanOfferNative<'info> {
   pub fn deposit_collateral_loan_offer_native(&mut self, amount: u64) -> Result<()> {
+    transition(self.loan_offer.status, LoanOfferEvent::DepositCollateral)?;
+
+    self.collateral_asset.add_collateral(amount)?;
     self.deposit_collateral(amount)?;
 
     let before_collateral_amount = self.loan_offer.collateral_amount;
     self.loan_offer.collateral_amount = before_collateral_amount.add(amount);
 
-    self.emit_event_deposit_collateral_loan_offer()?;
+    self.emit_event_deposit_collateral_loan_offer(amount)?;
     
     Ok(())
   }
 
-  fn emit_event_deposit_collateral_loan_offer(&self) -> Result<()> {
-    emit!(DepositCollateralLoanOfferEvent {
-      tier_id: self.loan_offer.tier_id.clone(),
-      lend_offer_id: self.loan_offer.lend_offer_id.clone(),
-      interest: self.loan_offer.interest,
-      borrow_amount: self.loan_offer.borrow_amount,
-      lender_fee_percent: self.loan_offer.lender_fee_percent,
-      duration: self.loan_offer.duration,
-      lend_mint_token: self.loan_offer.lend_mint_token,
-      lender: self.loan_offer.lender,
-      offer_id: self.loan_offer.offer_id.clone(),
+  fn emit_event_deposit_collateral_loan_offer(&self, deposited_amount: u64) -> Result<()> {
+    emit!(CollateralDeposited {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.borrower.key())?
+        .with_status(Some(self.loan_offer.status), Some(self.loan_offer.status)),
       borrower: self.loan_offer.borrower,
       collateral_mint_token: self.loan_offer.collateral_mint_token,
+      deposited_amount,
       collateral_amount: self.loan_offer.collateral_amount,
-      status: self.loan_offer.status,
-      borrower_fee_percent: self.loan_offer.borrower_fee_percent,
-      started_at: self.loan_offer.started_at,
     });
     
     Ok(())
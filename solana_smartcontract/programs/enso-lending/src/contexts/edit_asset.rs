@@ -6,12 +6,16 @@ use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 use crate::{
   Asset, 
-  EditAssetEvent, 
+  AssetError,
+  AssetEdited, 
+  EventHeader, 
   SettingAccountError, 
   ASSET_SEED, 
   ENSO_SEED, 
+  MAX_ISOLATED_LEND_MINTS,
   OPERATE_SYSTEM_PUBKEY
 };
+use crate::AccountVersionError;
 
 #[derive(Accounts)]
 pub struct EditAsset<'info> {
@@ -23,6 +27,7 @@ pub struct EditAsset<'info> {
   pub token_mint: Account<'info, Mint>,
   #[account(
     mut,
+    constraint = asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       ASSET_SEED.as_ref(),
@@ -43,7 +48,12 @@ impl<'info> EditAsset<'info> {
     is_collateral: Option<bool>, 
     price_feed_id: Option<String>,
     max_price_age_seconds: Option<u64>,
-    token_address: Option<String>
+    token_address: Option<String>,
+    max_collateral_amount: Option<u64>,
+    max_borrow_amount: Option<u64>,
+    isolated: Option<bool>,
+    isolated_lend_mints: Option<Vec<Pubkey>>,
+    isolated_debt_ceiling: Option<u64>
   ) -> Result<()> {
     let asset = &mut self.asset;
 
@@ -70,14 +80,34 @@ impl<'info> EditAsset<'info> {
       asset.token_address = Some(token_address);
     }
 
+    if let Some(max_collateral_amount) = max_collateral_amount {
+      asset.max_collateral_amount = max_collateral_amount;
+    }
+    if let Some(max_borrow_amount) = max_borrow_amount {
+      asset.max_borrow_amount = max_borrow_amount;
+    }
+
+    if let Some(isolated) = isolated {
+      asset.isolated = isolated;
+    }
+    if let Some(isolated_lend_mints) = isolated_lend_mints {
+      if isolated_lend_mints.len() > MAX_ISOLATED_LEND_MINTS {
+        return err!(AssetError::TooManyIsolatedLendMints);
+      }
+      asset.isolated_lend_mints = isolated_lend_mints;
+    }
+    if let Some(isolated_debt_ceiling) = isolated_debt_ceiling {
+      asset.isolated_debt_ceiling = isolated_debt_ceiling;
+    }
+
     self.emit_edit_asset_event()?;
 
     Ok(())
   }
 
   fn emit_edit_asset_event(&mut self) -> Result<()> {
-    emit!(EditAssetEvent {
-      name: self.asset.name.clone(),
+    emit!(AssetEdited {
+      header: EventHeader::new(self.asset.key(), self.asset.name.clone(), self.owner.key())?,
       token_mint: self.asset.token_mint.key(),
       decimals: self.asset.decimals,
       is_collateral: self.asset.is_collateral,
@@ -85,7 +115,14 @@ impl<'info> EditAsset<'info> {
       max_price_age_seconds: self.asset.max_price_age_seconds,
       price_feed_account: self.asset.price_feed_account.key(),
       price_feed_id: self.asset.price_feed_id.clone(),
-      bump: self.asset.bump
+      total_collateral_amount: self.asset.total_collateral_amount,
+      max_collateral_amount: self.asset.max_collateral_amount,
+      total_borrow_amount: self.asset.total_borrow_amount,
+      max_borrow_amount: self.asset.max_borrow_amount,
+      isolated: self.asset.isolated,
+      isolated_lend_mints: self.asset.isolated_lend_mints.clone(),
+      isolated_debt_ceiling: self.asset.isolated_debt_ceiling,
+      total_isolated_debt: self.asset.total_isolated_debt,
     });
           
     Ok(())
@@ -1,15 +1,18 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
 
-use crate::{common::{ENSO_SEED, LEND_OFFER_ACCOUNT_SEED}, EditLendOfferEvent, LendOfferAccount, LendOfferError, LendOfferStatus, MAX_ALLOWED_INTEREST};
+use crate::{common::{ENSO_SEED, LEND_OFFER_ACCOUNT_SEED, SETTING_ACCOUNT_SEED}, EventHeader, LendOfferAccount, LendOfferEdited, LendOfferError, SettingAccount, MAX_ALLOWED_INTEREST};
+use crate::AccountVersionError;
+use crate::{transition, LendOfferEvent};
 
 #[derive(Accounts)]
-#[instruction(offer_id: String)]
+#[instruction(offer_id: String, tier_id: String)]
 pub struct EditLendOffer<'info> {
   #[account(mut)]
   pub lender: Signer<'info>,
   #[account(
     mut,
-    constraint = lend_offer.status == LendOfferStatus::Created @ LendOfferError::InvalidOfferStatus,
+    constraint = lend_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(), 
       LEND_OFFER_ACCOUNT_SEED.as_ref(), 
@@ -20,36 +23,194 @@ pub struct EditLendOffer<'info> {
     bump = lend_offer.bump
   )]
   pub lend_offer: Account<'info, LendOfferAccount>,
+  #[account(
+    constraint = setting_account.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = lend_offer.tier_id.is_empty() || lend_offer.tier_id == setting_account.tier_id @ LendOfferError::TierIdNotMatch,
+    seeds = [
+      ENSO_SEED.as_ref(), 
+      SETTING_ACCOUNT_SEED.as_ref(),
+      tier_id.as_bytes(), 
+      crate::ID.key().as_ref(), 
+    ],
+    bump = setting_account.bump
+  )]
+  pub setting_account: Account<'info, SettingAccount>,
+  #[account(
+    constraint = mint_asset.key() == lend_offer.lend_mint_token @ LendOfferError::InvalidMintAsset,
+  )]
+  pub mint_asset: Account<'info, Mint>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = lender
+  )]
+  pub lender_ata_asset: Account<'info, TokenAccount>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = setting_account.receiver
+  )]
+  pub hot_wallet_ata: Option<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = lend_offer
+  )]
+  pub lend_offer_vault: Option<Account<'info, TokenAccount>>,
+  pub token_program: Program<'info, Token>,
 }
 
 impl<'info> EditLendOffer<'info> {
-    pub fn edit_lend_offer(&mut self, interest: f64) -> Result<()> {
-      if interest <= (0 as f64) {
-        return err!(LendOfferError::InterestGreaterThanZero);
+    pub fn edit_lend_offer(
+      &mut self,
+      interest: Option<f64>,
+      amount: Option<u64>,
+      duration: Option<u64>,
+    ) -> Result<()> {
+      transition(self.lend_offer.status, LendOfferEvent::Edit)?;
+
+      let old_interest = self.lend_offer.interest;
+      let old_amount = self.lend_offer.amount;
+      let old_duration = self.lend_offer.duration;
+
+      self.setting_account.validate_lend_mint(self.mint_asset.key())?;
+
+      // Note: Amount and duration bounds come from the tier, offers migrated without one can not prove theirs
+      if self.lend_offer.tier_id.is_empty() && (amount.is_some() || duration.is_some()) {
+        return err!(LendOfferError::UnknownLendOfferTier);
       }
 
-      if interest >= MAX_ALLOWED_INTEREST {
-        return err!(LendOfferError::InterestOverLimit);
+      if let Some(interest) = interest {
+        if interest <= (0 as f64) {
+          return err!(LendOfferError::InterestGreaterThanZero);
+        }
+
+        if interest >= MAX_ALLOWED_INTEREST {
+          return err!(LendOfferError::InterestOverLimit);
+        }
+
+        self.lend_offer.interest = interest;
       }
 
-      let lend_offer = &mut self.lend_offer;
-      lend_offer.interest = interest;
+      if let Some(duration) = duration {
+        self.setting_account.validate_duration(duration)?;
+        self.lend_offer.duration = duration;
+      }
 
-      self.emit_event_edit_lend_offer()?;
+      if let Some(amount) = amount {
+        self.setting_account.validate_lend_amount(amount, self.mint_asset.decimals)?;
+
+        if amount > old_amount {
+          self.top_up(amount - old_amount)?;
+        } else if amount < old_amount {
+          self.withdraw(old_amount - amount)?;
+        }
+
+        self.lend_offer.amount = amount;
+      }
+
+      self.emit_event_edit_lend_offer(old_interest, old_amount, old_duration)?;
 
       Ok(())
     }
 
-    fn emit_event_edit_lend_offer(&mut self) -> Result<()> {
-      emit!(EditLendOfferEvent {
-        lender: self.lender.key(),
-        interest: self.lend_offer.interest,
-        lender_fee_percent: self.lend_offer.lender_fee_percent,
+    fn top_up(&self, top_up_amount: u64) -> Result<()> {
+      if self.lender_ata_asset.amount < top_up_amount {
+        return err!(LendOfferError::NotEnoughAmount);
+      }
+
+      let to = if self.lend_offer.is_escrowed() {
+        match &self.lend_offer_vault {
+          Some(lend_offer_vault) => lend_offer_vault.to_account_info(),
+          None => return err!(LendOfferError::MissingLendOfferVault),
+        }
+      } else {
+        match &self.hot_wallet_ata {
+          Some(hot_wallet_ata) => hot_wallet_ata.to_account_info(),
+          None => return err!(LendOfferError::InvalidReceiver),
+        }
+      };
+
+      let ctx = CpiContext::new(
+        self.token_program.to_account_info(), 
+        TransferChecked {
+          from: self.lender_ata_asset.to_account_info(),
+          mint: self.mint_asset.to_account_info(),
+          to,
+          authority: self.lender.to_account_info(),
+        }
+      );
+
+      transfer_checked(
+        ctx,
+        top_up_amount,
+        self.mint_asset.decimals,
+      )
+    }
+
+    // Note: Principal of offers without expiry sits in the hot wallet, which this program can not move,
+    // so the withdrawal is recorded and the operator pays it back in system_withdraw_lend_offer
+    fn withdraw(&mut self, withdraw_amount: u64) -> Result<()> {
+      if !self.lend_offer.is_escrowed() {
+        self.lend_offer.pending_withdraw_amount = self.lend_offer.pending_withdraw_amount
+          .checked_add(withdraw_amount)
+          .ok_or(LendOfferError::InvalidLendAmount)?;
+
+        return Ok(());
+      }
+
+      let Some(lend_offer_vault) = &self.lend_offer_vault else {
+        return err!(LendOfferError::MissingLendOfferVault);
+      };
+
+      let lender_pub_key = self.lender.key();
+      let program_id = crate::ID.key();
+
+      let signer: &[&[&[u8]]] = &[&[
+        ENSO_SEED.as_ref(),
+        LEND_OFFER_ACCOUNT_SEED.as_ref(),
+        lender_pub_key.as_ref(),
+        self.lend_offer.offer_id.as_bytes(),
+        program_id.as_ref(),
+        &[self.lend_offer.bump]
+      ]];
+
+      let ctx = CpiContext::new_with_signer(
+        self.token_program.to_account_info(),
+        TransferChecked {
+          from: lend_offer_vault.to_account_info(),
+          mint: self.mint_asset.to_account_info(),
+          to: self.lender_ata_asset.to_account_info(),
+          authority: self.lend_offer.to_account_info(),
+        },
+        signer
+      );
+
+      transfer_checked(
+        ctx,
+        withdraw_amount,
+        self.mint_asset.decimals,
+      )
+    }
+
+    fn emit_event_edit_lend_offer(
+      &mut self,
+      old_interest: f64,
+      old_amount: u64,
+      old_duration: u64,
+    ) -> Result<()> {
+      emit!(LendOfferEdited {
+        header: EventHeader::new(self.lend_offer.key(), self.lend_offer.offer_id.clone(), self.lender.key())?
+          .with_status(Some(self.lend_offer.status), Some(self.lend_offer.status)),
         amount: self.lend_offer.amount,
+        interest: self.lend_offer.interest,
         duration: self.lend_offer.duration,
-        offer_id: self.lend_offer.offer_id.clone(),
-    });
+        old_amount,
+        old_interest,
+        old_duration,
+        pending_withdraw_amount: self.lend_offer.pending_withdraw_amount,
+      });
     
     Ok(())
     }
-}
\ No newline at end of file
+}
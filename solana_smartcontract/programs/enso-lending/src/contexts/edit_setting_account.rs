@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
-use crate::{SettingAccount, EditSettingAccountEvent, SettingAccountError, common::{ENSO_SEED, SETTING_ACCOUNT_SEED}};
+use crate::{SettingAccount, SettingAccountEdited, EventHeader, SettingAccountError, common::{ENSO_SEED, SETTING_ACCOUNT_SEED}, MAX_TIER_COLLATERAL_MINTS};
+use crate::AccountVersionError;
 
 #[derive(Accounts)]
 #[instruction(tier_id: String, amount: Option<u64>, duration: Option<u64>)]
@@ -11,6 +12,7 @@ pub struct EditSettingAccount<'info> {
   pub receiver: AccountInfo<'info>,
   #[account(
     mut,
+    constraint = setting_account.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     has_one = owner,
     constraint = setting_account.tier_id == tier_id @ SettingAccountError::InvalidTierId,
     seeds = [
@@ -31,7 +33,14 @@ impl<'info> EditSettingAccount<'info> {
     amount: Option<u64>,
     duration: Option<u64>,
     lender_fee_percent: Option<f64>,
-    borrower_fee_percent: Option<f64>
+    borrower_fee_percent: Option<f64>,
+    lend_mint_asset: Option<Pubkey>,
+    collateral_mint_assets: Option<Vec<Pubkey>>,
+    min_amount: Option<u64>,
+    max_amount: Option<u64>,
+    amount_step: Option<u64>,
+    min_duration: Option<u64>,
+    max_duration: Option<u64>
   ) -> Result<()>  {
     let setting_account = &mut self.setting_account;
     if let Some(amount) = amount {
@@ -50,6 +59,44 @@ impl<'info> EditSettingAccount<'info> {
       setting_account.borrower_fee_percent = borrower_fee_percent;
     }
 
+    if let Some(lend_mint_asset) = lend_mint_asset {
+      setting_account.lend_mint_asset = lend_mint_asset;
+    }
+
+    if let Some(collateral_mint_assets) = collateral_mint_assets {
+      if collateral_mint_assets.len() > MAX_TIER_COLLATERAL_MINTS {
+        return err!(SettingAccountError::TooManyCollateralMints);
+      }
+      setting_account.collateral_mint_assets = collateral_mint_assets;
+    }
+
+    if let Some(min_amount) = min_amount {
+      setting_account.min_amount = min_amount;
+    }
+
+    if let Some(max_amount) = max_amount {
+      setting_account.max_amount = max_amount;
+    }
+
+    if let Some(amount_step) = amount_step {
+      setting_account.amount_step = amount_step;
+    }
+
+    if let Some(min_duration) = min_duration {
+      setting_account.min_duration = min_duration;
+    }
+
+    if let Some(max_duration) = max_duration {
+      setting_account.max_duration = max_duration;
+    }
+
+    SettingAccount::validate_amount_range(
+      setting_account.min_amount,
+      setting_account.max_amount,
+      setting_account.min_duration,
+      setting_account.max_duration
+    )?;
+
     setting_account.receiver = self.receiver.key();
 
     self.emit_event_edit_setting_account()?;
@@ -60,12 +107,20 @@ impl<'info> EditSettingAccount<'info> {
   fn emit_event_edit_setting_account(
     &mut self,
   ) -> Result<()> {
-    emit!(EditSettingAccountEvent {
-      receiver: self.receiver.key(),
-      tier_id: self.setting_account.tier_id.clone(),
+    emit!(SettingAccountEdited {
+      header: EventHeader::new(self.setting_account.key(), self.setting_account.tier_id.clone(), self.owner.key())?,
       amount: self.setting_account.amount,
       duration: self.setting_account.duration,
+      receiver: self.receiver.key(),
       lender_fee_percent: self.setting_account.lender_fee_percent,
+      borrower_fee_percent: self.setting_account.borrower_fee_percent,
+      lend_mint_asset: self.setting_account.lend_mint_asset,
+      collateral_mint_assets: self.setting_account.collateral_mint_assets.clone(),
+      min_amount: self.setting_account.min_amount,
+      max_amount: self.setting_account.max_amount,
+      amount_step: self.setting_account.amount_step,
+      min_duration: self.setting_account.min_duration,
+      max_duration: self.setting_account.max_duration,
     });
 
 
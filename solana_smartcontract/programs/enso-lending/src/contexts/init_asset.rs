@@ -6,11 +6,13 @@ use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 use crate::{
   Asset, 
-  InitAssetEvent, 
+  AssetError,
+  AssetInitialized, 
+  EventHeader, 
   SettingAccountError, 
   ASSET_SEED, 
-  DISCRIMINATOR, 
   ENSO_SEED, 
+  MAX_ISOLATED_LEND_MINTS,
   OPERATE_SYSTEM_PUBKEY
 };
 
@@ -25,7 +27,7 @@ pub struct InitAsset<'info> {
   #[account(
     init,
     payer = owner,
-    space = (DISCRIMINATOR as usize) + Asset::INIT_SPACE,
+    space = 8 + Asset::INIT_SPACE,
     seeds = [
       ENSO_SEED.as_ref(),
       ASSET_SEED.as_ref(),
@@ -49,8 +51,17 @@ impl<'info> InitAsset<'info>{
       max_price_age_seconds: u64,
       token_address: Option<String>,
       chain_id: u16,
+      max_collateral_amount: u64,
+      max_borrow_amount: u64,
+      isolated: bool,
+      isolated_lend_mints: Vec<Pubkey>,
+      isolated_debt_ceiling: u64,
       bumps: &InitAssetBumps
     ) -> Result<()> {
+      if isolated_lend_mints.len() > MAX_ISOLATED_LEND_MINTS {
+        return err!(AssetError::TooManyIsolatedLendMints);
+      }
+
       self.asset.set_inner(Asset {
         name,
         token_mint: self.token_mint.key(),
@@ -62,7 +73,16 @@ impl<'info> InitAsset<'info>{
         price_feed_account: self.price_feed_account.key(),
         price_feed_id,
         chain_id,
-        bump: bumps.asset
+        bump: bumps.asset,
+        total_collateral_amount: 0,
+        max_collateral_amount,
+        total_borrow_amount: 0,
+        max_borrow_amount,
+        isolated,
+        isolated_lend_mints,
+        isolated_debt_ceiling,
+        total_isolated_debt: 0,
+        version: Asset::VERSION,
       });
 
       self.emit_init_asset_event()?;
@@ -71,8 +91,8 @@ impl<'info> InitAsset<'info>{
     }
 
     fn emit_init_asset_event(&mut self) -> Result<()> {
-      emit!(InitAssetEvent {
-        name: self.asset.name.clone(),
+      emit!(AssetInitialized {
+        header: EventHeader::new(self.asset.key(), self.asset.name.clone(), self.owner.key())?,
         token_address: self.asset.token_address.clone(),
         token_mint: self.asset.token_mint.key(),
         decimals: self.asset.decimals,
@@ -82,7 +102,11 @@ impl<'info> InitAsset<'info>{
         price_feed_account: self.asset.price_feed_account.key(),
         price_feed_id: self.asset.price_feed_id.clone(),
         chain_id: self.asset.chain_id,
-        bump: self.asset.bump
+        max_collateral_amount: self.asset.max_collateral_amount,
+        max_borrow_amount: self.asset.max_borrow_amount,
+        isolated: self.asset.isolated,
+        isolated_lend_mints: self.asset.isolated_lend_mints.clone(),
+        isolated_debt_ceiling: self.asset.isolated_debt_ceiling,
       });
             
       Ok(())
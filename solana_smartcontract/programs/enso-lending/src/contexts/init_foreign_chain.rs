@@ -3,7 +3,7 @@ use std::str::FromStr;
 pub use anchor_lang::prelude::*;
 use wormhole_anchor_sdk::wormhole::SEED_PREFIX_EMITTER;
 
-use crate::{common::{constant::OPERATE_SYSTEM_PUBKEY, ENSO_SEED}, EmitterAccountError, ForeignChain, InitForeignEmitterEvent, DISCRIMINATOR};
+use crate::{common::{constant::OPERATE_SYSTEM_PUBKEY, ENSO_SEED}, EmitterAccountError, EventHeader, ForeignChain, ForeignChainInitialized};
 
 #[derive(Accounts)]
 #[instruction(chain_id: u16, chain_address: String, emitter_address: String)]
@@ -13,7 +13,7 @@ pub struct InitForeignEmitter<'info> {
   #[account(
     init,
     payer = owner,
-    space = (DISCRIMINATOR as usize) + ForeignChain::INIT_SPACE,
+    space = 8 + ForeignChain::INIT_SPACE,
     seeds = [
       ENSO_SEED.as_ref(), 
       SEED_PREFIX_EMITTER.as_ref(),
@@ -52,9 +52,14 @@ impl<'info> InitForeignEmitter<'info> {
     }
 
     fn emit_init_foreign_chain_event(&mut self) -> Result<()> {
-      emit!(InitForeignEmitterEvent {
+      emit!(ForeignChainInitialized {
+          header: EventHeader::new(
+            self.foreign_chain.key(),
+            self.foreign_chain.chain_id.to_string(),
+            self.owner.key()
+          )?,
           chain_id: self.foreign_chain.chain_id,
-          chain_address: self.foreign_chain.emitter_address.clone(),
+          chain_address: self.foreign_chain.chain_address.clone(),
           emitter_address: self.foreign_chain.emitter_address.clone()
       });
             
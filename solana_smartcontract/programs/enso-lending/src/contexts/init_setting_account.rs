@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 pub use anchor_lang::prelude::*;
 
-use crate::{common::{constant::OPERATE_SYSTEM_PUBKEY, SettingAccountError, ENSO_SEED, SETTING_ACCOUNT_SEED}, InitSettingAccountEvent, SettingAccount, DISCRIMINATOR};
+use crate::{common::{constant::OPERATE_SYSTEM_PUBKEY, SettingAccountError, ENSO_SEED, SETTING_ACCOUNT_SEED}, EventHeader, SettingAccount, SettingAccountInitialized, MAX_TIER_COLLATERAL_MINTS};
 
 #[derive(Accounts)]
 #[instruction(tier_id: String, amount: u64, duration: u64)]
@@ -14,7 +14,7 @@ pub struct InitSettingAccount<'info> {
   #[account(
     init,
     payer = owner,
-    space = (DISCRIMINATOR as usize) + SettingAccount::INIT_SPACE,
+    space = 8 + SettingAccount::INIT_SPACE,
     seeds = [
       ENSO_SEED.as_ref(), 
       SETTING_ACCOUNT_SEED.as_ref(),
@@ -28,11 +28,32 @@ pub struct InitSettingAccount<'info> {
 }
 
 impl<'info> InitSettingAccount<'info> {
-    pub fn init_setting_account(&mut self, bumps: &InitSettingAccountBumps, tier_id: String, amount: u64, duration: u64, lender_fee_percent: f64, borrower_fee_percent: f64) -> Result<()> {
+    pub fn init_setting_account(
+      &mut self,
+      bumps: &InitSettingAccountBumps,
+      tier_id: String,
+      amount: u64,
+      duration: u64,
+      lender_fee_percent: f64,
+      borrower_fee_percent: f64,
+      lend_mint_asset: Pubkey,
+      collateral_mint_assets: Vec<Pubkey>,
+      min_amount: u64,
+      max_amount: u64,
+      amount_step: u64,
+      min_duration: u64,
+      max_duration: u64
+    ) -> Result<()> {
       if self.owner.key() != Pubkey::from_str(OPERATE_SYSTEM_PUBKEY).unwrap() {
         return err!(SettingAccountError::InvalidOwner)?;
       }
 
+      if collateral_mint_assets.len() > MAX_TIER_COLLATERAL_MINTS {
+        return err!(SettingAccountError::TooManyCollateralMints);
+      }
+
+      SettingAccount::validate_amount_range(min_amount, max_amount, min_duration, max_duration)?;
+
       self.setting_account.set_inner(SettingAccount {
         amount,
         duration,
@@ -43,9 +64,16 @@ impl<'info> InitSettingAccount<'info> {
         lender_fee_percent,
         borrower_fee_percent,
         collateral_mint_asset: Pubkey::default(),
-        lend_mint_asset: Pubkey::default(),
+        lend_mint_asset,
         lend_price_feed: Pubkey::default(),
-        collateral_price_feed: Pubkey::default()
+        collateral_price_feed: Pubkey::default(),
+        collateral_mint_assets,
+        min_amount,
+        max_amount,
+        amount_step,
+        min_duration,
+        max_duration,
+        version: SettingAccount::VERSION,
       });
 
       self.emit_init_setting_account_event()?;
@@ -54,14 +82,20 @@ impl<'info> InitSettingAccount<'info> {
     }
 
     fn emit_init_setting_account_event(&mut self) -> Result<()> {
-      emit!(InitSettingAccountEvent {
-          tier_id: self.setting_account.tier_id.clone(),
+      emit!(SettingAccountInitialized {
+          header: EventHeader::new(self.setting_account.key(), self.setting_account.tier_id.clone(), self.owner.key())?,
           amount: self.setting_account.amount,
           duration: self.setting_account.duration,
-          owner: self.setting_account.owner,
           receiver: self.setting_account.receiver,
           lender_fee_percent: self.setting_account.lender_fee_percent,
-          borrower_fee_percent: self.setting_account.borrower_fee_percent
+          borrower_fee_percent: self.setting_account.borrower_fee_percent,
+          lend_mint_asset: self.setting_account.lend_mint_asset,
+          collateral_mint_assets: self.setting_account.collateral_mint_assets.clone(),
+          min_amount: self.setting_account.min_amount,
+          max_amount: self.setting_account.max_amount,
+          amount_step: self.setting_account.amount_step,
+          min_duration: self.setting_account.min_duration,
+          max_duration: self.setting_account.max_duration
       });
             
       Ok(())
@@ -0,0 +1,67 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token::{Mint, Token, TokenAccount}};
+
+use crate::{
+  EventHeader,
+  SettingAccountError,
+  Treasury,
+  TreasuryInitialized,
+  ENSO_SEED,
+  OPERATE_SYSTEM_PUBKEY,
+  TREASURY_SEED
+};
+
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+  #[account(
+    mut,
+    constraint = owner.key() == Pubkey::from_str(OPERATE_SYSTEM_PUBKEY).unwrap() @ SettingAccountError::InvalidOwner
+  )]
+  pub owner: Signer<'info>,
+  pub token_mint: Box<Account<'info, Mint>>,
+  #[account(
+    init,
+    payer = owner,
+    space = 8 + Treasury::INIT_SPACE,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      TREASURY_SEED.as_ref(),
+      token_mint.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump
+  )]
+  pub treasury: Box<Account<'info, Treasury>>,
+  #[account(
+    init_if_needed,
+    payer = owner,
+    associated_token::mint = token_mint,
+    associated_token::authority = treasury
+  )]
+  pub treasury_ata: Box<Account<'info, TokenAccount>>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitTreasury<'info> {
+  pub fn init_treasury(&mut self, bumps: &InitTreasuryBumps) -> Result<()> {
+    self.treasury.set_inner(Treasury {
+      token_mint: self.token_mint.key(),
+      total_lender_fee: 0,
+      total_borrower_fee: 0,
+      total_withdrawn: 0,
+      bump: bumps.treasury,
+    });
+
+    emit!(TreasuryInitialized {
+      header: EventHeader::new(self.treasury.key(), String::new(), self.owner.key())?,
+      token_mint: self.token_mint.key(),
+      treasury_ata: self.treasury_ata.key(),
+    });
+
+    Ok(())
+  }
+}
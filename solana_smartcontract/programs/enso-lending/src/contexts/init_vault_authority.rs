@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{VaultAuthority, ENSO_SEED, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED};
+use crate::{EventHeader, VaultAuthority, VaultAuthorityInitialized, ENSO_SEED, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED};
 
 #[derive(Accounts)]
 pub struct InitVaultAuthority<'info> {
@@ -29,6 +29,10 @@ impl<'info> InitVaultAuthority<'info> {
       bump: bumps.vault_authority
     });
 
+    emit!(VaultAuthorityInitialized {
+      header: EventHeader::new(self.vault_authority.key(), String::new(), self.borrower.key())?,
+    });
+
     Ok(())
   }
 }
\ No newline at end of file
@@ -3,7 +3,7 @@ use std::str::FromStr;
 pub use anchor_lang::prelude::*;
 use wormhole_anchor_sdk::wormhole::{self, program::Wormhole};
 
-use crate::{WormholeConfig, WormholeEmitter, WormholeError, WormholeMessage, OPERATE_SYSTEM_PUBKEY, WORMHOLE_SENT_SEED};
+use crate::{EventHeader, WormholeConfig, WormholeEmitter, WormholeError, WormholeInitialized, WormholeMessage, OPERATE_SYSTEM_PUBKEY, WORMHOLE_SENT_SEED};
 
 #[derive(Accounts)]
 pub struct InitWormhole<'info> {
@@ -152,6 +152,11 @@ impl<'info> InitWormhole<'info> {
       )?;
     }
 
+    emit!(WormholeInitialized {
+      header: EventHeader::new(self.wormhole_config.key(), String::new(), self.owner.key())?,
+      emitter: self.wormhole_emitter.key(),
+    });
+
     Ok(())
   }
 }
\ No newline at end of file
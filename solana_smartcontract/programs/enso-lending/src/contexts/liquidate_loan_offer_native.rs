@@ -0,0 +1,297 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token::{spl_token::native_mint, transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{
+  amount::{self, LenderRepayAmountParams, TotalRepayLoanAmountParams},
+  common::{ASSET_SEED, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED, TREASURY_SEED},
+  convert_to_usd_price,
+  risk::{self, PricedLoanParams},
+  Asset, EventHeader, LiquidateOfferError, LiquidationReason, LiquidationSettled, LoanOfferAccount, LoanOfferError,
+  LoanOfferStatus, LoanPositionListing, Treasury, TreasuryFees, LIQUIDATION_BONUS_PERCENT, MIN_BORROW_HEALTH_RATIO
+};
+use crate::utils::{position, treasury};
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
+
+#[derive(Accounts)]
+#[instruction(offer_id: String)]
+pub struct LiquidateLoanOfferNative<'info> {
+  #[account(mut)]
+  pub liquidator: Signer<'info>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = liquidator
+  )]
+  pub liquidator_ata_asset: Box<Account<'info, TokenAccount>>,
+  #[account(mut)]
+  pub borrower: SystemAccount<'info>,
+  #[account(
+    mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = loan_offer.collateral_mint_token == native_mint::ID @ LiquidateOfferError::NotNativeCollateral,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LOAN_OFFER_ACCOUNT_SEED.as_ref(),
+      borrower.key().as_ref(),
+      offer_id.as_bytes(),
+      crate::ID.key().as_ref()
+    ],
+    bump = loan_offer.bump
+  )]
+  pub loan_offer: Box<Account<'info, LoanOfferAccount>>,
+  /// Current creditor of the loan, the holder of its position token
+  pub lender: SystemAccount<'info>,
+  #[account(
+    associated_token::mint = loan_offer.position_mint.unwrap_or_default(),
+    associated_token::authority = listing.as_ref().map_or(lender.key(), |listing| listing.key())
+  )]
+  pub lender_position_ata: Option<Box<Account<'info, TokenAccount>>>,
+  /// Open listing of the position token, it holds the token for the seller
+  pub listing: Option<Box<Account<'info, LoanPositionListing>>>,
+  #[account(
+    init_if_needed,
+    payer = liquidator,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = lender
+  )]
+  pub lender_ata_asset: Box<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      TREASURY_SEED.as_ref(),
+      lend_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = treasury.bump
+  )]
+  pub treasury: Box<Account<'info, Treasury>>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = treasury
+  )]
+  pub treasury_ata: Box<Account<'info, TokenAccount>>,
+  #[account(
+    constraint = lend_mint_asset.key() == loan_offer.lend_mint_token @ LoanOfferError::InvalidLendMintAsset,
+  )]
+  pub lend_mint_asset: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      lend_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = lend_asset.bump
+  )]
+  pub lend_asset: Box<Account<'info, Asset>>,
+  #[account(
+    mut,
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      native_mint::ID.as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = collateral_asset.bump
+  )]
+  pub collateral_asset: Box<Account<'info, Asset>>,
+  #[account(
+    constraint = lend_price_feed_account.key() == lend_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccount,
+  )]
+  pub lend_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+  #[account(
+    constraint = collateral_price_feed_account.key() == collateral_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccount,
+  )]
+  pub collateral_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub system_program: Program<'info, System>,
+}
+
+struct Settlement {
+  reason: LiquidationReason,
+  collateral_price: f64,
+  repaid_amount: u64,
+  lender_amount: u64,
+  borrower_fee: u64,
+  seized_collateral_amount: u64,
+}
+
+impl<'info> LiquidateLoanOfferNative<'info> {
+  pub fn liquidate_loan_offer_native(&mut self) -> Result<()> {
+    let status = transition(self.loan_offer.status, LoanOfferEvent::SettleLiquidation)?;
+
+    position::validate_creditor(
+      &self.loan_offer,
+      self.lender.key(),
+      self.lender_position_ata.as_deref().map(|ata| &**ata),
+      self.listing.as_deref(),
+    )?;
+
+    let settlement = self.get_settlement()?;
+    let fee_amount = settlement.repaid_amount - settlement.lender_amount;
+    let collateral_amount = self.loan_offer.collateral_amount;
+    let remaining_collateral_to_borrower = collateral_amount - settlement.seized_collateral_amount;
+
+    self.transfer_lend_asset(self.lender_ata_asset.to_account_info(), settlement.lender_amount)?;
+    if fee_amount > 0 {
+      self.transfer_lend_asset(self.treasury_ata.to_account_info(), fee_amount)?;
+    }
+    treasury::record_fees(
+      &mut self.treasury,
+      &self.loan_offer,
+      self.liquidator.key(),
+      &TreasuryFees {
+        lender_fee: fee_amount.saturating_sub(settlement.borrower_fee),
+        borrower_fee: settlement.borrower_fee.min(fee_amount),
+      },
+    )?;
+
+    // Note: Only the collateral leaves the loan account, its rent stays until the account is closed
+    self.loan_offer.sub_lamports(collateral_amount)?;
+    self.liquidator.add_lamports(settlement.seized_collateral_amount)?;
+    self.borrower.add_lamports(remaining_collateral_to_borrower)?;
+
+    self.collateral_asset.remove_collateral(collateral_amount);
+    self.lend_asset.remove_borrow(self.loan_offer.borrow_amount);
+    self.collateral_asset.remove_isolated_debt(self.loan_offer.borrow_amount, self.lend_asset.decimals);
+
+    let old_status = self.loan_offer.status;
+    let loan_offer = &mut self.loan_offer;
+    loan_offer.collateral_amount = 0;
+    loan_offer.liquidating_price = Some(settlement.collateral_price);
+    loan_offer.liquidating_at = Some(Clock::get()?.unix_timestamp);
+    loan_offer.status = status;
+
+    self.emit_event_liquidation_settled(old_status, &settlement, fee_amount, remaining_collateral_to_borrower)?;
+
+    Ok(())
+  }
+
+  fn get_settlement(&self) -> Result<Settlement> {
+    let loan_offer = &self.loan_offer;
+
+    let (_, collateral_price) = convert_to_usd_price(
+      &self.collateral_price_feed_account,
+      &self.collateral_asset.price_feed_id,
+      0f64,
+      self.collateral_asset.max_price_age_seconds,
+    )?;
+
+    let (_, lend_price) = convert_to_usd_price(
+      &self.lend_price_feed_account,
+      &self.lend_asset.price_feed_id,
+      0f64,
+      self.lend_asset.max_price_age_seconds,
+    )?;
+
+    let health_ratio = risk::get_health_ratio(&PricedLoanParams {
+      collateral_amount: loan_offer.collateral_amount,
+      collateral_decimals: self.collateral_asset.decimals,
+      collateral_price,
+      lend_amount: loan_offer.borrow_amount,
+      lend_decimals: self.lend_asset.decimals,
+      lend_price,
+    });
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let reason = if current_timestamp >= loan_offer.started_at + loan_offer.duration as i64 {
+      LiquidationReason::Expired
+    } else if health_ratio < MIN_BORROW_HEALTH_RATIO {
+      LiquidationReason::Unhealthy
+    } else {
+      return err!(LoanOfferError::HealthRatioInvalid);
+    };
+
+    let repaid_amount = amount::get_total_repay_loan_amount(TotalRepayLoanAmountParams {
+      borrower_fee_percent: loan_offer.borrower_fee_percent,
+      interest: loan_offer.interest,
+      duration: loan_offer.duration,
+      borrow_amount: loan_offer.borrow_amount,
+    });
+
+    let borrower_fee = amount::get_borrower_fee_amount(TotalRepayLoanAmountParams {
+      borrower_fee_percent: loan_offer.borrower_fee_percent,
+      interest: loan_offer.interest,
+      duration: loan_offer.duration,
+      borrow_amount: loan_offer.borrow_amount,
+    });
+
+    let lender_amount = amount::get_lender_repay_amount(LenderRepayAmountParams {
+      lender_fee_percent: loan_offer.lender_fee_percent,
+      interest: loan_offer.interest,
+      duration: loan_offer.duration,
+      borrow_amount: loan_offer.borrow_amount,
+    });
+
+    let seized_collateral_amount = risk::get_seized_collateral(
+      &PricedLoanParams {
+        collateral_amount: loan_offer.collateral_amount,
+        collateral_decimals: self.collateral_asset.decimals,
+        collateral_price,
+        lend_amount: repaid_amount,
+        lend_decimals: self.lend_asset.decimals,
+        lend_price,
+      },
+      LIQUIDATION_BONUS_PERCENT,
+    );
+
+    Ok(Settlement {
+      reason,
+      collateral_price,
+      repaid_amount,
+      lender_amount,
+      borrower_fee,
+      seized_collateral_amount,
+    })
+  }
+
+  fn transfer_lend_asset(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+    let ctx = CpiContext::new(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.liquidator_ata_asset.to_account_info(),
+        mint: self.lend_mint_asset.to_account_info(),
+        to,
+        authority: self.liquidator.to_account_info(),
+      }
+    );
+
+    transfer_checked(ctx, amount, self.lend_mint_asset.decimals)
+  }
+
+  fn emit_event_liquidation_settled(
+    &self,
+    old_status: LoanOfferStatus,
+    settlement: &Settlement,
+    fee_amount: u64,
+    remaining_collateral_to_borrower: u64,
+  ) -> Result<()> {
+    emit!(LiquidationSettled {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.liquidator.key())?
+        .with_status(Some(old_status), Some(self.loan_offer.status)),
+      borrower: self.borrower.key(),
+      lender: self.lender.key(),
+      liquidator: self.liquidator.key(),
+      reason: settlement.reason,
+      repaid_amount: settlement.repaid_amount,
+      lender_amount: settlement.lender_amount,
+      fee_amount,
+      collateral_price: settlement.collateral_price,
+      seized_collateral_amount: settlement.seized_collateral_amount,
+      remaining_collateral_to_borrower,
+    });
+
+    Ok(())
+  }
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+  common::{ENSO_SEED, LOAN_POSITION_LISTING_SEED},
+  EventHeader, LoanOfferAccount, LoanPositionListed, LoanPositionListing, LoanPositionListingError
+};
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
+
+#[derive(Accounts)]
+pub struct ListLoanPosition<'info> {
+  #[account(mut)]
+  pub seller: Signer<'info>,
+  #[account(
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = loan_offer.position_mint == Some(position_mint.key()) @ LoanPositionListingError::InvalidPositionMint,
+  )]
+  pub loan_offer: Box<Account<'info, LoanOfferAccount>>,
+  pub position_mint: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    constraint = seller_position_ata.amount == 1 @ LoanPositionListingError::InvalidPositionTokenAccount,
+    associated_token::mint = position_mint,
+    associated_token::authority = seller
+  )]
+  pub seller_position_ata: Box<Account<'info, TokenAccount>>,
+  #[account(
+    init,
+    payer = seller,
+    space = 8 + LoanPositionListing::INIT_SPACE,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LOAN_POSITION_LISTING_SEED.as_ref(),
+      position_mint.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump
+  )]
+  pub listing: Box<Account<'info, LoanPositionListing>>,
+  #[account(
+    init_if_needed,
+    payer = seller,
+    associated_token::mint = position_mint,
+    associated_token::authority = listing
+  )]
+  pub listing_position_ata: Box<Account<'info, TokenAccount>>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> ListLoanPosition<'info> {
+  pub fn list_loan_position(&mut self, bumps: &ListLoanPositionBumps, price: u64) -> Result<()> {
+    transition(self.loan_offer.status, LoanOfferEvent::TradePosition)?;
+
+    if price == 0 {
+      return err!(LoanPositionListingError::InvalidPrice);
+    }
+
+    self.listing.set_inner(LoanPositionListing {
+      seller: self.seller.key(),
+      loan_offer: self.loan_offer.key(),
+      position_mint: self.position_mint.key(),
+      lend_mint_token: self.loan_offer.lend_mint_token,
+      price,
+      listed_at: Clock::get()?.unix_timestamp,
+      bump: bumps.listing,
+    });
+
+    self.deposit_position_token()?;
+
+    self.emit_event_list_loan_position()?;
+
+    Ok(())
+  }
+
+  fn deposit_position_token(&self) -> Result<()> {
+    let ctx = CpiContext::new(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.seller_position_ata.to_account_info(),
+        mint: self.position_mint.to_account_info(),
+        to: self.listing_position_ata.to_account_info(),
+        authority: self.seller.to_account_info(),
+      }
+    );
+
+    transfer_checked(ctx, 1, self.position_mint.decimals)
+  }
+
+  fn emit_event_list_loan_position(&self) -> Result<()> {
+    emit!(LoanPositionListed {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.seller.key())?
+        .with_status(Some(self.loan_offer.status), Some(self.loan_offer.status)),
+      position_mint: self.position_mint.key(),
+      lend_mint_token: self.listing.lend_mint_token,
+      price: self.listing.price,
+      listed_at: self.listing.listed_at,
+    });
+
+    Ok(())
+  }
+}
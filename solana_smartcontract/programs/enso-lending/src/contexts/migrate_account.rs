@@ -0,0 +1,100 @@
+use anchor_lang::{prelude::*, solana_program::{program::invoke, system_instruction}, Discriminator};
+
+use crate::{
+  common::error::AccountVersionError,
+  states::Versioned,
+  AccountMigrated, Asset, EventHeader, LendOfferAccount, LoanOfferAccount, SettingAccount
+};
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  /// CHECK: Owner is checked here, discriminator is checked when migrating
+  #[account(
+    mut,
+    owner = crate::ID
+  )]
+  pub account: UncheckedAccount<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateAccount<'info> {
+  pub fn migrate_account(&self) -> Result<()> {
+    let discriminator: [u8; 8] = {
+      let data = self.account.try_borrow_data()?;
+      data.get(..8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ErrorCode::AccountDiscriminatorNotFound)?
+    };
+
+    if discriminator == LoanOfferAccount::DISCRIMINATOR {
+      self.migrate::<LoanOfferAccount>()
+    } else if discriminator == LendOfferAccount::DISCRIMINATOR {
+      self.migrate::<LendOfferAccount>()
+    } else if discriminator == Asset::DISCRIMINATOR {
+      self.migrate::<Asset>()
+    } else if discriminator == SettingAccount::DISCRIMINATOR {
+      self.migrate::<SettingAccount>()
+    } else {
+      err!(AccountVersionError::UnknownAccountType)
+    }
+  }
+
+  fn migrate<T: Versioned>(&self) -> Result<()> {
+    self.realloc(T::SPACE)?;
+
+    let account_info = self.account.to_account_info();
+    let mut data = account_info.try_borrow_mut_data()?;
+    let mut account = T::try_deserialize(&mut &data[..])?;
+
+    let from_version = account.version();
+    if from_version == T::VERSION {
+      return Ok(());
+    }
+
+    if from_version > T::VERSION {
+      return err!(AccountVersionError::UnsupportedAccountVersion);
+    }
+
+    account.migrate();
+    account.try_serialize(&mut &mut data[..])?;
+
+    emit!(AccountMigrated {
+      header: EventHeader::new(self.account.key(), String::new(), self.payer.key())?,
+      from_version,
+      to_version: T::VERSION,
+    });
+
+    Ok(())
+  }
+
+  fn realloc(&self, space: usize) -> Result<()> {
+    let account_info = self.account.to_account_info();
+    if account_info.data_len() >= space {
+      return Ok(());
+    }
+
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(space);
+    let top_up = rent_exempt_lamports.saturating_sub(account_info.lamports());
+
+    if top_up > 0 {
+      invoke(
+        &system_instruction::transfer(
+          &self.payer.key(),
+          &self.account.key(),
+          top_up,
+        ),
+        &[
+          self.payer.to_account_info(),
+          account_info.clone(),
+          self.system_program.to_account_info(),
+        ],
+      )?;
+    }
+
+    account_info.realloc(space, true)?;
+
+    Ok(())
+  }
+}
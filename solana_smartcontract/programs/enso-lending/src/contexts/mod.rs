@@ -13,6 +13,11 @@ pub use edit_asset::*;
 pub mod init_vault_authority;
 pub use init_vault_authority::*;
 
+pub mod init_treasury;
+pub use init_treasury::*;
+pub mod withdraw_treasury;
+pub use withdraw_treasury::*;
+
 pub mod create_lend_offer;
 pub use create_lend_offer::*;
 pub mod edit_lend_offer;
@@ -21,6 +26,10 @@ pub mod cancel_lend_offer;
 pub use cancel_lend_offer::*;
 pub mod system_cancel_lend_offer;
 pub use system_cancel_lend_offer::*;
+pub mod system_withdraw_lend_offer;
+pub use system_withdraw_lend_offer::*;
+pub mod reclaim_expired_lend_offer;
+pub use reclaim_expired_lend_offer::*;
 
 pub mod create_loan_offer_native;
 pub use create_loan_offer_native::*;
@@ -35,6 +44,8 @@ pub mod deposit_collateral_loan_offer;
 pub use deposit_collateral_loan_offer::*;
 pub mod repay_loan_offer;
 pub use repay_loan_offer::*;
+pub mod repay_loan_offer_to_lender;
+pub use repay_loan_offer_to_lender::*;
 pub mod withdraw_collateral_loan_offer;
 pub use withdraw_collateral_loan_offer::*;
 pub mod start_liquidate_loan_offer_health;
@@ -47,11 +58,15 @@ pub use withdraw_collateral_loan_offer_native::*;
 
 pub mod repay_loan_offer_native;
 pub use repay_loan_offer_native::*;
+pub mod repay_loan_offer_native_to_lender;
+pub use repay_loan_offer_native_to_lender::*;
 
 pub mod start_liquidate_loan_offer_native_expired;
 pub use start_liquidate_loan_offer_native_expired::*;
 pub mod start_liquidate_loan_offer_native_health;
 pub use start_liquidate_loan_offer_native_health::*;
+pub mod liquidate_loan_offer_native;
+pub use liquidate_loan_offer_native::*;
 
 pub mod system_liquidate_loan_offer;
 pub use system_liquidate_loan_offer::*;
@@ -93,4 +108,37 @@ pub mod start_liquidate_health_loan_cross_chain;
 pub use start_liquidate_health_loan_cross_chain::*;
 
 pub mod start_liquidate_expired_loan_cross_chain;
-pub use start_liquidate_expired_loan_cross_chain::*;
\ No newline at end of file
+pub use start_liquidate_expired_loan_cross_chain::*;
+
+pub mod list_loan_position;
+pub use list_loan_position::*;
+
+pub mod cancel_loan_position_listing;
+pub use cancel_loan_position_listing::*;
+
+pub mod buy_loan_position;
+pub use buy_loan_position::*;
+
+pub mod view_loan_position_listing;
+pub use view_loan_position_listing::*;
+
+pub mod swap_collateral;
+pub use swap_collateral::*;
+
+pub mod swap_collateral_cross_chain;
+pub use swap_collateral_cross_chain::*;
+
+pub mod close_loan_offer;
+pub use close_loan_offer::*;
+
+pub mod batch_close_accounts;
+pub use batch_close_accounts::*;
+
+pub mod migrate_account;
+pub use migrate_account::*;
+
+pub mod view_loan_offer;
+pub use view_loan_offer::*;
+
+pub mod quote_max_borrow;
+pub use quote_max_borrow::*;
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{
+  common::{ASSET_SEED, ENSO_SEED},
+  convert_to_usd_price, risk::{self, PricedLoanParams}, Asset, LoanOfferError, MaxBorrowQuote, MIN_BORROW_HEALTH_RATIO
+};
+use crate::AccountVersionError;
+
+#[derive(Accounts)]
+pub struct QuoteMaxBorrow<'info> {
+  #[account(
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      lend_asset.token_mint.as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = lend_asset.bump
+  )]
+  pub lend_asset: Box<Account<'info, Asset>>,
+  #[account(
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      collateral_asset.token_mint.as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = collateral_asset.bump
+  )]
+  pub collateral_asset: Box<Account<'info, Asset>>,
+  #[account(
+    constraint = lend_price_feed_account.key() == lend_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccountForLendAsset,
+  )]
+  pub lend_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+  #[account(
+    constraint = collateral_price_feed_account.key() == collateral_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccountForCollateralAsset,
+  )]
+  pub collateral_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+}
+
+impl<'info> QuoteMaxBorrow<'info> {
+  pub fn quote_max_borrow(&self, collateral_amount: u64) -> Result<MaxBorrowQuote> {
+    let (_, collateral_price) = convert_to_usd_price(
+      &self.collateral_price_feed_account,
+      &self.collateral_asset.price_feed_id,
+      0f64,
+      self.collateral_asset.max_price_age_seconds,
+    )?;
+
+    let (_, lend_price) = convert_to_usd_price(
+      &self.lend_price_feed_account,
+      &self.lend_asset.price_feed_id,
+      0f64,
+      self.lend_asset.max_price_age_seconds,
+    )?;
+
+    let priced_loan = PricedLoanParams {
+      collateral_amount,
+      collateral_decimals: self.collateral_asset.decimals,
+      collateral_price,
+      lend_amount: 0,
+      lend_decimals: self.lend_asset.decimals,
+      lend_price,
+    };
+
+    Ok(MaxBorrowQuote {
+      collateral_amount,
+      collateral_usd: priced_loan.collateral_usd(),
+      max_borrow_amount: risk::get_max_borrow_amount(&priced_loan, MIN_BORROW_HEALTH_RATIO),
+    })
+  }
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{
+  common::{
+    EventHeader, LendOfferError, LendOfferReclaimed, LendOfferStatus
+  }, states::lend_offer::LendOfferAccount, ENSO_SEED, LEND_OFFER_ACCOUNT_SEED
+};
+use crate::AccountVersionError;
+use crate::{transition, LendOfferEvent};
+
+#[derive(Accounts)]
+#[instruction(offer_id: String)]
+pub struct ReclaimExpiredLendOffer<'info> {
+  #[account(mut)]
+  pub cranker: Signer<'info>,
+  #[account(
+    mut,
+    constraint = lender.key() == lend_offer.lender @ LendOfferError::InvalidLender
+  )]
+  pub lender: SystemAccount<'info>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = lender
+  )]
+  pub lender_ata_asset: Account<'info, TokenAccount>,
+  #[account(
+    constraint = mint_asset.key() == lend_offer.lend_mint_token @ LendOfferError::InvalidMintAsset,
+  )]
+  pub mint_asset: Account<'info, Mint>,
+  #[account(
+    mut,
+    constraint = lend_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    close = lender,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LEND_OFFER_ACCOUNT_SEED.as_ref(),
+      lender.key().as_ref(),
+      offer_id.as_bytes(),
+      crate::ID.key().as_ref(),
+    ],
+    bump = lend_offer.bump
+  )]
+  pub lend_offer: Account<'info, LendOfferAccount>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = lend_offer
+  )]
+  pub lend_offer_vault: Account<'info, TokenAccount>,
+  pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ReclaimExpiredLendOffer<'info> {
+  pub fn reclaim_expired_lend_offer(&mut self) -> Result<()> {
+    let status = transition(self.lend_offer.status, LendOfferEvent::Reclaim)?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    if !self.lend_offer.is_expired(current_timestamp) {
+      return err!(LendOfferError::LendOfferNotExpired);
+    }
+
+    let old_status = self.lend_offer.status;
+
+    self.transfer_back_lend_asset()?;
+    self.close_lend_offer_vault()?;
+    self.pay_crank_tip()?;
+
+    self.lend_offer.status = status;
+
+    self.emit_event_reclaim_expired_lend_offer(old_status)?;
+
+    Ok(())
+  }
+
+  fn transfer_back_lend_asset(&self) -> Result<()> {
+    let lender_pub_key = self.lender.key();
+    let program_id = crate::ID.key();
+
+    let signer: &[&[&[u8]]] = &[&[
+      ENSO_SEED.as_ref(),
+      LEND_OFFER_ACCOUNT_SEED.as_ref(),
+      lender_pub_key.as_ref(),
+      self.lend_offer.offer_id.as_bytes(),
+      program_id.as_ref(),
+      &[self.lend_offer.bump]
+    ]];
+
+    let ctx = CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.lend_offer_vault.to_account_info(),
+        mint: self.mint_asset.to_account_info(),
+        to: self.lender_ata_asset.to_account_info(),
+        authority: self.lend_offer.to_account_info(),
+      },
+      signer
+    );
+
+    transfer_checked(
+      ctx,
+      self.lend_offer_vault.amount,
+      self.mint_asset.decimals,
+    )
+  }
+
+  fn close_lend_offer_vault(&self) -> Result<()> {
+    let lender_pub_key = self.lender.key();
+    let program_id = crate::ID.key();
+
+    let signer: &[&[&[u8]]] = &[&[
+      ENSO_SEED.as_ref(),
+      LEND_OFFER_ACCOUNT_SEED.as_ref(),
+      lender_pub_key.as_ref(),
+      self.lend_offer.offer_id.as_bytes(),
+      program_id.as_ref(),
+      &[self.lend_offer.bump]
+    ]];
+
+    let ctx = CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      CloseAccount {
+        account: self.lend_offer_vault.to_account_info(),
+        destination: self.lender.to_account_info(),
+        authority: self.lend_offer.to_account_info(),
+      },
+      signer
+    );
+
+    close_account(ctx)
+  }
+
+  fn pay_crank_tip(&mut self) -> Result<()> {
+    let crank_tip = self.lend_offer.crank_tip;
+    if crank_tip == 0 {
+      return Ok(());
+    }
+
+    self.lend_offer.sub_lamports(crank_tip)?;
+    self.cranker.add_lamports(crank_tip)?;
+
+    Ok(())
+  }
+
+  fn emit_event_reclaim_expired_lend_offer(&self, old_status: LendOfferStatus) -> Result<()> {
+    emit!(LendOfferReclaimed {
+      header: EventHeader::new(self.lend_offer.key(), self.lend_offer.offer_id.clone(), self.cranker.key())?
+        .with_status(Some(old_status), Some(self.lend_offer.status)),
+      lender: self.lender.key(),
+      lend_mint_token: self.lend_offer.lend_mint_token,
+      amount: self.lend_offer.amount,
+      crank_tip: self.lend_offer.crank_tip,
+      expires_at: self.lend_offer.expires_at.unwrap_or_default(),
+    });
+
+    Ok(())
+  }
+}
@@ -5,11 +5,17 @@ use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChe
 
 use crate::{
   amount::TotalRepayLoanAmountParams, common::{
+    ASSET_SEED,
     ENSO_SEED,
     LOAN_OFFER_ACCOUNT_SEED, 
+    TREASURY_SEED,
     VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED 
-  }, utils, LoanOfferAccount, LoanOfferError, LoanOfferStatus, RepayOfferError, SystemRepayLoanOfferEvent, VaultAuthority, HOT_WALLET_PUBKEY
+  }, utils, Asset, LoanOfferAccount, LoanOfferError, LoanOfferStatus, RepayOfferError, EventHeader, LoanRepaid, Treasury,
+  TreasuryFees, VaultAuthority, HOT_WALLET_PUBKEY
 };
+use crate::utils::treasury;
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(loan_offer_id: String)]
@@ -24,6 +30,30 @@ pub struct RepayLoanOffer<'info> {
     constraint = lend_mint_asset.key() == loan_offer.lend_mint_token @ LoanOfferError::InvalidLendMintAsset,
   )]
   pub lend_mint_asset: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      lend_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = lend_asset.bump
+  )]
+  pub lend_asset: Box<Account<'info, Asset>>,
+  #[account(
+    mut,
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      collateral_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = collateral_asset.bump
+  )]
+  pub collateral_asset: Box<Account<'info, Asset>>,
   #[account(
     mut,
     associated_token::mint = lend_mint_asset,
@@ -38,7 +68,7 @@ pub struct RepayLoanOffer<'info> {
   pub borrower_ata_collateral_asset: Box<Account<'info, TokenAccount>>,
   #[account(
     mut,
-    constraint = loan_offer.status == LoanOfferStatus::FundTransferred @ RepayOfferError::LoanOfferIsNotAvailable,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -73,11 +103,30 @@ pub struct RepayLoanOffer<'info> {
     associated_token::authority = Pubkey::from_str(HOT_WALLET_PUBKEY).unwrap()
   )]
   pub hot_wallet_ata_lend_asset: Account<'info, TokenAccount>,
+  #[account(
+    mut,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      TREASURY_SEED.as_ref(),
+      lend_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = treasury.bump
+  )]
+  pub treasury: Box<Account<'info, Treasury>>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = treasury
+  )]
+  pub treasury_ata: Box<Account<'info, TokenAccount>>,
   pub token_program: Program<'info, Token>,
 }
 
 impl<'info> RepayLoanOffer<'info> {
   pub fn repay_loan_offer(&mut self) -> Result<()> {
+    let status = transition(self.loan_offer.status, LoanOfferEvent::Repay)?;
+
     self.validate_loan_offer()?;
     
     let total_amount = utils::amount::get_total_repay_loan_amount(TotalRepayLoanAmountParams {
@@ -86,17 +135,37 @@ impl<'info> RepayLoanOffer<'info> {
       duration: self.loan_offer.duration,
       interest: self.loan_offer.interest,
   });
+    let borrower_fee = utils::amount::get_borrower_fee_amount(TotalRepayLoanAmountParams {
+      borrow_amount: self.loan_offer.borrow_amount,
+      borrower_fee_percent: self.loan_offer.borrower_fee_percent,
+      duration: self.loan_offer.duration,
+      interest: self.loan_offer.interest,
+    });
     
     if total_amount > self.borrower_ata_lend_asset.amount {
       return err!(RepayOfferError::NotEnoughAmount);
     } 
 
-    self.repay_lend_asset_to_hot_wallet(total_amount)?;
+    // The borrower fee goes to the treasury, principal and interest wait in the hot wallet for the lender
+    self.repay_lend_asset(self.hot_wallet_ata_lend_asset.to_account_info(), total_amount - borrower_fee)?;
+    if borrower_fee > 0 {
+      self.repay_lend_asset(self.treasury_ata.to_account_info(), borrower_fee)?;
+    }
+    treasury::record_fees(
+      &mut self.treasury,
+      &self.loan_offer,
+      self.borrower.key(),
+      &TreasuryFees { borrower_fee, ..Default::default() },
+    )?;
     self.transfer_collateral_to_borrower()?;
 
-    self.loan_offer.status = LoanOfferStatus::BorrowerPaid;
+    self.collateral_asset.remove_collateral(self.loan_offer.collateral_amount);
+    self.lend_asset.remove_borrow(self.loan_offer.borrow_amount);
+    self.collateral_asset.remove_isolated_debt(self.loan_offer.borrow_amount, self.lend_asset.decimals);
+    let old_status = self.loan_offer.status;
+    self.loan_offer.status = status;
 
-    self.emit_event_repay_loan_offer()?;
+    self.emit_event_repay_loan_offer(old_status)?;
     Ok(())
   }
 
@@ -112,13 +181,13 @@ impl<'info> RepayLoanOffer<'info> {
     Ok(())
   }    
 
-  fn repay_lend_asset_to_hot_wallet(&self, repay_amount: u64) -> Result<()> {
+  fn repay_lend_asset(&self, to: AccountInfo<'info>, repay_amount: u64) -> Result<()> {
     let cpi_ctx = CpiContext::new(
       self.token_program.to_account_info(), 
       TransferChecked {
         from: self.borrower_ata_lend_asset.to_account_info(),
         mint: self.lend_mint_asset.to_account_info(),
-        to: self.hot_wallet_ata_lend_asset.to_account_info(),
+        to,
         authority: self.borrower.to_account_info(),
       }
     );
@@ -160,16 +229,16 @@ impl<'info> RepayLoanOffer<'info> {
     )
   }
 
-  fn emit_event_repay_loan_offer(&mut self) -> Result<()> {
-    emit!(SystemRepayLoanOfferEvent {
-      lender: self.loan_offer.lender.key(),
+  fn emit_event_repay_loan_offer(&self, old_status: LoanOfferStatus) -> Result<()> {
+    emit!(LoanRepaid {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.borrower.key())?
+        .with_status(Some(old_status), Some(self.loan_offer.status)),
       borrower: self.borrower.key(),
-      interest: self.loan_offer.interest,
-      loan_amount: self.loan_offer.borrow_amount,
-      loan_offer_id: self.loan_offer.offer_id.clone(),
+      original_lender: self.loan_offer.lender,
       tier_id: self.loan_offer.tier_id.clone(),
+      borrow_amount: self.loan_offer.borrow_amount,
+      interest: self.loan_offer.interest,
       collateral_amount: self.loan_offer.collateral_amount,
-      status: self.loan_offer.status,
     });
           
     Ok(())
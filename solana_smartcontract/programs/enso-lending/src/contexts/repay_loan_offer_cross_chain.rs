@@ -8,9 +8,13 @@ use crate::{
 	amount::TotalRepayLoanAmountParams, common::{
     ENSO_SEED,
     LOAN_OFFER_ACCOUNT_SEED,
+    TREASURY_SEED,
 		WORMHOLE_SENT_SEED,
-  }, utils, Asset, ForeignChain, LoanOfferAccount, LoanOfferError, LoanOfferStatus, RepayOfferError, SystemRepayLoanOfferEvent, WormholeConfig, WormholeEmitter, WormholeError, HOT_WALLET_PUBKEY, REFUND_COLLATERAL_CROSS_CHAIN_FUNCTION
+  }, utils, Asset, ForeignChain, LoanOfferAccount, LoanOfferError, LoanOfferStatus, RepayOfferError, EventHeader, LoanRepaid, Treasury, TreasuryFees, WormholeConfig, WormholeEmitter, WormholeError, HOT_WALLET_PUBKEY, REFUND_COLLATERAL_CROSS_CHAIN_FUNCTION
 };
+use crate::utils::treasury;
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(
@@ -28,11 +32,15 @@ pub struct RepayLoanOfferCrossChain<'info> {
   )]
   pub lend_mint_asset: Box<Account<'info, Mint>>,
 	#[account(
+    mut,
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = collateral_asset.token_mint == loan_offer.collateral_mint_token @ LoanOfferError::InvalidCollateralMintAsset,
   )]
   pub collateral_asset: Box<Account<'info, Asset>>,
   #[account(
-    constraint = lend_mint_asset.key() == loan_offer.lend_mint_token @ LoanOfferError::InvalidLendMintAsset,
+    mut,
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = lend_asset.token_mint == loan_offer.lend_mint_token @ LoanOfferError::InvalidLendMintAsset,
   )]
   pub lend_asset: Box<Account<'info, Asset>>,
 	#[account(
@@ -43,7 +51,7 @@ pub struct RepayLoanOfferCrossChain<'info> {
   pub borrower_ata_lend_asset: Box<Account<'info, TokenAccount>>,
 	#[account(
     mut,
-    constraint = loan_offer.status == LoanOfferStatus::FundTransferred @ RepayOfferError::LoanOfferIsNotAvailable,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -60,6 +68,23 @@ pub struct RepayLoanOfferCrossChain<'info> {
     associated_token::authority = Pubkey::from_str(HOT_WALLET_PUBKEY).unwrap()
   )]
   pub hot_wallet_ata_lend_asset: Account<'info, TokenAccount>,
+  #[account(
+    mut,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      TREASURY_SEED.as_ref(),
+      lend_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = treasury.bump
+  )]
+  pub treasury: Box<Account<'info, Treasury>>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = treasury
+  )]
+  pub treasury_ata: Box<Account<'info, TokenAccount>>,
 	#[account(
     seeds = [WormholeConfig::SEED_PREFIX],
     bump,
@@ -113,6 +138,8 @@ impl<'info> RepayLoanOfferCrossChain<'info> {
 		bumps: &RepayLoanOfferCrossChainBumps,
 		_loan_offer_id: String,
 	) -> Result<()> {
+		let status = transition(self.loan_offer.status, LoanOfferEvent::Repay)?;
+
 		let target_chain = self.collateral_asset.chain_id;
 		
 		let send_message_fee = self.wormhole_bridge.fee();
@@ -124,14 +151,33 @@ impl<'info> RepayLoanOfferCrossChain<'info> {
       borrow_amount: self.loan_offer.borrow_amount,
       borrower_fee_percent: self.loan_offer.borrower_fee_percent,
       duration: self.loan_offer.duration,
+      interest: self.loan_offer.interest,
+  	});
+		let borrower_fee = utils::amount::get_borrower_fee_amount(TotalRepayLoanAmountParams {
+      borrow_amount: self.loan_offer.borrow_amount,
+      borrower_fee_percent: self.loan_offer.borrower_fee_percent,
+      duration: self.loan_offer.duration,
       interest: self.loan_offer.interest,
   	});
     
     if total_amount > self.borrower_ata_lend_asset.amount {
       return err!(RepayOfferError::NotEnoughAmount);
     };
-		self.repay_lend_asset_to_hot_wallet(total_amount)?;
-		self.loan_offer.status = LoanOfferStatus::BorrowerPaid;
+		self.repay_lend_asset(self.hot_wallet_ata_lend_asset.to_account_info(), total_amount - borrower_fee)?;
+		if borrower_fee > 0 {
+			self.repay_lend_asset(self.treasury_ata.to_account_info(), borrower_fee)?;
+		}
+		treasury::record_fees(
+			&mut self.treasury,
+			&self.loan_offer,
+			self.borrower.key(),
+			&TreasuryFees { borrower_fee, ..Default::default() },
+		)?;
+		self.collateral_asset.remove_collateral(self.loan_offer.collateral_amount);
+		self.lend_asset.remove_borrow(self.loan_offer.borrow_amount);
+		self.collateral_asset.remove_isolated_debt(self.loan_offer.borrow_amount, self.lend_asset.decimals);
+		let old_status = self.loan_offer.status;
+		self.loan_offer.status = status;
 
 		let payload = self.gen_repay_loan_payload(
 			target_chain,
@@ -169,7 +215,7 @@ impl<'info> RepayLoanOfferCrossChain<'info> {
 			self.config.finality.try_into().unwrap(),
 		)?;
 
-		let _ = self.emit_event_repay_loan_offer();
+		self.emit_event_repay_loan_offer(old_status)?;
 
 		Ok(())
 	}
@@ -188,13 +234,13 @@ impl<'info> RepayLoanOfferCrossChain<'info> {
 		)
 	}
 
-	fn repay_lend_asset_to_hot_wallet(&self, repay_amount: u64) -> Result<()> {
+	fn repay_lend_asset(&self, to: AccountInfo<'info>, repay_amount: u64) -> Result<()> {
     let cpi_ctx = CpiContext::new(
       self.token_program.to_account_info(), 
       TransferChecked {
         from: self.borrower_ata_lend_asset.to_account_info(),
         mint: self.lend_mint_asset.to_account_info(),
-        to: self.hot_wallet_ata_lend_asset.to_account_info(),
+        to,
         authority: self.borrower.to_account_info(),
       }
     );
@@ -218,16 +264,16 @@ impl<'info> RepayLoanOfferCrossChain<'info> {
 		Ok(payload.into_bytes())
 	}
 
-	fn emit_event_repay_loan_offer(&mut self) -> Result<()> {
-    emit!(SystemRepayLoanOfferEvent {
-      lender: self.loan_offer.lender.key(),
+	fn emit_event_repay_loan_offer(&self, old_status: LoanOfferStatus) -> Result<()> {
+    emit!(LoanRepaid {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.borrower.key())?
+        .with_status(Some(old_status), Some(self.loan_offer.status)),
       borrower: self.borrower.key(),
-      interest: self.loan_offer.interest,
-      loan_amount: self.loan_offer.borrow_amount,
-      loan_offer_id: self.loan_offer.offer_id.clone(),
+      original_lender: self.loan_offer.lender,
       tier_id: self.loan_offer.tier_id.clone(),
+      borrow_amount: self.loan_offer.borrow_amount,
+      interest: self.loan_offer.interest,
       collateral_amount: self.loan_offer.collateral_amount,
-      status: self.loan_offer.status,
     });
           
     Ok(())
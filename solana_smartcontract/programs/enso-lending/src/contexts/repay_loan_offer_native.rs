@@ -5,11 +5,14 @@ use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChe
 
 use crate::{
     amount::TotalRepayLoanAmountParams,
-    common::{ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED},
-    states::loan_offer::LoanOfferAccount,
-    utils, LoanOfferStatus, RepayOfferError, SystemRepayLoanOfferEvent,
+    common::{ASSET_SEED, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED, TREASURY_SEED},
+    states::{loan_offer::LoanOfferAccount, Asset},
+    utils, EventHeader, LoanOfferStatus, LoanRepaid, RepayOfferError, Treasury, TreasuryFees,
     HOT_WALLET_PUBKEY,
 };
+use crate::utils::treasury;
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(loan_offer_id: String)]
@@ -20,6 +23,30 @@ pub struct RepayLoanOfferNative<'info> {
         constraint = lend_mint_asset.key() == loan_offer.lend_mint_token @ RepayOfferError::InvalidMintAsset,
     )]
     pub lend_mint_asset: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+        seeds = [
+            ENSO_SEED.as_ref(),
+            ASSET_SEED.as_ref(),
+            lend_mint_asset.key().as_ref(),
+            crate::ID.key().as_ref()
+        ],
+        bump = lend_asset.bump
+    )]
+    pub lend_asset: Account<'info, Asset>,
+    #[account(
+        mut,
+        constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+        seeds = [
+            ENSO_SEED.as_ref(),
+            ASSET_SEED.as_ref(),
+            loan_offer.collateral_mint_token.as_ref(),
+            crate::ID.key().as_ref()
+        ],
+        bump = collateral_asset.bump
+    )]
+    pub collateral_asset: Account<'info, Asset>,
     #[account(
         mut,
         associated_token::mint = lend_mint_asset,
@@ -28,7 +55,7 @@ pub struct RepayLoanOfferNative<'info> {
     pub borrower_ata_lend_asset: Account<'info, TokenAccount>,
     #[account(
       mut,
-      constraint = loan_offer.status == LoanOfferStatus::FundTransferred @ RepayOfferError::LoanOfferIsNotAvailable,
+      constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
       seeds = [
         ENSO_SEED.as_ref(),
         LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -45,12 +72,31 @@ pub struct RepayLoanOfferNative<'info> {
         associated_token::authority = Pubkey::from_str(HOT_WALLET_PUBKEY).unwrap()
     )]
     pub hot_wallet_ata_lend_asset: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            ENSO_SEED.as_ref(),
+            TREASURY_SEED.as_ref(),
+            lend_mint_asset.key().as_ref(),
+            crate::ID.key().as_ref()
+        ],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    #[account(
+        mut,
+        associated_token::mint = lend_mint_asset,
+        associated_token::authority = treasury
+    )]
+    pub treasury_ata: Box<Account<'info, TokenAccount>>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> RepayLoanOfferNative<'info> {
     pub fn repay_loan_offer_native(&mut self) -> Result<()> {
+        let status = transition(self.loan_offer.status, LoanOfferEvent::Repay)?;
+
         self.validate_loan_offer()?;
 
         let total_amount = utils::amount::get_total_repay_loan_amount(TotalRepayLoanAmountParams {
@@ -59,29 +105,51 @@ impl<'info> RepayLoanOfferNative<'info> {
             duration: self.loan_offer.duration,
             interest: self.loan_offer.interest,
         });
+        let borrower_fee = utils::amount::get_borrower_fee_amount(TotalRepayLoanAmountParams {
+            borrow_amount: self.loan_offer.borrow_amount,
+            borrower_fee_percent: self.loan_offer.borrower_fee_percent,
+            duration: self.loan_offer.duration,
+            interest: self.loan_offer.interest,
+        });
 
         if total_amount > self.borrower_ata_lend_asset.amount {
             return err!(RepayOfferError::NotEnoughAmount);
         }
 
-        self.repay_lend_asset_to_hot_wallet(total_amount)?;
+        self.repay_lend_asset(self.hot_wallet_ata_lend_asset.to_account_info(), total_amount - borrower_fee)?;
+        if borrower_fee > 0 {
+            self.repay_lend_asset(self.treasury_ata.to_account_info(), borrower_fee)?;
+        }
+        treasury::record_fees(
+            &mut self.treasury,
+            &self.loan_offer,
+            self.borrower.key(),
+            &TreasuryFees { borrower_fee, ..Default::default() },
+        )?;
 
         self.loan_offer
             .sub_lamports(self.loan_offer.collateral_amount)?;
         self.borrower
             .add_lamports(self.loan_offer.collateral_amount)?;
-        self.loan_offer.status = LoanOfferStatus::BorrowerPaid;
+        self.collateral_asset
+            .remove_collateral(self.loan_offer.collateral_amount);
+        self.lend_asset
+            .remove_borrow(self.loan_offer.borrow_amount);
+        self.collateral_asset
+            .remove_isolated_debt(self.loan_offer.borrow_amount, self.lend_asset.decimals);
+        let old_status = self.loan_offer.status;
+        self.loan_offer.status = status;
 
-        self.emit_event_repay_loan_offer(self.loan_offer.collateral_amount)?;
+        self.emit_event_repay_loan_offer(old_status, self.loan_offer.collateral_amount)?;
 
         Ok(())
     }
 
-    fn repay_lend_asset_to_hot_wallet(&mut self, repay_amount: u64) -> Result<()> {
+    fn repay_lend_asset(&self, to: AccountInfo<'info>, repay_amount: u64) -> Result<()> {
         let cpi_accounts = TransferChecked {
             from: self.borrower_ata_lend_asset.to_account_info(),
             mint: self.lend_mint_asset.to_account_info(),
-            to: self.hot_wallet_ata_lend_asset.to_account_info(),
+            to,
             authority: self.borrower.to_account_info(),
         };
 
@@ -102,16 +170,16 @@ impl<'info> RepayLoanOfferNative<'info> {
         Ok(())
     }
 
-    fn emit_event_repay_loan_offer(&mut self, collateral_amount: u64) -> Result<()> {
-        emit!(SystemRepayLoanOfferEvent {
-            lender: self.loan_offer.lender.key(),
-            borrower: self.borrower.key(),
-            interest: self.loan_offer.interest,
-            loan_amount: self.loan_offer.borrow_amount,
-            loan_offer_id: self.loan_offer.offer_id.clone(),
-            tier_id: self.loan_offer.tier_id.clone(),
-            collateral_amount,
-            status: self.loan_offer.status,
+    fn emit_event_repay_loan_offer(&self, old_status: LoanOfferStatus, collateral_amount: u64) -> Result<()> {
+        emit!(LoanRepaid {
+          header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.borrower.key())?
+            .with_status(Some(old_status), Some(self.loan_offer.status)),
+          borrower: self.borrower.key(),
+          original_lender: self.loan_offer.lender,
+          tier_id: self.loan_offer.tier_id.clone(),
+          borrow_amount: self.loan_offer.borrow_amount,
+          interest: self.loan_offer.interest,
+          collateral_amount,
         });
 
         Ok(())
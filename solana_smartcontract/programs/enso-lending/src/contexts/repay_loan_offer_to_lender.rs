@@ -0,0 +1,255 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+  amount::{self, LenderRepayAmountParams, TotalRepayLoanAmountParams},
+  common::{ASSET_SEED, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED, TREASURY_SEED, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED},
+  Asset, EventHeader, LoanOfferAccount, LoanOfferError, LoanOfferStatus, LoanPositionListing, LoanRepaidToLender, RepayOfferError, Treasury,
+  TreasuryFees, VaultAuthority
+};
+use crate::utils::{position, treasury};
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
+
+#[derive(Accounts)]
+#[instruction(loan_offer_id: String)]
+pub struct RepayLoanOfferToLender<'info> {
+  #[account(mut)]
+  pub borrower: Signer<'info>,
+  #[account(
+    constraint = collateral_mint_asset.key() == loan_offer.collateral_mint_token @ LoanOfferError::InvalidCollateralMintAsset,
+  )]
+  pub collateral_mint_asset: Box<Account<'info, Mint>>,
+  #[account(
+    constraint = lend_mint_asset.key() == loan_offer.lend_mint_token @ LoanOfferError::InvalidLendMintAsset,
+  )]
+  pub lend_mint_asset: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      lend_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = lend_asset.bump
+  )]
+  pub lend_asset: Box<Account<'info, Asset>>,
+  #[account(
+    mut,
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      collateral_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = collateral_asset.bump
+  )]
+  pub collateral_asset: Box<Account<'info, Asset>>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = borrower
+  )]
+  pub borrower_ata_lend_asset: Box<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    associated_token::mint = collateral_mint_asset,
+    associated_token::authority = borrower
+  )]
+  pub borrower_ata_collateral_asset: Box<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LOAN_OFFER_ACCOUNT_SEED.as_ref(),
+      borrower.key().as_ref(),
+      loan_offer_id.as_bytes(),
+      crate::ID.key().as_ref()
+    ],
+    bump
+  )]
+  pub loan_offer: Box<Account<'info, LoanOfferAccount>>,
+  #[account(
+    mut,
+    constraint = vault_authority.initializer.key() == borrower.key() @ LoanOfferError::InvalidInitializerVaultAuthority,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      borrower.key().as_ref(),
+      VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED.as_ref(),
+      crate::ID.key().as_ref(),
+    ],
+    bump = vault_authority.bump
+  )]
+  pub vault_authority: Box<Account<'info, VaultAuthority>>,
+  #[account(
+    mut,
+    associated_token::mint = collateral_mint_asset,
+    associated_token::authority = vault_authority
+  )]
+  pub vault: Box<Account<'info, TokenAccount>>,
+  /// Current creditor of the loan, the holder of its position token
+  pub lender: SystemAccount<'info>,
+  #[account(
+    associated_token::mint = loan_offer.position_mint.unwrap_or_default(),
+    associated_token::authority = listing.as_ref().map_or(lender.key(), |listing| listing.key())
+  )]
+  pub lender_position_ata: Option<Box<Account<'info, TokenAccount>>>,
+  /// Open listing of the position token, it holds the token for the seller
+  pub listing: Option<Box<Account<'info, LoanPositionListing>>>,
+  #[account(
+    init_if_needed,
+    payer = borrower,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = lender
+  )]
+  pub lender_ata_asset: Box<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      TREASURY_SEED.as_ref(),
+      lend_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = treasury.bump
+  )]
+  pub treasury: Box<Account<'info, Treasury>>,
+  #[account(
+    mut,
+    associated_token::mint = lend_mint_asset,
+    associated_token::authority = treasury
+  )]
+  pub treasury_ata: Box<Account<'info, TokenAccount>>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> RepayLoanOfferToLender<'info> {
+  pub fn repay_loan_offer_to_lender(&mut self) -> Result<()> {
+    let status = transition(self.loan_offer.status, LoanOfferEvent::RepayToLender)?;
+
+    position::validate_creditor(
+      &self.loan_offer,
+      self.lender.key(),
+      self.lender_position_ata.as_deref().map(|ata| &**ata),
+      self.listing.as_deref(),
+    )?;
+
+    let repaid_amount = amount::get_total_repay_loan_amount(TotalRepayLoanAmountParams {
+      borrow_amount: self.loan_offer.borrow_amount,
+      borrower_fee_percent: self.loan_offer.borrower_fee_percent,
+      duration: self.loan_offer.duration,
+      interest: self.loan_offer.interest,
+    });
+    let lender_amount = amount::get_lender_repay_amount(LenderRepayAmountParams {
+      lender_fee_percent: self.loan_offer.lender_fee_percent,
+      interest: self.loan_offer.interest,
+      duration: self.loan_offer.duration,
+      borrow_amount: self.loan_offer.borrow_amount,
+    });
+    let fee_amount = repaid_amount - lender_amount;
+    let borrower_fee = amount::get_borrower_fee_amount(TotalRepayLoanAmountParams {
+      borrow_amount: self.loan_offer.borrow_amount,
+      borrower_fee_percent: self.loan_offer.borrower_fee_percent,
+      duration: self.loan_offer.duration,
+      interest: self.loan_offer.interest,
+    });
+    let fees = TreasuryFees {
+      lender_fee: fee_amount.saturating_sub(borrower_fee),
+      borrower_fee: borrower_fee.min(fee_amount),
+    };
+
+    if repaid_amount > self.borrower_ata_lend_asset.amount {
+      return err!(RepayOfferError::NotEnoughAmount);
+    }
+
+    self.transfer_lend_asset(self.lender_ata_asset.to_account_info(), lender_amount)?;
+    if fee_amount > 0 {
+      self.transfer_lend_asset(self.treasury_ata.to_account_info(), fee_amount)?;
+    }
+    treasury::record_fees(&mut self.treasury, &self.loan_offer, self.borrower.key(), &fees)?;
+    self.transfer_collateral_to_borrower()?;
+
+    self.collateral_asset.remove_collateral(self.loan_offer.collateral_amount);
+    self.lend_asset.remove_borrow(self.loan_offer.borrow_amount);
+    self.collateral_asset.remove_isolated_debt(self.loan_offer.borrow_amount, self.lend_asset.decimals);
+    let old_status = self.loan_offer.status;
+    self.loan_offer.status = status;
+
+    self.emit_event_loan_repaid_to_lender(old_status, repaid_amount, lender_amount, fee_amount)?;
+
+    Ok(())
+  }
+
+  fn transfer_lend_asset(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+    let cpi_ctx = CpiContext::new(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.borrower_ata_lend_asset.to_account_info(),
+        mint: self.lend_mint_asset.to_account_info(),
+        to,
+        authority: self.borrower.to_account_info(),
+      }
+    );
+
+    transfer_checked(cpi_ctx, amount, self.lend_mint_asset.decimals)
+  }
+
+  fn transfer_collateral_to_borrower(&self) -> Result<()> {
+    let borrower_pub_key = self.borrower.key();
+    let program_id = crate::ID.key();
+
+    let signer: &[&[&[u8]]] = &[&[
+      ENSO_SEED.as_ref(),
+      borrower_pub_key.as_ref(),
+      VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED.as_ref(),
+      program_id.as_ref(),
+      &[self.vault_authority.bump]
+    ]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.vault.to_account_info(),
+        mint: self.collateral_mint_asset.to_account_info(),
+        to: self.borrower_ata_collateral_asset.to_account_info(),
+        authority: self.vault_authority.to_account_info(),
+      },
+      signer
+    );
+
+    transfer_checked(cpi_ctx, self.loan_offer.collateral_amount, self.collateral_mint_asset.decimals)
+  }
+
+  fn emit_event_loan_repaid_to_lender(
+    &self,
+    old_status: LoanOfferStatus,
+    repaid_amount: u64,
+    lender_amount: u64,
+    fee_amount: u64,
+  ) -> Result<()> {
+    emit!(LoanRepaidToLender {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.borrower.key())?
+        .with_status(Some(old_status), Some(self.loan_offer.status)),
+      borrower: self.borrower.key(),
+      lender: self.lender.key(),
+      tier_id: self.loan_offer.tier_id.clone(),
+      borrow_amount: self.loan_offer.borrow_amount,
+      interest: self.loan_offer.interest,
+      repaid_amount,
+      lender_amount,
+      fee_amount,
+      collateral_amount: self.loan_offer.collateral_amount,
+    });
+
+    Ok(())
+  }
+}
@@ -3,9 +3,11 @@ use wormhole_anchor_sdk::wormhole::{self, program::Wormhole};
 
 
 use crate::{
-  common::{ENSO_SEED, LEND_OFFER_ACCOUNT_SEED}, RequestCancelCollateralCrossChainEvent, ForeignChain, LendOfferAccount, LendOfferStatus, LoanOfferError, WormholeConfig, WormholeEmitter, WormholeError, WormholeMessage, CANCEL_COLLATERAL_FUNCTION, POSTED_TIMESTAMP_THRESHOLD, WORMHOLE_SENT_SEED
+  common::{ENSO_SEED, LEND_OFFER_ACCOUNT_SEED}, CrossChainCancelRequested, EventHeader, ForeignChain, LendOfferAccount, LendOfferStatus, LoanOfferError, WormholeConfig, WormholeEmitter, WormholeError, WormholeMessage, CANCEL_COLLATERAL_FUNCTION, POSTED_TIMESTAMP_THRESHOLD, WORMHOLE_SENT_SEED
 };
 use crate::utils::vaa;
+use crate::AccountVersionError;
+use crate::{transition, LendOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(
@@ -21,6 +23,7 @@ pub struct RequestCancelCollateralCrossChain<'info> {
 
   #[account(
     mut,
+    constraint = lend_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(), 
       LEND_OFFER_ACCOUNT_SEED.as_ref(), 
@@ -117,6 +120,8 @@ impl<'info> RequestCancelCollateralCrossChain<'info> {
       borrower_address
     ) = self.parse_create_loan_payload(&payload).unwrap();
 
+    transition(self.lend_offer.status, LendOfferEvent::RefundCrossChainCollateral)?;
+
     if self.lend_offer.status == LendOfferStatus::Created {
       let posted_timestamp: u32 = self.posted.meta.timestamp;
       let current_timestamp: u32 = Clock::get()?.unix_timestamp.try_into().unwrap();
@@ -126,10 +131,6 @@ impl<'info> RequestCancelCollateralCrossChain<'info> {
       }
     }
 
-    if self.lend_offer.status == LendOfferStatus::Loaned {
-      return err!(LoanOfferError::InvalidOfferStatus);
-    }
-
     let _ = self.verify_payload_message_data(
       posted_lend_offer_id.clone(), 
       lend_amount,
@@ -267,11 +268,10 @@ impl<'info> RequestCancelCollateralCrossChain<'info> {
     chain_address: String,
     target_chain: u16
   ) -> Result<()> {
-    emit!(RequestCancelCollateralCrossChainEvent {
-      borrower: self.borrower.key(),
-      lend_offer_id,
+    emit!(CrossChainCancelRequested {
+      header: EventHeader::new(self.borrower.key(), lend_offer_id, self.borrower.key())?,
+      target_chain,
       chain_address,
-      target_chain
     });
 
     Ok(())
@@ -3,9 +3,11 @@ use wormhole_anchor_sdk::wormhole::{self, program::Wormhole};
 
 
 use crate::{
-  common::{ENSO_SEED, LEND_OFFER_ACCOUNT_SEED}, ForeignChain, LendOfferAccount, LendOfferStatus, LoanOfferAccount, LoanOfferError, RequestCancelCollateralCrossChainEvent, WormholeConfig, WormholeEmitter, WormholeError, WormholeMessage, CANCEL_COLLATERAL_FUNCTION, LOAN_OFFER_ACCOUNT_SEED, WORMHOLE_SENT_SEED
+  common::{ENSO_SEED, LEND_OFFER_ACCOUNT_SEED}, ForeignChain, LendOfferAccount, LoanOfferAccount, LoanOfferError, CrossChainCancelRequested, EventHeader, WormholeConfig, WormholeEmitter, WormholeError, WormholeMessage, CANCEL_COLLATERAL_FUNCTION, LOAN_OFFER_ACCOUNT_SEED, WORMHOLE_SENT_SEED
 };
 use crate::utils::vaa;
+use crate::AccountVersionError;
+use crate::{transition, LendOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(
@@ -22,6 +24,7 @@ pub struct RequestCancelLoanedCrossChain<'info> {
 
   #[account(
     mut,
+    constraint = lend_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(), 
       LEND_OFFER_ACCOUNT_SEED.as_ref(), 
@@ -35,6 +38,7 @@ pub struct RequestCancelLoanedCrossChain<'info> {
 
   #[account(
     mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -135,9 +139,7 @@ impl<'info> RequestCancelLoanedCrossChain<'info> {
       borrower_address
     ) = self.parse_create_loan_payload(&payload).unwrap();
 
-    if self.lend_offer.status != LendOfferStatus::Loaned {
-      return err!(LoanOfferError::InvalidOfferStatus);
-    }
+    transition(self.lend_offer.status, LendOfferEvent::RequestCancelLoaned)?;
 
     let _ = self.verify_payload_message_data(
       posted_lend_offer_id.clone(), 
@@ -279,11 +281,10 @@ impl<'info> RequestCancelLoanedCrossChain<'info> {
     chain_address: String,
     target_chain: u16
   ) -> Result<()> {
-    emit!(RequestCancelCollateralCrossChainEvent {
-      borrower: self.borrower.key(),
-      lend_offer_id,
+    emit!(CrossChainCancelRequested {
+      header: EventHeader::new(self.borrower.key(), lend_offer_id, self.borrower.key())?,
+      target_chain,
       chain_address,
-      target_chain
     });
 
     Ok(())
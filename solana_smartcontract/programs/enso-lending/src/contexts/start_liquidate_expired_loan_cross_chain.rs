@@ -8,7 +8,7 @@ use crate::{
     common::constant::{ ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED, OPERATE_SYSTEM_PUBKEY },
     Asset,
     ForeignChain,
-    LiquidatingCollateralEvent,
+    EventHeader, LiquidationReason, LiquidationStarted,
     LoanOfferAccount,
     LoanOfferError,
     LoanOfferStatus,
@@ -19,6 +19,8 @@ use crate::{
     START_LIQUIDATE_EXPIRED_LOAN_CROSS_CHAIN,
     WORMHOLE_SENT_SEED,
 };
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(offer_id: String)]
@@ -37,6 +39,7 @@ pub struct StartLiquidateExpiredLoanCrossChain<'info> {
     pub collateral_mint_asset: Account<'info, Mint>,
 
     #[account(
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = collateral_asset.is_collateral == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -50,6 +53,7 @@ pub struct StartLiquidateExpiredLoanCrossChain<'info> {
 
     #[account(
     mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -119,13 +123,11 @@ impl<'info> StartLiquidateExpiredLoanCrossChain<'info> {
         self.validate_expired_loan_offer()?;
 
         let loan_offer = &mut self.loan_offer;
-        if loan_offer.status != LoanOfferStatus::FundTransferred {
-            return err!(LoanOfferError::InvalidOfferStatus);
-        }
+        let status = transition(loan_offer.status, LoanOfferEvent::StartLiquidation)?;
 
         let current_timestamp = Clock::get().unwrap().unix_timestamp;
         loan_offer.liquidating_at = Some(current_timestamp);
-        loan_offer.status = LoanOfferStatus::Liquidating;
+        loan_offer.status = status;
 
         let payload_message = self
             .gen_start_liquidate_expired_loan_cross_chain_payload(
@@ -152,7 +154,7 @@ impl<'info> StartLiquidateExpiredLoanCrossChain<'info> {
                     message: self.wormhole_message.to_account_info(),
                     emitter: self.wormhole_emitter.to_account_info(),
                     sequence: self.wormhole_sequence.to_account_info(),
-                    payer: self.borrower.to_account_info(),
+                    payer: self.system.to_account_info(),
                     fee_collector: self.wormhole_fee_collector.to_account_info(),
                     clock: self.clock.to_account_info(),
                     rent: self.rent.to_account_info(),
@@ -214,7 +216,7 @@ impl<'info> StartLiquidateExpiredLoanCrossChain<'info> {
         Ok(
             solana_program::program::invoke(
                 &solana_program::system_instruction::transfer(
-                    &self.borrower.key(),
+                    &self.system.key(),
                     &self.wormhole_fee_collector.key(),
                     fee
                 ),
@@ -224,10 +226,15 @@ impl<'info> StartLiquidateExpiredLoanCrossChain<'info> {
     }
 
     fn emit_event_start_liquidate_contract(&self) -> Result<()> {
-        emit!(LiquidatingCollateralEvent {
-            offer_id: self.loan_offer.offer_id.clone(),
-            liquidating_at: self.loan_offer.liquidating_at,
-            liquidating_price: self.loan_offer.liquidating_price,
+        emit!(LiquidationStarted {
+          header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.system.key())?
+            .with_status(Some(LoanOfferStatus::FundTransferred), Some(self.loan_offer.status)),
+          borrower: self.loan_offer.borrower,
+          reason: LiquidationReason::Expired,
+          collateral_mint_token: self.loan_offer.collateral_mint_token,
+          collateral_amount: self.loan_offer.collateral_amount,
+          borrow_amount: self.loan_offer.borrow_amount,
+          liquidating_price: self.loan_offer.liquidating_price,
         });
 
         Ok(())
@@ -10,7 +10,7 @@ use crate::{
     health_ratio::{ self, HealthRatioParams },
     Asset,
     ForeignChain,
-    LiquidatingCollateralEvent,
+    EventHeader, LiquidationReason, LiquidationStarted,
     LoanOfferAccount,
     LoanOfferError,
     LoanOfferStatus,
@@ -21,6 +21,8 @@ use crate::{
     START_LIQUIDATE_HEALTH_LOAN_CROSS_CHAIN,
     WORMHOLE_SENT_SEED,
 };
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(loan_offer_id: String)]
@@ -35,6 +37,7 @@ pub struct StartLiquidateLoanHealthCrossChain<'info> {
 
     #[account(
     mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -57,6 +60,7 @@ pub struct StartLiquidateLoanHealthCrossChain<'info> {
     pub lend_mint_asset: Account<'info, Mint>,
 
     #[account(
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = lend_asset.is_lend == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -69,6 +73,7 @@ pub struct StartLiquidateLoanHealthCrossChain<'info> {
     pub lend_asset: Account<'info, Asset>,
 
     #[account(
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = collateral_asset.is_collateral == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -146,9 +151,7 @@ impl<'info> StartLiquidateLoanHealthCrossChain<'info> {
         bumps: &StartLiquidateLoanHealthCrossChainBumps
     ) -> Result<()> {
         let loan_offer = &mut self.loan_offer;
-        if loan_offer.status != LoanOfferStatus::FundTransferred {
-            return err!(LoanOfferError::InvalidOfferStatus);
-        }
+        let status = transition(loan_offer.status, LoanOfferEvent::StartLiquidation)?;
 
         let (current_health_ratio, current_collateral_price, _) =
             health_ratio::get_health_ratio_and_assets_price(HealthRatioParams {
@@ -162,12 +165,12 @@ impl<'info> StartLiquidateLoanHealthCrossChain<'info> {
                 lend_price_feed_id: self.lend_asset.price_feed_id.clone(),
                 lend_max_price_age_seconds: self.lend_asset.max_price_age_seconds,
                 lend_decimals: self.lend_asset.decimals,
-            });
+            })?;
 
         if current_health_ratio < MIN_BORROW_HEALTH_RATIO {
             loan_offer.liquidating_price = Some(current_collateral_price);
             loan_offer.liquidating_at = Some(Clock::get().unwrap().unix_timestamp);
-            loan_offer.status = LoanOfferStatus::Liquidating;
+            loan_offer.status = status;
 
             let payload_message = self
                 .gen_start_liquidate_loan_health_cross_chain_payload(
@@ -195,7 +198,7 @@ impl<'info> StartLiquidateLoanHealthCrossChain<'info> {
                         message: self.wormhole_message.to_account_info(),
                         emitter: self.wormhole_emitter.to_account_info(),
                         sequence: self.wormhole_sequence.to_account_info(),
-                        payer: self.borrower.to_account_info(),
+                        payer: self.system.to_account_info(),
                         fee_collector: self.wormhole_fee_collector.to_account_info(),
                         clock: self.clock.to_account_info(),
                         rent: self.rent.to_account_info(),
@@ -250,7 +253,7 @@ impl<'info> StartLiquidateLoanHealthCrossChain<'info> {
         Ok(
             solana_program::program::invoke(
                 &solana_program::system_instruction::transfer(
-                    &self.borrower.key(),
+                    &self.system.key(),
                     &self.wormhole_fee_collector.key(),
                     fee
                 ),
@@ -260,10 +263,15 @@ impl<'info> StartLiquidateLoanHealthCrossChain<'info> {
     }
 
     fn emit_event_start_liquidate_contract(&self) -> Result<()> {
-        emit!(LiquidatingCollateralEvent {
-            offer_id: self.loan_offer.offer_id.clone(),
-            liquidating_at: self.loan_offer.liquidating_at,
-            liquidating_price: self.loan_offer.liquidating_price,
+        emit!(LiquidationStarted {
+          header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.system.key())?
+            .with_status(Some(LoanOfferStatus::FundTransferred), Some(self.loan_offer.status)),
+          borrower: self.loan_offer.borrower,
+          reason: LiquidationReason::Unhealthy,
+          collateral_mint_token: self.loan_offer.collateral_mint_token,
+          collateral_amount: self.loan_offer.collateral_amount,
+          borrow_amount: self.loan_offer.borrow_amount,
+          liquidating_price: self.loan_offer.liquidating_price,
         });
 
         Ok(())
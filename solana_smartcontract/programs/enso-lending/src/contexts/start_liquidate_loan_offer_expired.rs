@@ -9,8 +9,10 @@ use crate::{
     LOAN_OFFER_ACCOUNT_SEED, 
     OPERATE_SYSTEM_PUBKEY,
     VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED
-  }, LiquidatingCollateralEvent, LoanOfferAccount, LoanOfferError, LoanOfferStatus, VaultAuthority, HOT_WALLET_PUBKEY, 
+  }, EventHeader, LiquidationReason, LiquidationStarted, LoanOfferAccount, LoanOfferError, LoanOfferStatus, VaultAuthority, HOT_WALLET_PUBKEY, 
 };
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(offer_id: String)]
@@ -27,6 +29,7 @@ pub struct StartLiquidateLoanOfferExpired<'info> {
   pub collateral_mint_asset: Account<'info, Mint>,
   #[account(
     mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -69,13 +72,11 @@ impl<'info> StartLiquidateLoanOfferExpired<'info> {
     self.validate_expired_loan_offer()?;
     
     let loan_offer = &mut self.loan_offer;
-    if loan_offer.status != LoanOfferStatus::FundTransferred {
-      return err!(LoanOfferError::InvalidOfferStatus);
-    }
+    let status = transition(loan_offer.status, LoanOfferEvent::StartLiquidation)?;
 
     let current_timestamp = Clock::get().unwrap().unix_timestamp;
     loan_offer.liquidating_at = Some(current_timestamp);
-    loan_offer.status = LoanOfferStatus::Liquidating;
+    loan_offer.status = status;
 
     self.transfer_collateral_to_hot_wallet()?;
 
@@ -85,9 +86,14 @@ impl<'info> StartLiquidateLoanOfferExpired<'info> {
   }
 
   fn emit_event_start_liquidate_contract(&self) -> Result<()> {
-    emit!(LiquidatingCollateralEvent {
-      offer_id: self.loan_offer.offer_id.clone(),
-      liquidating_at: self.loan_offer.liquidating_at,
+    emit!(LiquidationStarted {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.system.key())?
+        .with_status(Some(LoanOfferStatus::FundTransferred), Some(self.loan_offer.status)),
+      borrower: self.loan_offer.borrower,
+      reason: LiquidationReason::Expired,
+      collateral_mint_token: self.loan_offer.collateral_mint_token,
+      collateral_amount: self.loan_offer.collateral_amount,
+      borrow_amount: self.loan_offer.borrow_amount,
       liquidating_price: self.loan_offer.liquidating_price,
     });
 
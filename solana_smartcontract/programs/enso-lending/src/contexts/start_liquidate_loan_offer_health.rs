@@ -7,8 +7,10 @@ use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 use crate::{
   common::constant::{
     ASSET_SEED, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED, OPERATE_SYSTEM_PUBKEY, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED
-  }, health_ratio::{self, HealthRatioParams}, Asset, LiquidatingCollateralEvent, LoanOfferAccount, LoanOfferError, LoanOfferStatus, VaultAuthority, HOT_WALLET_PUBKEY, MIN_BORROW_HEALTH_RATIO
+  }, health_ratio::{self, HealthRatioParams}, Asset, EventHeader, LiquidationReason, LiquidationStarted, LoanOfferAccount, LoanOfferError, LoanOfferStatus, VaultAuthority, HOT_WALLET_PUBKEY, MIN_BORROW_HEALTH_RATIO
 };
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(offer_id: String)]
@@ -21,6 +23,7 @@ pub struct StartLiquidateLoanOfferHealth<'info> {
   pub borrower: SystemAccount<'info>,
   #[account(
     mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -40,6 +43,7 @@ pub struct StartLiquidateLoanOfferHealth<'info> {
   )]
   pub lend_mint_asset: Account<'info, Mint>,
   #[account(
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = lend_asset.is_lend == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -51,6 +55,7 @@ pub struct StartLiquidateLoanOfferHealth<'info> {
   )]
   pub lend_asset: Account<'info, Asset>,
   #[account(
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = collateral_asset.is_collateral == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -99,9 +104,7 @@ pub struct StartLiquidateLoanOfferHealth<'info> {
 impl<'info> StartLiquidateLoanOfferHealth<'info> {
   pub fn start_liquidate_loan_offer_health(&mut self) -> Result<()> {
     let loan_offer = &mut self.loan_offer;
-    if loan_offer.status != LoanOfferStatus::FundTransferred {
-      return err!(LoanOfferError::InvalidOfferStatus);
-    }
+    let status = transition(loan_offer.status, LoanOfferEvent::StartLiquidation)?;
 
     let (current_health_ratio, current_collateral_price, _) = health_ratio::get_health_ratio_and_assets_price(HealthRatioParams {
       collateral_price_feed_account: &self.collateral_price_feed_account,
@@ -114,12 +117,12 @@ impl<'info> StartLiquidateLoanOfferHealth<'info> {
       lend_price_feed_id: self.lend_asset.price_feed_id.clone(),
       lend_max_price_age_seconds: self.lend_asset.max_price_age_seconds,
       lend_decimals: self.lend_asset.decimals,
-    });
+    })?;
 
     if current_health_ratio < MIN_BORROW_HEALTH_RATIO {
       loan_offer.liquidating_price = Some(current_collateral_price);
       loan_offer.liquidating_at = Some(Clock::get().unwrap().unix_timestamp);
-      loan_offer.status = LoanOfferStatus::Liquidating;
+      loan_offer.status = status;
   
       self.transfer_collateral_to_hot_wallet()?;
 
@@ -162,9 +165,14 @@ impl<'info> StartLiquidateLoanOfferHealth<'info> {
   }
 
   fn emit_event_start_liquidate_contract(&self) -> Result<()> {
-    emit!(LiquidatingCollateralEvent {
-      offer_id: self.loan_offer.offer_id.clone(),
-      liquidating_at: self.loan_offer.liquidating_at,
+    emit!(LiquidationStarted {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.system.key())?
+        .with_status(Some(LoanOfferStatus::FundTransferred), Some(self.loan_offer.status)),
+      borrower: self.loan_offer.borrower,
+      reason: LiquidationReason::Unhealthy,
+      collateral_mint_token: self.loan_offer.collateral_mint_token,
+      collateral_amount: self.loan_offer.collateral_amount,
+      borrow_amount: self.loan_offer.borrow_amount,
       liquidating_price: self.loan_offer.liquidating_price,
     });
 
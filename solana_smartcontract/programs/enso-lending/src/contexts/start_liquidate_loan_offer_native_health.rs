@@ -10,13 +10,15 @@ use crate::{
   }, 
   health_ratio::{self, HealthRatioParams}, 
   Asset, 
-  LiquidatingCollateralEvent, 
+  EventHeader, LiquidationReason, LiquidationStarted, 
   LoanOfferAccount, 
   LoanOfferError, 
   LoanOfferStatus, 
   HOT_WALLET_PUBKEY, 
   MIN_BORROW_HEALTH_RATIO
 };
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(offer_id: String)]
@@ -29,6 +31,7 @@ pub struct StartLiquidateLoanOfferNativeHealth<'info> {
   pub borrower: SystemAccount<'info>,
   #[account(
     mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -53,6 +56,7 @@ pub struct StartLiquidateLoanOfferNativeHealth<'info> {
   )]
   pub lend_mint_asset: Account<'info, Mint>,
   #[account(
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = lend_asset.is_lend == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -64,6 +68,7 @@ pub struct StartLiquidateLoanOfferNativeHealth<'info> {
   )]
   pub lend_asset: Account<'info, Asset>,
   #[account(
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = collateral_asset.is_collateral == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -87,9 +92,7 @@ pub struct StartLiquidateLoanOfferNativeHealth<'info> {
 impl<'info> StartLiquidateLoanOfferNativeHealth<'info> {
   pub fn start_liquidate_loan_offer_native_health(&mut self) -> Result<()> {
     let loan_offer = &mut self.loan_offer;
-    if loan_offer.status != LoanOfferStatus::FundTransferred {
-      return err!(LoanOfferError::InvalidOfferStatus);
-    }
+    let status = transition(loan_offer.status, LoanOfferEvent::StartLiquidation)?;
 
     let (current_health_ratio, current_collateral_price, _) = health_ratio::get_health_ratio_and_assets_price(HealthRatioParams {
       collateral_price_feed_account: &self.collateral_price_feed_account,
@@ -102,12 +105,12 @@ impl<'info> StartLiquidateLoanOfferNativeHealth<'info> {
       lend_price_feed_id: self.lend_asset.price_feed_id.clone(),
       lend_max_price_age_seconds: self.lend_asset.max_price_age_seconds,
       lend_decimals: self.lend_asset.decimals,
-    });
+    })?;
 
     if current_health_ratio < MIN_BORROW_HEALTH_RATIO {
       loan_offer.liquidating_price = Some(current_collateral_price);
       loan_offer.liquidating_at = Some(Clock::get().unwrap().unix_timestamp);
-      loan_offer.status = LoanOfferStatus::Liquidating;
+      loan_offer.status = status;
   
       self.loan_offer.sub_lamports(self.loan_offer.collateral_amount)?;
       self.hot_wallet.add_lamports(self.loan_offer.collateral_amount)?;
@@ -122,9 +125,14 @@ impl<'info> StartLiquidateLoanOfferNativeHealth<'info> {
   }
 
   fn emit_event_start_liquidate_contract(&self) -> Result<()> {
-    emit!(LiquidatingCollateralEvent {
-      offer_id: self.loan_offer.offer_id.clone(),
-      liquidating_at: self.loan_offer.liquidating_at,
+    emit!(LiquidationStarted {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.system.key())?
+        .with_status(Some(LoanOfferStatus::FundTransferred), Some(self.loan_offer.status)),
+      borrower: self.loan_offer.borrower,
+      reason: LiquidationReason::Unhealthy,
+      collateral_mint_token: self.loan_offer.collateral_mint_token,
+      collateral_amount: self.loan_offer.collateral_amount,
+      borrow_amount: self.loan_offer.borrow_amount,
       liquidating_price: self.loan_offer.liquidating_price,
     });
 
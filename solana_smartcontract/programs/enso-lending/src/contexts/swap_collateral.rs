@@ -0,0 +1,294 @@
+use anchor_lang::{prelude::*, solana_program::{program::invoke, system_instruction}};
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token::{spl_token::native_mint, transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{
+  common::{ASSET_SEED, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED, SETTING_ACCOUNT_SEED},
+  health_ratio::{self, HealthRatioParams},
+  Asset, CollateralSwapped, EventHeader, LoanOfferAccount, LoanOfferError, SettingAccount, VaultAuthority, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED
+};
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
+
+#[derive(Accounts)]
+#[instruction(loan_offer_id: String)]
+pub struct SwapCollateral<'info> {
+  #[account(mut)]
+  pub borrower: Signer<'info>,
+  #[account(
+    constraint = lend_mint_asset.key() == loan_offer.lend_mint_token @ LoanOfferError::InvalidLendMintAsset,
+  )]
+  pub lend_mint_asset: Box<Account<'info, Mint>>,
+  #[account(
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      lend_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = lend_asset.bump
+  )]
+  pub lend_asset: Box<Account<'info, Asset>>,
+  #[account(
+    constraint = old_collateral_mint_asset.key() == loan_offer.collateral_mint_token @ LoanOfferError::InvalidCollateralMintAsset,
+  )]
+  pub old_collateral_mint_asset: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    constraint = old_collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      old_collateral_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = old_collateral_asset.bump
+  )]
+  pub old_collateral_asset: Box<Account<'info, Asset>>,
+  #[account(
+    constraint = new_collateral_mint_asset.key() != old_collateral_mint_asset.key() @ LoanOfferError::SameCollateralMintAsset,
+  )]
+  pub new_collateral_mint_asset: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    constraint = new_collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = new_collateral_asset.is_collateral @ LoanOfferError::InvalidAssetAccount,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      new_collateral_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = new_collateral_asset.bump
+  )]
+  pub new_collateral_asset: Box<Account<'info, Asset>>,
+  #[account(
+    constraint = setting_account.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      SETTING_ACCOUNT_SEED.as_ref(),
+      loan_offer.tier_id.as_bytes(),
+      crate::ID.key().as_ref(),
+    ],
+    bump = setting_account.bump
+  )]
+  pub setting_account: Box<Account<'info, SettingAccount>>,
+  #[account(
+    mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LOAN_OFFER_ACCOUNT_SEED.as_ref(),
+      borrower.key().as_ref(),
+      loan_offer_id.as_bytes(),
+      crate::ID.key().as_ref()
+    ],
+    bump = loan_offer.bump
+  )]
+  pub loan_offer: Box<Account<'info, LoanOfferAccount>>,
+  #[account(
+    constraint = vault_authority.initializer.key() == borrower.key() @ LoanOfferError::InvalidInitializerVaultAuthority,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      borrower.key().as_ref(),
+      VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED.as_ref(),
+      crate::ID.key().as_ref(),
+    ],
+    bump = vault_authority.bump
+  )]
+  pub vault_authority: Box<Account<'info, VaultAuthority>>,
+  #[account(
+    mut,
+    associated_token::mint = old_collateral_mint_asset,
+    associated_token::authority = vault_authority
+  )]
+  pub old_vault: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    mut,
+    associated_token::mint = old_collateral_mint_asset,
+    associated_token::authority = borrower
+  )]
+  pub borrower_ata_old_collateral_asset: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    init_if_needed,
+    payer = borrower,
+    associated_token::mint = new_collateral_mint_asset,
+    associated_token::authority = vault_authority
+  )]
+  pub new_vault: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    mut,
+    associated_token::mint = new_collateral_mint_asset,
+    associated_token::authority = borrower
+  )]
+  pub borrower_ata_new_collateral_asset: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    constraint = lend_price_feed_account.key() == lend_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccount,
+  )]
+  pub lend_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+  #[account(
+    constraint = new_collateral_price_feed_account.key() == new_collateral_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccount,
+  )]
+  pub new_collateral_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> SwapCollateral<'info> {
+  pub fn swap_collateral(&mut self, loan_offer_id: String, new_collateral_amount: u64) -> Result<()> {
+    transition(self.loan_offer.status, LoanOfferEvent::SwapCollateral)?;
+
+    self.validate_swap_collateral(new_collateral_amount)?;
+
+    let old_collateral_amount = self.loan_offer.collateral_amount;
+    let borrow_amount = self.loan_offer.borrow_amount;
+    let lend_decimals = self.lend_asset.decimals;
+
+    self.old_collateral_asset.remove_collateral(old_collateral_amount);
+    self.old_collateral_asset.remove_isolated_debt(borrow_amount, lend_decimals);
+    self.new_collateral_asset.add_collateral(new_collateral_amount)?;
+    self.new_collateral_asset.add_isolated_debt(self.lend_asset.token_mint, borrow_amount, lend_decimals)?;
+
+    self.deposit_new_collateral(new_collateral_amount)?;
+    self.withdraw_old_collateral(old_collateral_amount)?;
+
+    self.loan_offer.collateral_mint_token = self.new_collateral_mint_asset.key();
+    self.loan_offer.collateral_amount = new_collateral_amount;
+
+    self.emit_event_swap_collateral(loan_offer_id, old_collateral_amount)?;
+
+    Ok(())
+  }
+
+  fn validate_swap_collateral(&self, new_collateral_amount: u64) -> Result<()> {
+    self.setting_account.validate_collateral_mint(self.new_collateral_mint_asset.key())?;
+
+    health_ratio::validate_health_ratio(HealthRatioParams {
+      collateral_price_feed_account: &self.new_collateral_price_feed_account,
+      collateral_amount: new_collateral_amount,
+      collateral_price_feed_id: self.new_collateral_asset.price_feed_id.clone(),
+      collateral_max_price_age_seconds: self.new_collateral_asset.max_price_age_seconds,
+      collateral_decimals: self.new_collateral_asset.decimals,
+      lend_price_feed_account: &self.lend_price_feed_account,
+      lend_amount: self.loan_offer.borrow_amount,
+      lend_price_feed_id: self.lend_asset.price_feed_id.clone(),
+      lend_max_price_age_seconds: self.lend_asset.max_price_age_seconds,
+      lend_decimals: self.lend_asset.decimals,
+    })?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let end_borrowed_loan_offer = self.loan_offer.started_at + self.loan_offer.duration as i64;
+
+    if current_timestamp > end_borrowed_loan_offer {
+      return err!(LoanOfferError::LoanOfferExpired);
+    }
+
+    Ok(())
+  }
+
+  // Note: Native SOL collateral is held as lamports on the loan offer account, SPL collateral in the borrower vault
+  fn deposit_new_collateral(&self, collateral_amount: u64) -> Result<()> {
+    if self.new_collateral_mint_asset.key() == native_mint::ID {
+      invoke(
+        &system_instruction::transfer(
+          &self.borrower.key(),
+          &self.loan_offer.key(),
+          collateral_amount,
+        ),
+        &[
+          self.borrower.to_account_info(),
+          self.loan_offer.to_account_info(),
+          self.system_program.to_account_info(),
+        ],
+      )?;
+
+      return Ok(());
+    }
+
+    let (Some(borrower_ata_new_collateral_asset), Some(new_vault)) = (
+      &self.borrower_ata_new_collateral_asset,
+      &self.new_vault
+    ) else {
+      return err!(LoanOfferError::MissingCollateralTokenAccount);
+    };
+
+    let cpi_ctx = CpiContext::new(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: borrower_ata_new_collateral_asset.to_account_info(),
+        mint: self.new_collateral_mint_asset.to_account_info(),
+        to: new_vault.to_account_info(),
+        authority: self.borrower.to_account_info(),
+      }
+    );
+
+    transfer_checked(
+      cpi_ctx,
+      collateral_amount,
+      self.new_collateral_mint_asset.decimals,
+    )
+  }
+
+  fn withdraw_old_collateral(&mut self, collateral_amount: u64) -> Result<()> {
+    if self.old_collateral_mint_asset.key() == native_mint::ID {
+      self.loan_offer.sub_lamports(collateral_amount)?;
+      self.borrower.add_lamports(collateral_amount)?;
+
+      return Ok(());
+    }
+
+    let (Some(old_vault), Some(borrower_ata_old_collateral_asset)) = (
+      &self.old_vault,
+      &self.borrower_ata_old_collateral_asset
+    ) else {
+      return err!(LoanOfferError::MissingCollateralTokenAccount);
+    };
+
+    let borrower_pub_key = self.borrower.key();
+    let program_id = crate::ID.key();
+
+    let signer: &[&[&[u8]]] = &[&[
+      ENSO_SEED.as_ref(),
+      borrower_pub_key.as_ref(),
+      VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED.as_ref(),
+      program_id.as_ref(),
+      &[self.vault_authority.bump]
+    ]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: old_vault.to_account_info(),
+        mint: self.old_collateral_mint_asset.to_account_info(),
+        to: borrower_ata_old_collateral_asset.to_account_info(),
+        authority: self.vault_authority.to_account_info(),
+      },
+      signer
+    );
+
+    transfer_checked(
+      cpi_ctx,
+      collateral_amount,
+      self.old_collateral_mint_asset.decimals,
+    )
+  }
+
+  fn emit_event_swap_collateral(&self, loan_offer_id: String, old_collateral_amount: u64) -> Result<()> {
+    emit!(CollateralSwapped {
+      header: EventHeader::new(self.loan_offer.key(), loan_offer_id, self.borrower.key())?
+        .with_status(Some(self.loan_offer.status), Some(self.loan_offer.status)),
+      borrower: self.borrower.key(),
+      old_collateral_mint_token: self.old_collateral_mint_asset.key(),
+      old_collateral_amount,
+      new_collateral_mint_token: self.loan_offer.collateral_mint_token,
+      new_collateral_amount: self.loan_offer.collateral_amount,
+    });
+
+    Ok(())
+  }
+}
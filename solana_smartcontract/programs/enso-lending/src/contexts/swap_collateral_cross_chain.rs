@@ -0,0 +1,411 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use wormhole_anchor_sdk::wormhole::{self, program::Wormhole};
+
+use crate::{
+  common::{ASSET_SEED, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED, SETTING_ACCOUNT_SEED},
+  health_ratio::{self, HealthRatioParams},
+  Asset, ForeignChain, LoanOfferAccount, LoanOfferError, SettingAccount, CrossChainCollateralSwapped, EventHeader, WormholeConfig, WormholeEmitter, WormholeError, WormholeMessage, POSTED_TIMESTAMP_THRESHOLD, RELEASE_SWAPPED_COLLATERAL_FUNCTION, SWAP_COLLATERAL_CROSS_CHAIN_FUNCTION, WORMHOLE_SENT_SEED
+};
+use crate::utils::vaa;
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
+
+#[derive(Accounts)]
+#[instruction(
+  loan_offer_id: String,
+  vaa_hash: [u8; 32]
+)]
+pub struct SwapCollateralCrossChain<'info> {
+  #[account(mut)]
+  pub signer: Signer<'info>,
+
+  pub borrower: SystemAccount<'info>,
+
+  #[account(
+    constraint = lend_mint_asset.key() == loan_offer.lend_mint_token @ LoanOfferError::InvalidLendMintAsset,
+  )]
+  pub lend_mint_asset: Box<Account<'info, Mint>>,
+
+  #[account(
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      lend_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = lend_asset.bump
+  )]
+  pub lend_asset: Box<Account<'info, Asset>>,
+
+  #[account(
+    constraint = old_collateral_mint_asset.key() == loan_offer.collateral_mint_token @ LoanOfferError::InvalidCollateralMintAsset,
+  )]
+  pub old_collateral_mint_asset: Box<Account<'info, Mint>>,
+
+  #[account(
+    mut,
+    constraint = old_collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = old_collateral_asset.chain_id == foreign_chain.chain_id @ LoanOfferError::InvalidChainId,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      old_collateral_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = old_collateral_asset.bump
+  )]
+  pub old_collateral_asset: Box<Account<'info, Asset>>,
+
+  #[account(
+    constraint = new_collateral_mint_asset.key() != old_collateral_mint_asset.key() @ LoanOfferError::SameCollateralMintAsset,
+  )]
+  pub new_collateral_mint_asset: Box<Account<'info, Mint>>,
+
+  #[account(
+    mut,
+    constraint = new_collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = new_collateral_asset.is_collateral @ LoanOfferError::InvalidAssetAccount,
+    constraint = new_collateral_asset.chain_id == foreign_chain.chain_id @ LoanOfferError::InvalidChainId,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      new_collateral_mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = new_collateral_asset.bump
+  )]
+  pub new_collateral_asset: Box<Account<'info, Asset>>,
+
+  #[account(
+    constraint = setting_account.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      SETTING_ACCOUNT_SEED.as_ref(),
+      loan_offer.tier_id.as_bytes(),
+      crate::ID.key().as_ref(),
+    ],
+    bump = setting_account.bump
+  )]
+  pub setting_account: Box<Account<'info, SettingAccount>>,
+
+  #[account(
+    mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LOAN_OFFER_ACCOUNT_SEED.as_ref(),
+      borrower.key().as_ref(),
+      loan_offer_id.as_bytes(),
+      crate::ID.key().as_ref()
+    ],
+    bump = loan_offer.bump
+  )]
+  pub loan_offer: Box<Account<'info, LoanOfferAccount>>,
+
+  #[account(
+    constraint = lend_price_feed_account.key() == lend_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccount,
+  )]
+  pub lend_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+
+  #[account(
+    constraint = new_collateral_price_feed_account.key() == new_collateral_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccount,
+  )]
+  pub new_collateral_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+
+  #[account(
+    seeds = [
+      wormhole::SEED_PREFIX_POSTED_VAA,
+      &vaa_hash
+    ],
+    bump,
+    seeds::program = wormhole_program.key
+  )]
+  /// signatures and posted the account data here. Read-only.
+  pub posted: Box<Account<'info, wormhole::PostedVaa<WormholeMessage>>>,
+
+  #[account(mut)]
+  pub foreign_chain: Box<Account<'info, ForeignChain>>,
+
+  #[account(
+    seeds = [WormholeConfig::SEED_PREFIX],
+    bump,
+  )]
+  pub config: Box<Account<'info, WormholeConfig>>,
+
+  pub wormhole_program: Program<'info, Wormhole>,
+
+  #[account(
+    mut,
+    address = config.wormhole.bridge @ WormholeError::InvalidWormholeConfig
+  )]
+  pub wormhole_bridge: Box<Account<'info, wormhole::BridgeData>>,
+
+  #[account(
+    mut,
+    address = config.wormhole.fee_collector @ WormholeError::InvalidWormholeFeeCollector
+  )]
+  pub wormhole_fee_collector: Box<Account<'info, wormhole::FeeCollector>>,
+
+  #[account(
+    seeds = [WormholeEmitter::SEED_PREFIX],
+    bump,
+  )]
+  pub wormhole_emitter: Box<Account<'info, WormholeEmitter>>,
+
+  #[account(
+    mut,
+    address = config.wormhole.sequence @ WormholeError::InvalidSequence
+  )]
+  pub wormhole_sequence: Box<Account<'info, wormhole::SequenceTracker>>,
+
+  #[account(
+    mut,
+    seeds = [
+      WORMHOLE_SENT_SEED,
+      &wormhole_sequence.next_value().to_le_bytes()[..]
+    ],
+    bump,
+  )]
+  /// CHECK: Wormhole Message.
+  pub wormhole_message: UncheckedAccount<'info>,
+
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> SwapCollateralCrossChain<'info> {
+  pub fn swap_collateral_cross_chain(
+    &mut self,
+    bumps: &SwapCollateralCrossChainBumps,
+    loan_offer_id: String,
+    _vaa_hash: [u8; 32],
+  ) -> Result<()> {
+    transition(self.loan_offer.status, LoanOfferEvent::SwapCollateral)?;
+
+    self.validate_posted_vaa()?;
+
+    let posted_vaa = &self.posted.clone().into_inner();
+    let WormholeMessage::Message { payload } = posted_vaa.data();
+    let (
+      target_chain,
+      chain_address,
+      target_function,
+      posted_lend_offer_id,
+      new_collateral_amount,
+      new_collateral_address,
+      borrower_address
+    ) = vaa::parse_deposit_collateral_payload(payload)?;
+
+    self.verify_payload_message_data(
+      posted_lend_offer_id,
+      borrower_address,
+      target_function,
+      new_collateral_address.clone(),
+      new_collateral_amount,
+    )?;
+
+    let old_collateral_amount = self.loan_offer.collateral_amount;
+    let old_collateral_address = self.old_collateral_asset.token_address.clone().unwrap_or_default();
+    let borrow_amount = self.loan_offer.borrow_amount;
+    let lend_decimals = self.lend_asset.decimals;
+
+    self.old_collateral_asset.remove_collateral(old_collateral_amount);
+    self.old_collateral_asset.remove_isolated_debt(borrow_amount, lend_decimals);
+    self.new_collateral_asset.add_collateral(new_collateral_amount)?;
+    self.new_collateral_asset.add_isolated_debt(self.lend_asset.token_mint, borrow_amount, lend_decimals)?;
+
+    self.loan_offer.collateral_mint_token = self.new_collateral_mint_asset.key();
+    self.loan_offer.collateral_amount = new_collateral_amount;
+
+    // Foreign chain keeps the old collateral locked until this confirmation arrives
+    let payload_message = self.gen_release_swapped_collateral_payload(
+      self.foreign_chain.chain_id,
+      self.foreign_chain.chain_address.clone(),
+      self.loan_offer.lend_offer_id.clone(),
+      self.borrower.key().to_string(),
+      old_collateral_address.clone(),
+      old_collateral_amount,
+    );
+
+    self.post_message(bumps, payload_message)?;
+
+    self.emit_event_swap_collateral_cross_chain(
+      loan_offer_id,
+      chain_address,
+      target_chain,
+      old_collateral_address,
+      old_collateral_amount,
+      new_collateral_address,
+    )?;
+
+    Ok(())
+  }
+
+  fn validate_posted_vaa(&self) -> Result<()> {
+    let posted_chain_id = self.posted.meta.emitter_chain;
+    let chain_id = self.foreign_chain.chain_id;
+    let posted_emitter_address = self.posted.meta.emitter_address;
+    let emitter_address = self.foreign_chain.emitter_address.clone();
+    let posted_timestamp: u32 = self.posted.meta.timestamp;
+    let current_timestamp: u32 = Clock::get()?.unix_timestamp.try_into().unwrap();
+
+    if posted_timestamp + POSTED_TIMESTAMP_THRESHOLD < current_timestamp {
+      return err!(LoanOfferError::PostedVaaExpired);
+    }
+
+    Ok(vaa::validate_posted_vaa(
+      posted_chain_id,
+      chain_id,
+      posted_emitter_address,
+      emitter_address,
+    )?)
+  }
+
+  fn verify_payload_message_data(
+    &self,
+    lend_offer_id: String,
+    borrower_address: String,
+    target_function: String,
+    new_collateral_address: String,
+    new_collateral_amount: u64,
+  ) -> Result<()> {
+    if lend_offer_id != self.loan_offer.lend_offer_id {
+      return err!(LoanOfferError::InvalidLoanOffer);
+    }
+
+    if borrower_address != self.borrower.key().to_string() {
+      return err!(LoanOfferError::InvalidBorrower);
+    }
+
+    if target_function != SWAP_COLLATERAL_CROSS_CHAIN_FUNCTION {
+      return err!(LoanOfferError::InvalidTargetFunction);
+    }
+
+    if self.new_collateral_asset.token_address != Some(new_collateral_address) {
+      return err!(LoanOfferError::InvalidAssetAccount);
+    }
+
+    self.setting_account.validate_collateral_mint(self.new_collateral_mint_asset.key())?;
+
+    health_ratio::validate_health_ratio(HealthRatioParams {
+      collateral_price_feed_account: &self.new_collateral_price_feed_account,
+      collateral_amount: new_collateral_amount,
+      collateral_price_feed_id: self.new_collateral_asset.price_feed_id.clone(),
+      collateral_max_price_age_seconds: self.new_collateral_asset.max_price_age_seconds,
+      collateral_decimals: self.new_collateral_asset.decimals,
+      lend_price_feed_account: &self.lend_price_feed_account,
+      lend_amount: self.loan_offer.borrow_amount,
+      lend_price_feed_id: self.lend_asset.price_feed_id.clone(),
+      lend_max_price_age_seconds: self.lend_asset.max_price_age_seconds,
+      lend_decimals: self.lend_asset.decimals,
+    })?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let end_borrowed_loan_offer = self.loan_offer.started_at + self.loan_offer.duration as i64;
+
+    if current_timestamp > end_borrowed_loan_offer {
+      return err!(LoanOfferError::LoanOfferExpired);
+    }
+
+    Ok(())
+  }
+
+  fn gen_release_swapped_collateral_payload(
+    &self,
+    target_chain: u16,
+    chain_address: String,
+    lend_offer_id: String,
+    borrower_address: String,
+    old_collateral_address: String,
+    old_collateral_amount: u64,
+  ) -> Vec<u8> {
+    let payload = format!(
+      "{},{},{},{},{},{},{}",
+      target_chain,
+      chain_address,
+      RELEASE_SWAPPED_COLLATERAL_FUNCTION,
+      lend_offer_id,
+      borrower_address,
+      old_collateral_address,
+      old_collateral_amount
+    );
+    payload.into_bytes()
+  }
+
+  fn post_message(&self, bumps: &SwapCollateralCrossChainBumps, payload_message: Vec<u8>) -> Result<()> {
+    let send_message_fee = self.wormhole_bridge.fee();
+    if send_message_fee > 0 {
+      self.transfer_message_fee(send_message_fee)?;
+    }
+
+    let config = &self.config;
+    wormhole::post_message(
+      CpiContext::new_with_signer(
+        self.wormhole_program.to_account_info(),
+        wormhole::PostMessage {
+          config: self.wormhole_bridge.to_account_info(),
+          message: self.wormhole_message.to_account_info(),
+          emitter: self.wormhole_emitter.to_account_info(),
+          sequence: self.wormhole_sequence.to_account_info(),
+          payer: self.signer.to_account_info(),
+          fee_collector: self.wormhole_fee_collector.to_account_info(),
+          clock: self.clock.to_account_info(),
+          rent: self.rent.to_account_info(),
+          system_program: self.system_program.to_account_info(),
+        },
+        &[
+          &[
+            WORMHOLE_SENT_SEED.as_ref(),
+            &self.wormhole_sequence.next_value().to_le_bytes()[..],
+            &[bumps.wormhole_message],
+          ],
+          &[wormhole::SEED_PREFIX_EMITTER, &[bumps.wormhole_emitter]],
+        ],
+      ),
+      config.batch_id,
+      payload_message,
+      config.finality.try_into().unwrap(),
+    )
+  }
+
+  fn transfer_message_fee(&self, fee: u64) -> Result<()> {
+    Ok(solana_program::program::invoke(
+      &solana_program::system_instruction::transfer(
+        &self.signer.key(),
+        &self.wormhole_fee_collector.key(),
+        fee,
+      ),
+      &self.to_account_infos(),
+    )?)
+  }
+
+  fn emit_event_swap_collateral_cross_chain(
+    &self,
+    loan_offer_id: String,
+    chain_address: String,
+    target_chain: u16,
+    old_collateral_address: String,
+    old_collateral_amount: u64,
+    new_collateral_address: String,
+  ) -> Result<()> {
+    emit!(CrossChainCollateralSwapped {
+      header: EventHeader::new(self.loan_offer.key(), loan_offer_id, self.signer.key())?
+        .with_status(Some(self.loan_offer.status), Some(self.loan_offer.status)),
+      borrower: self.borrower.key(),
+      target_chain,
+      chain_address,
+      old_collateral_address,
+      old_collateral_amount,
+      new_collateral_address,
+      new_collateral_amount: self.loan_offer.collateral_amount,
+    });
+
+    Ok(())
+  }
+}
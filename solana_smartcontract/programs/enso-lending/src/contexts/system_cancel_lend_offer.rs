@@ -2,9 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
 use crate::{
   common::{
-    LendOfferError, LendOfferStatus
-  }, states::lend_offer::LendOfferAccount, LendOfferCanceledEvent, ENSO_SEED, LEND_OFFER_ACCOUNT_SEED
+    EventHeader, LendOfferCanceled, LendOfferError, LendOfferStatus
+  }, states::lend_offer::LendOfferAccount, ENSO_SEED, LEND_OFFER_ACCOUNT_SEED
 };
+use crate::AccountVersionError;
+use crate::{transition, LendOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(
@@ -28,7 +30,7 @@ pub struct SystemCancelLendOffer<'info> {
   pub mint_asset: Account<'info, Mint>,
   #[account(
     mut,
-    constraint = lend_offer.status == LendOfferStatus::Canceling @ LendOfferError::InvalidOfferStatus,
+    constraint = lend_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LEND_OFFER_ACCOUNT_SEED.as_ref(),  
@@ -52,7 +54,12 @@ pub struct SystemCancelLendOffer<'info> {
 
 impl<'info> SystemCancelLendOffer<'info> {
   pub fn system_cancel_lend_offer(&mut self, waiting_interest: u64) -> Result<()>  {
-    let total_repay = self.get_total_repay(self.lend_offer.amount, waiting_interest);
+    let status = transition(self.lend_offer.status, LendOfferEvent::ConfirmCancel)?;
+
+    let old_status = self.lend_offer.status;
+    // Note: A withdrawal still waiting for the operator is refunded together with the rest of the principal
+    let lend_amount = self.lend_offer.amount + self.lend_offer.pending_withdraw_amount;
+    let total_repay = self.get_total_repay(lend_amount, waiting_interest);
 
     if total_repay > self.system_ata.amount {
       return err!(LendOfferError::NotEnoughAmount);
@@ -60,9 +67,10 @@ impl<'info> SystemCancelLendOffer<'info> {
 
     self.transfer_back_lend_asset(total_repay)?;
 
-    self.lend_offer.status = LendOfferStatus::Canceled;
+    self.lend_offer.status = status;
+    self.lend_offer.pending_withdraw_amount = 0;
 
-    self.emit_event_cancel_lend_offer(total_repay)?;
+    self.emit_event_cancel_lend_offer(old_status, total_repay)?;
 
     Ok(())
   }
@@ -85,14 +93,16 @@ impl<'info> SystemCancelLendOffer<'info> {
     )
   }
 
-  fn emit_event_cancel_lend_offer(&mut self, total_repay: u64) -> Result<()> {
-    emit!(LendOfferCanceledEvent {
-      lender: self.lender.key(),
-      amount: total_repay,
-      duration: self.lend_offer.duration,
+  fn emit_event_cancel_lend_offer(&mut self, old_status: LendOfferStatus, total_repay: u64) -> Result<()> {
+    emit!(LendOfferCanceled {
+      header: EventHeader::new(self.lend_offer.key(), self.lend_offer.offer_id.clone(), self.system.key())?
+        .with_status(Some(old_status), Some(self.lend_offer.status)),
+      lend_mint_token: self.lend_offer.lend_mint_token,
+      amount: self.lend_offer.amount,
       interest: self.lend_offer.interest,
       lender_fee_percent: self.lend_offer.lender_fee_percent,
-      offer_id: self.lend_offer.offer_id.clone()
+      duration: self.lend_offer.duration,
+      refunded_amount: total_repay,
     });
 
     Ok(())
@@ -1,10 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
 use crate::{
+  amount::{self, LenderRepayAmountParams},
   common::{
     constant::LoanOfferStatus, RepayOfferError
-  }, duration_to_year, states::loan_offer::LoanOfferAccount, SystemFinishLoanOfferEvent, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED
+  }, duration_to_year, states::loan_offer::LoanOfferAccount, EventHeader, LoanFinished, LoanPositionListing, Treasury, TreasuryFees, ENSO_SEED,
+  LOAN_OFFER_ACCOUNT_SEED, TREASURY_SEED
 };
+use crate::utils::{position, treasury};
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(loan_offer_id: String)]
@@ -21,10 +26,15 @@ pub struct SystemFinishLoanOffer<'info> {
     constraint = mint_asset.key() == loan_offer.lend_mint_token @ RepayOfferError::InvalidMintAsset,
   )]
   pub mint_asset: Account<'info, Mint>,
+  /// Current creditor of the loan, the holder of its position token
+  pub lender: SystemAccount<'info>,
   #[account(
-    constraint = lender.key() == loan_offer.lender @ RepayOfferError::InvalidLender
+    associated_token::mint = loan_offer.position_mint.unwrap_or_default(),
+    associated_token::authority = listing.as_ref().map_or(lender.key(), |listing| listing.key())
   )]
-  pub lender: SystemAccount<'info>,
+  pub lender_position_ata: Option<Account<'info, TokenAccount>>,
+  /// Open listing of the position token, it holds the token for the seller
+  pub listing: Option<Box<Account<'info, LoanPositionListing>>>,
   #[account(
     mut,
     associated_token::mint = mint_asset,
@@ -35,8 +45,7 @@ pub struct SystemFinishLoanOffer<'info> {
   pub borrower: SystemAccount<'info>,
   #[account(
     mut,
-    constraint = loan_offer.status == LoanOfferStatus::BorrowerPaid 
-    || loan_offer.status == LoanOfferStatus::Liquidated @ RepayOfferError::InvalidOfferStatus,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -47,13 +56,45 @@ pub struct SystemFinishLoanOffer<'info> {
     bump = loan_offer.bump
   )]
   pub loan_offer: Account<'info, LoanOfferAccount>,
+  #[account(
+    mut,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      TREASURY_SEED.as_ref(),
+      mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = treasury.bump
+  )]
+  pub treasury: Box<Account<'info, Treasury>>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = treasury
+  )]
+  pub treasury_ata: Box<Account<'info, TokenAccount>>,
   pub token_program: Program<'info, Token>,
   pub system_program: Program<'info, System>,
 }
 
 impl<'info> SystemFinishLoanOffer<'info> {
   pub fn system_finish_loan_offer(&mut self, loan_amount: u64, waiting_interest: u64) -> Result<()>  {
+    let status = transition(self.loan_offer.status, LoanOfferEvent::Finish)?;
+
+    position::validate_creditor(
+      &self.loan_offer,
+      self.lender.key(),
+      self.lender_position_ata.as_deref(),
+      self.listing.as_deref(),
+    )?;
+
     let total_repay_to_lender = self.get_total_repay(loan_amount, waiting_interest);
+    let lender_fee = amount::get_lender_fee_amount(LenderRepayAmountParams {
+      lender_fee_percent: self.loan_offer.lender_fee_percent,
+      interest: self.loan_offer.interest,
+      duration: self.loan_offer.duration,
+      borrow_amount: loan_amount,
+    });
 
     // TODO: Allow system finish contract at any time
     // let current_timestamp = Clock::get().unwrap().unix_timestamp;
@@ -63,37 +104,51 @@ impl<'info> SystemFinishLoanOffer<'info> {
     //   return err!(RepayOfferError::TimeUnmetException);
     // }
 
-    self.transfer_asset_to_lender(loan_amount, total_repay_to_lender)?;
-    self.loan_offer.status = LoanOfferStatus::Finished;
+    self.transfer_asset_to_lender(loan_amount, total_repay_to_lender, lender_fee)?;
+    treasury::record_fees(
+      &mut self.treasury,
+      &self.loan_offer,
+      self.system.key(),
+      &TreasuryFees { lender_fee, ..Default::default() },
+    )?;
+    let old_status = self.loan_offer.status;
+    self.loan_offer.status = status;
 
     self.emit_event_system_finish_loan_offer(
-      loan_amount
+      old_status,
+      loan_amount,
+      waiting_interest,
+      total_repay_to_lender
     )?;
 
     Ok(())
   }
 
-  fn transfer_asset_to_lender(&mut self, loan_amount: u64, total_repay_to_lender: u64) -> Result<()> {
+  fn transfer_asset_to_lender(&mut self, loan_amount: u64, total_repay_to_lender: u64, lender_fee: u64) -> Result<()> {
     if loan_amount != self.loan_offer.borrow_amount {
       return err!(RepayOfferError::InvalidLendAmount);
     }
 
-    if total_repay_to_lender > self.system_ata.amount {
+    if total_repay_to_lender + lender_fee > self.system_ata.amount {
       return err!(RepayOfferError::NotEnoughAmount);
     }
 
-    self.process_transfer_lend_asset(total_repay_to_lender)?;
+    self.process_transfer_lend_asset(self.lender_ata_asset.to_account_info(), total_repay_to_lender)?;
+    // The lender fee was withheld from the lender, it goes to the treasury
+    if lender_fee > 0 {
+      self.process_transfer_lend_asset(self.treasury_ata.to_account_info(), lender_fee)?;
+    }
 
     Ok(())
   }
 
-  fn process_transfer_lend_asset(&mut self, total_repay: u64) -> Result<()> {
+  fn process_transfer_lend_asset(&self, to: AccountInfo<'info>, total_repay: u64) -> Result<()> {
     let ctx = CpiContext::new(
       self.token_program.to_account_info(),  
       TransferChecked {
         from: self.system_ata.to_account_info(),
         mint: self.mint_asset.to_account_info(),
-        to: self.lender_ata_asset.to_account_info(),
+        to,
         authority: self.system.to_account_info(),
     });
 
@@ -105,18 +160,22 @@ impl<'info> SystemFinishLoanOffer<'info> {
   }
 
   fn emit_event_system_finish_loan_offer(
-    &mut self,
-    loan_amount: u64
+    &self,
+    old_status: LoanOfferStatus,
+    loan_amount: u64,
+    waiting_interest: u64,
+    total_repay_to_lender: u64
   ) -> Result<()> {
-    emit!(SystemFinishLoanOfferEvent {
-      system: self.system.key(),
-      lender: self.lender.key(),
+    emit!(LoanFinished {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.system.key())?
+        .with_status(Some(old_status), Some(self.loan_offer.status)),
       borrower: self.borrower.key(),
+      lender: self.lender.key(),
+      tier_id: self.loan_offer.tier_id.clone(),
       interest: self.loan_offer.interest,
       loan_amount,
-      loan_offer_id: self.loan_offer.offer_id.clone(),
-      tier_id: self.loan_offer.tier_id.clone(),
-      status: self.loan_offer.status,
+      waiting_interest,
+      total_repay_to_lender,
     });
 
     Ok(())
@@ -1,13 +1,18 @@
 use std::str::FromStr;
 
 use crate::{
+    amount::{self, TotalRepayLoanAmountParams},
     common::{
         constant::{LoanOfferStatus, OPERATE_SYSTEM_PUBKEY},
         LiquidateOfferError,
-    }, duration_to_year, states::loan_offer::LoanOfferAccount, LiquidatedCollateralEvent, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED
+    }, states::loan_offer::LoanOfferAccount, Asset, EventHeader, LiquidationFinished, Treasury, TreasuryFees, ASSET_SEED,
+    ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED, TREASURY_SEED
 };
+use crate::utils::treasury;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(loan_offer_id: String)]
@@ -40,7 +45,7 @@ pub struct SystemLiquidateLoanOffer<'info> {
   pub borrower_ata_asset: Account<'info, TokenAccount>,
   #[account(
     mut,
-    constraint = loan_offer.status == LoanOfferStatus::Liquidating @ LiquidateOfferError::InvalidOfferStatus,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -51,6 +56,47 @@ pub struct SystemLiquidateLoanOffer<'info> {
     bump = loan_offer.bump
   )]
   pub loan_offer: Account<'info, LoanOfferAccount>,
+  #[account(
+    mut,
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = lend_asset.bump
+  )]
+  pub lend_asset: Box<Account<'info, Asset>>,
+  #[account(
+    mut,
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      loan_offer.collateral_mint_token.as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = collateral_asset.bump
+  )]
+  pub collateral_asset: Box<Account<'info, Asset>>,
+  #[account(
+    mut,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      TREASURY_SEED.as_ref(),
+      mint_asset.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = treasury.bump
+  )]
+  pub treasury: Box<Account<'info, Treasury>>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = treasury
+  )]
+  pub treasury_ata: Box<Account<'info, TokenAccount>>,
   pub token_program: Program<'info, Token>,
 }
 
@@ -61,16 +107,26 @@ impl<'info> SystemLiquidateLoanOffer<'info> {
     liquidated_price: u64,
     liquidated_tx: String,
   ) -> Result<()> {
-    let remaining_fund_to_borrower = self.get_remaining_fund(collateral_swapped_amount);
+    let status = transition(self.loan_offer.status, LoanOfferEvent::Liquidate)?;
+
+    let (remaining_fund_to_borrower, fees) = self.get_remaining_fund(collateral_swapped_amount);
 
     if remaining_fund_to_borrower > 0 {
       self.transfer_asset_to_borrower(remaining_fund_to_borrower)?;
     }
+    if fees.total() > 0 {
+      self.process_transfer(fees.total(), self.treasury_ata.to_account_info())?;
+    }
+    treasury::record_fees(&mut self.treasury, &self.loan_offer, self.system.key(), &fees)?;
+
+    self.collateral_asset.remove_collateral(self.loan_offer.collateral_amount);
+    self.lend_asset.remove_borrow(self.loan_offer.borrow_amount);
+    self.collateral_asset.remove_isolated_debt(self.loan_offer.borrow_amount, self.lend_asset.decimals);
 
     let loan_offer = &mut self.loan_offer;
     loan_offer.liquidated_price = Some(liquidated_price);
     loan_offer.liquidated_tx = Some(liquidated_tx);
-    loan_offer.status = LoanOfferStatus::Liquidated;
+    loan_offer.status = status;
     
     self.emit_event_system_liquidate_loan_offer(
       remaining_fund_to_borrower,
@@ -109,13 +165,12 @@ impl<'info> SystemLiquidateLoanOffer<'info> {
     remaining_fund_to_borrower: u64,
     collateral_swapped_amount: u64,
   ) -> Result<()> {
-    emit!(LiquidatedCollateralEvent {
-      system: self.system.key(),
-      lender: self.loan_offer.lender.key(),
+    emit!(LiquidationFinished {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.system.key())?
+        .with_status(Some(LoanOfferStatus::Liquidating), Some(self.loan_offer.status)),
       borrower: self.borrower.key(),
-      loan_offer_id: self.loan_offer.offer_id.clone(),
+      original_lender: self.loan_offer.lender,
       collateral_swapped_amount,
-      status: self.loan_offer.status,
       liquidated_price: self.loan_offer.liquidated_price.unwrap(),
       liquidated_tx: self.loan_offer.liquidated_tx.as_ref().unwrap().clone(),
       remaining_fund_to_borrower
@@ -123,19 +178,31 @@ impl<'info> SystemLiquidateLoanOffer<'info> {
     Ok(())
   }
 
-  fn get_remaining_fund(&self, collateral_swapped_amount: u64) -> u64 {
-    let loan_interest_percent = self.loan_offer.interest / 100.0;
-    let borrower_fee_percent = self.loan_offer.borrower_fee_percent / 100.0;
-    let time_borrowed = duration_to_year(self.loan_offer.duration);
-    let interest_loan_amount =
-        loan_interest_percent * self.loan_offer.borrow_amount as f64 * time_borrowed;
+  // Note: The borrower fee is only taken out of what the swap recovered over the lender's due, the rest goes back
+  // to the borrower without a liquidation penalty
+  fn get_remaining_fund(&self, collateral_swapped_amount: u64) -> (u64, TreasuryFees) {
+    let total_repay = amount::get_total_repay_loan_amount(TotalRepayLoanAmountParams {
+      borrower_fee_percent: self.loan_offer.borrower_fee_percent,
+      interest: self.loan_offer.interest,
+      duration: self.loan_offer.duration,
+      borrow_amount: self.loan_offer.borrow_amount,
+    });
+    let borrower_fee = amount::get_borrower_fee_amount(TotalRepayLoanAmountParams {
+      borrower_fee_percent: self.loan_offer.borrower_fee_percent,
+      interest: self.loan_offer.interest,
+      duration: self.loan_offer.duration,
+      borrow_amount: self.loan_offer.borrow_amount,
+    });
 
-    let borrower_fee_amount =
-        borrower_fee_percent * interest_loan_amount;
+    let recovered_over_debt = collateral_swapped_amount.saturating_sub(total_repay - borrower_fee);
+    let borrower_fee = borrower_fee.min(recovered_over_debt);
 
-    return (collateral_swapped_amount as f64
-      - self.loan_offer.borrow_amount as f64
-      - interest_loan_amount
-      - borrower_fee_amount) as u64;
+    (
+      recovered_over_debt - borrower_fee,
+      TreasuryFees {
+        lender_fee: 0,
+        borrower_fee,
+      },
+    )
   }
-}
+}
\ No newline at end of file
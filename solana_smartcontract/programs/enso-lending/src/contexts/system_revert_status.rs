@@ -2,7 +2,9 @@ use std::str::FromStr;
 
 use anchor_lang::prelude::*;
 
-use crate::{common::constant::{ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED, OPERATE_SYSTEM_PUBKEY}, LoanOfferAccount, LoanOfferError, LoanOfferStatus, SystemRevertEvent};
+use crate::{common::constant::{ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED, OPERATE_SYSTEM_PUBKEY}, LoanOfferAccount, LoanOfferError, LoanOfferStatus, EventHeader, LoanStatusReverted};
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(offer_id: String)]
@@ -13,6 +15,7 @@ pub struct SystemRevertStatus<'info> {
   pub borrower: AccountInfo<'info>,
   #[account(
     mut,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -32,13 +35,11 @@ impl<'info> SystemRevertStatus<'info> {
     }
 
     let loan_offer = &mut self.loan_offer;
-    if loan_offer.status != LoanOfferStatus::Liquidating {
-      return err!(LoanOfferError::InvalidOfferStatus);
-    }
+    let status = transition(loan_offer.status, LoanOfferEvent::RevertLiquidation)?;
 
     loan_offer.liquidating_price = None;
     loan_offer.liquidating_at = None;
-    loan_offer.status = LoanOfferStatus::FundTransferred;
+    loan_offer.status = status;
 
     self.emit_event_revert_status()?;
 
@@ -46,9 +47,10 @@ impl<'info> SystemRevertStatus<'info> {
   }
 
   fn emit_event_revert_status(&self) -> Result<()> {
-    emit!(SystemRevertEvent {
-      offer_id: self.loan_offer.offer_id.clone(),
-      status: self.loan_offer.status.clone(),
+    emit!(LoanStatusReverted {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.system.key())?
+        .with_status(Some(LoanOfferStatus::Liquidating), Some(self.loan_offer.status)),
+      borrower: self.borrower.key(),
     });
 
     Ok(())
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
 use crate::{
-  common::LoanOfferError, LoanOfferAccount, LoanOfferStatus, LoanOfferUpdateEvent, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED
+  common::LoanOfferError, LoanOfferAccount, LoanOfferStatus, EventHeader, LoanUpdated, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED
 };
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(
@@ -28,7 +30,7 @@ pub struct SystemUpdateLoanOffer<'info> {
   pub mint_asset: Account<'info, Mint>,
   #[account(
     mut,
-    constraint = loan_offer.status == LoanOfferStatus::Matched @ LoanOfferError::InvalidLoanOffer,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -53,15 +55,18 @@ pub struct SystemUpdateLoanOffer<'info> {
 
 impl<'info> SystemUpdateLoanOffer<'info> {
   pub fn system_update_loan_offer(&mut self, borrow_amount: u64) -> Result<()>  {
+    let status = transition(self.loan_offer.status, LoanOfferEvent::Fund)?;
+
     if borrow_amount != self.loan_offer.borrow_amount {
       return err!(LoanOfferError::InvalidBorrowAmount)?;
     }
 
     self.transfer_lend_asset_to_borrower(borrow_amount)?;
 
-    self.loan_offer.status = LoanOfferStatus::FundTransferred;
+    let old_status = self.loan_offer.status;
+    self.loan_offer.status = status;
 
-    self.emit_event_system_update_loan_offer()?;
+    self.emit_event_system_update_loan_offer(old_status)?;
 
     Ok(())
   }
@@ -83,22 +88,14 @@ impl<'info> SystemUpdateLoanOffer<'info> {
     )
   }
 
-  fn emit_event_system_update_loan_offer(&mut self) -> Result<()> {
-    emit!(LoanOfferUpdateEvent {
-      tier_id: self.loan_offer.tier_id.clone(),
-      lend_offer_id: self.loan_offer.lend_offer_id.clone(),
-      interest: self.loan_offer.interest,
-      borrow_amount: self.loan_offer.borrow_amount,
-      lender_fee_percent: self.loan_offer.lender_fee_percent,
-      duration: self.loan_offer.duration,
-      lend_mint_token: self.loan_offer.lend_mint_token,
-      lender: self.loan_offer.lender,
-      offer_id: self.loan_offer.offer_id.clone(),
+  fn emit_event_system_update_loan_offer(&self, old_status: LoanOfferStatus) -> Result<()> {
+    emit!(LoanUpdated {
+      header: EventHeader::new(self.loan_offer.key(), self.loan_offer.offer_id.clone(), self.system.key())?
+        .with_status(Some(old_status), Some(self.loan_offer.status)),
       borrower: self.loan_offer.borrower,
+      borrow_amount: self.loan_offer.borrow_amount,
       collateral_mint_token: self.loan_offer.collateral_mint_token,
       collateral_amount: self.loan_offer.collateral_amount,
-      status: self.loan_offer.status,
-      borrower_fee_percent: self.loan_offer.borrower_fee_percent,
       started_at: self.loan_offer.started_at,
     });
 
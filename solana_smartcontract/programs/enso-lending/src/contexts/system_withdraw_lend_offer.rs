@@ -0,0 +1,106 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+use crate::{
+  common::{
+    constant::OPERATE_SYSTEM_PUBKEY, EventHeader, LendOfferError, LendOfferWithdrawn, SettingAccountError
+  }, states::lend_offer::LendOfferAccount, ENSO_SEED, LEND_OFFER_ACCOUNT_SEED
+};
+use crate::AccountVersionError;
+use crate::{transition, LendOfferEvent};
+
+#[derive(Accounts)]
+#[instruction(offer_id: String)]
+pub struct SystemWithdrawLendOffer<'info> {
+  #[account(
+    constraint = lender.key() == lend_offer.lender @ LendOfferError::InvalidLender
+  )]
+  pub lender: SystemAccount<'info>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = lender
+  )]
+  pub lender_ata_asset: Account<'info, TokenAccount>,
+  #[account(
+    constraint = mint_asset.key() == lend_offer.lend_mint_token @ LendOfferError::InvalidMintAsset,
+  )]
+  pub mint_asset: Account<'info, Mint>,
+  #[account(
+    mut,
+    constraint = lend_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = lend_offer.pending_withdraw_amount > 0 @ LendOfferError::InvalidLendAmount,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LEND_OFFER_ACCOUNT_SEED.as_ref(),
+      lender.key().as_ref(),
+      offer_id.as_bytes(),
+      crate::ID.key().as_ref(),
+    ],
+    bump = lend_offer.bump
+  )]
+  pub lend_offer: Account<'info, LendOfferAccount>,
+  #[account(
+    mut,
+    constraint = system.key() == Pubkey::from_str(OPERATE_SYSTEM_PUBKEY).unwrap() @ SettingAccountError::InvalidOwner
+  )]
+  pub system: Signer<'info>,
+  #[account(
+    mut,
+    associated_token::mint = mint_asset,
+    associated_token::authority = system
+  )]
+  pub system_ata: Account<'info, TokenAccount>,
+  pub token_program: Program<'info, Token>
+}
+
+impl<'info> SystemWithdrawLendOffer<'info> {
+  pub fn system_withdraw_lend_offer(&mut self) -> Result<()> {
+    transition(self.lend_offer.status, LendOfferEvent::ConfirmWithdraw)?;
+
+    let withdrawn_amount = self.lend_offer.pending_withdraw_amount;
+
+    if withdrawn_amount > self.system_ata.amount {
+      return err!(LendOfferError::NotEnoughAmount);
+    }
+
+    self.transfer_back_lend_asset(withdrawn_amount)?;
+
+    self.lend_offer.pending_withdraw_amount = 0;
+
+    self.emit_event_withdraw_lend_offer(withdrawn_amount)?;
+
+    Ok(())
+  }
+
+  fn transfer_back_lend_asset(&mut self, withdrawn_amount: u64) -> Result<()> {
+    let ctx = CpiContext::new(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.system_ata.to_account_info(),
+        mint: self.mint_asset.to_account_info(),
+        to: self.lender_ata_asset.to_account_info(),
+        authority: self.system.to_account_info(),
+      }
+    );
+
+    transfer_checked(
+        ctx,
+        withdrawn_amount,
+        self.mint_asset.decimals,
+    )
+  }
+
+  fn emit_event_withdraw_lend_offer(&mut self, withdrawn_amount: u64) -> Result<()> {
+    emit!(LendOfferWithdrawn {
+      header: EventHeader::new(self.lend_offer.key(), self.lend_offer.offer_id.clone(), self.system.key())?
+        .with_status(Some(self.lend_offer.status), Some(self.lend_offer.status)),
+      lend_mint_token: self.lend_offer.lend_mint_token,
+      amount: self.lend_offer.amount,
+      withdrawn_amount,
+    });
+
+    Ok(())
+  }
+}
@@ -4,9 +4,11 @@ use wormhole_anchor_sdk::wormhole::{self, program::Wormhole};
 
 use crate::{Asset, ASSET_SEED};
 use crate::{
-  common::ENSO_SEED, ForeignChain, LoanOfferAccount, LoanOfferError, LoanOfferStatus, UpdateDepositCollateralCrossChainEvent, WormholeMessage, LOAN_OFFER_ACCOUNT_SEED, UPDATE_DEPOSIT_COLLATERAL_CROSS_CHAIN_FUNCTION
+  common::ENSO_SEED, ForeignChain, LoanOfferAccount, LoanOfferError, CrossChainCollateralDeposited, EventHeader, WormholeMessage, LOAN_OFFER_ACCOUNT_SEED, UPDATE_DEPOSIT_COLLATERAL_CROSS_CHAIN_FUNCTION
 };
 use crate::utils::vaa;
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(
@@ -25,6 +27,8 @@ pub struct UpdateDepositCollateralCrossChain<'info> {
   pub collateral_mint_asset: Box<Account<'info, Mint>>,
 
   #[account(
+    mut,
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = collateral_asset.is_collateral == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -38,8 +42,7 @@ pub struct UpdateDepositCollateralCrossChain<'info> {
 
   #[account(
     mut,
-    constraint = loan_offer.status == LoanOfferStatus::Matched || loan_offer.status == LoanOfferStatus::FundTransferred 
-    @ LoanOfferError::CanNotDepositCollateralToContractThatNotAvailable,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -79,6 +82,8 @@ impl<'info>UpdateDepositCollateralCrossChain<'info> {
     loan_offer_id: String, 
     _vaa_hash: [u8; 32],
   ) -> Result<()> {
+    transition(self.loan_offer.status, LoanOfferEvent::SyncCrossChainCollateral)?;
+
     self.validate_posted_vaa()?;
 
     let posted_vaa = &self.posted.clone().into_inner();
@@ -100,6 +105,11 @@ impl<'info>UpdateDepositCollateralCrossChain<'info> {
       collateral_address.clone()
     )?; 
 
+    if collateral_amount > self.loan_offer.collateral_amount {
+      self.collateral_asset.add_collateral(collateral_amount - self.loan_offer.collateral_amount)?;
+    } else {
+      self.collateral_asset.remove_collateral(self.loan_offer.collateral_amount - collateral_amount);
+    }
     self.loan_offer.collateral_amount = collateral_amount;
 
     self.emit_event_update_deposit_collateral_cross_chain(
@@ -168,13 +178,14 @@ impl<'info>UpdateDepositCollateralCrossChain<'info> {
     collateral_amount: u64,
     collateral_address: String
   ) -> Result<()> {
-    emit!(UpdateDepositCollateralCrossChainEvent {
+    emit!(CrossChainCollateralDeposited {
+      header: EventHeader::new(self.loan_offer.key(), loan_offer_id, self.signer.key())?
+        .with_status(Some(self.loan_offer.status), Some(self.loan_offer.status)),
+      borrower: self.borrower.key(),
       target_chain,
       chain_address,
-      loan_offer_id,
-      collateral_amount,
       collateral_address,
-      borrower: self.borrower.key(),
+      collateral_amount,
     });
 
     Ok(())
@@ -5,9 +5,11 @@ use wormhole_anchor_sdk::wormhole::{self, program::Wormhole};
 
 use crate::{Asset, ASSET_SEED};
 use crate::{
-  common::ENSO_SEED, ForeignChain, LoanOfferAccount, LoanOfferError, LoanOfferStatus, UpdateWithdrawCollateralCrossChainEvent, WormholeMessage, LOAN_OFFER_ACCOUNT_SEED, UPDATE_WITHDRAW_COLLATERAL_CROSS_CHAIN_FUNCTION
+  common::ENSO_SEED, ForeignChain, LoanOfferAccount, LoanOfferError, CrossChainCollateralWithdrawn, EventHeader, WormholeMessage, LOAN_OFFER_ACCOUNT_SEED, UPDATE_WITHDRAW_COLLATERAL_CROSS_CHAIN_FUNCTION
 };
 use crate::utils::vaa;
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(
@@ -26,6 +28,8 @@ pub struct UpdateWithdrawCollateralCrossChain<'info> {
   pub collateral_mint_asset: Box<Account<'info, Mint>>,
 
   #[account(
+    mut,
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     constraint = collateral_asset.is_collateral == true @ LoanOfferError::InvalidAssetAccount,
     seeds = [
       ENSO_SEED.as_ref(),
@@ -39,8 +43,7 @@ pub struct UpdateWithdrawCollateralCrossChain<'info> {
 
   #[account(
     mut,
-    constraint = loan_offer.status == LoanOfferStatus::Matched || loan_offer.status == LoanOfferStatus::FundTransferred 
-    @ LoanOfferError::InvalidLoanOffer,
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
     seeds = [
       ENSO_SEED.as_ref(),
       LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -80,6 +83,8 @@ impl<'info>UpdateWithdrawCollateralCrossChain<'info> {
     loan_offer_id: String, 
     _vaa_hash: [u8; 32],
   ) -> Result<()> {
+    transition(self.loan_offer.status, LoanOfferEvent::SyncCrossChainCollateral)?;
+
     self.validate_posted_vaa()?;
 
     let posted_vaa = &self.posted.clone().into_inner();
@@ -104,6 +109,7 @@ impl<'info>UpdateWithdrawCollateralCrossChain<'info> {
       collateral_address.clone()
     )?; 
 
+    self.collateral_asset.remove_collateral(withdraw_amount);
     self.loan_offer.collateral_amount = remaining_collateral_amount;
 
     self.emit_event_update_withdraw_collateral_cross_chain(
@@ -180,14 +186,15 @@ impl<'info>UpdateWithdrawCollateralCrossChain<'info> {
     remaining_collateral_amount: u64,
     collateral_address: String
   ) -> Result<()> {
-    emit!(UpdateWithdrawCollateralCrossChainEvent {
+    emit!(CrossChainCollateralWithdrawn {
+      header: EventHeader::new(self.loan_offer.key(), loan_offer_id, self.signer.key())?
+        .with_status(Some(self.loan_offer.status), Some(self.loan_offer.status)),
+      borrower: self.borrower.key(),
       target_chain,
       chain_address,
-      loan_offer_id,
+      collateral_address,
       withdraw_amount,
       remaining_collateral_amount,
-      collateral_address,
-      borrower: self.borrower.key(),
     });
 
     Ok(())
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{
+  common::{ASSET_SEED, ENSO_SEED},
+  convert_to_usd_price,
+  risk::{self, PricedLoanParams},
+  utils::amount::{self, TotalRepayLoanAmountParams},
+  Asset, LiquidationPriceView, LoanHealthView, LoanOfferAccount, LoanOfferError, 
+  MaxWithdrawableCollateralView, RepayLoanQuote, MIN_BORROW_HEALTH_RATIO
+};
+use crate::{LoanOfferEvent, Status};
+use crate::AccountVersionError;
+
+#[derive(Accounts)]
+pub struct ViewLoanOffer<'info> {
+  #[account(
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+  )]
+  pub loan_offer: Box<Account<'info, LoanOfferAccount>>,
+  #[account(
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      loan_offer.lend_mint_token.as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = lend_asset.bump
+  )]
+  pub lend_asset: Box<Account<'info, Asset>>,
+  #[account(
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      loan_offer.collateral_mint_token.as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = collateral_asset.bump
+  )]
+  pub collateral_asset: Box<Account<'info, Asset>>,
+  #[account(
+    constraint = lend_price_feed_account.key() == lend_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccountForLendAsset,
+  )]
+  pub lend_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+  #[account(
+    constraint = collateral_price_feed_account.key() == collateral_asset.price_feed_account @ LoanOfferError::InvalidPriceFeedAccountForCollateralAsset,
+  )]
+  pub collateral_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+}
+
+impl<'info> ViewLoanOffer<'info> {
+  pub fn view_loan_health(&self) -> Result<LoanHealthView> {
+    let priced_loan = self.get_priced_loan()?;
+
+    Ok(LoanHealthView {
+      health_ratio: risk::get_health_ratio(&priced_loan),
+      collateral_usd: priced_loan.collateral_usd(),
+      lend_usd: priced_loan.lend_usd(),
+      collateral_price: priced_loan.collateral_price,
+      lend_price: priced_loan.lend_price,
+    })
+  }
+
+  pub fn quote_repay_loan_offer(&self) -> Result<RepayLoanQuote> {
+    let total_repay_amount = amount::get_total_repay_loan_amount(TotalRepayLoanAmountParams {
+      borrow_amount: self.loan_offer.borrow_amount,
+      borrower_fee_percent: self.loan_offer.borrower_fee_percent,
+      duration: self.loan_offer.duration,
+      interest: self.loan_offer.interest,
+    });
+
+    Ok(RepayLoanQuote {
+      borrow_amount: self.loan_offer.borrow_amount,
+      interest_amount: total_repay_amount.saturating_sub(self.loan_offer.borrow_amount),
+      total_repay_amount,
+      due_at: self.loan_offer.started_at + self.loan_offer.duration as i64,
+    })
+  }
+
+  pub fn view_max_withdrawable_collateral(&self) -> Result<MaxWithdrawableCollateralView> {
+    let collateral_amount = self.loan_offer.collateral_amount;
+
+    // Same rule as withdraw_collateral_loan_offer: only funded loans can withdraw
+    if self.loan_offer.status.next(LoanOfferEvent::WithdrawCollateral).is_none() {
+      return Ok(MaxWithdrawableCollateralView {
+        collateral_amount,
+        max_withdrawable_amount: 0,
+      });
+    }
+
+    let priced_loan = self.get_priced_loan()?;
+
+    Ok(MaxWithdrawableCollateralView {
+      collateral_amount,
+      max_withdrawable_amount: risk::get_max_withdrawable_collateral(&priced_loan, MIN_BORROW_HEALTH_RATIO),
+    })
+  }
+
+  pub fn view_liquidation_price(&self) -> Result<LiquidationPriceView> {
+    let priced_loan = self.get_priced_loan()?;
+
+    Ok(LiquidationPriceView {
+      collateral_price: priced_loan.collateral_price,
+      liquidation_price: risk::get_liquidation_price(&priced_loan, MIN_BORROW_HEALTH_RATIO),
+      health_ratio_threshold: MIN_BORROW_HEALTH_RATIO,
+    })
+  }
+
+  fn get_priced_loan(&self) -> Result<PricedLoanParams> {
+    let (_, collateral_price) = convert_to_usd_price(
+      &self.collateral_price_feed_account,
+      &self.collateral_asset.price_feed_id,
+      0f64,
+      self.collateral_asset.max_price_age_seconds,
+    )?;
+
+    let (_, lend_price) = convert_to_usd_price(
+      &self.lend_price_feed_account,
+      &self.lend_asset.price_feed_id,
+      0f64,
+      self.lend_asset.max_price_age_seconds,
+    )?;
+
+    Ok(PricedLoanParams {
+      collateral_amount: self.loan_offer.collateral_amount,
+      collateral_decimals: self.collateral_asset.decimals,
+      collateral_price,
+      lend_amount: self.loan_offer.borrow_amount,
+      lend_decimals: self.lend_asset.decimals,
+      lend_price,
+    })
+  }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{
+  common::{ASSET_SEED, ENSO_SEED, LOAN_POSITION_LISTING_SEED},
+  convert_to_usd_price,
+  risk::{self, PricedLoanParams},
+  utils::amount::{self, AccruedLenderInterestParams},
+  Asset, LoanOfferAccount, LoanPositionListing, LoanPositionListingError, LoanPositionListingView
+};
+use crate::AccountVersionError;
+
+#[derive(Accounts)]
+pub struct ViewLoanPositionListing<'info> {
+  #[account(
+    seeds = [
+      ENSO_SEED.as_ref(),
+      LOAN_POSITION_LISTING_SEED.as_ref(),
+      listing.position_mint.as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = listing.bump
+  )]
+  pub listing: Box<Account<'info, LoanPositionListing>>,
+  #[account(
+    constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    constraint = loan_offer.key() == listing.loan_offer @ LoanPositionListingError::InvalidLoanOffer,
+  )]
+  pub loan_offer: Box<Account<'info, LoanOfferAccount>>,
+  #[account(
+    constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      loan_offer.lend_mint_token.as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = lend_asset.bump
+  )]
+  pub lend_asset: Box<Account<'info, Asset>>,
+  #[account(
+    constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      ASSET_SEED.as_ref(),
+      loan_offer.collateral_mint_token.as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = collateral_asset.bump
+  )]
+  pub collateral_asset: Box<Account<'info, Asset>>,
+  #[account(
+    constraint = lend_price_feed_account.key() == lend_asset.price_feed_account @ LoanPositionListingError::InvalidPriceFeedAccount,
+  )]
+  pub lend_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+  #[account(
+    constraint = collateral_price_feed_account.key() == collateral_asset.price_feed_account @ LoanPositionListingError::InvalidPriceFeedAccount,
+  )]
+  pub collateral_price_feed_account: Box<Account<'info, PriceUpdateV2>>,
+}
+
+impl<'info> ViewLoanPositionListing<'info> {
+  pub fn view_loan_position_listing(&self) -> Result<LoanPositionListingView> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    let accrued_interest = amount::get_accrued_lender_interest(AccruedLenderInterestParams {
+      lender_fee_percent: self.loan_offer.lender_fee_percent,
+      interest: self.loan_offer.interest,
+      borrow_amount: self.loan_offer.borrow_amount,
+      started_at: self.loan_offer.started_at,
+      duration: self.loan_offer.duration,
+      current_timestamp,
+    });
+
+    let (_, collateral_price) = convert_to_usd_price(
+      &self.collateral_price_feed_account,
+      &self.collateral_asset.price_feed_id,
+      0f64,
+      self.collateral_asset.max_price_age_seconds,
+    )?;
+
+    let (_, lend_price) = convert_to_usd_price(
+      &self.lend_price_feed_account,
+      &self.lend_asset.price_feed_id,
+      0f64,
+      self.lend_asset.max_price_age_seconds,
+    )?;
+
+    let health_ratio = risk::get_health_ratio(&PricedLoanParams {
+      collateral_amount: self.loan_offer.collateral_amount,
+      collateral_decimals: self.collateral_asset.decimals,
+      collateral_price,
+      lend_amount: self.loan_offer.borrow_amount,
+      lend_decimals: self.lend_asset.decimals,
+      lend_price,
+    });
+
+    let maturity = self.loan_offer.started_at + self.loan_offer.duration as i64;
+
+    Ok(LoanPositionListingView {
+      seller: self.listing.seller,
+      loan_offer: self.listing.loan_offer,
+      price: self.listing.price,
+      borrow_amount: self.loan_offer.borrow_amount,
+      accrued_interest,
+      health_ratio,
+      time_to_maturity: (maturity - current_timestamp).max(0),
+    })
+  }
+}
@@ -7,8 +7,10 @@ use crate::{
     ASSET_SEED, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED
   }, health_ratio::{self, HealthRatioParams}, states::{
     Asset, LoanOfferAccount
-  }, LoanOfferError, LoanOfferStatus, VaultAuthority, WithdrawCollateralEvent, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED
+  }, LoanOfferError, VaultAuthority, CollateralWithdrawn, EventHeader, VAULT_AUTHORITY_LOAN_OFFER_ACCOUNT_SEED
 };
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(loan_offer_id: String)]
@@ -24,6 +26,7 @@ pub struct WithdrawCollateralLoanOffer<'info> {
     )]
     pub lend_mint_asset: Account<'info, Mint>,
     #[account(
+      constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
       constraint = lend_asset.is_lend == true @ LoanOfferError::InvalidAssetAccount,
       seeds = [
         ENSO_SEED.as_ref(),
@@ -35,6 +38,8 @@ pub struct WithdrawCollateralLoanOffer<'info> {
     )]
     pub lend_asset: Account<'info, Asset>,
     #[account(
+      mut,
+      constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
       constraint = collateral_asset.is_collateral == true @ LoanOfferError::InvalidAssetAccount,
       seeds = [
         ENSO_SEED.as_ref(),
@@ -53,7 +58,7 @@ pub struct WithdrawCollateralLoanOffer<'info> {
     pub borrower_ata_collateral_asset: Box<Account<'info, TokenAccount>>,
     #[account(
       mut,
-      constraint = loan_offer.status == LoanOfferStatus::FundTransferred @ LoanOfferError::NotAvailableToWithdraw,
+      constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
       seeds = [
         ENSO_SEED.as_ref(),
         LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -100,6 +105,7 @@ impl<'info> WithdrawCollateralLoanOffer<'info> {
     self.transfer_collateral_to_borrower(withdraw_amount)?;
 
     self.loan_offer.collateral_amount = self.loan_offer.collateral_amount - withdraw_amount;
+    self.collateral_asset.remove_collateral(withdraw_amount);
 
     self.emit_event_withdraw_collateral(
       loan_offer_id,
@@ -110,6 +116,8 @@ impl<'info> WithdrawCollateralLoanOffer<'info> {
   }
 
   fn validate_withdraw_collateral(&self, withdraw_amount: u64) -> Result<()> {
+    transition(self.loan_offer.status, LoanOfferEvent::WithdrawCollateral)?;
+
     if withdraw_amount > self.loan_offer.collateral_amount {
       return err!(LoanOfferError::NotEnoughCollateral);
     }
@@ -170,11 +178,13 @@ impl<'info> WithdrawCollateralLoanOffer<'info> {
   }
 
   fn emit_event_withdraw_collateral(&mut self, loan_offer_id: String, withdraw_amount: u64) -> Result<()> {
-    emit!(WithdrawCollateralEvent {
+    emit!(CollateralWithdrawn {
+      header: EventHeader::new(self.loan_offer.key(), loan_offer_id, self.borrower.key())?
+        .with_status(Some(self.loan_offer.status), Some(self.loan_offer.status)),
       borrower: self.borrower.key(),
-      loan_offer_id,
-      collateral_amount: self.loan_offer.collateral_amount,
+      collateral_mint_token: self.loan_offer.collateral_mint_token,
       withdraw_amount,
+      collateral_amount: self.loan_offer.collateral_amount,
     });
 
     Ok(())
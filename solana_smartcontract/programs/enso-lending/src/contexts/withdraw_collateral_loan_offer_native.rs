@@ -4,9 +4,11 @@ use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 use crate::{
   common::{
-    WithdrawCollateralEvent, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED,
-  }, health_ratio::{self, HealthRatioParams}, states::loan_offer::LoanOfferAccount, Asset, LoanOfferError, LoanOfferStatus, ASSET_SEED
+    CollateralWithdrawn, EventHeader, ENSO_SEED, LOAN_OFFER_ACCOUNT_SEED,
+  }, health_ratio::{self, HealthRatioParams}, states::loan_offer::LoanOfferAccount, Asset, LoanOfferError, ASSET_SEED
 };
+use crate::AccountVersionError;
+use crate::{transition, LoanOfferEvent};
 
 #[derive(Accounts)]
 #[instruction(loan_offer_id: String)]
@@ -22,6 +24,7 @@ pub struct WithdrawCollateralLoanOfferNative<'info> {
     )]
     pub lend_mint_asset: Account<'info, Mint>,
     #[account(
+      constraint = lend_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
       constraint = lend_asset.is_lend == true @ LoanOfferError::InvalidAssetAccount,
       seeds = [
         ENSO_SEED.as_ref(),
@@ -33,6 +36,8 @@ pub struct WithdrawCollateralLoanOfferNative<'info> {
     )]
     pub lend_asset: Account<'info, Asset>,
     #[account(
+      mut,
+      constraint = collateral_asset.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
       constraint = collateral_asset.is_collateral == true @ LoanOfferError::InvalidAssetAccount,
       seeds = [
         ENSO_SEED.as_ref(),
@@ -45,7 +50,7 @@ pub struct WithdrawCollateralLoanOfferNative<'info> {
     pub collateral_asset: Account<'info, Asset>,
     #[account(
       mut,
-      constraint = loan_offer.status == LoanOfferStatus::FundTransferred @ LoanOfferError::NotAvailableToWithdraw,
+      constraint = loan_offer.is_current_version() @ AccountVersionError::OutdatedAccountVersion,
       seeds = [
         ENSO_SEED.as_ref(),
         LOAN_OFFER_ACCOUNT_SEED.as_ref(),
@@ -74,6 +79,7 @@ impl<'info> WithdrawCollateralLoanOfferNative<'info> {
     self.borrower.add_lamports(withdraw_amount)?;
 
     self.loan_offer.collateral_amount = self.loan_offer.collateral_amount - withdraw_amount;
+    self.collateral_asset.remove_collateral(withdraw_amount);
 
     self.emit_event_withdraw_collateral(
       loan_offer_id,
@@ -84,6 +90,8 @@ impl<'info> WithdrawCollateralLoanOfferNative<'info> {
   }
 
   fn validate_withdraw_collateral(&self, withdraw_amount: u64) -> Result<()> {
+    transition(self.loan_offer.status, LoanOfferEvent::WithdrawCollateral)?;
+
     if withdraw_amount > self.loan_offer.collateral_amount {
       return err!(LoanOfferError::NotEnoughCollateral);
     }
@@ -114,11 +122,13 @@ impl<'info> WithdrawCollateralLoanOfferNative<'info> {
   }
 
   fn emit_event_withdraw_collateral(&mut self, loan_offer_id: String, withdraw_amount: u64) -> Result<()> {
-    emit!(WithdrawCollateralEvent {
+    emit!(CollateralWithdrawn {
+      header: EventHeader::new(self.loan_offer.key(), loan_offer_id, self.borrower.key())?
+        .with_status(Some(self.loan_offer.status), Some(self.loan_offer.status)),
       borrower: self.borrower.key(),
-      loan_offer_id,
-      collateral_amount: self.loan_offer.collateral_amount,
+      collateral_mint_token: self.loan_offer.collateral_mint_token,
       withdraw_amount,
+      collateral_amount: self.loan_offer.collateral_amount,
     });
 
     Ok(())
@@ -0,0 +1,98 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{
+  EventHeader,
+  SettingAccountError,
+  Treasury,
+  TreasuryError,
+  TreasuryWithdrawn,
+  ENSO_SEED,
+  OPERATE_SYSTEM_PUBKEY,
+  TREASURY_SEED
+};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+  #[account(
+    constraint = owner.key() == Pubkey::from_str(OPERATE_SYSTEM_PUBKEY).unwrap() @ SettingAccountError::InvalidOwner
+  )]
+  pub owner: Signer<'info>,
+  pub token_mint: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    seeds = [
+      ENSO_SEED.as_ref(),
+      TREASURY_SEED.as_ref(),
+      token_mint.key().as_ref(),
+      crate::ID.key().as_ref()
+    ],
+    bump = treasury.bump
+  )]
+  pub treasury: Box<Account<'info, Treasury>>,
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = treasury
+  )]
+  pub treasury_ata: Box<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    token::mint = token_mint
+  )]
+  pub receiver_ata: Box<Account<'info, TokenAccount>>,
+  pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawTreasury<'info> {
+  pub fn withdraw_treasury(&mut self, amount: u64) -> Result<()> {
+    if amount == 0 {
+      return err!(TreasuryError::InvalidAmount);
+    }
+
+    if amount > self.treasury_ata.amount {
+      return err!(TreasuryError::NotEnoughAmount);
+    }
+
+    self.transfer_to_receiver(amount)?;
+    self.treasury.add_withdrawn(amount);
+
+    emit!(TreasuryWithdrawn {
+      header: EventHeader::new(self.treasury.key(), String::new(), self.owner.key())?,
+      token_mint: self.token_mint.key(),
+      receiver_ata: self.receiver_ata.key(),
+      amount,
+      remaining_amount: self.treasury_ata.amount - amount,
+    });
+
+    Ok(())
+  }
+
+  fn transfer_to_receiver(&self, amount: u64) -> Result<()> {
+    let token_mint = self.token_mint.key();
+    let program_id = crate::ID.key();
+
+    let signer: &[&[&[u8]]] = &[&[
+      ENSO_SEED.as_ref(),
+      TREASURY_SEED.as_ref(),
+      token_mint.as_ref(),
+      program_id.as_ref(),
+      &[self.treasury.bump]
+    ]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.treasury_ata.to_account_info(),
+        mint: self.token_mint.to_account_info(),
+        to: self.receiver_ata.to_account_info(),
+        authority: self.treasury.to_account_info(),
+      },
+      signer
+    );
+
+    transfer_checked(cpi_ctx, amount, self.token_mint.decimals)
+  }
+}
@@ -4,12 +4,13 @@ mod program_id;
 use program_id::PROGRAM_ID;
 mod contexts;
 use contexts::*;
-mod states;
+pub mod states;
 use states::*;
-mod common;
+pub mod common;
 use common::*;
 mod utils;
 use utils::*;
+pub use utils::{amount, risk};
 
 declare_id!(PROGRAM_ID);
 
@@ -24,6 +25,13 @@ pub mod enso_lending {
         duration: u64,
         lender_fee_percent: f64,
         borrower_fee_percent: f64,
+        lend_mint_asset: Pubkey,
+        collateral_mint_assets: Vec<Pubkey>,
+        min_amount: u64,
+        max_amount: u64,
+        amount_step: u64,
+        min_duration: u64,
+        max_duration: u64,
     ) -> Result<()> {
         ctx.accounts.init_setting_account(
             &ctx.bumps,
@@ -32,6 +40,13 @@ pub mod enso_lending {
             duration,
             lender_fee_percent,
             borrower_fee_percent,
+            lend_mint_asset,
+            collateral_mint_assets,
+            min_amount,
+            max_amount,
+            amount_step,
+            min_duration,
+            max_duration,
         )?;
 
         Ok(())
@@ -44,12 +59,26 @@ pub mod enso_lending {
         duration: Option<u64>,
         lender_fee_percent: Option<f64>,
         borrower_fee_percent: Option<f64>,
+        lend_mint_asset: Option<Pubkey>,
+        collateral_mint_assets: Option<Vec<Pubkey>>,
+        min_amount: Option<u64>,
+        max_amount: Option<u64>,
+        amount_step: Option<u64>,
+        min_duration: Option<u64>,
+        max_duration: Option<u64>,
     ) -> Result<()> {
         ctx.accounts.edit_setting_account(
             amount,
             duration,
             lender_fee_percent,
             borrower_fee_percent,
+            lend_mint_asset,
+            collateral_mint_assets,
+            min_amount,
+            max_amount,
+            amount_step,
+            min_duration,
+            max_duration,
         )?;
 
         Ok(())
@@ -69,7 +98,12 @@ pub mod enso_lending {
         price_feed_id: String,
         max_price_age_seconds: u64,
         token_address: Option<String>,
-        chain_id: u16
+        chain_id: u16,
+        max_collateral_amount: u64,
+        max_borrow_amount: u64,
+        isolated: bool,
+        isolated_lend_mints: Vec<Pubkey>,
+        isolated_debt_ceiling: u64
     ) -> Result<()> {
         ctx.accounts.init_asset(
             name, 
@@ -79,6 +113,11 @@ pub mod enso_lending {
             max_price_age_seconds, 
             token_address,
             chain_id,
+            max_collateral_amount,
+            max_borrow_amount,
+            isolated,
+            isolated_lend_mints,
+            isolated_debt_ceiling,
             &ctx.bumps
         )?;
 
@@ -92,7 +131,12 @@ pub mod enso_lending {
         is_collateral: Option<bool>,
         price_feed_id: Option<String>,
         max_price_age_seconds: Option<u64>,
-        token_address: Option<String>
+        token_address: Option<String>,
+        max_collateral_amount: Option<u64>,
+        max_borrow_amount: Option<u64>,
+        isolated: Option<bool>,
+        isolated_lend_mints: Option<Vec<Pubkey>>,
+        isolated_debt_ceiling: Option<u64>
     ) -> Result<()> {
         ctx.accounts.edit_asset(
             name, 
@@ -100,7 +144,12 @@ pub mod enso_lending {
             is_collateral, 
             price_feed_id, 
             max_price_age_seconds,
-            token_address
+            token_address,
+            max_collateral_amount,
+            max_borrow_amount,
+            isolated,
+            isolated_lend_mints,
+            isolated_debt_ceiling
         )?;
 
         Ok(())
@@ -112,13 +161,29 @@ pub mod enso_lending {
         Ok(())
     }
 
+    pub fn init_treasury(ctx: Context<InitTreasury>) -> Result<()> {
+        ctx.accounts.init_treasury(&ctx.bumps)?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_treasury(amount)?;
+
+        Ok(())
+    }
+
     pub fn create_lend_offer(
         ctx: Context<CreateLendOffer>,
         offer_id: String,
         _tier_id: String,
         interest: f64,
+        amount: u64,
+        duration: u64,
+        expires_at: Option<i64>,
+        crank_tip: u64,
     ) -> Result<()> {
-        ctx.accounts.create_lend_offer(&ctx.bumps, offer_id, interest)?;
+        ctx.accounts.create_lend_offer(&ctx.bumps, offer_id, interest, amount, duration, expires_at, crank_tip)?;
 
         Ok(())
     }
@@ -126,9 +191,12 @@ pub mod enso_lending {
     pub fn edit_lend_offer(
         ctx: Context<EditLendOffer>,
         _offer_id: String,
-        interest: f64,
+        _tier_id: String,
+        interest: Option<f64>,
+        amount: Option<u64>,
+        duration: Option<u64>,
     ) -> Result<()> {
-        ctx.accounts.edit_lend_offer(interest)?;
+        ctx.accounts.edit_lend_offer(interest, amount, duration)?;
 
         Ok(())
     }
@@ -144,26 +212,38 @@ pub mod enso_lending {
         Ok(())
     }
 
+    pub fn system_withdraw_lend_offer(ctx: Context<SystemWithdrawLendOffer>, _offer_id: String) -> Result<()> {
+        ctx.accounts.system_withdraw_lend_offer()?;
+
+        Ok(())
+    }
+
     pub fn cancel_lend_offer(ctx: Context<CancelLendOffer>, _offer_id: String) -> Result<()> {
         ctx.accounts.cancel_lend_offer()?;
 
         Ok(())
     }
 
+    pub fn reclaim_expired_lend_offer(ctx: Context<ReclaimExpiredLendOffer>, _offer_id: String) -> Result<()> {
+        ctx.accounts.reclaim_expired_lend_offer()?;
+
+        Ok(())
+    }
+
     pub fn create_loan_offer(
         ctx: Context<CreateLoanOffer>,
         offer_id: String,
         lend_offer_id: String,
         tier_id: String,
         collateral_amount: u64,
-        interest: f64
+        expected_terms: ExpectedLoanTerms
     ) -> Result<()> {
         ctx.accounts.create_loan_offer(
             offer_id,
             lend_offer_id,
             tier_id,
             collateral_amount,
-            interest,
+            expected_terms,
             &ctx.bumps,
         )?;
         Ok(())
@@ -175,7 +255,7 @@ pub mod enso_lending {
         lend_offer_id: String,
         tier_id: String,
         collateral_amount: u64,
-        interest: f64,
+        expected_terms: ExpectedLoanTerms,
     ) -> Result<()> {
         ctx.accounts.create_loan_offer_native(
             &ctx.bumps,
@@ -183,7 +263,7 @@ pub mod enso_lending {
             lend_offer_id,
             tier_id,
             collateral_amount,
-            interest
+            expected_terms
         )?;
 
         Ok(())
@@ -234,6 +314,21 @@ pub mod enso_lending {
         Ok(())
     }
 
+    pub fn repay_loan_offer_native_to_lender(
+        ctx: Context<RepayLoanOfferNativeToLender>,
+        _loan_offer_id: String,
+    ) -> Result<()> {
+        ctx.accounts.repay_loan_offer_native_to_lender()?;
+
+        Ok(())
+    }
+
+    pub fn repay_loan_offer_to_lender(ctx: Context<RepayLoanOfferToLender>, _loan_offer_id: String) -> Result<()> {
+        ctx.accounts.repay_loan_offer_to_lender()?;
+
+        Ok(())
+    }
+
     pub fn withdraw_collateral_loan_offer_native(
         ctx: Context<WithdrawCollateralLoanOfferNative>,
         loan_offer_id: String,
@@ -291,6 +386,15 @@ pub mod enso_lending {
         Ok(())
     }
 
+    pub fn liquidate_loan_offer_native(
+        ctx: Context<LiquidateLoanOfferNative>,
+        _offer_id: String,
+    ) -> Result<()> {
+        ctx.accounts.liquidate_loan_offer_native()?;
+
+        Ok(())
+    }
+
     pub fn finish_liquidate_contract(
         ctx: Context<SystemLiquidateLoanOffer>,
         _loan_offer_id: String,
@@ -464,4 +568,84 @@ pub mod enso_lending {
 
         Ok(())
     }
+
+    pub fn list_loan_position(ctx: Context<ListLoanPosition>, price: u64) -> Result<()> {
+        ctx.accounts.list_loan_position(&ctx.bumps, price)?;
+
+        Ok(())
+    }
+
+    pub fn cancel_loan_position_listing(ctx: Context<CancelLoanPositionListing>) -> Result<()> {
+        ctx.accounts.cancel_loan_position_listing()?;
+
+        Ok(())
+    }
+
+    pub fn buy_loan_position(ctx: Context<BuyLoanPosition>, expected_price: u64) -> Result<()> {
+        ctx.accounts.buy_loan_position(expected_price)?;
+
+        Ok(())
+    }
+
+    pub fn view_loan_position_listing(ctx: Context<ViewLoanPositionListing>) -> Result<LoanPositionListingView> {
+        ctx.accounts.view_loan_position_listing()
+    }
+
+    pub fn swap_collateral(
+        ctx: Context<SwapCollateral>,
+        loan_offer_id: String,
+        new_collateral_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.swap_collateral(loan_offer_id, new_collateral_amount)?;
+
+        Ok(())
+    }
+
+    pub fn swap_collateral_cross_chain(
+        ctx: Context<SwapCollateralCrossChain>,
+        loan_offer_id: String,
+        vaa_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.swap_collateral_cross_chain(&ctx.bumps, loan_offer_id, vaa_hash)?;
+
+        Ok(())
+    }
+
+    pub fn close_loan_offer(ctx: Context<CloseLoanOffer>) -> Result<()> {
+        ctx.accounts.close_loan_offer()?;
+
+        Ok(())
+    }
+
+    pub fn batch_close_accounts<'info>(ctx: Context<'_, '_, 'info, 'info, BatchCloseAccounts<'info>>) -> Result<()> {
+        ctx.accounts.batch_close_accounts(ctx.remaining_accounts)?;
+
+        Ok(())
+    }
+
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        ctx.accounts.migrate_account()?;
+
+        Ok(())
+    }
+
+    pub fn view_loan_health(ctx: Context<ViewLoanOffer>) -> Result<LoanHealthView> {
+        ctx.accounts.view_loan_health()
+    }
+
+    pub fn quote_repay_loan_offer(ctx: Context<ViewLoanOffer>) -> Result<RepayLoanQuote> {
+        ctx.accounts.quote_repay_loan_offer()
+    }
+
+    pub fn view_max_withdrawable_collateral(ctx: Context<ViewLoanOffer>) -> Result<MaxWithdrawableCollateralView> {
+        ctx.accounts.view_max_withdrawable_collateral()
+    }
+
+    pub fn view_liquidation_price(ctx: Context<ViewLoanOffer>) -> Result<LiquidationPriceView> {
+        ctx.accounts.view_liquidation_price()
+    }
+
+    pub fn quote_max_borrow(ctx: Context<QuoteMaxBorrow>, collateral_amount: u64) -> Result<MaxBorrowQuote> {
+        ctx.accounts.quote_max_borrow(collateral_amount)
+    }
 }
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{LoanOfferError, ISOLATED_DEBT_DECIMALS, MAX_ISOLATED_LEND_MINTS};
+
 #[account]
 #[derive(InitSpace, Debug)]
 pub struct Asset {
@@ -17,4 +19,96 @@ pub struct Asset {
   #[max_len(100)]
   pub token_address: Option<String>,
   pub chain_id: u16,
-}
\ No newline at end of file
+  // Note: Aggregate exposure of the protocol to this asset, a cap of 0 means no cap
+  pub total_collateral_amount: u64,
+  pub max_collateral_amount: u64,
+  pub total_borrow_amount: u64,
+  pub max_borrow_amount: u64,
+  // Note: Isolation mode, collateral can only back the listed lend mints up to the debt ceiling
+  pub isolated: bool,
+  #[max_len(MAX_ISOLATED_LEND_MINTS)]
+  pub isolated_lend_mints: Vec<Pubkey>,
+  pub isolated_debt_ceiling: u64,
+  pub total_isolated_debt: u64,
+  pub version: u8,
+}
+
+impl Asset {
+  pub const VERSION: u8 = 1;
+
+  pub fn is_current_version(&self) -> bool {
+    self.version == Self::VERSION
+  }
+
+  pub fn add_collateral(&mut self, amount: u64) -> Result<()> {
+    let total_collateral_amount = self.total_collateral_amount
+      .checked_add(amount)
+      .ok_or(LoanOfferError::CollateralCapExceeded)?;
+
+    if self.max_collateral_amount > 0 && total_collateral_amount > self.max_collateral_amount {
+      return err!(LoanOfferError::CollateralCapExceeded);
+    }
+
+    self.total_collateral_amount = total_collateral_amount;
+
+    Ok(())
+  }
+
+  pub fn remove_collateral(&mut self, amount: u64) {
+    // Loans opened before the counters existed are not tracked, so never underflow
+    self.total_collateral_amount = self.total_collateral_amount.saturating_sub(amount);
+  }
+
+  pub fn add_borrow(&mut self, amount: u64) -> Result<()> {
+    let total_borrow_amount = self.total_borrow_amount
+      .checked_add(amount)
+      .ok_or(LoanOfferError::BorrowCapExceeded)?;
+
+    if self.max_borrow_amount > 0 && total_borrow_amount > self.max_borrow_amount {
+      return err!(LoanOfferError::BorrowCapExceeded);
+    }
+
+    self.total_borrow_amount = total_borrow_amount;
+
+    Ok(())
+  }
+
+  pub fn remove_borrow(&mut self, amount: u64) {
+    self.total_borrow_amount = self.total_borrow_amount.saturating_sub(amount);
+  }
+
+  pub fn add_isolated_debt(&mut self, lend_mint: Pubkey, borrow_amount: u64, lend_decimals: u8) -> Result<()> {
+    if !self.isolated {
+      return Ok(());
+    }
+
+    if !self.isolated_lend_mints.contains(&lend_mint) {
+      return err!(LoanOfferError::LendMintNotAllowedForIsolatedCollateral);
+    }
+
+    let total_isolated_debt = self.total_isolated_debt
+      .checked_add(Self::to_isolated_debt(borrow_amount, lend_decimals))
+      .ok_or(LoanOfferError::IsolatedDebtCeilingExceeded)?;
+
+    if total_isolated_debt > self.isolated_debt_ceiling {
+      return err!(LoanOfferError::IsolatedDebtCeilingExceeded);
+    }
+
+    self.total_isolated_debt = total_isolated_debt;
+
+    Ok(())
+  }
+
+  pub fn remove_isolated_debt(&mut self, borrow_amount: u64, lend_decimals: u8) {
+    self.total_isolated_debt = self.total_isolated_debt
+      .saturating_sub(Self::to_isolated_debt(borrow_amount, lend_decimals));
+  }
+
+  // Isolated lend mints are stablecoins, so debt of different mints is summed at a common precision
+  fn to_isolated_debt(borrow_amount: u64, lend_decimals: u8) -> u64 {
+    let amount = borrow_amount as u128 * 10u128.pow(ISOLATED_DEBT_DECIMALS as u32)
+      / 10u128.pow(lend_decimals as u32);
+
+    u64::try_from(amount).unwrap_or(u64::MAX)
+  }
+}
@@ -1,6 +1,6 @@
 pub use anchor_lang::prelude::*;
 
-use crate::LendOfferStatus;
+use crate::{LendOfferEvent, LendOfferStatus, LoanOfferAccount, Status};
 
 #[account]
 #[derive(InitSpace, Debug)]
@@ -14,5 +14,50 @@ pub struct LendOfferAccount {
   pub lend_mint_token: Pubkey,
   pub amount: u64,
   pub bump: u8,
-  pub status: LendOfferStatus
+  pub status: LendOfferStatus,
+  // Note: Offers with an expiry hold their principal in a vault owned by the lend offer account
+  pub expires_at: Option<i64>,
+  pub crank_tip: u64,
+  pub version: u8,
+  // Note: Tier the offer was created under, empty for offers migrated from version 1 or earlier
+  #[max_len(50)]
+  pub tier_id: String,
+  // Note: Principal the lender withdrew from an offer without expiry, the operator pays it back from the hot wallet
+  pub pending_withdraw_amount: u64,
+}
+
+impl LendOfferAccount {
+  pub const VERSION: u8 = 2;
+
+  pub fn is_current_version(&self) -> bool {
+    self.version == Self::VERSION
+  }
+
+  pub fn is_escrowed(&self) -> bool {
+    self.expires_at.is_some()
+  }
+
+  pub fn is_closable(&self, loan_offer: Option<&LoanOfferAccount>) -> bool {
+    if self.pending_withdraw_amount > 0 {
+      return false;
+    }
+
+    match self.status.next(LendOfferEvent::Close) {
+      Some(LendOfferStatus::Loaned) => match loan_offer {
+        Some(loan_offer) => loan_offer.lend_offer_id == self.offer_id
+          && loan_offer.lender == self.lender
+          && loan_offer.is_finalized(),
+        None => false,
+      },
+      Some(_) => true,
+      None => false,
+    }
+  }
+
+  pub fn is_expired(&self, current_timestamp: i64) -> bool {
+    match self.expires_at {
+      Some(expires_at) => current_timestamp >= expires_at,
+      None => false,
+    }
+  }
 }
@@ -1,6 +1,6 @@
 pub use anchor_lang::prelude::*;
 
-use crate::LoanOfferStatus;
+use crate::{LoanOfferEvent, LoanOfferStatus, Status, ENSO_SEED, LEND_OFFER_ACCOUNT_SEED};
 
 #[account]
 #[derive(InitSpace, Debug)]
@@ -30,4 +30,44 @@ pub struct LoanOfferAccount {
   pub liquidated_tx: Option<String>,
   pub liquidated_price: Option<u64>,
   pub bump: u8,
+  // Note: Holder of this mint's single token is the creditor of the loan, None for loans created before position tokens
+  pub position_mint: Option<Pubkey>,
+  pub rent_payer: Pubkey,
+  pub version: u8,
+}
+
+impl LoanOfferAccount {
+  pub const VERSION: u8 = 1;
+
+  pub fn is_current_version(&self) -> bool {
+    self.version == Self::VERSION
+  }
+
+  pub fn is_finalized(&self) -> bool {
+    self.status.next(LoanOfferEvent::Close).is_some()
+  }
+
+  // Note: The lend offer the loan was taken from, the loan can only be closed once it is closed, otherwise the
+  // lend offer could no longer prove its loan finished
+  pub fn lend_offer_address(&self) -> Pubkey {
+    Pubkey::find_program_address(
+      &[
+        ENSO_SEED.as_ref(),
+        LEND_OFFER_ACCOUNT_SEED.as_ref(),
+        self.lender.as_ref(),
+        self.lend_offer_id.as_bytes(),
+        crate::ID.key().as_ref(),
+      ],
+      &crate::ID,
+    ).0
+  }
+
+  // Note: Loans created before rent_payer was recorded were always paid for by the borrower
+  pub fn rent_receiver(&self) -> Pubkey {
+    if self.rent_payer == Pubkey::default() {
+      return self.borrower;
+    }
+
+    self.rent_payer
+  }
 }
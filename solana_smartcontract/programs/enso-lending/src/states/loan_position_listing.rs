@@ -0,0 +1,24 @@
+pub use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct LoanPositionListing {
+  pub seller: Pubkey,
+  pub loan_offer: Pubkey,
+  pub position_mint: Pubkey,
+  pub lend_mint_token: Pubkey,
+  pub price: u64,
+  pub listed_at: i64,
+  pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LoanPositionListingView {
+  pub seller: Pubkey,
+  pub loan_offer: Pubkey,
+  pub price: u64,
+  pub borrow_amount: u64,
+  pub accrued_interest: u64,
+  pub health_ratio: f64,
+  pub time_to_maturity: i64,
+}
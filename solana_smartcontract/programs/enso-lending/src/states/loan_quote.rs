@@ -0,0 +1,38 @@
+pub use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LoanHealthView {
+  pub health_ratio: f64,
+  pub collateral_usd: f64,
+  pub lend_usd: f64,
+  pub collateral_price: f64,
+  pub lend_price: f64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RepayLoanQuote {
+  pub borrow_amount: u64,
+  pub interest_amount: u64,
+  pub total_repay_amount: u64,
+  pub due_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MaxWithdrawableCollateralView {
+  pub collateral_amount: u64,
+  pub max_withdrawable_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MaxBorrowQuote {
+  pub collateral_amount: u64,
+  pub collateral_usd: f64,
+  pub max_borrow_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LiquidationPriceView {
+  pub collateral_price: f64,
+  pub liquidation_price: f64,
+  pub health_ratio_threshold: f64,
+}
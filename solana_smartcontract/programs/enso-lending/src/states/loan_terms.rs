@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{LendOfferAccount, LoanOfferError, SettingAccount};
+
+// Note: Terms the borrower was quoted, loan creation fails if the offer or tier moved against them since
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ExpectedLoanTerms {
+  pub amount: u64,
+  pub duration: u64,
+  pub max_interest: f64,
+  pub max_borrower_fee_percent: f64,
+  pub max_lender_fee_percent: f64,
+}
+
+impl ExpectedLoanTerms {
+  pub fn validate(&self, lend_offer: &LendOfferAccount, setting_account: &SettingAccount) -> Result<()> {
+    if lend_offer.amount != self.amount {
+      return err!(LoanOfferError::CanNotCreateLoanCauseLendAmountUpdated);
+    }
+
+    if lend_offer.duration != self.duration {
+      return err!(LoanOfferError::CanNotCreateLoanCauseLendDurationUpdated);
+    }
+
+    if lend_offer.interest > self.max_interest {
+      return err!(LoanOfferError::CanNotCreateLoanCauseLendInterestUpdated);
+    }
+
+    if setting_account.borrower_fee_percent > self.max_borrower_fee_percent {
+      return err!(LoanOfferError::CanNotCreateLoanCauseBorrowerFeeUpdated);
+    }
+
+    if lend_offer.lender_fee_percent > self.max_lender_fee_percent {
+      return err!(LoanOfferError::CanNotCreateLoanCauseLenderFeeUpdated);
+    }
+
+    Ok(())
+  }
+}
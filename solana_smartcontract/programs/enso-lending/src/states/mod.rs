@@ -23,4 +23,19 @@ pub mod wormhole_emitter;
 pub use wormhole_emitter::*;
 
 pub mod wormhole_config;
-pub use wormhole_config::*;
\ No newline at end of file
+pub use wormhole_config::*;
+
+pub mod loan_terms;
+pub use loan_terms::*;
+
+pub mod loan_position_listing;
+pub use loan_position_listing::*;
+
+pub mod treasury;
+pub use treasury::*;
+
+pub mod versioned;
+pub use versioned::*;
+
+pub mod loan_quote;
+pub use loan_quote::*;
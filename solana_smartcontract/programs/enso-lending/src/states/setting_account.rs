@@ -1,5 +1,7 @@
 pub use anchor_lang::prelude::*;
 
+use crate::{LendOfferError, SettingAccountError, MAX_TIER_COLLATERAL_MINTS};
+
 #[account]
 #[derive(InitSpace, Debug)]
 pub struct SettingAccount {
@@ -7,8 +9,9 @@ pub struct SettingAccount {
     pub duration: u64,
     pub owner: Pubkey,
     pub receiver: Pubkey,
-    // Note: Not used 2 fields, space for future to used
+    // Note: Lend mint this tier applies to, default pubkey allows any lend mint
     pub lend_mint_asset: Pubkey,
+    // Note: Not used field, space for future to used
     pub collateral_mint_asset: Pubkey,
     #[max_len(50)]
     pub tier_id: String,
@@ -18,4 +21,92 @@ pub struct SettingAccount {
     pub lend_price_feed: Pubkey,
     pub collateral_price_feed: Pubkey,
     pub bump: u8,
-}
\ No newline at end of file
+    // Note: Collateral mints allowed in this tier, empty allows any collateral mint
+    #[max_len(MAX_TIER_COLLATERAL_MINTS)]
+    pub collateral_mint_assets: Vec<Pubkey>,
+    // Note: Lend amount bounds in base units, max_amount of 0 keeps the fixed whole token `amount`
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub amount_step: u64,
+    // Note: Duration bounds in seconds, max_duration of 0 keeps the fixed `duration`
+    pub min_duration: u64,
+    pub max_duration: u64,
+    pub version: u8,
+}
+
+impl SettingAccount {
+    pub const VERSION: u8 = 1;
+
+    pub fn is_current_version(&self) -> bool {
+        self.version == Self::VERSION
+    }
+
+    pub fn validate_lend_mint(&self, lend_mint: Pubkey) -> Result<()> {
+        if self.lend_mint_asset != Pubkey::default() && self.lend_mint_asset != lend_mint {
+            return err!(SettingAccountError::LendMintNotAllowed);
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_amount_range(min_amount: u64, max_amount: u64, min_duration: u64, max_duration: u64) -> Result<()> {
+        if min_amount > max_amount {
+            return err!(SettingAccountError::InvalidAmountRange);
+        }
+
+        if min_duration > max_duration {
+            return err!(SettingAccountError::InvalidDurationRange);
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_lend_amount(&self, amount: u64, decimals: u8) -> Result<()> {
+        if self.max_amount == 0 {
+            let fixed_amount = 10_u64
+                .checked_pow(decimals as u32)
+                .and_then(|unit| self.amount.checked_mul(unit))
+                .ok_or(LendOfferError::InvalidLendAmount)?;
+
+            if amount != fixed_amount {
+                return err!(LendOfferError::InvalidLendAmount);
+            }
+
+            return Ok(());
+        }
+
+        if amount < self.min_amount || amount > self.max_amount {
+            return err!(LendOfferError::InvalidLendAmount);
+        }
+
+        if self.amount_step > 0 && !(amount - self.min_amount).is_multiple_of(self.amount_step) {
+            return err!(LendOfferError::InvalidLendAmount);
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_duration(&self, duration: u64) -> Result<()> {
+        if self.max_duration == 0 {
+            if duration != self.duration {
+                return err!(LendOfferError::InvalidDuration);
+            }
+
+            return Ok(());
+        }
+
+        if duration < self.min_duration || duration > self.max_duration {
+            return err!(LendOfferError::InvalidDuration);
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_collateral_mint(&self, collateral_mint: Pubkey) -> Result<()> {
+        if !self.collateral_mint_assets.is_empty() && !self.collateral_mint_assets.contains(&collateral_mint) {
+            return err!(SettingAccountError::CollateralMintNotAllowed);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+/// Owner of the token account protocol fees of one lend mint are paid into, with the fees it
+/// received by source.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Treasury {
+  pub token_mint: Pubkey,
+  pub total_lender_fee: u64,
+  pub total_borrower_fee: u64,
+  pub total_withdrawn: u64,
+  pub bump: u8,
+}
+
+impl Treasury {
+  pub fn add_fees(&mut self, fees: &TreasuryFees) {
+    self.total_lender_fee = self.total_lender_fee.saturating_add(fees.lender_fee);
+    self.total_borrower_fee = self.total_borrower_fee.saturating_add(fees.borrower_fee);
+  }
+
+  pub fn add_withdrawn(&mut self, amount: u64) {
+    self.total_withdrawn = self.total_withdrawn.saturating_add(amount);
+  }
+}
+
+/// Fees a single instruction pays into the treasury.
+#[derive(Default, Debug)]
+pub struct TreasuryFees {
+  pub lender_fee: u64,
+  pub borrower_fee: u64,
+}
+
+impl TreasuryFees {
+  pub fn total(&self) -> u64 {
+    self.lender_fee + self.borrower_fee
+  }
+}
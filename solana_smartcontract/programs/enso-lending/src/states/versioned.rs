@@ -0,0 +1,66 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{Asset, LendOfferAccount, LoanOfferAccount, SettingAccount};
+
+// Note: version 0 is the layout of accounts created before versioning, every later field was appended and reads as zero
+pub trait Versioned: AccountSerialize + AccountDeserialize + Discriminator {
+  const VERSION: u8;
+  const SPACE: usize;
+
+  fn version(&self) -> u8;
+
+  // Converts the account from its stored version to VERSION, the account is already realloc'd to SPACE
+  fn migrate(&mut self);
+}
+
+impl Versioned for LoanOfferAccount {
+  const VERSION: u8 = LoanOfferAccount::VERSION;
+  const SPACE: usize = 8 + LoanOfferAccount::INIT_SPACE;
+
+  fn version(&self) -> u8 {
+    self.version
+  }
+
+  fn migrate(&mut self) {
+    self.version = LoanOfferAccount::VERSION;
+  }
+}
+
+impl Versioned for LendOfferAccount {
+  const VERSION: u8 = LendOfferAccount::VERSION;
+  const SPACE: usize = 8 + LendOfferAccount::INIT_SPACE;
+
+  fn version(&self) -> u8 {
+    self.version
+  }
+
+  fn migrate(&mut self) {
+    self.version = LendOfferAccount::VERSION;
+  }
+}
+
+impl Versioned for Asset {
+  const VERSION: u8 = Asset::VERSION;
+  const SPACE: usize = 8 + Asset::INIT_SPACE;
+
+  fn version(&self) -> u8 {
+    self.version
+  }
+
+  fn migrate(&mut self) {
+    self.version = Asset::VERSION;
+  }
+}
+
+impl Versioned for SettingAccount {
+  const VERSION: u8 = SettingAccount::VERSION;
+  const SPACE: usize = 8 + SettingAccount::INIT_SPACE;
+
+  fn version(&self) -> u8 {
+    self.version
+  }
+
+  fn migrate(&mut self) {
+    self.version = SettingAccount::VERSION;
+  }
+}
@@ -27,3 +27,83 @@ pub fn get_total_repay_loan_amount(params: TotalRepayLoanAmountParams) -> u64 {
 
     return (borrow_amount as f64 + interest_amount + borrower_fee_amount) as u64;
 }
+
+pub struct AccruedLenderInterestParams {
+    pub lender_fee_percent: f64,
+    pub interest: f64,
+    pub borrow_amount: u64,
+    pub started_at: i64,
+    pub duration: u64,
+    pub current_timestamp: i64,
+}
+
+pub fn get_accrued_lender_interest(params: AccruedLenderInterestParams) -> u64 {
+    let AccruedLenderInterestParams {
+        lender_fee_percent,
+        interest,
+        borrow_amount,
+        started_at,
+        duration,
+        current_timestamp,
+    } = params;
+
+    let elapsed = (current_timestamp - started_at).clamp(0, duration as i64) as u64;
+
+    let lender_fee_percent = lender_fee_percent / 100.0;
+
+    let loan_interest_percent = interest / 100.0;
+
+    let interest_amount = (borrow_amount as f64) * loan_interest_percent * duration_to_year(elapsed);
+
+    let lender_fee_amount = lender_fee_percent * interest_amount;
+
+    return (interest_amount - lender_fee_amount) as u64;
+}
+
+pub struct LenderRepayAmountParams {
+    pub lender_fee_percent: f64,
+    pub interest: f64,
+    pub duration: u64,
+    pub borrow_amount: u64,
+}
+
+pub fn get_lender_repay_amount(params: LenderRepayAmountParams) -> u64 {
+    let LenderRepayAmountParams {
+        lender_fee_percent,
+        interest,
+        duration,
+        borrow_amount,
+    } = params;
+
+    let lender_fee_percent = lender_fee_percent / 100.0;
+
+    let loan_interest_percent = interest / 100.0;
+
+    let interest_amount = (borrow_amount as f64) * loan_interest_percent * duration_to_year(duration);
+
+    let lender_fee_amount = lender_fee_percent * interest_amount;
+
+    return (borrow_amount as f64 + interest_amount - lender_fee_amount) as u64;
+}
+
+fn get_principal_and_interest(interest: f64, duration: u64, borrow_amount: u64) -> u64 {
+    let loan_interest_percent = interest / 100.0;
+
+    let interest_amount = (borrow_amount as f64) * loan_interest_percent * duration_to_year(duration);
+
+    (borrow_amount as f64 + interest_amount) as u64
+}
+
+/// Part of `get_total_repay_loan_amount` that is the borrower fee.
+pub fn get_borrower_fee_amount(params: TotalRepayLoanAmountParams) -> u64 {
+    let principal_and_interest = get_principal_and_interest(params.interest, params.duration, params.borrow_amount);
+
+    get_total_repay_loan_amount(params).saturating_sub(principal_and_interest)
+}
+
+/// Lender fee withheld from `get_lender_repay_amount`, the two add up to principal and interest.
+pub fn get_lender_fee_amount(params: LenderRepayAmountParams) -> u64 {
+    let principal_and_interest = get_principal_and_interest(params.interest, params.duration, params.borrow_amount);
+
+    principal_and_interest.saturating_sub(get_lender_repay_amount(params))
+}
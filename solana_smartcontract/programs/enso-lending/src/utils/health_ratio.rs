@@ -3,7 +3,7 @@ use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 use crate::{LoanOfferError, MIN_BORROW_HEALTH_RATIO};
 
-use super::convert_to_usd_price;
+use super::{convert_to_usd_price, risk::{self, PricedLoanParams}};
 
 pub struct HealthRatioParams<'a> {
     pub collateral_price_feed_account: &'a PriceUpdateV2,
@@ -19,7 +19,7 @@ pub struct HealthRatioParams<'a> {
 }
 
 pub fn validate_health_ratio(params: HealthRatioParams) -> Result<()> {
-    let (health_ratio, _, _) = get_health_ratio_and_assets_price(params);
+    let (health_ratio, _, _) = get_health_ratio_and_assets_price(params)?;
 
     msg!("Health ratio: {}", health_ratio);
 
@@ -30,7 +30,7 @@ pub fn validate_health_ratio(params: HealthRatioParams) -> Result<()> {
     Ok(())
 }
 
-pub fn get_health_ratio_and_assets_price(params: HealthRatioParams) -> (f64, f64, f64) {
+pub fn get_health_ratio_and_assets_price(params: HealthRatioParams) -> Result<(f64, f64, f64)> {
     let HealthRatioParams {
         collateral_amount,
         collateral_max_price_age_seconds,
@@ -44,28 +44,32 @@ pub fn get_health_ratio_and_assets_price(params: HealthRatioParams) -> (f64, f64
         lend_decimals,
     } = params;
 
-    let (convert_collateral_amount_to_usd, collateral_price) = convert_to_usd_price(
+    let (_, collateral_price) = convert_to_usd_price(
         collateral_price_feed_account,
         &collateral_price_feed_id,
-        collateral_amount as f64 / 10f64.powf(collateral_decimals as f64),
+        0f64,
         collateral_max_price_age_seconds,
-    )
-    .unwrap();
-    msg!(
-        "Convert collateral amount to USD: {}",
-        convert_collateral_amount_to_usd
-    );
+    )?;
 
-    let (convert_lend_amount_to_usd, lend_price) = convert_to_usd_price(
+    let (_, lend_price) = convert_to_usd_price(
         lend_price_feed_account,
         &lend_price_feed_id,
-        lend_amount as f64 / 10f64.powf(lend_decimals as f64),
+        0f64,
         lend_max_price_age_seconds,
-    )
-    .unwrap();
-    msg!("Convert lend amount to USD: {}", convert_lend_amount_to_usd);
+    )?;
 
-    let health_ratio = convert_collateral_amount_to_usd / convert_lend_amount_to_usd;
+    let priced_loan = PricedLoanParams {
+        collateral_amount,
+        collateral_decimals,
+        collateral_price,
+        lend_amount,
+        lend_decimals,
+        lend_price,
+    };
+    msg!("Convert collateral amount to USD: {}", priced_loan.collateral_usd());
+    msg!("Convert lend amount to USD: {}", priced_loan.lend_usd());
+
+    let health_ratio = risk::get_health_ratio(&priced_loan);
 
-    return (health_ratio, collateral_price, lend_price);
+    Ok((health_ratio, collateral_price, lend_price))
 }
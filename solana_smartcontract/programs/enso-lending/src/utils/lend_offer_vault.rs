@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, TransferChecked};
+
+use crate::{LendOfferAccount, LoanOfferError, ENSO_SEED, LEND_OFFER_ACCOUNT_SEED};
+
+pub struct ReleaseLendOfferVaultParams<'a, 'info> {
+  pub token_program: AccountInfo<'info>,
+  pub lend_mint: &'a Account<'info, Mint>,
+  pub lend_offer: &'a Account<'info, LendOfferAccount>,
+  pub lend_offer_vault: Option<AccountInfo<'info>>,
+  pub hot_wallet_ata: Option<AccountInfo<'info>>,
+}
+
+// Note: Moves the escrowed principal of a matched lend offer to the hot wallet, offers without escrow are a no-op
+pub fn release_lend_offer_vault(params: ReleaseLendOfferVaultParams) -> Result<()> {
+  let lend_offer = params.lend_offer;
+  if !lend_offer.is_escrowed() {
+    return Ok(());
+  }
+
+  let (Some(lend_offer_vault), Some(hot_wallet_ata)) = (params.lend_offer_vault, params.hot_wallet_ata) else {
+    return err!(LoanOfferError::MissingLendOfferVault);
+  };
+
+  let program_id = crate::ID.key();
+
+  let signer: &[&[&[u8]]] = &[&[
+    ENSO_SEED.as_ref(),
+    LEND_OFFER_ACCOUNT_SEED.as_ref(),
+    lend_offer.lender.as_ref(),
+    lend_offer.offer_id.as_bytes(),
+    program_id.as_ref(),
+    &[lend_offer.bump]
+  ]];
+
+  transfer_checked(
+    CpiContext::new_with_signer(
+      params.token_program,
+      TransferChecked {
+        from: lend_offer_vault,
+        mint: params.lend_mint.to_account_info(),
+        to: hot_wallet_ata,
+        authority: lend_offer.to_account_info(),
+      },
+      signer
+    ),
+    lend_offer.amount,
+    params.lend_mint.decimals,
+  )
+}
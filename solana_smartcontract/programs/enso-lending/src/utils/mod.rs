@@ -8,4 +8,12 @@ pub mod health_ratio;
 
 pub mod amount;
 
-pub mod vaa;
\ No newline at end of file
+pub mod vaa;
+
+pub mod position;
+
+pub mod lend_offer_vault;
+
+pub mod risk;
+
+pub mod treasury;
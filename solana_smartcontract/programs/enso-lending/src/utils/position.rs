@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, set_authority, spl_token::instruction::AuthorityType, MintTo, SetAuthority, TokenAccount};
+
+use crate::{LoanOfferAccount, LoanPositionListing, RepayOfferError, ENSO_SEED, LOAN_POSITION_MINT_SEED};
+
+pub struct MintPositionTokenParams<'a, 'info> {
+  pub token_program: AccountInfo<'info>,
+  pub position_mint: AccountInfo<'info>,
+  pub lender_position_ata: AccountInfo<'info>,
+  pub loan_offer: &'a Pubkey,
+  pub position_mint_bump: u8,
+}
+
+// Note: Mints the single position token of a loan to the lender, then drops the mint authority so supply stays at 1
+pub fn mint_position_token(params: MintPositionTokenParams) -> Result<()> {
+  let program_id = crate::ID.key();
+
+  let signer: &[&[&[u8]]] = &[&[
+    ENSO_SEED.as_ref(),
+    LOAN_POSITION_MINT_SEED.as_ref(),
+    params.loan_offer.as_ref(),
+    program_id.as_ref(),
+    &[params.position_mint_bump]
+  ]];
+
+  mint_to(
+    CpiContext::new_with_signer(
+      params.token_program.clone(),
+      MintTo {
+        mint: params.position_mint.clone(),
+        to: params.lender_position_ata,
+        authority: params.position_mint.clone(),
+      },
+      signer
+    ),
+    1,
+  )?;
+
+  set_authority(
+    CpiContext::new_with_signer(
+      params.token_program,
+      SetAuthority {
+        current_authority: params.position_mint.clone(),
+        account_or_mint: params.position_mint,
+      },
+      signer
+    ),
+    AuthorityType::MintTokens,
+    None,
+  )
+}
+
+// Note: Loans created before position tokens pay the recorded lender, newer ones pay whoever holds the token.
+// A listed token is held by its listing, the seller stays the creditor until the position is bought
+pub fn validate_creditor(
+  loan_offer: &LoanOfferAccount,
+  creditor: Pubkey,
+  creditor_position_ata: Option<&TokenAccount>,
+  listing: Option<&Account<LoanPositionListing>>,
+) -> Result<()> {
+  let Some(position_mint) = loan_offer.position_mint else {
+    if creditor != loan_offer.lender {
+      return err!(RepayOfferError::InvalidLender);
+    }
+
+    return Ok(());
+  };
+
+  let Some(creditor_position_ata) = creditor_position_ata else {
+    return err!(RepayOfferError::InvalidPositionTokenAccount);
+  };
+
+  let holder = match listing {
+    Some(listing) => {
+      if listing.position_mint != position_mint || listing.seller != creditor {
+        return err!(RepayOfferError::InvalidLender);
+      }
+
+      listing.key()
+    }
+    None => creditor,
+  };
+
+  if creditor_position_ata.mint != position_mint
+    || creditor_position_ata.owner != holder
+    || creditor_position_ata.amount != 1
+  {
+    return err!(RepayOfferError::InvalidPositionTokenAccount);
+  }
+
+  Ok(())
+}
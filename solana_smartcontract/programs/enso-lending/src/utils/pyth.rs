@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
 
+use super::risk;
+
 pub fn convert_to_usd_price(
     price_feed_account: &PriceUpdateV2,
     price_fee_id: &str,
@@ -12,7 +14,7 @@ pub fn convert_to_usd_price(
     let current_price =
         price_feed_account.get_price_no_older_than(&Clock::get()?, max_price_age_seconds, &feed_id)?;
 
-    let display_price = current_price.price as f64 / 10f64.powf(-current_price.exponent as f64);
+    let display_price = risk::to_ui_price(current_price.price, current_price.exponent);
 
     msg!("Current price: {}", display_price);
     msg!("Amount: {}", amount);
@@ -0,0 +1,71 @@
+//! Pure loan risk math shared by the on-chain health checks and off-chain keepers.
+//! Prices are USD per whole token, amounts are in base units of their mint.
+
+#[derive(Clone, Copy, Debug)]
+pub struct PricedLoanParams {
+    pub collateral_amount: u64,
+    pub collateral_decimals: u8,
+    pub collateral_price: f64,
+    pub lend_amount: u64,
+    pub lend_decimals: u8,
+    pub lend_price: f64,
+}
+
+impl PricedLoanParams {
+    pub fn collateral_usd(&self) -> f64 {
+        to_ui_amount(self.collateral_amount, self.collateral_decimals) * self.collateral_price
+    }
+
+    pub fn lend_usd(&self) -> f64 {
+        to_ui_amount(self.lend_amount, self.lend_decimals) * self.lend_price
+    }
+}
+
+pub fn to_ui_amount(amount: u64, decimals: u8) -> f64 {
+    amount as f64 / 10f64.powf(decimals as f64)
+}
+
+/// USD price of a Pyth price with its exponent applied.
+pub fn to_ui_price(price: i64, exponent: i32) -> f64 {
+    price as f64 / 10f64.powf(-exponent as f64)
+}
+
+pub fn get_health_ratio(params: &PricedLoanParams) -> f64 {
+    params.collateral_usd() / params.lend_usd()
+}
+
+/// Collateral price at which the loan health ratio falls to `threshold`.
+pub fn get_liquidation_price(params: &PricedLoanParams, threshold: f64) -> f64 {
+    threshold * params.lend_usd() / to_ui_amount(params.collateral_amount, params.collateral_decimals)
+}
+
+/// Smallest collateral amount, in base units, that keeps the health ratio at or above `target_ratio`.
+pub fn get_min_collateral_for_ratio(params: &PricedLoanParams, target_ratio: f64) -> u64 {
+    let min_collateral = (target_ratio * params.lend_usd() / params.collateral_price)
+        * 10f64.powf(params.collateral_decimals as f64);
+
+    min_collateral.ceil() as u64
+}
+
+pub fn get_max_withdrawable_collateral(params: &PricedLoanParams, threshold: f64) -> u64 {
+    params
+        .collateral_amount
+        .saturating_sub(get_min_collateral_for_ratio(params, threshold))
+}
+
+/// Collateral, in base units, worth the lend amount plus `bonus_percent` of it, capped at the
+/// collateral of the loan.
+pub fn get_seized_collateral(params: &PricedLoanParams, bonus_percent: f64) -> u64 {
+    let seized = (params.lend_usd() * (1.0 + bonus_percent / 100.0) / params.collateral_price)
+        * 10f64.powf(params.collateral_decimals as f64);
+
+    (seized.ceil() as u64).min(params.collateral_amount)
+}
+
+/// Largest lend amount, in base units, the collateral can back at `threshold`. `lend_amount` is ignored.
+pub fn get_max_borrow_amount(params: &PricedLoanParams, threshold: f64) -> u64 {
+    let max_borrow = (params.collateral_usd() / threshold / params.lend_price)
+        * 10f64.powf(params.lend_decimals as f64);
+
+    max_borrow.floor() as u64
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{EventHeader, LoanOfferAccount, Treasury, TreasuryFees, TreasuryFeesCollected};
+
+// Note: Only records the fees, the caller transfers `fees.total()` into the treasury token account
+pub fn record_fees(
+  treasury: &mut Account<Treasury>,
+  loan_offer: &Account<LoanOfferAccount>,
+  actor: Pubkey,
+  fees: &TreasuryFees,
+) -> Result<()> {
+  if fees.total() == 0 {
+    return Ok(());
+  }
+
+  treasury.add_fees(fees);
+
+  emit!(TreasuryFeesCollected {
+    header: EventHeader::new(treasury.key(), loan_offer.offer_id.clone(), actor)?,
+    token_mint: treasury.token_mint,
+    loan_offer: loan_offer.key(),
+    lender_fee: fees.lender_fee,
+    borrower_fee: fees.borrower_fee,
+  });
+
+  Ok(())
+}
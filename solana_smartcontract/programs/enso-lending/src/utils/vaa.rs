@@ -1,6 +1,6 @@
 use solana_program::msg;
 
-use crate::{ParseVaaError, HEX_MIN_WIDTH};
+use crate::{ExpectedLoanTerms, ParseVaaError, HEX_MIN_WIDTH};
 
 pub fn parse_create_loan_payload(
     posted_vaa: &Vec<u8>,
@@ -32,6 +32,40 @@ pub fn parse_create_loan_payload(
   ))
 }
 
+// Note: Create payloads from foreign chain contracts without expected loan terms carry 9 fields,
+// the terms are only parsed and enforced once a payload carries all 13
+const CREATE_LOAN_PAYLOAD_FIELDS: usize = 9;
+const CREATE_LOAN_EXPECTED_TERMS_PAYLOAD_FIELDS: usize = 13;
+
+pub fn parse_create_loan_expected_terms(
+    posted_vaa: &Vec<u8>,
+    lend_amount: u64,
+) -> Result<Option<ExpectedLoanTerms>, ParseVaaError> {
+  let message = String::from_utf8_lossy(posted_vaa).into_owned();
+  let data: Vec<&str> = message.split(',').collect();
+
+  if data.len() == CREATE_LOAN_PAYLOAD_FIELDS {
+    return Ok(None);
+  }
+
+  if data.len() < CREATE_LOAN_EXPECTED_TERMS_PAYLOAD_FIELDS {
+    return Err(ParseVaaError::InvalidExpectedLoanTerms);
+  }
+
+  let duration = data[9].parse::<u64>().map_err(|_| ParseVaaError::InvalidExpectedLoanTerms)?;
+  let max_interest = data[10].parse::<f64>().map_err(|_| ParseVaaError::InvalidExpectedLoanTerms)?;
+  let max_borrower_fee_percent = data[11].parse::<f64>().map_err(|_| ParseVaaError::InvalidExpectedLoanTerms)?;
+  let max_lender_fee_percent = data[12].parse::<f64>().map_err(|_| ParseVaaError::InvalidExpectedLoanTerms)?;
+
+  Ok(Some(ExpectedLoanTerms {
+    amount: lend_amount,
+    duration,
+    max_interest,
+    max_borrower_fee_percent,
+    max_lender_fee_percent,
+  }))
+}
+
 pub fn validate_posted_vaa(
     posted_chain_id: u16,
     chain_id: u16,
@@ -0,0 +1,153 @@
+
+mod common;
+
+use common::{
+    assert_error,
+    market::{Market, COLLATERAL_AMOUNT, LEND_AMOUNT, TIER_AMOUNT, TIER_ID},
+    Harness,
+};
+use enso_lending::{
+    common::{LoanOfferError, SettingAccountError, TreasuryError},
+    instruction as args,
+    states::{Asset, SettingAccount},
+};
+use enso_lending_client::{instructions as ix, pda};
+use solana_sdk::signer::Signer;
+
+#[tokio::test]
+async fn initializes_tier_and_assets() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+
+    let setting: SettingAccount = harness.account(pda::setting_account(TIER_ID).0).await;
+    assert_eq!(setting.tier_id, TIER_ID);
+    assert_eq!(setting.amount, TIER_AMOUNT);
+    assert_eq!(setting.owner, harness.system.pubkey());
+    assert_eq!(setting.receiver, harness.hot_wallet.pubkey());
+
+    let asset: Asset = harness.account(pda::asset(&market.collateral_mint).0).await;
+    assert!(asset.is_collateral);
+    assert_eq!(asset.price_feed_account, market.collateral_feed.address);
+    assert_eq!(asset.price_feed_id, market.collateral_feed.id_hex());
+}
+
+#[tokio::test]
+async fn rejects_tier_from_another_owner() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let owner = harness.new_wallet(common::LAMPORTS_PER_SOL);
+
+    let mut setting_args = market.setting_args();
+    setting_args.tier_id = "tier-2".to_string();
+    let instruction = ix::init_setting_account(owner.pubkey(), harness.hot_wallet.pubkey(), setting_args);
+    assert_error(harness.process(&[instruction], &[&owner]).await, SettingAccountError::InvalidOwner);
+}
+
+#[tokio::test]
+async fn edits_and_closes_tier() {
+    let mut harness = Harness::start().await;
+    let _market = Market::setup(&mut harness).await;
+    let system = harness.system.insecure_clone();
+
+    let edit = ix::edit_setting_account(
+        system.pubkey(),
+        harness.hot_wallet.pubkey(),
+        args::EditSettingAccount {
+            _tier_id: TIER_ID.to_string(),
+            amount: Some(2 * TIER_AMOUNT),
+            duration: None,
+            lender_fee_percent: Some(1.0),
+            borrower_fee_percent: None,
+            lend_mint_asset: None,
+            collateral_mint_assets: None,
+            min_amount: None,
+            max_amount: None,
+            amount_step: None,
+            min_duration: None,
+            max_duration: None,
+        },
+    );
+    harness.process(&[edit], &[&system]).await.unwrap();
+    let setting: SettingAccount = harness.account(pda::setting_account(TIER_ID).0).await;
+    assert_eq!(setting.amount, 2 * TIER_AMOUNT);
+    assert_eq!(setting.lender_fee_percent, 1.0);
+
+    let close = ix::close_setting_account(system.pubkey(), args::CloseSettingAccount { tier_id: TIER_ID.to_string() });
+    harness.process(&[close], &[&system]).await.unwrap();
+    assert!(harness.get_account(pda::setting_account(TIER_ID).0).await.is_none());
+}
+
+#[tokio::test]
+async fn collateral_cap_limits_new_loans() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let system = harness.system.insecure_clone();
+
+    let edit = ix::edit_asset(
+        market.collateral_mint,
+        None,
+        args::EditAsset {
+            name: None,
+            is_lend: None,
+            is_collateral: None,
+            price_feed_id: None,
+            max_price_age_seconds: None,
+            token_address: None,
+            max_collateral_amount: Some(COLLATERAL_AMOUNT),
+            max_borrow_amount: None,
+            isolated: None,
+            isolated_lend_mints: None,
+            isolated_debt_ceiling: None,
+        },
+    );
+    harness.process(&[edit], &[&system]).await.unwrap();
+
+    market.open_token_loan(&mut harness, "within-cap").await;
+    let asset: Asset = harness.account(pda::asset(&market.collateral_mint).0).await;
+    assert_eq!(asset.total_collateral_amount, COLLATERAL_AMOUNT);
+
+    let loan = market.new_loan(&mut harness, "over-cap", false).await;
+    let instruction = market.create_loan_instruction(&harness, &loan.borrower, &loan.lender, &loan, COLLATERAL_AMOUNT);
+    assert_error(harness.process(&[instruction], &[&loan.borrower]).await, LoanOfferError::CollateralCapExceeded);
+
+    let lender_ata = pda::ata(&loan.lender.pubkey(), &market.lend_mint);
+    assert_eq!(harness.token_balance(lender_ata).await, 0);
+    assert_eq!(loan.lend_offer(&mut harness).await.amount, LEND_AMOUNT);
+}
+
+#[tokio::test]
+async fn operator_withdraws_treasury_fees() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let system = harness.system.insecure_clone();
+    let treasury_ata = harness.set_token_balance(pda::treasury(&market.lend_mint).0, market.lend_mint, 50_000_000);
+    let receiver_ata = harness.set_token_balance(system.pubkey(), market.lend_mint, 0);
+
+    let withdraw = ix::withdraw_treasury(market.lend_mint, receiver_ata, args::WithdrawTreasury { amount: 20_000_000 });
+    harness.process(&[withdraw], &[&system]).await.unwrap();
+
+    assert_eq!(harness.token_balance(treasury_ata).await, 30_000_000);
+    assert_eq!(harness.token_balance(receiver_ata).await, 20_000_000);
+    assert_eq!(market.treasury(&mut harness).await.total_withdrawn, 20_000_000);
+
+    harness.refresh_blockhash().await;
+    let overdraw = ix::withdraw_treasury(market.lend_mint, receiver_ata, args::WithdrawTreasury { amount: 30_000_001 });
+    assert_error(harness.process(&[overdraw], &[&system]).await, TreasuryError::NotEnoughAmount);
+
+    let empty = ix::withdraw_treasury(market.lend_mint, receiver_ata, args::WithdrawTreasury { amount: 0 });
+    assert_error(harness.process(&[empty], &[&system]).await, TreasuryError::InvalidAmount);
+}
+
+#[tokio::test]
+async fn rejects_treasury_withdrawal_by_non_operator() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let intruder = harness.new_wallet(1_000_000_000);
+    harness.set_token_balance(pda::treasury(&market.lend_mint).0, market.lend_mint, 50_000_000);
+    let receiver_ata = harness.set_token_balance(intruder.pubkey(), market.lend_mint, 0);
+
+    let mut withdraw = ix::withdraw_treasury(market.lend_mint, receiver_ata, args::WithdrawTreasury { amount: 50_000_000 });
+    withdraw.accounts[0].pubkey = intruder.pubkey();
+    assert_error(harness.process(&[withdraw], &[&intruder]).await, SettingAccountError::InvalidOwner);
+    assert_eq!(harness.token_balance(receiver_ata).await, 0);
+}
@@ -0,0 +1,404 @@
+//! Protocol fixture shared by the tests: one tier, a USDC like lend asset, a token and a SOL
+//! collateral asset, a collateral asset held on a foreign chain and their price feeds.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token::spl_token::native_mint;
+use enso_lending::{
+    instruction as args,
+    states::{ExpectedLoanTerms, LendOfferAccount, LoanOfferAccount, Treasury},
+};
+use enso_lending_client::{
+    instructions::{self as ix, LoanAssets},
+    pda,
+};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use super::{Harness, LAMPORTS_PER_SOL};
+
+pub const TIER_ID: &str = "tier-1";
+/// Fixed tier amount in whole lend tokens.
+pub const TIER_AMOUNT: u64 = 1_000;
+pub const DURATION: u64 = 14 * 24 * 60 * 60;
+pub const LENDER_FEE_PERCENT: f64 = 5.0;
+pub const BORROWER_FEE_PERCENT: f64 = 5.0;
+pub const INTEREST: f64 = 10.0;
+pub const MAX_PRICE_AGE_SECONDS: u64 = 60;
+
+pub const LEND_DECIMALS: u8 = 6;
+pub const LEND_AMOUNT: u64 = TIER_AMOUNT * 10u64.pow(LEND_DECIMALS as u32);
+pub const COLLATERAL_DECIMALS: u8 = 9;
+
+pub const PRICE_EXPONENT: i32 = -8;
+pub const LEND_PRICE: i64 = 100_000_000;
+pub const COLLATERAL_PRICE: i64 = 10_000_000_000;
+pub const SOL_PRICE: i64 = 15_000_000_000;
+
+/// 15 tokens at 100 USD against 1000 USD borrowed, a health ratio of 1.5.
+pub const COLLATERAL_AMOUNT: u64 = 15 * 10u64.pow(COLLATERAL_DECIMALS as u32);
+/// 10 SOL at 150 USD against 1000 USD borrowed, a health ratio of 1.5.
+pub const SOL_COLLATERAL_AMOUNT: u64 = 10 * LAMPORTS_PER_SOL;
+
+/// Wormhole chain holding the cross-chain collateral and the emitter trusted on it.
+pub const FOREIGN_CHAIN_ID: u16 = 21;
+pub const FOREIGN_EMITTER: [u8; 32] = [7; 32];
+pub const FOREIGN_CHAIN_ADDRESS: &str = "0xe50b";
+pub const FOREIGN_TOKEN_ADDRESS: &str = "0x2::sui::SUI";
+
+#[derive(Clone, Copy)]
+pub struct Feed {
+    pub address: Pubkey,
+    pub id: [u8; 32],
+    pub price: i64,
+}
+
+impl Feed {
+    fn new(id: u8, price: i64) -> Self {
+        Self {
+            address: Pubkey::new_unique(),
+            id: [id; 32],
+            price,
+        }
+    }
+
+    pub fn id_hex(&self) -> String {
+        self.id.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+pub struct Market {
+    pub lend_mint: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub foreign_collateral_mint: Pubkey,
+    pub lend_feed: Feed,
+    pub collateral_feed: Feed,
+    pub sol_feed: Feed,
+    pub foreign_collateral_feed: Feed,
+}
+
+impl Market {
+    /// Creates the tier, the assets, the lend mint treasury, the hot wallet and operator token
+    /// accounts and publishes current prices.
+    pub async fn setup(harness: &mut Harness) -> Self {
+        let market = Self {
+            lend_mint: Pubkey::new_unique(),
+            collateral_mint: Pubkey::new_unique(),
+            foreign_collateral_mint: Pubkey::new_unique(),
+            lend_feed: Feed::new(1, LEND_PRICE),
+            collateral_feed: Feed::new(2, COLLATERAL_PRICE),
+            sol_feed: Feed::new(3, SOL_PRICE),
+            foreign_collateral_feed: Feed::new(4, COLLATERAL_PRICE),
+        };
+
+        harness.set_mint(market.lend_mint, LEND_DECIMALS);
+        harness.set_mint(market.collateral_mint, COLLATERAL_DECIMALS);
+        harness.set_mint(market.foreign_collateral_mint, COLLATERAL_DECIMALS);
+        market.refresh_prices(harness).await;
+
+        let hot_wallet = harness.hot_wallet.pubkey();
+        let system = harness.system.pubkey();
+        for mint in [market.lend_mint, market.collateral_mint, market.foreign_collateral_mint] {
+            harness.set_token_balance(hot_wallet, mint, 0);
+        }
+        harness.set_token_balance(system, market.lend_mint, 100 * LEND_AMOUNT);
+
+        let system_keypair = harness.system.insecure_clone();
+        let instructions = [
+            ix::init_setting_account(system, hot_wallet, market.setting_args()),
+            ix::init_asset(market.lend_mint, market.lend_feed.address, asset_args("USDC", true, false, &market.lend_feed, None, 1)),
+            ix::init_treasury(market.lend_mint),
+            ix::init_asset(
+                market.collateral_mint,
+                market.collateral_feed.address,
+                asset_args("WETH", false, true, &market.collateral_feed, None, 1),
+            ),
+            ix::init_asset(native_mint::ID, market.sol_feed.address, asset_args("SOL", false, true, &market.sol_feed, None, 1)),
+            ix::init_asset(
+                market.foreign_collateral_mint,
+                market.foreign_collateral_feed.address,
+                asset_args(
+                    "SUI",
+                    false,
+                    true,
+                    &market.foreign_collateral_feed,
+                    Some(FOREIGN_TOKEN_ADDRESS.to_string()),
+                    FOREIGN_CHAIN_ID,
+                ),
+            ),
+        ];
+        harness.process(&instructions, &[&system_keypair]).await.unwrap();
+
+        market
+    }
+
+    pub fn setting_args(&self) -> args::InitSettingAccount {
+        args::InitSettingAccount {
+            tier_id: TIER_ID.to_string(),
+            amount: TIER_AMOUNT,
+            duration: DURATION,
+            lender_fee_percent: LENDER_FEE_PERCENT,
+            borrower_fee_percent: BORROWER_FEE_PERCENT,
+            lend_mint_asset: self.lend_mint,
+            collateral_mint_assets: vec![],
+            min_amount: 0,
+            max_amount: 0,
+            amount_step: 0,
+            min_duration: 0,
+            max_duration: 0,
+        }
+    }
+
+    /// Republishes every feed at the current clock, call after moving time forward.
+    pub async fn refresh_prices(&self, harness: &mut Harness) {
+        for feed in [self.lend_feed, self.collateral_feed, self.sol_feed, self.foreign_collateral_feed] {
+            harness.set_price(feed.address, feed.id, feed.price, PRICE_EXPONENT).await;
+        }
+    }
+
+    pub async fn set_collateral_price(&mut self, harness: &mut Harness, price: i64) {
+        self.collateral_feed.price = price;
+        self.sol_feed.price = price;
+        self.foreign_collateral_feed.price = price;
+        self.refresh_prices(harness).await;
+    }
+
+    pub fn token_assets(&self) -> LoanAssets {
+        LoanAssets {
+            lend_mint: self.lend_mint,
+            collateral_mint: self.collateral_mint,
+            lend_price_feed: self.lend_feed.address,
+            collateral_price_feed: self.collateral_feed.address,
+        }
+    }
+
+    pub fn native_assets(&self) -> LoanAssets {
+        LoanAssets {
+            lend_mint: self.lend_mint,
+            collateral_mint: native_mint::ID,
+            lend_price_feed: self.lend_feed.address,
+            collateral_price_feed: self.sol_feed.address,
+        }
+    }
+
+    pub fn foreign_assets(&self) -> LoanAssets {
+        LoanAssets {
+            lend_mint: self.lend_mint,
+            collateral_mint: self.foreign_collateral_mint,
+            lend_price_feed: self.lend_feed.address,
+            collateral_price_feed: self.foreign_collateral_feed.address,
+        }
+    }
+
+    /// Funded lender with the lend tokens of one offer.
+    pub fn new_lender(&self, harness: &mut Harness) -> Keypair {
+        let lender = harness.new_wallet(10 * LAMPORTS_PER_SOL);
+        harness.set_token_balance(lender.pubkey(), self.lend_mint, LEND_AMOUNT);
+
+        lender
+    }
+
+    /// Funded borrower with a vault authority, collateral tokens and SOL for native collateral.
+    pub async fn new_borrower(&self, harness: &mut Harness) -> Keypair {
+        let borrower = harness.new_wallet(100 * LAMPORTS_PER_SOL);
+        harness.set_token_balance(borrower.pubkey(), self.collateral_mint, 10 * COLLATERAL_AMOUNT);
+        harness.set_token_balance(borrower.pubkey(), self.lend_mint, 0);
+        harness
+            .process(&[ix::init_vault_authority(borrower.pubkey())], &[&borrower])
+            .await
+            .unwrap();
+
+        borrower
+    }
+
+    pub fn lend_offer_args(&self, offer_id: &str, expires_at: Option<i64>) -> args::CreateLendOffer {
+        args::CreateLendOffer {
+            offer_id: offer_id.to_string(),
+            _tier_id: TIER_ID.to_string(),
+            interest: INTEREST,
+            amount: LEND_AMOUNT,
+            duration: DURATION,
+            expires_at,
+            crank_tip: if expires_at.is_some() { 5_000 } else { 0 },
+        }
+    }
+
+    pub async fn create_lend_offer(&self, harness: &mut Harness, lender: &Keypair, offer_id: &str, expires_at: Option<i64>) {
+        let instruction = ix::create_lend_offer(
+            lender.pubkey(),
+            self.lend_mint,
+            harness.hot_wallet.pubkey(),
+            self.lend_offer_args(offer_id, expires_at),
+        );
+        harness.process(&[instruction], &[lender]).await.unwrap();
+    }
+
+    pub fn expected_terms(&self) -> ExpectedLoanTerms {
+        ExpectedLoanTerms {
+            amount: LEND_AMOUNT,
+            duration: DURATION,
+            max_interest: INTEREST,
+            max_borrower_fee_percent: BORROWER_FEE_PERCENT,
+            max_lender_fee_percent: LENDER_FEE_PERCENT,
+        }
+    }
+
+    pub fn create_loan_instruction(
+        &self,
+        harness: &Harness,
+        borrower: &Keypair,
+        lender: &Keypair,
+        loan: &Loan,
+        collateral_amount: u64,
+    ) -> anchor_lang::solana_program::instruction::Instruction {
+        ix::create_loan_offer(
+            borrower.pubkey(),
+            lender.pubkey(),
+            &self.token_assets(),
+            harness.hot_wallet.pubkey(),
+            loan.escrowed,
+            args::CreateLoanOffer {
+                offer_id: loan.offer_id.clone(),
+                lend_offer_id: loan.lend_offer_id.clone(),
+                tier_id: TIER_ID.to_string(),
+                collateral_amount,
+                expected_terms: self.expected_terms(),
+            },
+        )
+    }
+
+    pub fn create_loan_native_instruction(
+        &self,
+        harness: &Harness,
+        borrower: &Keypair,
+        lender: &Keypair,
+        loan: &Loan,
+        collateral_amount: u64,
+    ) -> anchor_lang::solana_program::instruction::Instruction {
+        ix::create_loan_offer_native(
+            borrower.pubkey(),
+            lender.pubkey(),
+            &self.native_assets(),
+            harness.hot_wallet.pubkey(),
+            loan.escrowed,
+            args::CreateLoanOfferNative {
+                offer_id: loan.offer_id.clone(),
+                lend_offer_id: loan.lend_offer_id.clone(),
+                tier_id: TIER_ID.to_string(),
+                collateral_amount,
+                expected_terms: self.expected_terms(),
+            },
+        )
+    }
+
+    /// Lend offer taken by a loan with token collateral, left `Matched`.
+    pub async fn open_token_loan(&self, harness: &mut Harness, name: &str) -> Loan {
+        let loan = self.new_loan(harness, name, false).await;
+        let instruction = self.create_loan_instruction(harness, &loan.borrower, &loan.lender, &loan, COLLATERAL_AMOUNT);
+        harness.process(&[instruction], &[&loan.borrower]).await.unwrap();
+
+        loan
+    }
+
+    /// Lend offer taken by a loan with SOL collateral, left `Matched`.
+    pub async fn open_native_loan(&self, harness: &mut Harness, name: &str) -> Loan {
+        let mut loan = self.new_loan(harness, name, false).await;
+        loan.native = true;
+        let instruction =
+            self.create_loan_native_instruction(harness, &loan.borrower, &loan.lender, &loan, SOL_COLLATERAL_AMOUNT);
+        harness.process(&[instruction], &[&loan.borrower]).await.unwrap();
+
+        loan
+    }
+
+    /// Lender, borrower and lend offer of a loan that is not created yet.
+    pub async fn new_loan(&self, harness: &mut Harness, name: &str, escrowed: bool) -> Loan {
+        let lender = self.new_lender(harness);
+        let borrower = self.new_borrower(harness).await;
+        let lend_offer_id = format!("lend-{name}");
+        let expires_at = match escrowed {
+            true => Some(harness.now().await + 24 * 60 * 60),
+            false => None,
+        };
+        self.create_lend_offer(harness, &lender, &lend_offer_id, expires_at).await;
+
+        Loan {
+            lender,
+            borrower,
+            offer_id: format!("loan-{name}"),
+            lend_offer_id,
+            escrowed,
+            native: false,
+        }
+    }
+
+    /// Fee treasury of the lend mint.
+    pub async fn treasury(&self, harness: &mut Harness) -> Treasury {
+        harness.account(pda::treasury(&self.lend_mint).0).await
+    }
+
+    /// Operator transfer of the principal to the borrower, `Matched` to `FundTransferred`.
+    pub async fn fund_loan(&self, harness: &mut Harness, loan: &Loan) {
+        let system = harness.system.insecure_clone();
+        let instruction = ix::system_update_loan_offer(
+            system.pubkey(),
+            loan.borrower.pubkey(),
+            self.lend_mint,
+            args::SystemUpdateLoanOffer {
+                _offer_id: loan.offer_id.clone(),
+                _tier_id: TIER_ID.to_string(),
+                borrow_amount: LEND_AMOUNT,
+            },
+        );
+        harness.process(&[instruction], &[&system]).await.unwrap();
+    }
+}
+
+fn asset_args(
+    name: &str,
+    is_lend: bool,
+    is_collateral: bool,
+    feed: &Feed,
+    token_address: Option<String>,
+    chain_id: u16,
+) -> args::InitAsset {
+    args::InitAsset {
+        name: name.to_string(),
+        is_lend,
+        is_collateral,
+        price_feed_id: feed.id_hex(),
+        max_price_age_seconds: MAX_PRICE_AGE_SECONDS,
+        token_address,
+        chain_id,
+        max_collateral_amount: 0,
+        max_borrow_amount: 0,
+        isolated: false,
+        isolated_lend_mints: vec![],
+        isolated_debt_ceiling: 0,
+    }
+}
+
+pub struct Loan {
+    pub lender: Keypair,
+    pub borrower: Keypair,
+    pub offer_id: String,
+    pub lend_offer_id: String,
+    pub escrowed: bool,
+    pub native: bool,
+}
+
+impl Loan {
+    pub fn address(&self) -> Pubkey {
+        pda::loan_offer(&self.borrower.pubkey(), &self.offer_id).0
+    }
+
+    pub fn lend_offer_address(&self) -> Pubkey {
+        pda::lend_offer(&self.lender.pubkey(), &self.lend_offer_id).0
+    }
+
+    pub async fn state(&self, harness: &mut Harness) -> LoanOfferAccount {
+        harness.account(self.address()).await
+    }
+
+    pub async fn lend_offer(&self, harness: &mut Harness) -> LendOfferAccount {
+        harness.account(self.lend_offer_address()).await
+    }
+}
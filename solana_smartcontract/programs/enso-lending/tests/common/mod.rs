@@ -0,0 +1,374 @@
+//! In-process harness for the integration tests: the program runs natively in
+//! `solana-program-test`, Pyth price updates and Wormhole posted VAAs are written straight into
+//! accounts and the Wormhole core bridge is replaced by a stub that records posted messages.
+//!
+//! The suite needs the `test-keys` feature, which swaps the operator and hot wallet pubkeys
+//! for keypairs derived from fixed seeds, and does not build without it:
+//! `cargo test -p enso-lending --features devnet,test-keys`.
+
+#![allow(dead_code)]
+
+#[cfg(not(feature = "test-keys"))]
+compile_error!("the integration tests need the `test-keys` feature: cargo test -p enso-lending --features devnet,test-keys");
+
+pub mod market;
+pub mod wormhole_stub;
+
+use anchor_lang::{
+    error::Error as AnchorError,
+    prelude::{AccountInfo, Pubkey},
+    solana_program::{
+        entrypoint::ProgramResult,
+        instruction::{Instruction, InstructionError},
+        program_option::COption,
+        program_pack::Pack,
+        sysvar::clock::Clock,
+    },
+    AccountDeserialize, AccountSerialize,
+};
+use anchor_spl::token::spl_token::{self, native_mint};
+use enso_lending_client::pda;
+use pyth_solana_receiver_sdk::price_update::{PriceUpdateV2, VerificationLevel};
+use pythnet_sdk::messages::PriceFeedMessage;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    signature::{keypair_from_seed, Keypair},
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use wormhole_anchor_sdk::wormhole::{
+    self, BridgeConfig, BridgeData, FeeCollector, PostedVaaMeta, SequenceTracker, SEED_PREFIX_POSTED_VAA,
+};
+
+pub const OPERATE_SYSTEM_SEED: &[u8; 32] = b"enso-lending operate system test";
+pub const HOT_WALLET_SEED: &[u8; 32] = b"enso-lending hot wallet test key";
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+/// Rent is not checked for accounts written by the harness, this keeps them alive.
+const FIXTURE_LAMPORTS: u64 = LAMPORTS_PER_SOL;
+pub const WORMHOLE_FEE: u64 = 100;
+
+/// Anchor 0.29 ties the lifetime of the account slice to the lifetime of the accounts, which
+/// `processor!` cannot satisfy. The slice only has to outlive the instruction.
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+
+    enso_lending::entry(program_id, accounts, data)
+}
+
+pub fn operate_system() -> Keypair {
+    keypair_from_seed(OPERATE_SYSTEM_SEED).unwrap()
+}
+
+pub fn hot_wallet() -> Keypair {
+    keypair_from_seed(HOT_WALLET_SEED).unwrap()
+}
+
+pub struct Harness {
+    pub context: ProgramTestContext,
+    pub system: Keypair,
+    pub hot_wallet: Keypair,
+}
+
+impl Harness {
+    pub async fn start() -> Self {
+        let system = operate_system();
+        let hot_wallet = hot_wallet();
+
+        let mut program_test = ProgramTest::new("enso_lending", enso_lending::ID, processor!(process_instruction));
+        program_test.add_program(
+            "wormhole_core_stub",
+            wormhole::program::ID,
+            processor!(wormhole_stub::process_instruction),
+        );
+        program_test.prefer_bpf(false);
+
+        for wallet in [system.pubkey(), hot_wallet.pubkey()] {
+            program_test.add_account(wallet, system_account(100 * LAMPORTS_PER_SOL));
+        }
+        let bridge = BridgeData {
+            guardian_set_index: 0,
+            last_lamports: FIXTURE_LAMPORTS,
+            config: BridgeConfig {
+                guardian_set_expiration_time: 0,
+                fee: WORMHOLE_FEE,
+            },
+        };
+        program_test.add_account(pda::wormhole_bridge().0, borsh_account(&bridge, wormhole::program::ID));
+        program_test.add_account(
+            pda::wormhole_fee_collector().0,
+            borsh_account(&FeeCollector::default(), anchor_lang::system_program::ID),
+        );
+        program_test.add_account(
+            pda::wormhole_sequence().0,
+            borsh_account(&SequenceTracker::default(), wormhole::program::ID),
+        );
+
+        let mut harness = Self {
+            context: program_test.start_with_context().await,
+            system,
+            hot_wallet,
+        };
+        harness.set_mint(native_mint::ID, native_mint::DECIMALS);
+
+        harness
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// Sends the instructions in one transaction paid by the context payer.
+    pub async fn process(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend(signers.iter().copied().filter(|signer| signer.pubkey() != self.payer()));
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            self.context.last_blockhash,
+        );
+
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    /// Return data of a simulated instruction, for the read-only view instructions.
+    pub async fn simulate<T: anchor_lang::AnchorDeserialize>(&mut self, instruction: Instruction) -> T {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.context.payer.pubkey()),
+            &[&self.context.payer],
+            self.context.last_blockhash,
+        );
+        let simulation = self.context.banks_client.simulate_transaction(transaction).await.unwrap();
+        if let Some(Err(err)) = simulation.result {
+            panic!("simulation failed: {err:?}, logs: {:#?}", simulation.simulation_details.map(|d| d.logs));
+        }
+        let return_data = simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .expect("instruction returned no data");
+
+        T::try_from_slice(&return_data.data).unwrap()
+    }
+
+    /// Identical transactions are deduplicated by signature, call this before sending the same
+    /// instructions twice.
+    pub async fn refresh_blockhash(&mut self) {
+        self.context.last_blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+    }
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context.banks_client.get_sysvar::<Clock>().await.unwrap()
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    /// Moves the clock forward, price updates written before then age accordingly. The blockhash
+    /// is refreshed first so an instruction rejected earlier can be sent again.
+    pub async fn advance_time(&mut self, seconds: i64) {
+        self.refresh_blockhash().await;
+        let mut clock = self.clock().await;
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub fn set_account(&mut self, address: Pubkey, account: Account) {
+        self.context.set_account(&address, &AccountSharedData::from(account));
+    }
+
+    pub async fn get_account(&mut self, address: Pubkey) -> Option<Account> {
+        self.context.banks_client.get_account(address).await.unwrap()
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.get_account(address).await.map_or(0, |account| account.lamports)
+    }
+
+    /// Decodes a program account, panics when it does not exist.
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self
+            .get_account(address)
+            .await
+            .unwrap_or_else(|| panic!("account {address} does not exist"));
+
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Overwrites the data of a program account in place, keeping its lamports and owner.
+    pub async fn write_account<T: AccountSerialize>(&mut self, address: Pubkey, value: &T) {
+        let mut account = self.get_account(address).await.expect("account does not exist");
+        value.try_serialize(&mut &mut account.data[..]).unwrap();
+        self.set_account(address, account);
+    }
+
+    pub fn new_wallet(&mut self, lamports: u64) -> Keypair {
+        let wallet = Keypair::new();
+        self.set_account(wallet.pubkey(), system_account(lamports));
+
+        wallet
+    }
+
+    pub fn set_mint(&mut self, mint: Pubkey, decimals: u8) {
+        let state = spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: u64::MAX / 2,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        self.set_account(mint, packed_account(state, spl_token::ID));
+    }
+
+    /// Writes the associated token account of `owner` holding `amount`, returns its address.
+    pub fn set_token_balance(&mut self, owner: Pubkey, mint: Pubkey, amount: u64) -> Pubkey {
+        let address = pda::ata(&owner, &mint);
+        let state = spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        self.set_account(address, packed_account(state, spl_token::ID));
+
+        address
+    }
+
+    /// Balance of a token account, 0 when it does not exist.
+    pub async fn token_balance(&mut self, address: Pubkey) -> u64 {
+        match self.get_account(address).await {
+            Some(account) => spl_token::state::Account::unpack(&account.data).unwrap().amount,
+            None => 0,
+        }
+    }
+
+    /// Writes a fully verified Pyth price update published now.
+    pub async fn set_price(&mut self, address: Pubkey, feed_id: [u8; 32], price: i64, exponent: i32) {
+        let publish_time = self.now().await;
+        self.set_price_at(address, feed_id, price, exponent, publish_time);
+    }
+
+    pub fn set_price_at(&mut self, address: Pubkey, feed_id: [u8; 32], price: i64, exponent: i32, publish_time: i64) {
+        let price_update = PriceUpdateV2 {
+            write_authority: Pubkey::default(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage {
+                feed_id,
+                price,
+                conf: 0,
+                exponent,
+                publish_time,
+                prev_publish_time: publish_time,
+                ema_price: price,
+                ema_conf: 0,
+            },
+            posted_slot: 0,
+        };
+        let mut data = Vec::with_capacity(PriceUpdateV2::LEN);
+        price_update.try_serialize(&mut data).unwrap();
+        data.resize(PriceUpdateV2::LEN, 0);
+
+        self.set_account(address, data_account(data, pyth_solana_receiver_sdk::ID));
+    }
+
+    /// Writes the account the core bridge would create for a verified VAA, returns the hash it
+    /// is keyed by.
+    pub async fn post_vaa(&mut self, emitter_chain: u16, emitter_address: [u8; 32], payload: &[u8]) -> [u8; 32] {
+        let timestamp = self.now().await as u32;
+        self.post_vaa_at(emitter_chain, emitter_address, payload, timestamp)
+    }
+
+    pub fn post_vaa_at(&mut self, emitter_chain: u16, emitter_address: [u8; 32], payload: &[u8], timestamp: u32) -> [u8; 32] {
+        let meta = PostedVaaMeta {
+            version: 1,
+            finality: wormhole::Finality::Finalized as u8,
+            timestamp,
+            signature_set: Pubkey::new_unique(),
+            posted_timestamp: timestamp,
+            batch_id: 0,
+            sequence: 0,
+            emitter_chain,
+            emitter_address,
+        };
+        let vaa_hash = anchor_lang::solana_program::keccak::hashv(&[&timestamp.to_be_bytes(), &emitter_address, payload]).0;
+
+        let mut data = b"vaa".to_vec();
+        anchor_lang::AnchorSerialize::serialize(&meta, &mut data).unwrap();
+        data.extend((payload.len() as u32).to_le_bytes());
+        data.extend(payload);
+
+        let (posted, _) = Pubkey::find_program_address(&[SEED_PREFIX_POSTED_VAA, &vaa_hash], &wormhole::program::ID);
+        self.set_account(posted, data_account(data, wormhole::program::ID));
+
+        vaa_hash
+    }
+
+    /// Current value of the program emitter sequence tracker, outgoing messages use the next one.
+    pub async fn wormhole_sequence(&mut self) -> u64 {
+        self.account::<SequenceTracker>(pda::wormhole_sequence().0).await.sequence
+    }
+
+    /// Payload of a message posted to the stub bridge.
+    pub async fn posted_message(&mut self, sequence: u64) -> Vec<u8> {
+        let address = pda::wormhole_message(sequence).0;
+        self.get_account(address)
+            .await
+            .unwrap_or_else(|| panic!("no message posted with sequence {sequence}"))
+            .data
+    }
+}
+
+pub fn system_account(lamports: u64) -> Account {
+    Account::new(lamports, 0, &anchor_lang::system_program::ID)
+}
+
+fn data_account(data: Vec<u8>, owner: Pubkey) -> Account {
+    Account {
+        lamports: FIXTURE_LAMPORTS,
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn borsh_account(value: &impl anchor_lang::AnchorSerialize, owner: Pubkey) -> Account {
+    data_account(value.try_to_vec().unwrap(), owner)
+}
+
+fn packed_account<T: Pack>(state: T, owner: Pubkey) -> Account {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+
+    data_account(data, owner)
+}
+
+/// Custom program error code of a failed transaction.
+pub fn error_code(result: Result<(), BanksClientError>) -> u32 {
+    match result.expect_err("transaction succeeded").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        err => panic!("transaction failed without a custom error: {err:?}"),
+    }
+}
+
+/// Asserts the transaction failed with the given anchor or program error.
+pub fn assert_error(result: Result<(), BanksClientError>, expected: impl Into<AnchorError>) {
+    let expected = match expected.into() {
+        AnchorError::AnchorError(err) => err.error_code_number,
+        AnchorError::ProgramError(err) => panic!("not a custom error: {err:?}"),
+    };
+
+    assert_eq!(error_code(result), expected);
+}
+
+/// Asserts the transaction failed, for failures that abort the program without an error code.
+/// A panicking instruction takes the native bank down with it, so this has to be the last
+/// transaction of the test.
+pub fn assert_failed(result: Result<(), BanksClientError>) {
+    result.expect_err("transaction succeeded");
+}
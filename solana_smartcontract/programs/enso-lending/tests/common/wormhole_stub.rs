@@ -0,0 +1,48 @@
+//! Stand-in for the Wormhole core bridge. `post_message` checks the fee was paid, stores the
+//! payload in the message account and advances the emitter sequence, no guardian is involved.
+
+use anchor_lang::{
+    prelude::{AccountInfo, Pubkey, Rent},
+    solana_program::{
+        entrypoint::ProgramResult, program::invoke, program_error::ProgramError, system_instruction,
+        sysvar::Sysvar,
+    },
+    AnchorDeserialize, AnchorSerialize,
+};
+use wormhole_anchor_sdk::wormhole::{BridgeData, Instruction, SequenceTracker};
+
+pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let Instruction::PostMessage { payload, .. } =
+        Instruction::try_from_slice(data).map_err(|_| ProgramError::InvalidInstructionData)?
+    else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let [bridge, message, _emitter, sequence, payer, fee_collector, _clock, system_program, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let mut bridge_data = BridgeData::deserialize(&mut &bridge.data.borrow()[..])?;
+    if fee_collector.lamports() < bridge_data.last_lamports + bridge_data.fee() {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    bridge_data.last_lamports = fee_collector.lamports();
+    bridge_data.serialize(&mut &mut bridge.data.borrow_mut()[..])?;
+
+    invoke(
+        &system_instruction::create_account(
+            payer.key,
+            message.key,
+            Rent::get()?.minimum_balance(payload.len()),
+            payload.len() as u64,
+            program_id,
+        ),
+        &[payer.clone(), message.clone(), system_program.clone()],
+    )?;
+    message.data.borrow_mut().copy_from_slice(&payload);
+
+    let mut tracker = SequenceTracker::deserialize(&mut &sequence.data.borrow()[..])?;
+    tracker.sequence += 1;
+    tracker.serialize(&mut &mut sequence.data.borrow_mut()[..])?;
+
+    Ok(())
+}
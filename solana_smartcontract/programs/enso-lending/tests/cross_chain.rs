@@ -0,0 +1,552 @@
+
+mod common;
+
+use common::{
+    assert_error, assert_failed,
+    market::{
+        Loan, Market, BORROWER_FEE_PERCENT, COLLATERAL_AMOUNT, COLLATERAL_DECIMALS, COLLATERAL_PRICE, DURATION,
+        FOREIGN_CHAIN_ADDRESS, FOREIGN_CHAIN_ID, FOREIGN_EMITTER, FOREIGN_TOKEN_ADDRESS, INTEREST, LENDER_FEE_PERCENT,
+        LEND_AMOUNT, MAX_PRICE_AGE_SECONDS, PRICE_EXPONENT, TIER_ID,
+    },
+    Harness,
+};
+use enso_lending::{
+    common::{
+        LendOfferStatus, LoanOfferError, LoanOfferStatus, ParseVaaError, StatusTransitionError,
+        CANCEL_COLLATERAL_FUNCTION, CREATE_LOAN_OFFER_CROSS_CHAIN_FUNCTION, REFUND_COLLATERAL_CROSS_CHAIN_FUNCTION,
+        RELEASE_SWAPPED_COLLATERAL_FUNCTION, SWAP_COLLATERAL_CROSS_CHAIN_FUNCTION, UPDATE_DEPOSIT_COLLATERAL_CROSS_CHAIN_FUNCTION,
+        UPDATE_WITHDRAW_COLLATERAL_CROSS_CHAIN_FUNCTION,
+    },
+    instruction as args,
+    states::ForeignChain,
+};
+use enso_lending_client::{instructions as ix, pda};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+/// Older than the 30 minute window a posted VAA is accepted in.
+const EXPIRED_VAA_AGE: i64 = 31 * 60;
+
+async fn setup_wormhole(harness: &mut Harness) {
+    let system = harness.system.insecure_clone();
+    let emitter_address = FOREIGN_EMITTER.iter().map(|byte| format!("{byte:02x}")).collect();
+    let instructions = [
+        ix::init_wormhole(),
+        ix::init_foreign_chain_account(
+            system.pubkey(),
+            args::InitForeignChainAccount {
+                chain_id: FOREIGN_CHAIN_ID,
+                chain_address: FOREIGN_CHAIN_ADDRESS.to_string(),
+                emitter_address,
+            },
+        ),
+    ];
+    harness.process(&instructions, &[&system]).await.unwrap();
+}
+
+async fn setup(harness: &mut Harness) -> Market {
+    let market = Market::setup(harness).await;
+    setup_wormhole(harness).await;
+
+    market
+}
+
+fn create_payload(loan: &Loan, collateral_amount: u64) -> Vec<u8> {
+    format!(
+        "1,{},{},{},{},{},{},{},{},{},{},{},{}",
+        enso_lending::ID,
+        CREATE_LOAN_OFFER_CROSS_CHAIN_FUNCTION,
+        TIER_ID,
+        loan.lend_offer_id,
+        LEND_AMOUNT,
+        collateral_amount,
+        FOREIGN_TOKEN_ADDRESS,
+        loan.borrower.pubkey(),
+        DURATION,
+        INTEREST,
+        BORROWER_FEE_PERCENT,
+        LENDER_FEE_PERCENT,
+    )
+    .into_bytes()
+}
+
+fn deposit_payload(loan: &Loan, collateral_amount: u64) -> Vec<u8> {
+    format!(
+        "1,{},{},{},{},{},{}",
+        enso_lending::ID,
+        UPDATE_DEPOSIT_COLLATERAL_CROSS_CHAIN_FUNCTION,
+        loan.lend_offer_id,
+        collateral_amount,
+        FOREIGN_TOKEN_ADDRESS,
+        loan.borrower.pubkey(),
+    )
+    .into_bytes()
+}
+
+fn withdraw_payload(loan: &Loan, withdraw_amount: u64, remaining_amount: u64) -> Vec<u8> {
+    format!(
+        "1,{},{},{},{},{},{},{}",
+        enso_lending::ID,
+        UPDATE_WITHDRAW_COLLATERAL_CROSS_CHAIN_FUNCTION,
+        loan.lend_offer_id,
+        withdraw_amount,
+        remaining_amount,
+        FOREIGN_TOKEN_ADDRESS,
+        loan.borrower.pubkey(),
+    )
+    .into_bytes()
+}
+
+fn create_instruction(
+    harness: &Harness,
+    market: &Market,
+    loan: &Loan,
+    loan_offer_id: &str,
+    vaa_hash: [u8; 32],
+) -> anchor_lang::solana_program::instruction::Instruction {
+    ix::create_loan_offer_cross_chain(
+        ix::CreateLoanOfferCrossChainParams {
+            signer: harness.system.pubkey(),
+            borrower: loan.borrower.pubkey(),
+            lender: loan.lender.pubkey(),
+            lend_mint: market.lend_mint,
+            collateral_mint: market.foreign_collateral_mint,
+            receiver: harness.hot_wallet.pubkey(),
+            lend_offer_escrowed: loan.escrowed,
+            chain_id: FOREIGN_CHAIN_ID,
+        },
+        args::CreateLoanOfferCrossChain {
+            tier_id: TIER_ID.to_string(),
+            loan_offer_id: loan_offer_id.to_string(),
+            lend_offer_id: loan.lend_offer_id.clone(),
+            vaa_hash,
+        },
+    )
+}
+
+/// Loan created from a VAA carrying collateral locked on the foreign chain, left `Matched`.
+async fn open_cross_chain_loan(harness: &mut Harness, market: &Market, name: &str) -> Loan {
+    let loan = market.new_loan(harness, name, false).await;
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID, FOREIGN_EMITTER, &create_payload(&loan, COLLATERAL_AMOUNT)).await;
+    let instruction = create_instruction(harness, market, &loan, &loan.offer_id, vaa_hash);
+    let system = harness.system.insecure_clone();
+    harness.process(&[instruction], &[&system]).await.unwrap();
+
+    loan
+}
+
+#[tokio::test]
+async fn registers_foreign_chain() {
+    let mut harness = Harness::start().await;
+    setup(&mut harness).await;
+
+    let foreign_chain: ForeignChain = harness.account(pda::foreign_chain(FOREIGN_CHAIN_ID).0).await;
+    assert_eq!(foreign_chain.chain_address, FOREIGN_CHAIN_ADDRESS);
+    assert_eq!(foreign_chain.emitter_address, "07".repeat(32));
+    // `init_wormhole` posts a first message to claim the emitter sequence.
+    assert_eq!(harness.wormhole_sequence().await, 1);
+}
+
+#[tokio::test]
+async fn creates_loan_from_posted_vaa() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = open_cross_chain_loan(&mut harness, &market, "vaa").await;
+
+    let state = loan.state(&mut harness).await;
+    assert_eq!(state.status, LoanOfferStatus::Matched);
+    assert_eq!(state.collateral_amount, COLLATERAL_AMOUNT);
+    assert_eq!(state.collateral_mint_token, market.foreign_collateral_mint);
+    assert_eq!(loan.lend_offer(&mut harness).await.status, LendOfferStatus::Loaned);
+}
+
+#[tokio::test]
+async fn rejects_vaa_from_unknown_emitter() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "emitter", false).await;
+
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID, [8; 32], &create_payload(&loan, COLLATERAL_AMOUNT)).await;
+    let instruction = create_instruction(&harness, &market, &loan, &loan.offer_id, vaa_hash);
+    let system = harness.system.insecure_clone();
+    assert_error(harness.process(&[instruction], &[&system]).await, ParseVaaError::InvalidForeignEmitter);
+}
+
+#[tokio::test]
+async fn rejects_vaa_from_another_chain() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "chain", false).await;
+
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID + 1, FOREIGN_EMITTER, &create_payload(&loan, COLLATERAL_AMOUNT)).await;
+    let instruction = create_instruction(&harness, &market, &loan, &loan.offer_id, vaa_hash);
+    let system = harness.system.insecure_clone();
+    assert_error(harness.process(&[instruction], &[&system]).await, ParseVaaError::InvalidTargetChain);
+}
+
+/// Create payload cut to its first `fields` fields, as sent by foreign chain contracts without expected loan terms.
+fn truncated_create_payload(loan: &Loan, fields: usize) -> Vec<u8> {
+    let payload = String::from_utf8(create_payload(loan, COLLATERAL_AMOUNT)).unwrap();
+    payload.split(',').take(fields).collect::<Vec<_>>().join(",").into_bytes()
+}
+
+#[tokio::test]
+async fn creates_loan_from_payload_without_expected_terms() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "legacy", false).await;
+
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID, FOREIGN_EMITTER, &truncated_create_payload(&loan, 9)).await;
+    let instruction = create_instruction(&harness, &market, &loan, &loan.offer_id, vaa_hash);
+    let system = harness.system.insecure_clone();
+    harness.process(&[instruction], &[&system]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Matched);
+}
+
+#[tokio::test]
+async fn rejects_payload_with_partial_expected_terms() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "partial", false).await;
+
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID, FOREIGN_EMITTER, &truncated_create_payload(&loan, 11)).await;
+    let instruction = create_instruction(&harness, &market, &loan, &loan.offer_id, vaa_hash);
+    let system = harness.system.insecure_clone();
+    assert_error(harness.process(&[instruction], &[&system]).await, ParseVaaError::InvalidExpectedLoanTerms);
+}
+
+#[tokio::test]
+async fn rejects_expired_vaa() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "expired-vaa", false).await;
+
+    let posted_at = (harness.now().await - EXPIRED_VAA_AGE) as u32;
+    let vaa_hash = harness.post_vaa_at(FOREIGN_CHAIN_ID, FOREIGN_EMITTER, &create_payload(&loan, COLLATERAL_AMOUNT), posted_at);
+    let instruction = create_instruction(&harness, &market, &loan, &loan.offer_id, vaa_hash);
+    let system = harness.system.insecure_clone();
+    assert_error(harness.process(&[instruction], &[&system]).await, LoanOfferError::PostedVaaExpired);
+}
+
+#[tokio::test]
+async fn rejects_replayed_create_vaa() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "replay", false).await;
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID, FOREIGN_EMITTER, &create_payload(&loan, COLLATERAL_AMOUNT)).await;
+    let system = harness.system.insecure_clone();
+    let instruction = create_instruction(&harness, &market, &loan, &loan.offer_id, vaa_hash);
+    harness.process(std::slice::from_ref(&instruction), &[&system]).await.unwrap();
+
+    // Under a new loan id the lend offer is already taken.
+    let replay = create_instruction(&harness, &market, &loan, "loan-replay-2", vaa_hash);
+    assert_error(harness.process(&[replay], &[&system]).await, StatusTransitionError::InvalidTransition);
+
+    // Under the same loan id the loan account already exists.
+    harness.refresh_blockhash().await;
+    assert_failed(harness.process(&[instruction], &[&system]).await);
+}
+
+#[tokio::test]
+async fn tracks_collateral_deposited_and_withdrawn_on_the_foreign_chain() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = open_cross_chain_loan(&mut harness, &market, "update").await;
+    let system = harness.system.insecure_clone();
+
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID, FOREIGN_EMITTER, &deposit_payload(&loan, 2 * COLLATERAL_AMOUNT)).await;
+    let deposit = ix::update_deposit_collateral_cross_chain(
+        system.pubkey(),
+        loan.borrower.pubkey(),
+        market.foreign_collateral_mint,
+        FOREIGN_CHAIN_ID,
+        args::UpdateDepositCollateralCrossChain { loan_offer_id: loan.offer_id.clone(), vaa_hash },
+    );
+    harness.process(&[deposit], &[&system]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.collateral_amount, 2 * COLLATERAL_AMOUNT);
+
+    let payload = withdraw_payload(&loan, COLLATERAL_AMOUNT, COLLATERAL_AMOUNT);
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID, FOREIGN_EMITTER, &payload).await;
+    let withdraw = ix::update_withdraw_collateral_cross_chain(
+        system.pubkey(),
+        loan.borrower.pubkey(),
+        market.foreign_collateral_mint,
+        FOREIGN_CHAIN_ID,
+        args::UpdateWithdrawCollateralCrossChain { loan_offer_id: loan.offer_id.clone(), vaa_hash },
+    );
+    harness.process(std::slice::from_ref(&withdraw), &[&system]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.collateral_amount, COLLATERAL_AMOUNT);
+
+    // The amounts of a replayed withdrawal no longer add up to the collateral held.
+    harness.refresh_blockhash().await;
+    assert_error(harness.process(&[withdraw], &[&system]).await, LoanOfferError::WithdrawAmountNotMatch);
+}
+
+#[tokio::test]
+async fn repay_posts_refund_message() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = open_cross_chain_loan(&mut harness, &market, "repay").await;
+    market.fund_loan(&mut harness, &loan).await;
+    harness.set_token_balance(loan.borrower.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+
+    let sequence = harness.wormhole_sequence().await + 1;
+    let repay = ix::repay_loan_offer_cross_chain(
+        loan.borrower.pubkey(),
+        market.lend_mint,
+        market.foreign_collateral_mint,
+        FOREIGN_CHAIN_ID,
+        sequence,
+        args::RepayLoanOfferCrossChain { loan_offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[repay], &[&loan.borrower]).await.unwrap();
+
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::BorrowerPaid);
+    assert_eq!(harness.wormhole_sequence().await, sequence);
+    let message = String::from_utf8(harness.posted_message(sequence).await).unwrap();
+    assert_eq!(
+        message,
+        format!(
+            "{FOREIGN_CHAIN_ID},{FOREIGN_CHAIN_ADDRESS},{REFUND_COLLATERAL_CROSS_CHAIN_FUNCTION},{},{}",
+            loan.lend_offer_id,
+            loan.borrower.pubkey()
+        )
+    );
+}
+
+#[tokio::test]
+async fn expired_cross_chain_loan_is_liquidated() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = open_cross_chain_loan(&mut harness, &market, "liquidate").await;
+    market.fund_loan(&mut harness, &loan).await;
+    harness.advance_time(DURATION as i64 + 1).await;
+
+    let sequence = harness.wormhole_sequence().await + 1;
+    let system = harness.system.insecure_clone();
+    let start = ix::start_liquidate_expired_loan_cross_chain(
+        loan.borrower.pubkey(),
+        market.foreign_collateral_mint,
+        FOREIGN_CHAIN_ID,
+        sequence,
+        args::StartLiquidateExpiredLoanCrossChain { _loan_offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[start], &[&system]).await.unwrap();
+
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Liquidating);
+    assert_eq!(harness.wormhole_sequence().await, sequence);
+}
+
+#[tokio::test]
+async fn unhealthy_cross_chain_loan_is_liquidated() {
+    let mut harness = Harness::start().await;
+    let mut market = setup(&mut harness).await;
+    let loan = open_cross_chain_loan(&mut harness, &market, "health").await;
+    market.fund_loan(&mut harness, &loan).await;
+    // 15 tokens at 70 USD against 1000 USD borrowed.
+    market.set_collateral_price(&mut harness, 7_000_000_000).await;
+
+    let sequence = harness.wormhole_sequence().await + 1;
+    let system = harness.system.insecure_clone();
+    let start = ix::start_liquidate_health_loan_cross_chain(
+        loan.borrower.pubkey(),
+        &market.foreign_assets(),
+        FOREIGN_CHAIN_ID,
+        sequence,
+        args::StartLiquidateHealthLoanCrossChain { _loan_offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[start], &[&system]).await.unwrap();
+
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Liquidating);
+    assert_eq!(harness.wormhole_sequence().await, sequence);
+}
+
+#[tokio::test]
+async fn borrower_reclaims_collateral_of_a_vaa_never_used() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "cancel-collateral", false).await;
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID, FOREIGN_EMITTER, &create_payload(&loan, COLLATERAL_AMOUNT)).await;
+    let cancel = |sequence| {
+        ix::request_cancel_collateral_cross_chain(
+            loan.borrower.pubkey(),
+            loan.lender.pubkey(),
+            FOREIGN_CHAIN_ID,
+            sequence,
+            args::RequestCancelCollateralCrossChain {
+                tier_id: TIER_ID.to_string(),
+                lend_offer_id: loan.lend_offer_id.clone(),
+                vaa_hash,
+            },
+        )
+    };
+
+    // The VAA can still be used to create the loan.
+    let sequence = harness.wormhole_sequence().await + 1;
+    assert_error(
+        harness.process(&[cancel(sequence)], &[&loan.borrower]).await,
+        LoanOfferError::LoanOfferInValidity,
+    );
+
+    harness.advance_time(EXPIRED_VAA_AGE).await;
+    harness.process(&[cancel(sequence)], &[&loan.borrower]).await.unwrap();
+    let message = String::from_utf8(harness.posted_message(sequence).await).unwrap();
+    assert_eq!(
+        message,
+        format!(
+            "{FOREIGN_CHAIN_ID},{FOREIGN_CHAIN_ADDRESS},{CANCEL_COLLATERAL_FUNCTION},{},{}",
+            loan.lend_offer_id,
+            loan.borrower.pubkey()
+        )
+    );
+}
+
+#[tokio::test]
+async fn borrower_of_the_loan_can_not_cancel_it_as_loaned() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = open_cross_chain_loan(&mut harness, &market, "cancel-loaned").await;
+
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID, FOREIGN_EMITTER, &create_payload(&loan, COLLATERAL_AMOUNT)).await;
+    let sequence = harness.wormhole_sequence().await + 1;
+    let cancel = ix::request_cancel_loaned_cross_chain(
+        loan.borrower.pubkey(),
+        loan.lender.pubkey(),
+        FOREIGN_CHAIN_ID,
+        sequence,
+        args::RequestCancelLoanedCrossChain {
+            tier_id: TIER_ID.to_string(),
+            lend_offer_id: loan.lend_offer_id.clone(),
+            loan_offer_id: loan.offer_id.clone(),
+            vaa_hash,
+        },
+    );
+    assert_error(
+        harness.process(&[cancel], &[&loan.borrower]).await,
+        LoanOfferError::BorrowerSignedLoanOffer,
+    );
+}
+
+const SWAP_TOKEN_ADDRESS: &str = "0x5d4b::coin::COIN";
+
+/// Registers a collateral asset held on `chain_id` for the loan to swap into.
+async fn init_swap_collateral_asset(harness: &mut Harness, chain_id: u16) -> (Pubkey, common::market::Feed) {
+    let new_mint = Pubkey::new_unique();
+    let new_feed = common::market::Feed { address: Pubkey::new_unique(), id: [5; 32], price: COLLATERAL_PRICE };
+    harness.set_mint(new_mint, COLLATERAL_DECIMALS);
+    harness.set_price(new_feed.address, new_feed.id, new_feed.price, PRICE_EXPONENT).await;
+    let system = harness.system.insecure_clone();
+    let init_asset = ix::init_asset(
+        new_mint,
+        new_feed.address,
+        args::InitAsset {
+            name: "COIN".to_string(),
+            is_lend: false,
+            is_collateral: true,
+            price_feed_id: new_feed.id_hex(),
+            max_price_age_seconds: MAX_PRICE_AGE_SECONDS,
+            token_address: Some(SWAP_TOKEN_ADDRESS.to_string()),
+            chain_id,
+            max_collateral_amount: 0,
+            max_borrow_amount: 0,
+            isolated: false,
+            isolated_lend_mints: vec![],
+            isolated_debt_ceiling: 0,
+        },
+    );
+    harness.process(&[init_asset], &[&system]).await.unwrap();
+
+    (new_mint, new_feed)
+}
+
+fn swap_payload(loan: &Loan) -> Vec<u8> {
+    format!(
+        "1,{},{},{},{},{},{}",
+        enso_lending::ID,
+        SWAP_COLLATERAL_CROSS_CHAIN_FUNCTION,
+        loan.lend_offer_id,
+        COLLATERAL_AMOUNT,
+        SWAP_TOKEN_ADDRESS,
+        loan.borrower.pubkey(),
+    )
+    .into_bytes()
+}
+
+fn swap_instruction(
+    harness: &Harness,
+    market: &Market,
+    loan: &Loan,
+    new_collateral: (Pubkey, &common::market::Feed),
+    chain_id: u16,
+    vaa_hash: [u8; 32],
+    sequence: u64,
+) -> solana_sdk::instruction::Instruction {
+    ix::swap_collateral_cross_chain(
+        ix::SwapCollateralCrossChainParams {
+            signer: harness.system.pubkey(),
+            borrower: loan.borrower.pubkey(),
+            tier_id: TIER_ID.to_string(),
+            lend_mint: market.lend_mint,
+            lend_price_feed: market.lend_feed.address,
+            old_collateral_mint: market.foreign_collateral_mint,
+            new_collateral_mint: new_collateral.0,
+            new_collateral_price_feed: new_collateral.1.address,
+            chain_id,
+            sequence,
+        },
+        args::SwapCollateralCrossChain { loan_offer_id: loan.offer_id.clone(), vaa_hash },
+    )
+}
+
+#[tokio::test]
+async fn swaps_collateral_to_another_foreign_asset() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = open_cross_chain_loan(&mut harness, &market, "swap").await;
+    market.fund_loan(&mut harness, &loan).await;
+    let (new_mint, new_feed) = init_swap_collateral_asset(&mut harness, FOREIGN_CHAIN_ID).await;
+
+    let vaa_hash = harness.post_vaa(FOREIGN_CHAIN_ID, FOREIGN_EMITTER, &swap_payload(&loan)).await;
+    let sequence = harness.wormhole_sequence().await + 1;
+    let swap = swap_instruction(&harness, &market, &loan, (new_mint, &new_feed), FOREIGN_CHAIN_ID, vaa_hash, sequence);
+    let system = harness.system.insecure_clone();
+    harness.process(&[swap], &[&system]).await.unwrap();
+
+    let state = loan.state(&mut harness).await;
+    assert_eq!(state.collateral_mint_token, new_mint);
+    assert_eq!(harness.wormhole_sequence().await, sequence);
+    let message = String::from_utf8(harness.posted_message(sequence).await).unwrap();
+    assert_eq!(
+        message,
+        format!(
+            "{FOREIGN_CHAIN_ID},{FOREIGN_CHAIN_ADDRESS},{RELEASE_SWAPPED_COLLATERAL_FUNCTION},{},{},{FOREIGN_TOKEN_ADDRESS},{COLLATERAL_AMOUNT}",
+            loan.lend_offer_id,
+            loan.borrower.pubkey()
+        )
+    );
+}
+
+#[tokio::test]
+async fn rejects_swap_from_a_chain_not_holding_the_collateral() {
+    let mut harness = Harness::start().await;
+    let market = setup(&mut harness).await;
+    let loan = open_cross_chain_loan(&mut harness, &market, "swap-chain").await;
+    market.fund_loan(&mut harness, &loan).await;
+
+    let other_chain_id = FOREIGN_CHAIN_ID + 1;
+    let system = harness.system.insecure_clone();
+    let init_other_chain = ix::init_foreign_chain_account(
+        system.pubkey(),
+        args::InitForeignChainAccount {
+            chain_id: other_chain_id,
+            chain_address: FOREIGN_CHAIN_ADDRESS.to_string(),
+            emitter_address: FOREIGN_EMITTER.iter().map(|byte| format!("{byte:02x}")).collect(),
+        },
+    );
+    harness.process(&[init_other_chain], &[&system]).await.unwrap();
+    let (new_mint, new_feed) = init_swap_collateral_asset(&mut harness, other_chain_id).await;
+
+    let vaa_hash = harness.post_vaa(other_chain_id, FOREIGN_EMITTER, &swap_payload(&loan)).await;
+    let sequence = harness.wormhole_sequence().await + 1;
+    let swap = swap_instruction(&harness, &market, &loan, (new_mint, &new_feed), other_chain_id, vaa_hash, sequence);
+    assert_error(harness.process(&[swap], &[&system]).await, LoanOfferError::InvalidChainId);
+}
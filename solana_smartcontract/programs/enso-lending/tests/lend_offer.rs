@@ -0,0 +1,318 @@
+
+mod common;
+
+use common::{
+    assert_error,
+    market::{Market, COLLATERAL_AMOUNT, LEND_AMOUNT, TIER_ID},
+    Harness,
+};
+use enso_lending::{
+    common::{LendOfferError, LendOfferStatus},
+    instruction as args,
+};
+use enso_lending_client::{instructions as ix, pda};
+use solana_sdk::{instruction::AccountMeta, signer::Signer};
+
+const DAY: i64 = 24 * 60 * 60;
+
+#[tokio::test]
+async fn creates_lend_offer_into_hot_wallet() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let lender = market.new_lender(&mut harness);
+
+    market.create_lend_offer(&mut harness, &lender, "hot-wallet", None).await;
+
+    let lend_offer: enso_lending::states::LendOfferAccount =
+        harness.account(pda::lend_offer(&lender.pubkey(), "hot-wallet").0).await;
+    assert_eq!(lend_offer.status, LendOfferStatus::Created);
+    assert_eq!(lend_offer.amount, LEND_AMOUNT);
+    let hot_wallet_ata = pda::ata(&harness.hot_wallet.pubkey(), &market.lend_mint);
+    assert_eq!(harness.token_balance(hot_wallet_ata).await, LEND_AMOUNT);
+}
+
+#[tokio::test]
+async fn rejects_expiry_in_the_past() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let lender = market.new_lender(&mut harness);
+
+    let expires_at = harness.now().await - 1;
+    let instruction = ix::create_lend_offer(
+        lender.pubkey(),
+        market.lend_mint,
+        harness.hot_wallet.pubkey(),
+        market.lend_offer_args("past", Some(expires_at)),
+    );
+    assert_error(harness.process(&[instruction], &[&lender]).await, LendOfferError::InvalidExpiry);
+}
+
+#[tokio::test]
+async fn rejects_vault_for_offer_without_expiry() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let lender = market.new_lender(&mut harness);
+
+    let mut instruction = ix::create_lend_offer(
+        lender.pubkey(),
+        market.lend_mint,
+        harness.hot_wallet.pubkey(),
+        market.lend_offer_args("no-expiry", None),
+    );
+    let lend_offer_vault = pda::ata(&pda::lend_offer(&lender.pubkey(), "no-expiry").0, &market.lend_mint);
+    instruction.accounts[7] = AccountMeta::new(lend_offer_vault, false);
+    assert_error(harness.process(&[instruction], &[&lender]).await, LendOfferError::UnexpectedLendOfferVault);
+    assert!(harness.get_account(lend_offer_vault).await.is_none());
+}
+
+#[tokio::test]
+async fn edits_lend_offer_interest() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let lender = market.new_lender(&mut harness);
+    market.create_lend_offer(&mut harness, &lender, "edit", None).await;
+
+    let edit = ix::edit_lend_offer(
+        lender.pubkey(),
+        market.lend_mint,
+        harness.hot_wallet.pubkey(),
+        false,
+        args::EditLendOffer {
+            _offer_id: "edit".to_string(),
+            _tier_id: TIER_ID.to_string(),
+            interest: Some(8.0),
+            amount: None,
+            duration: None,
+        },
+    );
+    harness.process(&[edit], &[&lender]).await.unwrap();
+
+    let lend_offer: enso_lending::states::LendOfferAccount =
+        harness.account(pda::lend_offer(&lender.pubkey(), "edit").0).await;
+    assert_eq!(lend_offer.interest, 8.0);
+}
+
+/// Lets the tier take any lend amount from half to twice the fixed amount.
+async fn range_tier_amount(harness: &mut Harness, tier_id: &str) {
+    let system = harness.system.insecure_clone();
+    let edit = ix::edit_setting_account(
+        system.pubkey(),
+        harness.hot_wallet.pubkey(),
+        args::EditSettingAccount {
+            _tier_id: tier_id.to_string(),
+            amount: None,
+            duration: None,
+            lender_fee_percent: None,
+            borrower_fee_percent: None,
+            lend_mint_asset: None,
+            collateral_mint_assets: None,
+            min_amount: Some(LEND_AMOUNT / 2),
+            max_amount: Some(2 * LEND_AMOUNT),
+            amount_step: None,
+            min_duration: None,
+            max_duration: None,
+        },
+    );
+    harness.process(&[edit], &[&system]).await.unwrap();
+}
+
+fn edit_amount_args(offer_id: &str, tier_id: &str, amount: u64) -> args::EditLendOffer {
+    args::EditLendOffer {
+        _offer_id: offer_id.to_string(),
+        _tier_id: tier_id.to_string(),
+        interest: None,
+        amount: Some(amount),
+        duration: None,
+    }
+}
+
+#[tokio::test]
+async fn hot_wallet_offer_withdrawal_is_paid_by_the_operator() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    range_tier_amount(&mut harness, TIER_ID).await;
+    let lender = market.new_lender(&mut harness);
+    market.create_lend_offer(&mut harness, &lender, "withdraw", None).await;
+    let address = pda::lend_offer(&lender.pubkey(), "withdraw").0;
+
+    let edit = ix::edit_lend_offer(
+        lender.pubkey(),
+        market.lend_mint,
+        harness.hot_wallet.pubkey(),
+        false,
+        edit_amount_args("withdraw", TIER_ID, LEND_AMOUNT * 3 / 4),
+    );
+    harness.process(&[edit], &[&lender]).await.unwrap();
+    let lend_offer: enso_lending::states::LendOfferAccount = harness.account(address).await;
+    assert_eq!(lend_offer.status, LendOfferStatus::Created);
+    assert_eq!(lend_offer.amount, LEND_AMOUNT * 3 / 4);
+    assert_eq!(lend_offer.pending_withdraw_amount, LEND_AMOUNT / 4);
+    let lender_ata = pda::ata(&lender.pubkey(), &market.lend_mint);
+    assert_eq!(harness.token_balance(lender_ata).await, 0);
+
+    let system = harness.system.insecure_clone();
+    harness.set_token_balance(system.pubkey(), market.lend_mint, LEND_AMOUNT);
+    let withdraw = ix::system_withdraw_lend_offer(
+        system.pubkey(),
+        lender.pubkey(),
+        market.lend_mint,
+        args::SystemWithdrawLendOffer { _offer_id: "withdraw".to_string() },
+    );
+    harness.process(std::slice::from_ref(&withdraw), &[&system]).await.unwrap();
+    let lend_offer: enso_lending::states::LendOfferAccount = harness.account(address).await;
+    assert_eq!(lend_offer.pending_withdraw_amount, 0);
+    assert_eq!(harness.token_balance(lender_ata).await, LEND_AMOUNT / 4);
+
+    harness.refresh_blockhash().await;
+    assert_error(harness.process(&[withdraw], &[&system]).await, LendOfferError::InvalidLendAmount);
+}
+
+#[tokio::test]
+async fn rejects_edit_under_another_tier() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let system = harness.system.insecure_clone();
+    let mut setting_args = market.setting_args();
+    setting_args.tier_id = "tier-2".to_string();
+    let init = ix::init_setting_account(system.pubkey(), harness.hot_wallet.pubkey(), setting_args);
+    harness.process(&[init], &[&system]).await.unwrap();
+    range_tier_amount(&mut harness, "tier-2").await;
+
+    let lender = market.new_lender(&mut harness);
+    harness.set_token_balance(lender.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+    market.create_lend_offer(&mut harness, &lender, "tier", None).await;
+    let lend_offer: enso_lending::states::LendOfferAccount =
+        harness.account(pda::lend_offer(&lender.pubkey(), "tier").0).await;
+    assert_eq!(lend_offer.tier_id, TIER_ID);
+
+    let edit = ix::edit_lend_offer(
+        lender.pubkey(),
+        market.lend_mint,
+        harness.hot_wallet.pubkey(),
+        false,
+        edit_amount_args("tier", "tier-2", 2 * LEND_AMOUNT),
+    );
+    assert_error(harness.process(&[edit], &[&lender]).await, LendOfferError::TierIdNotMatch);
+}
+
+#[tokio::test]
+async fn cancel_is_completed_by_the_operator() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let lender = market.new_lender(&mut harness);
+    market.create_lend_offer(&mut harness, &lender, "cancel", None).await;
+    let address = pda::lend_offer(&lender.pubkey(), "cancel").0;
+
+    let cancel = ix::cancel_lend_offer(
+        lender.pubkey(),
+        market.lend_mint,
+        false,
+        args::CancelLendOffer { _offer_id: "cancel".to_string() },
+    );
+    harness.process(&[cancel], &[&lender]).await.unwrap();
+    let lend_offer: enso_lending::states::LendOfferAccount = harness.account(address).await;
+    assert_eq!(lend_offer.status, LendOfferStatus::Canceling);
+
+    let system = harness.system.insecure_clone();
+    let system_cancel = ix::system_cancel_lend_offer(
+        system.pubkey(),
+        lender.pubkey(),
+        market.lend_mint,
+        args::SystemCancelLendOffer {
+            _offer_id: "cancel".to_string(),
+            _tier_id: TIER_ID.to_string(),
+            waiting_interest: 0,
+        },
+    );
+    harness.process(&[system_cancel], &[&system]).await.unwrap();
+    let lend_offer: enso_lending::states::LendOfferAccount = harness.account(address).await;
+    assert_eq!(lend_offer.status, LendOfferStatus::Canceled);
+    let lender_ata = pda::ata(&lender.pubkey(), &market.lend_mint);
+    assert_eq!(harness.token_balance(lender_ata).await, LEND_AMOUNT);
+
+    let close = ix::close_lend_offer(
+        lender.pubkey(),
+        lender.pubkey(),
+        market.lend_mint,
+        false,
+        None,
+        args::CloseLendOffer { offer_id: "cancel".to_string() },
+    );
+    harness.process(&[close], &[&lender]).await.unwrap();
+    assert!(harness.get_account(address).await.is_none());
+}
+
+#[tokio::test]
+async fn escrowed_offer_is_canceled_from_its_vault() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let lender = market.new_lender(&mut harness);
+    let expires_at = harness.now().await + DAY;
+    market.create_lend_offer(&mut harness, &lender, "escrow-cancel", Some(expires_at)).await;
+
+    let cancel = ix::cancel_lend_offer(
+        lender.pubkey(),
+        market.lend_mint,
+        true,
+        args::CancelLendOffer { _offer_id: "escrow-cancel".to_string() },
+    );
+    harness.process(&[cancel], &[&lender]).await.unwrap();
+
+    let lend_offer: enso_lending::states::LendOfferAccount =
+        harness.account(pda::lend_offer(&lender.pubkey(), "escrow-cancel").0).await;
+    assert_eq!(lend_offer.status, LendOfferStatus::Canceled);
+    let lender_ata = pda::ata(&lender.pubkey(), &market.lend_mint);
+    assert_eq!(harness.token_balance(lender_ata).await, LEND_AMOUNT);
+}
+
+#[tokio::test]
+async fn reclaims_expired_lend_offer_only_after_expiry() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let lender = market.new_lender(&mut harness);
+    let cranker = harness.new_wallet(common::LAMPORTS_PER_SOL);
+    let expires_at = harness.now().await + DAY;
+    market.create_lend_offer(&mut harness, &lender, "reclaim", Some(expires_at)).await;
+
+    let reclaim = ix::reclaim_expired_lend_offer(
+        cranker.pubkey(),
+        lender.pubkey(),
+        market.lend_mint,
+        args::ReclaimExpiredLendOffer { _offer_id: "reclaim".to_string() },
+    );
+    assert_error(
+        harness.process(std::slice::from_ref(&reclaim), &[&cranker]).await,
+        LendOfferError::LendOfferNotExpired,
+    );
+
+    harness.advance_time(2 * DAY).await;
+    let cranker_before = harness.lamports(cranker.pubkey()).await;
+    harness.process(&[reclaim], &[&cranker]).await.unwrap();
+
+    let lender_ata = pda::ata(&lender.pubkey(), &market.lend_mint);
+    assert_eq!(harness.token_balance(lender_ata).await, LEND_AMOUNT);
+    assert!(harness.get_account(pda::lend_offer(&lender.pubkey(), "reclaim").0).await.is_none());
+    assert!(harness.lamports(cranker.pubkey()).await > cranker_before);
+}
+
+#[tokio::test]
+async fn loaned_offer_stays_open_while_the_loan_is_active() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "close-loaned").await;
+    assert_eq!(loan.lend_offer(&mut harness).await.status, LendOfferStatus::Loaned);
+
+    let close = ix::close_lend_offer(
+        loan.lender.pubkey(),
+        loan.lender.pubkey(),
+        market.lend_mint,
+        false,
+        Some(loan.address()),
+        args::CloseLendOffer { offer_id: loan.lend_offer_id.clone() },
+    );
+    assert_error(
+        harness.process(&[close], &[&loan.lender]).await,
+        LendOfferError::InvalidOfferStatus,
+    );
+    assert_eq!(loan.state(&mut harness).await.collateral_amount, COLLATERAL_AMOUNT);
+}
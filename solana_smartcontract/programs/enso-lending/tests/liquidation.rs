@@ -0,0 +1,394 @@
+
+mod common;
+
+use common::{
+    assert_error,
+    market::{Loan, Market, COLLATERAL_AMOUNT, DURATION, LEND_AMOUNT, LEND_DECIMALS, MAX_PRICE_AGE_SECONDS, SOL_COLLATERAL_AMOUNT},
+    Harness, LAMPORTS_PER_SOL,
+};
+use enso_lending::{
+    amount::{self, LenderRepayAmountParams},
+    common::{LendOfferError, LendOfferStatus, LiquidateOfferError, LoanOfferError, LoanOfferStatus, LIQUIDATION_BONUS_PERCENT},
+    instruction as args,
+    risk::{self, PricedLoanParams},
+};
+use enso_lending_client::{instructions as ix, pda};
+use pyth_solana_receiver_sdk::error::GetPriceError;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+/// 15 tokens at 70 USD against 1000 USD borrowed, a health ratio of 1.05.
+const UNHEALTHY_COLLATERAL_PRICE: i64 = 7_000_000_000;
+
+async fn funded_token_loan(harness: &mut Harness, market: &Market, name: &str) -> Loan {
+    let loan = market.open_token_loan(harness, name).await;
+    market.fund_loan(harness, &loan).await;
+
+    loan
+}
+
+fn start_health(market: &Market, loan: &Loan) -> anchor_lang::solana_program::instruction::Instruction {
+    ix::start_liquidate_loan_offer_health(
+        loan.borrower.pubkey(),
+        &market.token_assets(),
+        args::StartLiquidateLoanOfferHealth { _offer_id: loan.offer_id.clone() },
+    )
+}
+
+fn start_expired(market: &Market, loan: &Loan) -> anchor_lang::solana_program::instruction::Instruction {
+    ix::start_liquidate_loan_offer_expired(
+        loan.borrower.pubkey(),
+        market.collateral_mint,
+        args::StartLiquidateLoanOfferExpired { _offer_id: loan.offer_id.clone() },
+    )
+}
+
+#[tokio::test]
+async fn healthy_loan_can_not_be_liquidated() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = funded_token_loan(&mut harness, &market, "healthy").await;
+
+    let system = harness.system.insecure_clone();
+    assert_error(
+        harness.process(&[start_health(&market, &loan)], &[&system]).await,
+        LoanOfferError::HealthRatioInvalid,
+    );
+}
+
+#[tokio::test]
+async fn liquidates_unhealthy_loan_and_settles_the_lender() {
+    let mut harness = Harness::start().await;
+    let mut market = Market::setup(&mut harness).await;
+    let loan = funded_token_loan(&mut harness, &market, "unhealthy").await;
+    market.set_collateral_price(&mut harness, UNHEALTHY_COLLATERAL_PRICE).await;
+
+    let system = harness.system.insecure_clone();
+    harness.process(&[start_health(&market, &loan)], &[&system]).await.unwrap();
+    let state = loan.state(&mut harness).await;
+    assert_eq!(state.status, LoanOfferStatus::Liquidating);
+    assert_eq!(state.liquidating_price, Some(70.0));
+    let hot_wallet_ata = pda::ata(&harness.hot_wallet.pubkey(), &market.collateral_mint);
+    assert_eq!(harness.token_balance(hot_wallet_ata).await, COLLATERAL_AMOUNT);
+
+    // The operator sold the collateral for 1050 USDC, the surplus over debt and interest goes back
+    // to the borrower.
+    let finish = ix::finish_liquidate_contract(
+        loan.borrower.pubkey(),
+        market.lend_mint,
+        market.collateral_mint,
+        args::FinishLiquidateContract {
+            _loan_offer_id: loan.offer_id.clone(),
+            collateral_swapped_amount: LEND_AMOUNT * 105 / 100,
+            liquidated_price: 70,
+            liquidated_tx: "liquidation-tx".to_string(),
+        },
+    );
+    harness.process(&[finish], &[&system]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Liquidated);
+    let borrower_lend_ata = pda::ata(&loan.borrower.pubkey(), &market.lend_mint);
+    let surplus = harness.token_balance(borrower_lend_ata).await - LEND_AMOUNT;
+    assert!(surplus > 0 && surplus < LEND_AMOUNT * 5 / 100);
+
+    // Only the borrower fee comes out of the surplus into the treasury, there is no liquidation penalty.
+    let treasury = market.treasury(&mut harness).await;
+    assert!(treasury.total_borrower_fee > 0);
+    let treasury_ata = pda::treasury_ata(&market.lend_mint);
+    assert_eq!(harness.token_balance(treasury_ata).await, treasury.total_borrower_fee);
+
+    let close = || {
+        ix::close_lend_offer(
+            loan.lender.pubkey(),
+            loan.lender.pubkey(),
+            market.lend_mint,
+            false,
+            Some(loan.address()),
+            args::CloseLendOffer { offer_id: loan.lend_offer_id.clone() },
+        )
+    };
+    assert_error(
+        harness.process(&[close()], &[&loan.lender]).await,
+        LendOfferError::InvalidOfferStatus,
+    );
+
+    // The lender is settled once the loan finishes, only then the lend offer can be closed.
+    let settle = ix::system_finish_loan_offer(
+        system.pubkey(),
+        loan.borrower.pubkey(),
+        loan.lender.pubkey(),
+        market.lend_mint,
+        loan.state(&mut harness).await.position_mint,
+        false,
+        args::SystemFinishLoanOffer {
+            _loan_offer_id: loan.offer_id.clone(),
+            loan_amount: LEND_AMOUNT,
+            interest_amount: 0,
+        },
+    );
+    harness.process(&[settle], &[&system]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Finished);
+    harness.refresh_blockhash().await;
+    harness.process(&[close()], &[&loan.lender]).await.unwrap();
+    assert!(harness.get_account(loan.lend_offer_address()).await.is_none());
+}
+
+#[tokio::test]
+async fn expired_loan_is_liquidated_only_after_its_duration() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = funded_token_loan(&mut harness, &market, "expired").await;
+
+    let system = harness.system.insecure_clone();
+    assert_error(
+        harness.process(&[start_expired(&market, &loan)], &[&system]).await,
+        LoanOfferError::LoanOfferNotExpired,
+    );
+
+    harness.advance_time(DURATION as i64 + 1).await;
+    harness.process(&[start_expired(&market, &loan)], &[&system]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Liquidating);
+}
+
+#[tokio::test]
+async fn expired_native_loan_moves_sol_to_the_hot_wallet() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_native_loan(&mut harness, "native-expired").await;
+    market.fund_loan(&mut harness, &loan).await;
+
+    harness.advance_time(DURATION as i64 + 1).await;
+    let before = harness.lamports(harness.hot_wallet.pubkey()).await;
+    let system = harness.system.insecure_clone();
+    let start = ix::start_liquidate_loan_offer_native_expired(
+        loan.borrower.pubkey(),
+        args::StartLiquidateLoanOfferNativeExpired { _offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[start], &[&system]).await.unwrap();
+
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Liquidating);
+    assert_eq!(harness.lamports(harness.hot_wallet.pubkey()).await, before + SOL_COLLATERAL_AMOUNT);
+}
+
+#[tokio::test]
+async fn unhealthy_native_loan_is_liquidated() {
+    let mut harness = Harness::start().await;
+    let mut market = Market::setup(&mut harness).await;
+    let loan = market.open_native_loan(&mut harness, "native-health").await;
+    market.fund_loan(&mut harness, &loan).await;
+    // 10 SOL at 100 USD against 1000 USD borrowed.
+    market.set_collateral_price(&mut harness, 10_000_000_000).await;
+
+    let system = harness.system.insecure_clone();
+    let start = ix::start_liquidate_loan_offer_native_health(
+        loan.borrower.pubkey(),
+        &market.native_assets(),
+        args::StartLiquidateLoanOfferNativeHealth { _offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[start], &[&system]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Liquidating);
+}
+
+/// Keeper holding enough of the lend mint to repay a loan with its interest and fees.
+fn new_keeper(harness: &mut Harness, market: &Market) -> Keypair {
+    let keeper = harness.new_wallet(LAMPORTS_PER_SOL);
+    harness.set_token_balance(keeper.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+
+    keeper
+}
+
+async fn settle_native(
+    harness: &mut Harness,
+    market: &Market,
+    loan: &Loan,
+    keeper: &Keypair,
+) -> Result<(), solana_program_test::BanksClientError> {
+    let position_mint = loan.state(harness).await.position_mint;
+    let settle = ix::liquidate_loan_offer_native(
+        keeper.pubkey(),
+        loan.borrower.pubkey(),
+        loan.lender.pubkey(),
+        position_mint,
+        false,
+        &market.native_assets(),
+        args::LiquidateLoanOfferNative { _offer_id: loan.offer_id.clone() },
+    );
+
+    harness.process(&[settle], &[keeper]).await
+}
+
+#[tokio::test]
+async fn keeper_settles_unhealthy_native_loan_without_the_hot_wallet() {
+    let mut harness = Harness::start().await;
+    let mut market = Market::setup(&mut harness).await;
+    let loan = market.open_native_loan(&mut harness, "native-settle").await;
+    market.fund_loan(&mut harness, &loan).await;
+    // 10 SOL at 115 USD against 1000 USD borrowed, a health ratio of 1.15.
+    market.set_collateral_price(&mut harness, 11_500_000_000).await;
+
+    let keeper = new_keeper(&mut harness, &market);
+    let keeper_ata = pda::ata(&keeper.pubkey(), &market.lend_mint);
+    let lender_ata = pda::ata(&loan.lender.pubkey(), &market.lend_mint);
+    let treasury_ata = pda::treasury_ata(&market.lend_mint);
+    let keeper_balance = harness.token_balance(keeper_ata).await;
+    let lender_balance = harness.token_balance(lender_ata).await;
+    let treasury_balance = harness.token_balance(treasury_ata).await;
+    let keeper_lamports = harness.lamports(keeper.pubkey()).await;
+    let borrower_lamports = harness.lamports(loan.borrower.pubkey()).await;
+    let hot_wallet_lamports = harness.lamports(harness.hot_wallet.pubkey()).await;
+    let loan_lamports = harness.lamports(loan.address()).await;
+
+    settle_native(&mut harness, &market, &loan, &keeper).await.unwrap();
+
+    let state = loan.state(&mut harness).await;
+    assert_eq!(state.status, LoanOfferStatus::Finished);
+    assert_eq!(state.collateral_amount, 0);
+    assert_eq!(state.liquidating_price, Some(115.0));
+
+    // The lender gets principal and interest less its fee, the fees go to the treasury.
+    let repaid = keeper_balance - harness.token_balance(keeper_ata).await;
+    let lender_amount = harness.token_balance(lender_ata).await - lender_balance;
+    assert!(lender_amount > LEND_AMOUNT);
+    assert_eq!(harness.token_balance(treasury_ata).await - treasury_balance, repaid - lender_amount);
+    let treasury = market.treasury(&mut harness).await;
+    assert_eq!(treasury.total_lender_fee + treasury.total_borrower_fee, repaid - lender_amount);
+
+    let seized = risk::get_seized_collateral(
+        &PricedLoanParams {
+            collateral_amount: SOL_COLLATERAL_AMOUNT,
+            collateral_decimals: 9,
+            collateral_price: 115.0,
+            lend_amount: repaid,
+            lend_decimals: LEND_DECIMALS,
+            lend_price: 1.0,
+        },
+        LIQUIDATION_BONUS_PERCENT,
+    );
+    assert!(seized < SOL_COLLATERAL_AMOUNT);
+    assert_eq!(harness.lamports(keeper.pubkey()).await, keeper_lamports + seized);
+    assert_eq!(
+        harness.lamports(loan.borrower.pubkey()).await,
+        borrower_lamports + SOL_COLLATERAL_AMOUNT - seized
+    );
+    assert_eq!(harness.lamports(harness.hot_wallet.pubkey()).await, hot_wallet_lamports);
+    assert_eq!(harness.lamports(loan.address()).await, loan_lamports - SOL_COLLATERAL_AMOUNT);
+}
+
+#[tokio::test]
+async fn healthy_native_loan_is_settled_only_after_its_duration() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_native_loan(&mut harness, "native-settle-expired").await;
+    market.fund_loan(&mut harness, &loan).await;
+    let keeper = new_keeper(&mut harness, &market);
+
+    assert_error(
+        settle_native(&mut harness, &market, &loan, &keeper).await,
+        LoanOfferError::HealthRatioInvalid,
+    );
+
+    harness.advance_time(DURATION as i64 + 1).await;
+    market.refresh_prices(&mut harness).await;
+    settle_native(&mut harness, &market, &loan, &keeper).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Finished);
+}
+
+#[tokio::test]
+async fn native_loan_is_not_settled_on_stale_prices() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_native_loan(&mut harness, "native-settle-stale").await;
+    market.fund_loan(&mut harness, &loan).await;
+    let keeper = new_keeper(&mut harness, &market);
+
+    harness.advance_time(MAX_PRICE_AGE_SECONDS as i64 + 1).await;
+    assert_error(
+        settle_native(&mut harness, &market, &loan, &keeper).await,
+        GetPriceError::PriceTooOld,
+    );
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::FundTransferred);
+}
+
+#[tokio::test]
+async fn token_collateral_loan_can_not_be_settled_as_native() {
+    let mut harness = Harness::start().await;
+    let mut market = Market::setup(&mut harness).await;
+    let loan = funded_token_loan(&mut harness, &market, "token-settle").await;
+    market.set_collateral_price(&mut harness, UNHEALTHY_COLLATERAL_PRICE).await;
+    let keeper = new_keeper(&mut harness, &market);
+
+    assert_error(
+        settle_native(&mut harness, &market, &loan, &keeper).await,
+        LiquidateOfferError::NotNativeCollateral,
+    );
+}
+
+#[tokio::test]
+async fn operator_reverts_a_started_liquidation() {
+    let mut harness = Harness::start().await;
+    let mut market = Market::setup(&mut harness).await;
+    let loan = funded_token_loan(&mut harness, &market, "revert").await;
+    market.set_collateral_price(&mut harness, UNHEALTHY_COLLATERAL_PRICE).await;
+    let system = harness.system.insecure_clone();
+    harness.process(&[start_health(&market, &loan)], &[&system]).await.unwrap();
+
+    let revert = ix::system_revert_status(
+        system.pubkey(),
+        loan.borrower.pubkey(),
+        args::SystemRevertStatus { _offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[revert], &[&system]).await.unwrap();
+
+    let state = loan.state(&mut harness).await;
+    assert_eq!(state.status, LoanOfferStatus::FundTransferred);
+    assert_eq!(state.liquidating_price, None);
+}
+
+#[tokio::test]
+async fn repaid_loan_is_finished_by_the_operator() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = funded_token_loan(&mut harness, &market, "finish").await;
+    harness.set_token_balance(loan.borrower.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+    let repay = ix::repay_loan_offer(
+        loan.borrower.pubkey(),
+        market.lend_mint,
+        market.collateral_mint,
+        args::RepayLoanOffer { _loan_offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[repay], &[&loan.borrower]).await.unwrap();
+
+    let state = loan.state(&mut harness).await;
+    let system = harness.system.insecure_clone();
+    let finish = ix::system_finish_loan_offer(
+        system.pubkey(),
+        loan.borrower.pubkey(),
+        loan.lender.pubkey(),
+        market.lend_mint,
+        state.position_mint,
+        false,
+        args::SystemFinishLoanOffer {
+            _loan_offer_id: loan.offer_id.clone(),
+            loan_amount: LEND_AMOUNT,
+            interest_amount: LEND_AMOUNT / 100,
+        },
+    );
+    harness.process(&[finish], &[&system]).await.unwrap();
+
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Finished);
+    let lender_ata = pda::ata(&loan.lender.pubkey(), &market.lend_mint);
+    assert!(harness.token_balance(lender_ata).await > LEND_AMOUNT);
+    assert_eq!(loan.lend_offer(&mut harness).await.status, LendOfferStatus::Loaned);
+
+    let lender_fee = amount::get_lender_fee_amount(LenderRepayAmountParams {
+        lender_fee_percent: state.lender_fee_percent,
+        interest: state.interest,
+        duration: state.duration,
+        borrow_amount: LEND_AMOUNT,
+    });
+    let treasury = market.treasury(&mut harness).await;
+    assert!(lender_fee > 0);
+    assert_eq!(treasury.total_lender_fee, lender_fee);
+    assert_eq!(
+        harness.token_balance(pda::treasury_ata(&market.lend_mint)).await,
+        treasury.total_borrower_fee + lender_fee
+    );
+}
@@ -0,0 +1,443 @@
+
+mod common;
+
+use anchor_spl::token::spl_token::native_mint;
+use common::{
+    assert_error, assert_failed,
+    market::{Loan, Market, COLLATERAL_AMOUNT, LEND_AMOUNT, MAX_PRICE_AGE_SECONDS, SOL_COLLATERAL_AMOUNT, TIER_ID},
+    Harness,
+};
+use enso_lending::{
+    amount::{self, LenderRepayAmountParams, TotalRepayLoanAmountParams},
+    common::{LendOfferError, LoanOfferError, LoanOfferStatus, RepayOfferError},
+    instruction as args,
+    states::LoanOfferAccount,
+};
+use enso_lending_client::{instructions as ix, pda};
+use pyth_solana_receiver_sdk::error::GetPriceError;
+use solana_sdk::signer::Signer;
+
+#[tokio::test]
+async fn create_fund_and_repay_token_loan() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "repay").await;
+
+    let state = loan.state(&mut harness).await;
+    assert_eq!(state.status, LoanOfferStatus::Matched);
+    assert_eq!(state.collateral_amount, COLLATERAL_AMOUNT);
+    let vault = pda::vault(&loan.borrower.pubkey(), &market.collateral_mint);
+    assert_eq!(harness.token_balance(vault).await, COLLATERAL_AMOUNT);
+
+    market.fund_loan(&mut harness, &loan).await;
+    let borrower_lend_ata = pda::ata(&loan.borrower.pubkey(), &market.lend_mint);
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::FundTransferred);
+    assert_eq!(harness.token_balance(borrower_lend_ata).await, LEND_AMOUNT);
+
+    // Interest and fee on top of the principal.
+    harness.set_token_balance(loan.borrower.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+    let repay = ix::repay_loan_offer(
+        loan.borrower.pubkey(),
+        market.lend_mint,
+        market.collateral_mint,
+        args::RepayLoanOffer { _loan_offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[repay], &[&loan.borrower]).await.unwrap();
+
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::BorrowerPaid);
+    assert_eq!(harness.token_balance(vault).await, 0);
+    let borrower_collateral_ata = pda::ata(&loan.borrower.pubkey(), &market.collateral_mint);
+    assert_eq!(harness.token_balance(borrower_collateral_ata).await, 10 * COLLATERAL_AMOUNT);
+
+    // The borrower fee is held by the treasury, the rest goes to the hot wallet.
+    let state = loan.state(&mut harness).await;
+    let borrower_fee = amount::get_borrower_fee_amount(TotalRepayLoanAmountParams {
+        borrower_fee_percent: state.borrower_fee_percent,
+        interest: state.interest,
+        duration: state.duration,
+        borrow_amount: state.borrow_amount,
+    });
+    let treasury = market.treasury(&mut harness).await;
+    assert!(borrower_fee > 0);
+    assert_eq!(treasury.total_borrower_fee, borrower_fee);
+    assert_eq!(treasury.total_lender_fee, 0);
+    assert_eq!(harness.token_balance(pda::treasury_ata(&market.lend_mint)).await, borrower_fee);
+}
+
+#[tokio::test]
+async fn create_and_repay_native_loan() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_native_loan(&mut harness, "native").await;
+    assert_eq!(loan.state(&mut harness).await.collateral_amount, SOL_COLLATERAL_AMOUNT);
+
+    market.fund_loan(&mut harness, &loan).await;
+    harness.set_token_balance(loan.borrower.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+    let before = harness.lamports(loan.borrower.pubkey()).await;
+    let repay = ix::repay_loan_offer_native(
+        loan.borrower.pubkey(),
+        market.lend_mint,
+        args::RepayLoanOfferNative { _loan_offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[repay], &[&loan.borrower]).await.unwrap();
+
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::BorrowerPaid);
+    assert_eq!(harness.lamports(loan.borrower.pubkey()).await, before + SOL_COLLATERAL_AMOUNT);
+}
+
+/// Amount the borrower repays and the part of it the lender receives.
+fn repay_amounts(state: &LoanOfferAccount) -> (u64, u64) {
+    let repaid = amount::get_total_repay_loan_amount(TotalRepayLoanAmountParams {
+        borrower_fee_percent: state.borrower_fee_percent,
+        interest: state.interest,
+        duration: state.duration,
+        borrow_amount: state.borrow_amount,
+    });
+    let lender_amount = amount::get_lender_repay_amount(LenderRepayAmountParams {
+        lender_fee_percent: state.lender_fee_percent,
+        interest: state.interest,
+        duration: state.duration,
+        borrow_amount: state.borrow_amount,
+    });
+
+    (repaid, lender_amount)
+}
+
+#[tokio::test]
+async fn repays_token_loan_straight_to_the_lender() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "repay-to-lender").await;
+    market.fund_loan(&mut harness, &loan).await;
+    harness.set_token_balance(loan.borrower.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+
+    let state = loan.state(&mut harness).await;
+    let (repaid, lender_amount) = repay_amounts(&state);
+    assert!(lender_amount > LEND_AMOUNT && repaid > lender_amount);
+    let hot_wallet_ata = pda::ata(&harness.hot_wallet.pubkey(), &market.lend_mint);
+    let hot_wallet_balance = harness.token_balance(hot_wallet_ata).await;
+
+    let repay = ix::repay_loan_offer_to_lender(
+        loan.borrower.pubkey(),
+        loan.lender.pubkey(),
+        state.position_mint,
+        false,
+        market.lend_mint,
+        market.collateral_mint,
+        args::RepayLoanOfferToLender { _loan_offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[repay], &[&loan.borrower]).await.unwrap();
+
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Finished);
+    let lender_ata = pda::ata(&loan.lender.pubkey(), &market.lend_mint);
+    assert_eq!(harness.token_balance(lender_ata).await, lender_amount);
+    assert_eq!(harness.token_balance(pda::treasury_ata(&market.lend_mint)).await, repaid - lender_amount);
+    assert_eq!(harness.token_balance(hot_wallet_ata).await, hot_wallet_balance);
+    let treasury = market.treasury(&mut harness).await;
+    assert_eq!(treasury.total_lender_fee + treasury.total_borrower_fee, repaid - lender_amount);
+    let borrower_lend_ata = pda::ata(&loan.borrower.pubkey(), &market.lend_mint);
+    assert_eq!(harness.token_balance(borrower_lend_ata).await, 2 * LEND_AMOUNT - repaid);
+    let vault = pda::vault(&loan.borrower.pubkey(), &market.collateral_mint);
+    assert_eq!(harness.token_balance(vault).await, 0);
+    let borrower_collateral_ata = pda::ata(&loan.borrower.pubkey(), &market.collateral_mint);
+    assert_eq!(harness.token_balance(borrower_collateral_ata).await, 10 * COLLATERAL_AMOUNT);
+}
+
+#[tokio::test]
+async fn repays_native_loan_straight_to_the_lender() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_native_loan(&mut harness, "native-to-lender").await;
+    market.fund_loan(&mut harness, &loan).await;
+    harness.set_token_balance(loan.borrower.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+
+    let state = loan.state(&mut harness).await;
+    let (repaid, lender_amount) = repay_amounts(&state);
+    let before = harness.lamports(loan.borrower.pubkey()).await;
+    let repay = ix::repay_loan_offer_native_to_lender(
+        loan.borrower.pubkey(),
+        loan.lender.pubkey(),
+        state.position_mint,
+        false,
+        market.lend_mint,
+        args::RepayLoanOfferNativeToLender { _loan_offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[repay], &[&loan.borrower]).await.unwrap();
+
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Finished);
+    let lender_ata = pda::ata(&loan.lender.pubkey(), &market.lend_mint);
+    assert_eq!(harness.token_balance(lender_ata).await, lender_amount);
+    assert_eq!(harness.token_balance(pda::treasury_ata(&market.lend_mint)).await, repaid - lender_amount);
+    assert_eq!(harness.lamports(loan.borrower.pubkey()).await, before + SOL_COLLATERAL_AMOUNT);
+}
+
+#[tokio::test]
+async fn rejects_repay_to_lender_without_the_position_token() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "repay-no-position").await;
+    market.fund_loan(&mut harness, &loan).await;
+    harness.set_token_balance(loan.borrower.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+    assert!(loan.state(&mut harness).await.position_mint.is_some());
+
+    let repay = ix::repay_loan_offer_to_lender(
+        loan.borrower.pubkey(),
+        loan.lender.pubkey(),
+        None,
+        false,
+        market.lend_mint,
+        market.collateral_mint,
+        args::RepayLoanOfferToLender { _loan_offer_id: loan.offer_id.clone() },
+    );
+    let result = harness.process(&[repay], &[&loan.borrower]).await;
+
+    assert_error(result, RepayOfferError::InvalidPositionTokenAccount);
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::FundTransferred);
+}
+
+#[tokio::test]
+async fn deposit_and_withdraw_collateral() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "collateral").await;
+    market.fund_loan(&mut harness, &loan).await;
+
+    let deposit = ix::deposit_collateral_loan_offer(
+        loan.borrower.pubkey(),
+        market.collateral_mint,
+        args::DepositCollateralLoanOffer {
+            _offer_id: loan.offer_id.clone(),
+            _tier_id: TIER_ID.to_string(),
+            amount: COLLATERAL_AMOUNT,
+        },
+    );
+    harness.process(&[deposit], &[&loan.borrower]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.collateral_amount, 2 * COLLATERAL_AMOUNT);
+
+    let withdraw = ix::withdraw_collateral_loan_offer(
+        loan.borrower.pubkey(),
+        &market.token_assets(),
+        args::WithdrawCollateralLoanOffer {
+            loan_offer_id: loan.offer_id.clone(),
+            withdraw_amount: COLLATERAL_AMOUNT,
+        },
+    );
+    harness.process(&[withdraw], &[&loan.borrower]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.collateral_amount, COLLATERAL_AMOUNT);
+
+    // Withdrawing anything more would leave the loan below the borrow health ratio.
+    let withdraw = ix::withdraw_collateral_loan_offer(
+        loan.borrower.pubkey(),
+        &market.token_assets(),
+        args::WithdrawCollateralLoanOffer {
+            loan_offer_id: loan.offer_id.clone(),
+            withdraw_amount: COLLATERAL_AMOUNT / 2,
+        },
+    );
+    assert_error(harness.process(&[withdraw], &[&loan.borrower]).await, LoanOfferError::HealthRatioInvalid);
+}
+
+#[tokio::test]
+async fn deposit_and_withdraw_native_collateral() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_native_loan(&mut harness, "native-collateral").await;
+    market.fund_loan(&mut harness, &loan).await;
+
+    let deposit = ix::deposit_collateral_loan_offer_native(
+        loan.borrower.pubkey(),
+        args::DepositCollateralLoanOfferNative {
+            _offer_id: loan.offer_id.clone(),
+            _tier_id: TIER_ID.to_string(),
+            amount: SOL_COLLATERAL_AMOUNT,
+        },
+    );
+    harness.process(&[deposit], &[&loan.borrower]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.collateral_amount, 2 * SOL_COLLATERAL_AMOUNT);
+
+    let before = harness.lamports(loan.borrower.pubkey()).await;
+    let withdraw = ix::withdraw_collateral_loan_offer_native(
+        loan.borrower.pubkey(),
+        &market.native_assets(),
+        args::WithdrawCollateralLoanOfferNative {
+            loan_offer_id: loan.offer_id.clone(),
+            withdraw_amount: SOL_COLLATERAL_AMOUNT,
+        },
+    );
+    harness.process(&[withdraw], &[&loan.borrower]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.collateral_amount, SOL_COLLATERAL_AMOUNT);
+    assert!(harness.lamports(loan.borrower.pubkey()).await > before);
+}
+
+#[tokio::test]
+async fn rejects_loan_below_borrow_health_ratio() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "unhealthy", false).await;
+
+    // 10 tokens at 100 USD against 1000 USD borrowed, a health ratio of 1.0.
+    let instruction =
+        market.create_loan_instruction(&harness, &loan.borrower, &loan.lender, &loan, COLLATERAL_AMOUNT * 2 / 3);
+    assert_error(harness.process(&[instruction], &[&loan.borrower]).await, LoanOfferError::HealthRatioInvalid);
+}
+
+#[tokio::test]
+async fn rejects_loan_under_another_tier() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let system = harness.system.insecure_clone();
+    // A tier without borrower fee, the lend offer was created under the fixture tier.
+    let mut setting_args = market.setting_args();
+    setting_args.tier_id = "tier-2".to_string();
+    setting_args.borrower_fee_percent = 0.0;
+    let init = ix::init_setting_account(system.pubkey(), harness.hot_wallet.pubkey(), setting_args);
+    harness.process(&[init], &[&system]).await.unwrap();
+    let loan = market.new_loan(&mut harness, "other-tier", false).await;
+
+    let create = ix::create_loan_offer(
+        loan.borrower.pubkey(),
+        loan.lender.pubkey(),
+        &market.token_assets(),
+        harness.hot_wallet.pubkey(),
+        false,
+        args::CreateLoanOffer {
+            offer_id: loan.offer_id.clone(),
+            lend_offer_id: loan.lend_offer_id.clone(),
+            tier_id: "tier-2".to_string(),
+            collateral_amount: COLLATERAL_AMOUNT,
+            expected_terms: market.expected_terms(),
+        },
+    );
+    assert_error(harness.process(&[create], &[&loan.borrower]).await, LendOfferError::TierIdNotMatch);
+
+    let create_native = ix::create_loan_offer_native(
+        loan.borrower.pubkey(),
+        loan.lender.pubkey(),
+        &market.native_assets(),
+        harness.hot_wallet.pubkey(),
+        false,
+        args::CreateLoanOfferNative {
+            offer_id: loan.offer_id.clone(),
+            lend_offer_id: loan.lend_offer_id.clone(),
+            tier_id: "tier-2".to_string(),
+            collateral_amount: SOL_COLLATERAL_AMOUNT,
+            expected_terms: market.expected_terms(),
+        },
+    );
+    assert_error(harness.process(&[create_native], &[&loan.borrower]).await, LendOfferError::TierIdNotMatch);
+}
+
+#[tokio::test]
+async fn rejects_price_feed_account_of_another_asset() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "wrong-feed", false).await;
+
+    let mut instruction = market.create_loan_instruction(&harness, &loan.borrower, &loan.lender, &loan, COLLATERAL_AMOUNT);
+    for meta in instruction.accounts.iter_mut() {
+        if meta.pubkey == market.collateral_feed.address {
+            meta.pubkey = market.sol_feed.address;
+        }
+    }
+    assert_error(harness.process(&[instruction], &[&loan.borrower]).await, LoanOfferError::InvalidPriceFeedAccount);
+}
+
+#[tokio::test]
+async fn rejects_price_update_for_another_feed() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "wrong-feed-id", false).await;
+
+    // Right account, but it carries the price of the SOL feed.
+    let feed = market.collateral_feed;
+    harness.set_price(feed.address, market.sol_feed.id, feed.price, common::market::PRICE_EXPONENT).await;
+    let instruction = market.create_loan_instruction(&harness, &loan.borrower, &loan.lender, &loan, COLLATERAL_AMOUNT);
+    assert_failed(harness.process(&[instruction], &[&loan.borrower]).await);
+}
+
+#[tokio::test]
+async fn rejects_stale_price() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "stale-price", false).await;
+
+    harness.advance_time(10 * 60).await;
+    let instruction = market.create_loan_instruction(&harness, &loan.borrower, &loan.lender, &loan, COLLATERAL_AMOUNT);
+    assert_failed(harness.process(&[instruction], &[&loan.borrower]).await);
+}
+
+#[tokio::test]
+async fn rejects_expired_lend_offer() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "expired", true).await;
+
+    harness.advance_time(2 * 24 * 60 * 60).await;
+    market.refresh_prices(&mut harness).await;
+    let instruction = market.create_loan_instruction(&harness, &loan.borrower, &loan.lender, &loan, COLLATERAL_AMOUNT);
+    assert_error(harness.process(&[instruction], &[&loan.borrower]).await, LoanOfferError::LendOfferExpired);
+}
+
+#[tokio::test]
+async fn takes_escrowed_lend_offer_before_expiry() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "escrowed", true).await;
+    let vault = pda::ata(&loan.lend_offer_address(), &market.lend_mint);
+    assert_eq!(harness.token_balance(vault).await, LEND_AMOUNT);
+
+    let instruction = market.create_loan_instruction(&harness, &loan.borrower, &loan.lender, &loan, COLLATERAL_AMOUNT);
+    harness.process(&[instruction], &[&loan.borrower]).await.unwrap();
+
+    assert_eq!(harness.token_balance(vault).await, 0);
+    let hot_wallet_ata = pda::ata(&harness.hot_wallet.pubkey(), &market.lend_mint);
+    assert_eq!(harness.token_balance(hot_wallet_ata).await, LEND_AMOUNT);
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Matched);
+}
+
+fn swap_to_sol(market: &Market, loan: &Loan) -> anchor_lang::solana_program::instruction::Instruction {
+    ix::swap_collateral(
+        ix::SwapCollateralParams {
+            borrower: loan.borrower.pubkey(),
+            tier_id: TIER_ID.to_string(),
+            lend_mint: market.lend_mint,
+            lend_price_feed: market.lend_feed.address,
+            old_collateral_mint: market.collateral_mint,
+            new_collateral_mint: native_mint::ID,
+            new_collateral_price_feed: market.sol_feed.address,
+        },
+        args::SwapCollateral {
+            loan_offer_id: loan.offer_id.clone(),
+            new_collateral_amount: SOL_COLLATERAL_AMOUNT,
+        },
+    )
+}
+
+#[tokio::test]
+async fn swaps_token_collateral_for_sol() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "swap").await;
+    market.fund_loan(&mut harness, &loan).await;
+
+    harness.process(&[swap_to_sol(&market, &loan)], &[&loan.borrower]).await.unwrap();
+
+    let state = loan.state(&mut harness).await;
+    assert_eq!(state.collateral_mint_token, native_mint::ID);
+    assert_eq!(state.collateral_amount, SOL_COLLATERAL_AMOUNT);
+}
+
+#[tokio::test]
+async fn rejects_swap_on_stale_price() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "swap-stale").await;
+    market.fund_loan(&mut harness, &loan).await;
+
+    harness.advance_time(MAX_PRICE_AGE_SECONDS as i64 + 1).await;
+    assert_error(
+        harness.process(&[swap_to_sol(&market, &loan)], &[&loan.borrower]).await,
+        GetPriceError::PriceTooOld,
+    );
+    assert_eq!(loan.state(&mut harness).await.collateral_mint_token, market.collateral_mint);
+}
@@ -0,0 +1,253 @@
+
+mod common;
+
+use common::{
+    assert_error,
+    market::{Loan, Market, LEND_AMOUNT},
+    Harness, LAMPORTS_PER_SOL,
+};
+use enso_lending::{
+    common::{AccountVersionError, LoanOfferError, LoanOfferStatus, StatusTransitionError},
+    instruction as args,
+    states::{LoanOfferAccount, Versioned},
+};
+use enso_lending_client::{instructions as ix, pda};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+/// Repaid and finished loan, left closable.
+async fn finished_loan(harness: &mut Harness, market: &Market, name: &str) -> Loan {
+    let loan = market.open_token_loan(harness, name).await;
+    market.fund_loan(harness, &loan).await;
+    harness.set_token_balance(loan.borrower.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+    let repay = ix::repay_loan_offer(
+        loan.borrower.pubkey(),
+        market.lend_mint,
+        market.collateral_mint,
+        args::RepayLoanOffer { _loan_offer_id: loan.offer_id.clone() },
+    );
+    harness.process(&[repay], &[&loan.borrower]).await.unwrap();
+
+    let state = loan.state(harness).await;
+    let system = harness.system.insecure_clone();
+    let finish = ix::system_finish_loan_offer(
+        system.pubkey(),
+        loan.borrower.pubkey(),
+        loan.lender.pubkey(),
+        market.lend_mint,
+        state.position_mint,
+        false,
+        args::SystemFinishLoanOffer {
+            _loan_offer_id: loan.offer_id.clone(),
+            loan_amount: LEND_AMOUNT,
+            interest_amount: 0,
+        },
+    );
+    harness.process(&[finish], &[&system]).await.unwrap();
+
+    loan
+}
+
+#[tokio::test]
+async fn closes_finished_loan_to_its_rent_payer() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let cranker = harness.new_wallet(LAMPORTS_PER_SOL);
+    let loan = market.open_token_loan(&mut harness, "active").await;
+
+    let close = ix::close_loan_offer(
+        cranker.pubkey(),
+        loan.borrower.pubkey(),
+        loan.address(),
+        loan.lend_offer_address(),
+        args::CloseLoanOffer {},
+    );
+    assert_error(harness.process(&[close], &[&cranker]).await, StatusTransitionError::InvalidTransition);
+
+    let loan = finished_loan(&mut harness, &market, "finished").await;
+    let close = ix::close_loan_offer(
+        cranker.pubkey(),
+        loan.borrower.pubkey(),
+        loan.address(),
+        loan.lend_offer_address(),
+        args::CloseLoanOffer {},
+    );
+    // The lend offer needs the loan to prove it finished, so it is closed first.
+    assert_error(harness.process(std::slice::from_ref(&close), &[&cranker]).await, LoanOfferError::LendOfferNotClosed);
+    let close_lend_offer = ix::close_lend_offer(
+        loan.lender.pubkey(),
+        loan.lender.pubkey(),
+        market.lend_mint,
+        false,
+        Some(loan.address()),
+        args::CloseLendOffer { offer_id: loan.lend_offer_id.clone() },
+    );
+    harness.process(&[close_lend_offer], &[&loan.lender]).await.unwrap();
+
+    let before = harness.lamports(loan.borrower.pubkey()).await;
+    let rent = harness.lamports(loan.address()).await;
+    harness.refresh_blockhash().await;
+    harness.process(&[close], &[&cranker]).await.unwrap();
+
+    assert!(harness.get_account(loan.address()).await.is_none());
+    assert_eq!(harness.lamports(loan.borrower.pubkey()).await, before + rent);
+}
+
+#[tokio::test]
+async fn batch_closes_finished_loans_and_their_lend_offers() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let cranker = harness.new_wallet(LAMPORTS_PER_SOL);
+    let first = finished_loan(&mut harness, &market, "batch-1").await;
+    let second = finished_loan(&mut harness, &market, "batch-2").await;
+
+    // A loan listed before its lend offer would strand the lend offer.
+    let loan_first = ix::batch_close_accounts(
+        cranker.pubkey(),
+        &[
+            first.address(),
+            first.borrower.pubkey(),
+            first.lend_offer_address(),
+            first.lend_offer_address(),
+            first.lender.pubkey(),
+            first.address(),
+        ],
+        args::BatchCloseAccounts {},
+    );
+    assert_error(harness.process(&[loan_first], &[&cranker]).await, LoanOfferError::LendOfferNotClosed);
+
+    let mut groups = vec![];
+    for loan in [&first, &second] {
+        groups.extend([loan.lend_offer_address(), loan.lender.pubkey(), loan.address()]);
+        groups.extend([loan.address(), loan.borrower.pubkey(), loan.lend_offer_address()]);
+    }
+    let batch = ix::batch_close_accounts(cranker.pubkey(), &groups, args::BatchCloseAccounts {});
+    harness.process(&[batch], &[&cranker]).await.unwrap();
+
+    for loan in [&first, &second] {
+        assert!(harness.get_account(loan.address()).await.is_none());
+        assert!(harness.get_account(loan.lend_offer_address()).await.is_none());
+    }
+}
+
+#[tokio::test]
+async fn liquidated_loan_is_not_closed_before_it_finishes() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let cranker = harness.new_wallet(LAMPORTS_PER_SOL);
+    let loan = market.open_token_loan(&mut harness, "liquidated").await;
+
+    let mut state = loan.state(&mut harness).await;
+    state.status = LoanOfferStatus::Liquidated;
+    harness.write_account(loan.address(), &state).await;
+
+    let close = ix::close_loan_offer(
+        cranker.pubkey(),
+        loan.borrower.pubkey(),
+        loan.address(),
+        loan.lend_offer_address(),
+        args::CloseLoanOffer {},
+    );
+    assert_error(harness.process(&[close], &[&cranker]).await, StatusTransitionError::InvalidTransition);
+    let batch = ix::batch_close_accounts(
+        cranker.pubkey(),
+        &[loan.address(), loan.borrower.pubkey(), loan.lend_offer_address()],
+        args::BatchCloseAccounts {},
+    );
+    assert_error(harness.process(&[batch], &[&cranker]).await, StatusTransitionError::InvalidTransition);
+}
+
+#[tokio::test]
+async fn batch_closes_canceled_lend_offer_with_its_vault() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let cranker = harness.new_wallet(LAMPORTS_PER_SOL);
+    let lender = market.new_lender(&mut harness);
+    let expires_at = harness.now().await + 24 * 60 * 60;
+    market.create_lend_offer(&mut harness, &lender, "batch-escrow", Some(expires_at)).await;
+    let cancel = ix::cancel_lend_offer(
+        lender.pubkey(),
+        market.lend_mint,
+        true,
+        args::CancelLendOffer { _offer_id: "batch-escrow".to_string() },
+    );
+    harness.process(&[cancel], &[&lender]).await.unwrap();
+
+    let lend_offer = pda::lend_offer(&lender.pubkey(), "batch-escrow").0;
+    let lend_offer_vault = pda::ata(&lend_offer, &market.lend_mint);
+    let before = harness.lamports(lender.pubkey()).await;
+    let rent = harness.lamports(lend_offer).await + harness.lamports(lend_offer_vault).await;
+    let batch = ix::batch_close_accounts(
+        cranker.pubkey(),
+        &[lend_offer, lender.pubkey(), lend_offer_vault],
+        args::BatchCloseAccounts {},
+    );
+    harness.process(&[batch], &[&cranker]).await.unwrap();
+
+    assert!(harness.get_account(lend_offer).await.is_none());
+    assert!(harness.get_account(lend_offer_vault).await.is_none());
+    assert_eq!(harness.lamports(lender.pubkey()).await, before + rent);
+}
+
+#[tokio::test]
+async fn batch_closes_empty_collateral_vault_to_the_borrower() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let cranker = harness.new_wallet(LAMPORTS_PER_SOL);
+    let active = market.open_token_loan(&mut harness, "vault-active").await;
+    let close_vault = |borrower: Pubkey| {
+        ix::batch_close_accounts(
+            cranker.pubkey(),
+            &[pda::vault(&borrower, &market.collateral_mint), pda::vault_authority(&borrower).0, borrower],
+            args::BatchCloseAccounts {},
+        )
+    };
+    assert_error(
+        harness.process(&[close_vault(active.borrower.pubkey())], &[&cranker]).await,
+        LoanOfferError::CollateralVaultNotEmpty,
+    );
+
+    let loan = finished_loan(&mut harness, &market, "vault-finished").await;
+    let vault = pda::vault(&loan.borrower.pubkey(), &market.collateral_mint);
+    let before = harness.lamports(loan.borrower.pubkey()).await;
+    let rent = harness.lamports(vault).await;
+    harness.process(&[close_vault(loan.borrower.pubkey())], &[&cranker]).await.unwrap();
+
+    assert!(harness.get_account(vault).await.is_none());
+    assert_eq!(harness.lamports(loan.borrower.pubkey()).await, before + rent);
+}
+
+#[tokio::test]
+async fn outdated_account_is_migrated_before_use() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "migrate").await;
+    let data_len = |account: Option<solana_sdk::account::Account>| account.unwrap().data.len();
+    assert_eq!(data_len(harness.get_account(loan.address()).await), <LoanOfferAccount as Versioned>::SPACE);
+
+    let mut state = loan.state(&mut harness).await;
+    state.version = 0;
+    harness.write_account(loan.address(), &state).await;
+    let system = harness.system.insecure_clone();
+    let fund = ix::system_update_loan_offer(
+        system.pubkey(),
+        loan.borrower.pubkey(),
+        market.lend_mint,
+        args::SystemUpdateLoanOffer {
+            _offer_id: loan.offer_id.clone(),
+            _tier_id: common::market::TIER_ID.to_string(),
+            borrow_amount: LEND_AMOUNT,
+        },
+    );
+    assert_error(
+        harness.process(&[fund], &[&system]).await,
+        AccountVersionError::OutdatedAccountVersion,
+    );
+
+    let migrate = ix::migrate_account(harness.payer(), loan.address(), args::MigrateAccount {});
+    harness.process(&[migrate], &[]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.version, LoanOfferAccount::VERSION);
+    assert_eq!(data_len(harness.get_account(loan.address()).await), <LoanOfferAccount as Versioned>::SPACE);
+
+    harness.refresh_blockhash().await;
+    market.fund_loan(&mut harness, &loan).await;
+}
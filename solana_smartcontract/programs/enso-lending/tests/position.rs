@@ -0,0 +1,185 @@
+
+mod common;
+
+use common::{
+    assert_error,
+    market::{Market, LEND_AMOUNT, MAX_PRICE_AGE_SECONDS},
+    Harness, LAMPORTS_PER_SOL,
+};
+use enso_lending::{
+    common::{LoanOfferStatus, LoanPositionListingError, RepayOfferError, StatusTransitionError},
+    instruction as args,
+    states::{LoanPositionListing, LoanPositionListingView},
+};
+use enso_lending_client::{instructions as ix, pda};
+use pyth_solana_receiver_sdk::error::GetPriceError;
+use solana_sdk::signer::Signer;
+
+const PRICE: u64 = LEND_AMOUNT / 2;
+
+#[tokio::test]
+async fn lender_sells_position_to_a_buyer() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "sell").await;
+    market.fund_loan(&mut harness, &loan).await;
+    let position_mint = loan.state(&mut harness).await.position_mint.unwrap();
+    let seller = loan.lender.insecure_clone();
+    assert_eq!(harness.token_balance(pda::ata(&seller.pubkey(), &position_mint)).await, 1);
+
+    let list = ix::list_loan_position(seller.pubkey(), loan.address(), position_mint, args::ListLoanPosition { price: PRICE });
+    harness.process(&[list], &[&seller]).await.unwrap();
+    let listing: LoanPositionListing = harness.account(pda::loan_position_listing(&position_mint).0).await;
+    assert_eq!(listing.price, PRICE);
+
+    let view: LoanPositionListingView = harness
+        .simulate(ix::view_loan_position_listing(
+            loan.address(),
+            position_mint,
+            &market.token_assets(),
+            args::ViewLoanPositionListing {},
+        ))
+        .await;
+    assert_eq!(view.seller, seller.pubkey());
+    assert_eq!(view.borrow_amount, LEND_AMOUNT);
+    assert!(view.health_ratio > 1.0);
+
+    let buyer = harness.new_wallet(LAMPORTS_PER_SOL);
+    harness.set_token_balance(buyer.pubkey(), market.lend_mint, PRICE);
+    let buy = |expected_price| {
+        ix::buy_loan_position(
+            buyer.pubkey(),
+            seller.pubkey(),
+            loan.address(),
+            position_mint,
+            market.lend_mint,
+            args::BuyLoanPosition { expected_price },
+        )
+    };
+    assert_error(
+        harness.process(&[buy(PRICE - 1)], &[&buyer]).await,
+        LoanPositionListingError::PriceUpdated,
+    );
+    harness.process(&[buy(PRICE)], &[&buyer]).await.unwrap();
+
+    assert_eq!(harness.token_balance(pda::ata(&buyer.pubkey(), &position_mint)).await, 1);
+    assert_eq!(harness.token_balance(pda::ata(&seller.pubkey(), &market.lend_mint)).await, PRICE);
+}
+
+#[tokio::test]
+async fn seller_cancels_listing() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "unlist").await;
+    market.fund_loan(&mut harness, &loan).await;
+    let position_mint = loan.state(&mut harness).await.position_mint.unwrap();
+    let seller = loan.lender.insecure_clone();
+
+    let list = ix::list_loan_position(seller.pubkey(), loan.address(), position_mint, args::ListLoanPosition { price: 0 });
+    assert_error(harness.process(&[list], &[&seller]).await, LoanPositionListingError::InvalidPrice);
+
+    let list = ix::list_loan_position(seller.pubkey(), loan.address(), position_mint, args::ListLoanPosition { price: PRICE });
+    harness.process(&[list], &[&seller]).await.unwrap();
+    let cancel = ix::cancel_loan_position_listing(seller.pubkey(), position_mint, args::CancelLoanPositionListing {});
+    harness.process(&[cancel], &[&seller]).await.unwrap();
+
+    assert!(harness.get_account(pda::loan_position_listing(&position_mint).0).await.is_none());
+    assert_eq!(harness.token_balance(pda::ata(&seller.pubkey(), &position_mint)).await, 1);
+}
+
+#[tokio::test]
+async fn listed_loan_is_repaid_to_the_seller() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "repay-listed").await;
+    market.fund_loan(&mut harness, &loan).await;
+    let position_mint = loan.state(&mut harness).await.position_mint.unwrap();
+    let seller = loan.lender.insecure_clone();
+    harness.set_token_balance(loan.borrower.pubkey(), market.lend_mint, 2 * LEND_AMOUNT);
+
+    let list = ix::list_loan_position(seller.pubkey(), loan.address(), position_mint, args::ListLoanPosition { price: PRICE });
+    harness.process(&[list], &[&seller]).await.unwrap();
+
+    let repay = |creditor, listed| {
+        ix::repay_loan_offer_to_lender(
+            loan.borrower.pubkey(),
+            creditor,
+            Some(position_mint),
+            listed,
+            market.lend_mint,
+            market.collateral_mint,
+            args::RepayLoanOfferToLender { _loan_offer_id: loan.offer_id.clone() },
+        )
+    };
+    assert_error(
+        harness.process(&[repay(seller.pubkey(), false)], &[&loan.borrower]).await,
+        RepayOfferError::InvalidPositionTokenAccount,
+    );
+    let other = harness.new_wallet(LAMPORTS_PER_SOL);
+    assert_error(
+        harness.process(&[repay(other.pubkey(), true)], &[&loan.borrower]).await,
+        RepayOfferError::InvalidLender,
+    );
+
+    // The listing holds the token for the seller, who is paid while the position is listed.
+    harness.process(&[repay(seller.pubkey(), true)], &[&loan.borrower]).await.unwrap();
+    assert_eq!(loan.state(&mut harness).await.status, LoanOfferStatus::Finished);
+    assert!(harness.token_balance(pda::ata(&seller.pubkey(), &market.lend_mint)).await > LEND_AMOUNT);
+
+    // A settled position can not be bought, the seller takes the token back.
+    let buyer = harness.new_wallet(LAMPORTS_PER_SOL);
+    harness.set_token_balance(buyer.pubkey(), market.lend_mint, PRICE);
+    let buy = ix::buy_loan_position(
+        buyer.pubkey(),
+        seller.pubkey(),
+        loan.address(),
+        position_mint,
+        market.lend_mint,
+        args::BuyLoanPosition { expected_price: PRICE },
+    );
+    assert_error(harness.process(&[buy], &[&buyer]).await, StatusTransitionError::InvalidTransition);
+    let cancel = ix::cancel_loan_position_listing(seller.pubkey(), position_mint, args::CancelLoanPositionListing {});
+    harness.process(&[cancel], &[&seller]).await.unwrap();
+    assert_eq!(harness.token_balance(pda::ata(&seller.pubkey(), &position_mint)).await, 1);
+}
+
+#[tokio::test]
+async fn position_of_an_unfunded_loan_can_not_be_listed() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "unfunded").await;
+    let position_mint = loan.state(&mut harness).await.position_mint.unwrap();
+
+    let list = ix::list_loan_position(
+        loan.lender.pubkey(),
+        loan.address(),
+        position_mint,
+        args::ListLoanPosition { price: PRICE },
+    );
+    assert_error(
+        harness.process(&[list], &[&loan.lender]).await,
+        StatusTransitionError::InvalidTransition,
+    );
+}
+
+#[tokio::test]
+async fn listing_view_fails_on_stale_prices() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "stale").await;
+    market.fund_loan(&mut harness, &loan).await;
+    let position_mint = loan.state(&mut harness).await.position_mint.unwrap();
+    let seller = loan.lender.insecure_clone();
+
+    let list = ix::list_loan_position(seller.pubkey(), loan.address(), position_mint, args::ListLoanPosition { price: PRICE });
+    harness.process(&[list], &[&seller]).await.unwrap();
+
+    harness.advance_time(MAX_PRICE_AGE_SECONDS as i64 + 1).await;
+    let view = ix::view_loan_position_listing(
+        loan.address(),
+        position_mint,
+        &market.token_assets(),
+        args::ViewLoanPositionListing {},
+    );
+    assert_error(harness.process(&[view], &[]).await, GetPriceError::PriceTooOld);
+}
@@ -0,0 +1,146 @@
+use enso_lending::risk::{self, PricedLoanParams};
+use proptest::prelude::*;
+
+const EPSILON: f64 = 1e-9;
+
+fn priced_loan() -> impl Strategy<Value = PricedLoanParams> {
+    (
+        1u64..1_000_000_000_000,
+        0u8..=9,
+        0.0001f64..100_000.0,
+        1u64..1_000_000_000_000,
+        0u8..=9,
+        0.0001f64..100_000.0,
+    )
+        .prop_map(
+            |(collateral_amount, collateral_decimals, collateral_price, lend_amount, lend_decimals, lend_price)| {
+                PricedLoanParams {
+                    collateral_amount,
+                    collateral_decimals,
+                    collateral_price,
+                    lend_amount,
+                    lend_decimals,
+                    lend_price,
+                }
+            },
+        )
+}
+
+fn threshold() -> impl Strategy<Value = f64> {
+    1.0f64..3.0
+}
+
+fn assert_at_least(actual: f64, expected: f64) {
+    assert!(
+        actual >= expected * (1.0 - EPSILON),
+        "expected {} to be at least {}",
+        actual,
+        expected
+    );
+}
+
+proptest! {
+    #[test]
+    fn health_ratio_is_collateral_usd_over_lend_usd(loan in priced_loan()) {
+        let health_ratio = risk::get_health_ratio(&loan);
+
+        prop_assert!(health_ratio > 0.0);
+        prop_assert!((health_ratio * loan.lend_usd() - loan.collateral_usd()).abs() <= loan.collateral_usd() * EPSILON);
+    }
+
+    #[test]
+    fn health_ratio_grows_with_collateral(loan in priced_loan(), extra in 1u64..1_000_000_000) {
+        let more_collateral = PricedLoanParams {
+            collateral_amount: loan.collateral_amount + extra,
+            ..loan
+        };
+
+        prop_assert!(risk::get_health_ratio(&more_collateral) > risk::get_health_ratio(&loan));
+    }
+
+    #[test]
+    fn health_ratio_at_liquidation_price_equals_threshold(loan in priced_loan(), threshold in threshold()) {
+        let liquidation_price = risk::get_liquidation_price(&loan, threshold);
+        let at_liquidation = PricedLoanParams {
+            collateral_price: liquidation_price,
+            ..loan
+        };
+
+        let health_ratio = risk::get_health_ratio(&at_liquidation);
+        prop_assert!((health_ratio - threshold).abs() <= threshold * 1e-6);
+    }
+
+    #[test]
+    fn liquidation_price_is_below_price_when_healthy(loan in priced_loan(), threshold in threshold()) {
+        prop_assume!(risk::get_health_ratio(&loan) >= threshold);
+
+        let liquidation_price = risk::get_liquidation_price(&loan, threshold);
+        prop_assert!(liquidation_price <= loan.collateral_price * (1.0 + EPSILON));
+    }
+
+    #[test]
+    fn min_collateral_reaches_target_ratio(loan in priced_loan(), target_ratio in threshold()) {
+        let min_collateral = risk::get_min_collateral_for_ratio(&loan, target_ratio);
+        // Saturated: the target needs more collateral than a token account can hold
+        prop_assume!(min_collateral < u64::MAX);
+
+        let at_min = PricedLoanParams {
+            collateral_amount: min_collateral,
+            ..loan
+        };
+
+        assert_at_least(risk::get_health_ratio(&at_min), target_ratio);
+
+        if min_collateral > 1 {
+            let below_min = PricedLoanParams {
+                collateral_amount: min_collateral - 1,
+                ..loan
+            };
+            prop_assert!(risk::get_health_ratio(&below_min) < target_ratio * (1.0 + EPSILON));
+        }
+    }
+
+    #[test]
+    fn max_withdrawable_keeps_loan_healthy(loan in priced_loan(), threshold in threshold()) {
+        let max_withdrawable = risk::get_max_withdrawable_collateral(&loan, threshold);
+        prop_assert!(max_withdrawable <= loan.collateral_amount);
+
+        if risk::get_health_ratio(&loan) < threshold {
+            prop_assert_eq!(max_withdrawable, 0);
+        }
+
+        if max_withdrawable > 0 {
+            let after_withdraw = PricedLoanParams {
+                collateral_amount: loan.collateral_amount - max_withdrawable,
+                ..loan
+            };
+            assert_at_least(risk::get_health_ratio(&after_withdraw), threshold);
+        }
+    }
+
+    #[test]
+    fn max_borrow_stays_within_threshold(loan in priced_loan(), threshold in threshold()) {
+        let max_borrow = risk::get_max_borrow_amount(&loan, threshold);
+        prop_assume!(max_borrow > 0);
+
+        let at_max = PricedLoanParams {
+            lend_amount: max_borrow,
+            ..loan
+        };
+        assert_at_least(risk::get_health_ratio(&at_max), threshold);
+    }
+
+    #[test]
+    fn seized_collateral_covers_debt_and_bonus_unless_capped(loan in priced_loan(), bonus_percent in 0.0f64..20.0) {
+        let seized = risk::get_seized_collateral(&loan, bonus_percent);
+        prop_assert!(seized <= loan.collateral_amount);
+
+        if seized < loan.collateral_amount {
+            let seized_loan = PricedLoanParams {
+                collateral_amount: seized,
+                ..loan
+            };
+            assert_at_least(seized_loan.collateral_usd(), loan.lend_usd() * (1.0 + bonus_percent / 100.0));
+        }
+    }
+}
@@ -0,0 +1,324 @@
+
+mod common;
+
+use common::{
+    market::{
+        Loan, Market, COLLATERAL_AMOUNT, COLLATERAL_DECIMALS, COLLATERAL_PRICE, DURATION, LEND_AMOUNT, LEND_DECIMALS,
+        LEND_PRICE, PRICE_EXPONENT, TIER_ID,
+    },
+    Harness,
+};
+use enso_lending::{
+    common::{
+        transition, LendOfferEvent, LendOfferStatus, LoanOfferEvent, LoanOfferStatus, Status, StatusTransitionError,
+        MIN_BORROW_HEALTH_RATIO,
+    },
+    instruction as args,
+    risk::{self, PricedLoanParams},
+};
+use enso_lending_client::{instructions as ix, pda};
+use proptest::prelude::*;
+use anchor_spl::token::spl_token;
+use solana_sdk::{instruction::Instruction, signer::Signer};
+
+const STATUSES: [LoanOfferStatus; 7] = [
+    LoanOfferStatus::Matched,
+    LoanOfferStatus::FundTransferred,
+    LoanOfferStatus::Repay,
+    LoanOfferStatus::BorrowerPaid,
+    LoanOfferStatus::Liquidating,
+    LoanOfferStatus::Liquidated,
+    LoanOfferStatus::Finished,
+];
+
+#[derive(Clone, Debug)]
+enum Action {
+    Fund,
+    Deposit(u64),
+    Withdraw(u64),
+    Repay,
+    Finish,
+    SetCollateralPrice(i64),
+    Expire,
+    StartLiquidateHealth,
+    StartLiquidateExpired,
+    FinishLiquidate,
+    Revert,
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        3 => Just(Action::Fund),
+        2 => (1..=COLLATERAL_AMOUNT).prop_map(Action::Deposit),
+        2 => (1..=COLLATERAL_AMOUNT).prop_map(Action::Withdraw),
+        2 => Just(Action::Repay),
+        2 => Just(Action::Finish),
+        2 => (COLLATERAL_PRICE / 2..=COLLATERAL_PRICE * 3 / 2).prop_map(Action::SetCollateralPrice),
+        1 => Just(Action::Expire),
+        2 => Just(Action::StartLiquidateHealth),
+        2 => Just(Action::StartLiquidateExpired),
+        2 => Just(Action::FinishLiquidate),
+        2 => Just(Action::Revert),
+    ]
+}
+
+/// Collateral of the borrower, wherever it currently is.
+struct Holdings {
+    borrower: u64,
+    vault: u64,
+    hot_wallet: u64,
+}
+
+impl Holdings {
+    fn total(&self) -> u64 {
+        self.borrower + self.vault + self.hot_wallet
+    }
+}
+
+async fn holdings(harness: &mut Harness, market: &Market, loan: &Loan) -> Holdings {
+    let mint = market.collateral_mint;
+    let hot_wallet = harness.hot_wallet.pubkey();
+
+    Holdings {
+        borrower: harness.token_balance(pda::ata(&loan.borrower.pubkey(), &mint)).await,
+        vault: harness.token_balance(pda::vault(&loan.borrower.pubkey(), &mint)).await,
+        hot_wallet: harness.token_balance(pda::ata(&hot_wallet, &mint)).await,
+    }
+}
+
+fn opening_health(collateral_amount: u64) -> f64 {
+    risk::get_health_ratio(&PricedLoanParams {
+        collateral_amount,
+        collateral_decimals: COLLATERAL_DECIMALS,
+        collateral_price: risk::to_ui_price(COLLATERAL_PRICE, PRICE_EXPONENT),
+        lend_amount: LEND_AMOUNT,
+        lend_decimals: LEND_DECIMALS,
+        lend_price: risk::to_ui_price(LEND_PRICE, PRICE_EXPONENT),
+    })
+}
+
+fn instruction(harness: &Harness, market: &Market, loan: &Loan, action: &Action) -> Option<Instruction> {
+    let borrower = loan.borrower.pubkey();
+    let system = harness.system.pubkey();
+    let offer_id = loan.offer_id.clone();
+
+    let instruction = match *action {
+        Action::Fund => ix::system_update_loan_offer(
+            system,
+            borrower,
+            market.lend_mint,
+            args::SystemUpdateLoanOffer {
+                _offer_id: offer_id,
+                _tier_id: TIER_ID.to_string(),
+                borrow_amount: LEND_AMOUNT,
+            },
+        ),
+        Action::Deposit(amount) => ix::deposit_collateral_loan_offer(
+            borrower,
+            market.collateral_mint,
+            args::DepositCollateralLoanOffer {
+                _offer_id: offer_id,
+                _tier_id: TIER_ID.to_string(),
+                amount,
+            },
+        ),
+        Action::Withdraw(withdraw_amount) => ix::withdraw_collateral_loan_offer(
+            borrower,
+            &market.token_assets(),
+            args::WithdrawCollateralLoanOffer {
+                loan_offer_id: offer_id,
+                withdraw_amount,
+            },
+        ),
+        Action::Repay => ix::repay_loan_offer(
+            borrower,
+            market.lend_mint,
+            market.collateral_mint,
+            args::RepayLoanOffer { _loan_offer_id: offer_id },
+        ),
+        Action::Finish => ix::system_finish_loan_offer(
+            system,
+            borrower,
+            loan.lender.pubkey(),
+            market.lend_mint,
+            Some(pda::loan_position_mint(&loan.address()).0),
+            false,
+            args::SystemFinishLoanOffer {
+                _loan_offer_id: offer_id,
+                loan_amount: LEND_AMOUNT,
+                interest_amount: 0,
+            },
+        ),
+        Action::StartLiquidateHealth => ix::start_liquidate_loan_offer_health(
+            borrower,
+            &market.token_assets(),
+            args::StartLiquidateLoanOfferHealth { _offer_id: offer_id },
+        ),
+        Action::StartLiquidateExpired => ix::start_liquidate_loan_offer_expired(
+            borrower,
+            market.collateral_mint,
+            args::StartLiquidateLoanOfferExpired { _offer_id: offer_id },
+        ),
+        Action::FinishLiquidate => ix::finish_liquidate_contract(
+            borrower,
+            market.lend_mint,
+            market.collateral_mint,
+            args::FinishLiquidateContract {
+                _loan_offer_id: offer_id,
+                collateral_swapped_amount: LEND_AMOUNT * 105 / 100,
+                liquidated_price: 70,
+                liquidated_tx: "liquidation-tx".to_string(),
+            },
+        ),
+        Action::Revert => ix::system_revert_status(system, borrower, args::SystemRevertStatus { _offer_id: offer_id }),
+        Action::SetCollateralPrice(_) | Action::Expire => return None,
+    };
+
+    Some(instruction)
+}
+
+fn return_collateral(harness: &Harness, market: &Market, loan: &Loan, amount: u64) -> Instruction {
+    let hot_wallet = harness.hot_wallet.pubkey();
+    spl_token::instruction::transfer(
+        &spl_token::ID,
+        &pda::ata(&hot_wallet, &market.collateral_mint),
+        &pda::vault(&loan.borrower.pubkey(), &market.collateral_mint),
+        &hot_wallet,
+        &[],
+        amount,
+    )
+    .unwrap()
+}
+
+/// Opens a token loan with the given collateral and applies the actions, checking the loan
+/// invariants after each of them whether the instruction succeeded or not.
+async fn run(collateral_amount: u64, actions: Vec<Action>) -> Result<(), TestCaseError> {
+    let mut harness = Harness::start().await;
+    let mut market = Market::setup(&mut harness).await;
+    let loan = market.new_loan(&mut harness, "fuzz", false).await;
+
+    let open = market.create_loan_instruction(&harness, &loan.borrower, &loan.lender, &loan, collateral_amount);
+    let opened = harness.process(&[open], &[&loan.borrower]).await.is_ok();
+    let health = opening_health(collateral_amount);
+    prop_assert_eq!(opened, health >= MIN_BORROW_HEALTH_RATIO, "opening health {}", health);
+    if !opened {
+        return Ok(());
+    }
+
+    // Covers the interest on top of the principal received on funding.
+    harness.set_token_balance(loan.borrower.pubkey(), market.lend_mint, LEND_AMOUNT);
+    let system = harness.system.insecure_clone();
+    let hot_wallet = harness.hot_wallet.insecure_clone();
+    let initial_total = holdings(&mut harness, &market, &loan).await.total();
+
+    for action in actions {
+        let before = loan.state(&mut harness).await;
+
+        match action {
+            Action::SetCollateralPrice(price) => market.set_collateral_price(&mut harness, price).await,
+            Action::Expire => {
+                harness.advance_time(DURATION as i64 + 1).await;
+                market.refresh_prices(&mut harness).await;
+            }
+            Action::Revert => {
+                // The operator sends the collateral back to the vault before reverting a liquidation.
+                let instructions = [
+                    return_collateral(&harness, &market, &loan, before.collateral_amount),
+                    instruction(&harness, &market, &loan, &action).unwrap(),
+                ];
+                let _ = harness.process(&instructions, &[&system, &hot_wallet]).await;
+            }
+            _ => {
+                let instruction = instruction(&harness, &market, &loan, &action).unwrap();
+                let signer = match action {
+                    Action::Deposit(_) | Action::Withdraw(_) | Action::Repay => &loan.borrower,
+                    _ => &system,
+                };
+                let _ = harness.process(&[instruction], &[signer]).await;
+            }
+        }
+
+        let after = loan.state(&mut harness).await;
+        if after.status != before.status {
+            prop_assert!(
+                before.status.can_transition_to(after.status),
+                "{:?} moved the loan from {:?} to {:?}",
+                action,
+                before.status,
+                after.status
+            );
+        }
+        prop_assert!(!before.status.is_final() || after.status == before.status);
+
+        let holdings = holdings(&mut harness, &market, &loan).await;
+        prop_assert_eq!(holdings.total(), initial_total, "collateral not conserved after {:?}", action);
+        if matches!(after.status, LoanOfferStatus::Matched | LoanOfferStatus::FundTransferred) {
+            prop_assert_eq!(holdings.vault, after.collateral_amount);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn finished_is_the_only_final_status_a_loan_enters() {
+    let entered = STATUSES
+        .into_iter()
+        .filter(|status| *status == LoanOfferStatus::Matched || LoanOfferStatus::TRANSITIONS.iter().any(|(_, _, to)| to == status));
+
+    for status in entered {
+        assert_eq!(status.is_final(), status == LoanOfferStatus::Finished, "{status:?}");
+    }
+}
+
+#[test]
+fn every_transition_is_reachable_from_matched() {
+    let mut reachable = vec![LoanOfferStatus::Matched];
+    let mut index = 0;
+    while index < reachable.len() {
+        for &(from, _, to) in LoanOfferStatus::TRANSITIONS {
+            if from == reachable[index] && !reachable.contains(&to) {
+                reachable.push(to);
+            }
+        }
+        index += 1;
+    }
+
+    for &(from, _, _) in LoanOfferStatus::TRANSITIONS {
+        assert!(reachable.contains(&from), "{from:?} is never entered");
+    }
+    assert!(!reachable.contains(&LoanOfferStatus::Repay));
+}
+
+#[test]
+fn transition_returns_next_status_or_the_unified_error() {
+    assert_eq!(
+        transition(LoanOfferStatus::FundTransferred, LoanOfferEvent::Repay).unwrap(),
+        LoanOfferStatus::BorrowerPaid
+    );
+    assert_eq!(
+        transition(LoanOfferStatus::Matched, LoanOfferEvent::Repay).unwrap_err(),
+        StatusTransitionError::InvalidTransition.into()
+    );
+    assert_eq!(
+        transition(LendOfferStatus::Canceling, LendOfferEvent::ConfirmCancel).unwrap(),
+        LendOfferStatus::Canceled
+    );
+    assert_eq!(
+        transition(LendOfferStatus::Loaned, LendOfferEvent::Take).unwrap_err(),
+        StatusTransitionError::InvalidTransition.into()
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn random_instruction_sequences_keep_loan_invariants(
+        collateral_amount in COLLATERAL_AMOUNT / 2..=COLLATERAL_AMOUNT * 2,
+        actions in prop::collection::vec(action(), 1..16),
+    ) {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(run(collateral_amount, actions))?;
+    }
+}
@@ -0,0 +1,54 @@
+
+mod common;
+
+use common::{
+    market::{Market, COLLATERAL_AMOUNT, LEND_AMOUNT},
+    Harness,
+};
+use enso_lending::{
+    instruction as args,
+    states::{LiquidationPriceView, LoanHealthView, MaxBorrowQuote, MaxWithdrawableCollateralView, RepayLoanQuote},
+};
+use enso_lending_client::instructions as ix;
+
+#[tokio::test]
+async fn views_open_loan() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+    let loan = market.open_token_loan(&mut harness, "view").await;
+    market.fund_loan(&mut harness, &loan).await;
+    let assets = market.token_assets();
+
+    let health: LoanHealthView = harness.simulate(ix::view_loan_health(loan.address(), &assets)).await;
+    assert!((health.health_ratio - 1.5).abs() < 1e-9);
+    assert_eq!(health.collateral_price, 100.0);
+    assert_eq!(health.lend_usd, 1_000.0);
+
+    let quote: RepayLoanQuote = harness.simulate(ix::quote_repay_loan_offer(loan.address(), &assets)).await;
+    assert_eq!(quote.borrow_amount, LEND_AMOUNT);
+    assert_eq!(quote.total_repay_amount, quote.borrow_amount + quote.interest_amount);
+    assert!(quote.interest_amount > 0);
+
+    let withdrawable: MaxWithdrawableCollateralView =
+        harness.simulate(ix::view_max_withdrawable_collateral(loan.address(), &assets)).await;
+    assert_eq!(withdrawable.collateral_amount, COLLATERAL_AMOUNT);
+    assert!(withdrawable.max_withdrawable_amount > 0 && withdrawable.max_withdrawable_amount < COLLATERAL_AMOUNT);
+
+    let liquidation: LiquidationPriceView = harness.simulate(ix::view_liquidation_price(loan.address(), &assets)).await;
+    assert!(liquidation.liquidation_price < liquidation.collateral_price);
+}
+
+#[tokio::test]
+async fn quotes_max_borrow_for_collateral() {
+    let mut harness = Harness::start().await;
+    let market = Market::setup(&mut harness).await;
+
+    let quote: MaxBorrowQuote = harness
+        .simulate(ix::quote_max_borrow(
+            &market.token_assets(),
+            args::QuoteMaxBorrow { collateral_amount: COLLATERAL_AMOUNT },
+        ))
+        .await;
+    assert_eq!(quote.collateral_usd, 1_500.0);
+    assert!(quote.max_borrow_amount >= LEND_AMOUNT && quote.max_borrow_amount < 2 * LEND_AMOUNT);
+}
@@ -8,15 +8,22 @@ module enso_lending::collateral_holder {
 	use fun std::string::utf8 as vector.to_string;
 
 	const HOLDING_COLLATERAL_DURATION: u64 = 300; //seconds
+	const HOLDING_SWAP_COLLATERAL_DURATION: u64 = 3600; //seconds, longer than the lend chain accepts the swap vaa
 	const CREATED_STATUS: vector<u8> = b"Created";
 	const BORROWER_PAID_STATUS: vector<u8> = b"BorrowerPaid";
 	const LIQUIDATED_STATUS: vector<u8> = b"Liquidated";
+	const SWAPPING_STATUS: vector<u8> = b"Swapping";
 
 	public struct CollateralHolderKey has store, copy, drop {
 		offer_id: String,
 		lend_chain_borrower: String,
 	}
 
+	public struct SwapCollateralHolderKey has store, copy, drop {
+		offer_id: String,
+		lend_chain_borrower: String,
+	}
+
 	public struct CollateralHolder<phantom CollateralCoinType> has key, store {
 		id: UID,
 		offer_id: String,
@@ -65,6 +72,22 @@ module enso_lending::collateral_holder {
 		remaining_collateral_amount: u64,
 	}
 
+	public struct SwapCollateralEvent has copy, drop {
+		offer_id: String,
+		borrower: address,
+		collateral_amount: u64,
+		collateral_token: String,
+		lend_chain: u16,
+	}
+
+	public struct ReleaseSwappedCollateralEvent has copy, drop {
+		offer_id: String,
+		borrower: address,
+		released_collateral_amount: u64,
+		collateral_amount: u64,
+		lend_chain: u16,
+	}
+
 	public struct LiquidateCollateralEvent has copy, drop {
 		offer_id: String,
 		borrower: address,
@@ -125,6 +148,57 @@ module enso_lending::collateral_holder {
 		collateral
 	}
 
+	public(package) fun new_swapping<CollateralCoinType, NewCollateralCoinType>(
+		collateral_holder: &CollateralHolder<CollateralCoinType>,
+		collateral: Balance<NewCollateralCoinType>,
+		collateral_token: String,
+		start_timestamp: u64,
+		ctx: &mut TxContext,
+	): CollateralHolder<NewCollateralCoinType> {
+		let swapping_holder = CollateralHolder<NewCollateralCoinType> {
+			id: object::new(ctx),
+			offer_id: collateral_holder.offer_id,
+			lend_amount: collateral_holder.lend_amount,
+			borrower: collateral_holder.borrower,
+			collateral,
+			start_timestamp,
+			lend_chain: collateral_holder.lend_chain,
+			status: SWAPPING_STATUS.to_string()
+		};
+
+		event::emit(SwapCollateralEvent {
+			offer_id: swapping_holder.offer_id,
+			borrower: swapping_holder.borrower,
+			collateral_amount: swapping_holder.collateral.value(),
+			collateral_token,
+			lend_chain: swapping_holder.lend_chain,
+		});
+
+		swapping_holder
+	}
+
+	public(package) fun release_swapped<CollateralCoinType, NewCollateralCoinType>(
+		collateral_holder: CollateralHolder<CollateralCoinType>,
+		swapping_holder: &mut CollateralHolder<NewCollateralCoinType>,
+	): Balance<CollateralCoinType> {
+		let CollateralHolder {
+			id, offer_id, lend_amount: _, borrower, collateral, start_timestamp: _, lend_chain, status: _,
+		} = collateral_holder;
+
+		object::delete(id);
+		swapping_holder.status = CREATED_STATUS.to_string();
+
+		event::emit(ReleaseSwappedCollateralEvent {
+			offer_id,
+			borrower,
+			released_collateral_amount: collateral.value(),
+			collateral_amount: swapping_holder.collateral.value(),
+			lend_chain,
+		});
+
+		collateral
+	}
+
 	public(package) fun deposit_collateral<CollateralCoinType>(
 		collateral_holder: &CollateralHolder<CollateralCoinType>,
 		collateral_token: String,
@@ -198,6 +272,19 @@ module enso_lending::collateral_holder {
 		}
 	}
 
+	public fun new_swap_holder_key(offer_id: String, lend_chain_borrower: String): SwapCollateralHolderKey {
+		SwapCollateralHolderKey {
+			offer_id,
+			lend_chain_borrower,
+		}
+	}
+
+	public fun is_swapping_status<CollateralCoinType>(
+		collateral_holder: &CollateralHolder<CollateralCoinType>
+	): bool {
+		collateral_holder.status == SWAPPING_STATUS.to_string()
+	}
+
 	public fun is_created_status<CollateralCoinType>(
 		collateral_holder: &CollateralHolder<CollateralCoinType>
 	): bool {
@@ -237,4 +324,8 @@ module enso_lending::collateral_holder {
 	public fun holding_collateral_duration(): u64 {
 		HOLDING_COLLATERAL_DURATION
 	}
+
+	public fun holding_swap_collateral_duration(): u64 {
+		HOLDING_SWAP_COLLATERAL_DURATION
+	}
 }
\ No newline at end of file
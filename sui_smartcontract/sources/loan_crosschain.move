@@ -14,7 +14,7 @@ module enso_lending::loan_crosschain {
         version::Version,
 		loan_registry::is_valid_collateral_amount,
         asset::Asset,
-		collateral_holder::{Self, CollateralHolderKey, CollateralHolder},
+		collateral_holder::{Self, CollateralHolderKey, SwapCollateralHolderKey, CollateralHolder},
 		wormhole::{Self, ProtectedEC, parse_and_verify_vaa},
 		foreign_chain::{Self, ForeignChainKey, ForeignChain},
         utils::get_type,
@@ -38,6 +38,8 @@ module enso_lending::loan_crosschain {
 	const EInvalidCollateralHolderStatus: u64 = 10;
 	const ECollateralIsInsufficient: u64 = 11;
 	const EInvalidForeignTargetChain: u64 = 12;
+	const ESameCollateralCoin: u64 = 13;
+	const EAlreadySwappingCollateralForThisOffer: u64 = 14;
 
 	entry fun deposit_collateral_to_take_loan<LendCoinType, CollateralCoinType>(
 		version: &Version,
@@ -328,6 +330,85 @@ module enso_lending::loan_crosschain {
         );
 	}
 
+	entry fun swap_collateral<LendCoinType, CollateralCoinType, NewCollateralCoinType>(
+		version: &Version,
+		state: &mut State,
+		configuration: &Configuration,
+		protected_ec: &mut ProtectedEC,
+		wormhole_state: &mut WormholeState,
+		message_fee: Coin<SUI>,
+		offer_id: vector<u8>,
+		lend_chain_borrower: vector<u8>,
+		new_collateral: Coin<NewCollateralCoinType>,
+		price_info_object_lending: &PriceInfoObject,
+		price_info_object_collateral: &PriceInfoObject,
+		clock: &Clock,
+		ctx: &mut TxContext,
+	) {
+		version.assert_current_version();
+		let sender = ctx.sender();
+		let current_timestamp = clock.timestamp_ms();
+		let new_collateral_amount = new_collateral.value<NewCollateralCoinType>();
+
+		let lend_asset = configuration.borrow<String, Asset<LendCoinType>>(get_type<LendCoinType>());
+		let collateral_asset = configuration.borrow<String, Asset<NewCollateralCoinType>>(get_type<NewCollateralCoinType>());
+		assert!(get_type<CollateralCoinType>() != get_type<NewCollateralCoinType>(), ESameCollateralCoin);
+		assert!(collateral_asset.is_collateral_coin<NewCollateralCoinType>(), ECollateralCoinIsInvalid);
+		assert!(price_info_object_lending.is_valid<LendCoinType>(lend_asset), EPriceInfoObjectLendingIsInvalid);
+		assert!(price_info_object_collateral.is_valid<NewCollateralCoinType>(collateral_asset), EPriceInfoObjectCollateralIsInvalid);
+
+		let collateral_holder_key = collateral_holder::new_holder_key(offer_id.to_string(), lend_chain_borrower.to_string());
+		assert!(state.contain<CollateralHolderKey, CollateralHolder<CollateralCoinType>>(collateral_holder_key), ECollateralHolderNotFound);
+		let swap_holder_key = collateral_holder::new_swap_holder_key(offer_id.to_string(), lend_chain_borrower.to_string());
+		assert!(!state.contain<SwapCollateralHolderKey, CollateralHolder<NewCollateralCoinType>>(swap_holder_key), EAlreadySwappingCollateralForThisOffer);
+		let collateral_holder = state.borrow<CollateralHolderKey, CollateralHolder<CollateralCoinType>>(collateral_holder_key);
+
+		assert!(collateral_holder.is_created_status(), EInvalidCollateralHolderStatus);
+		assert!(sender == collateral_holder.borrower(), ESenderIsInvalid);
+
+		let foreign_chain_key = foreign_chain::new_foreign_chain_key(collateral_holder.lend_chain());
+		assert!(configuration.contain<ForeignChainKey, ForeignChain>(foreign_chain_key), EInvalidForeignTargetChain);
+		let foreign_chain = configuration.borrow<ForeignChainKey, ForeignChain>(foreign_chain_key);
+
+		assert!(is_valid_collateral_amount<LendCoinType, NewCollateralCoinType>(
+            configuration.min_health_ratio(),
+            collateral_holder.lend_amount(),
+            new_collateral_amount,
+            lend_asset,
+            collateral_asset,
+            price_info_object_lending,
+            price_info_object_collateral,
+            clock,
+        ), ECollateralNotValidToMinHealthRatio);
+
+		// Note: The old collateral stays locked until the lend chain confirms the swap with a release vaa
+		let swapping_holder = collateral_holder.new_swapping<CollateralCoinType, NewCollateralCoinType>(
+			new_collateral.into_balance<NewCollateralCoinType>(),
+			get_type<NewCollateralCoinType>(),
+			current_timestamp,
+			ctx,
+		);
+
+		let payload = gen_swap_collateral_message_payload(
+			(collateral_holder.lend_chain() as u64),
+			foreign_chain.chain_address(),
+			offer_id,
+			new_collateral_amount,
+			*get_type<NewCollateralCoinType>().bytes(),
+			lend_chain_borrower,
+		);
+
+		wormhole::send_message(
+            protected_ec,
+            wormhole_state,
+            payload,
+            message_fee,
+            clock,
+        );
+
+		state.add<SwapCollateralHolderKey, CollateralHolder<NewCollateralCoinType>>(swap_holder_key, swapping_holder);
+	}
+
 	fun gen_collateral_created_message_payload(
         target_chain: u64,
         target_address: vector<u8>,
@@ -415,4 +496,30 @@ module enso_lending::loan_crosschain {
         payload
     }
 
+	fun gen_swap_collateral_message_payload(
+        target_chain: u64,
+        target_address: vector<u8>,
+        offer_id: vector<u8>,
+        collateral_amount: u64,
+        collateral_coin_type: vector<u8>,
+        lend_chain_borrower: vector<u8>,
+    ): vector<u8> {
+        let mut payload: vector<u8> = vector[];
+        vector::append(&mut payload, target_chain.to_string());
+        vector::append(&mut payload, b",");
+        vector::append(&mut payload, target_address);
+        vector::append(&mut payload, b",");
+        vector::append(&mut payload, b"swap_collateral_cross_chain");
+        vector::append(&mut payload, b",");
+        vector::append(&mut payload, offer_id);
+        vector::append(&mut payload, b",");
+        vector::append(&mut payload, collateral_amount.to_string());
+        vector::append(&mut payload, b",");
+        vector::append(&mut payload, collateral_coin_type);
+        vector::append(&mut payload, b",");
+        vector::append(&mut payload, lend_chain_borrower);
+
+        payload
+    }
+
 }
\ No newline at end of file
@@ -9,10 +9,11 @@ module enso_lending::operator_loan_crosschain {
         state::State,
         configuration::Configuration,
         version::Version,
-		collateral_holder::{Self, CollateralHolderKey, CollateralHolder},
+		collateral_holder::{Self, CollateralHolderKey, SwapCollateralHolderKey, CollateralHolder},
 		wormhole::parse_and_verify_vaa,
 		foreign_chain::{Self, ForeignChainKey, ForeignChain},
 		vaa_utils,
+		utils::get_type,
     };
 	use fun std::string::utf8 as vector.to_string;
 	use fun sui::coin::from_balance as Balance.to_coin;
@@ -21,6 +22,8 @@ module enso_lending::operator_loan_crosschain {
 	const EInvalidCollateralHolderStatus: u64 = 2; 
 	const EMismatchDataWithVaa: u64 = 3;
 	const EInvalidForeignTargetChain: u64 = 4;
+	const ESwapCollateralHolderNotFound: u64 = 5;
+	const ESwapCollateralIsNotExpired: u64 = 6;
 
 	entry fun system_cancel_collateral<CollateralCoinType>(
 		_: &OperatorCap,
@@ -148,4 +151,87 @@ module enso_lending::operator_loan_crosschain {
 
 		collateral_holder.liquidate_collateral(liquidating_price, liquidating_at);
 	}
+
+	entry fun release_swapped_collateral<CollateralCoinType, NewCollateralCoinType>(
+		_: &OperatorCap,
+		version: &Version,
+		configuration: &Configuration,
+		state: &mut State,
+		wormhole_state: &WormholeState,
+		offer_id: vector<u8>,
+		lend_chain_borrower: vector<u8>,
+		vaa_buf: vector<u8>,
+		clock: &Clock,
+		ctx: &mut TxContext,
+	) {
+		version.assert_current_version();
+
+		let collateral_holder_key = collateral_holder::new_holder_key(offer_id.to_string(), lend_chain_borrower.to_string());
+		assert!(state.contain<CollateralHolderKey, CollateralHolder<CollateralCoinType>>(collateral_holder_key), ECollateralHolderNotFound);
+		let swap_holder_key = collateral_holder::new_swap_holder_key(offer_id.to_string(), lend_chain_borrower.to_string());
+		assert!(state.contain<SwapCollateralHolderKey, CollateralHolder<NewCollateralCoinType>>(swap_holder_key), ESwapCollateralHolderNotFound);
+
+		let collateral_holder = state.remove<CollateralHolderKey, CollateralHolder<CollateralCoinType>>(collateral_holder_key);
+		let mut swapping_holder = state.remove<SwapCollateralHolderKey, CollateralHolder<NewCollateralCoinType>>(swap_holder_key);
+		let borrower = collateral_holder.borrower();
+
+		assert!(collateral_holder.is_created_status(), EInvalidCollateralHolderStatus);
+		assert!(swapping_holder.is_swapping_status(), EInvalidCollateralHolderStatus);
+
+		let foreign_chain_key = foreign_chain::new_foreign_chain_key(collateral_holder.lend_chain());
+		assert!(configuration.contain<ForeignChainKey, ForeignChain>(foreign_chain_key), EInvalidForeignTargetChain);
+		let foreign_chain = configuration.borrow<ForeignChainKey, ForeignChain>(foreign_chain_key);
+
+		let payload_body = parse_and_verify_vaa(
+			wormhole_state,
+			vaa_buf,
+			collateral_holder.lend_chain(),
+			foreign_chain.emitter_address(),
+			b"release_swapped_collateral",
+			clock,
+		);
+
+		let (parsed_offer_id, parsed_lend_chain_borrower, parsed_collateral_token, parsed_collateral_amount) =
+			vaa_utils::parse_release_swapped_collateral_payload(payload_body);
+
+		assert!(offer_id.to_string() == parsed_offer_id && lend_chain_borrower.to_string() == parsed_lend_chain_borrower, EMismatchDataWithVaa);
+		assert!(
+			get_type<CollateralCoinType>() == parsed_collateral_token &&
+			collateral_holder.collateral_amount() == parsed_collateral_amount,
+			EMismatchDataWithVaa
+		);
+
+		let released_collateral_balance = collateral_holder.release_swapped(&mut swapping_holder);
+		transfer::public_transfer(released_collateral_balance.to_coin(ctx), borrower);
+
+		state.add<CollateralHolderKey, CollateralHolder<NewCollateralCoinType>>(collateral_holder_key, swapping_holder);
+	}
+
+	entry fun system_cancel_swap_collateral<NewCollateralCoinType>(
+		_: &OperatorCap,
+		version: &Version,
+		state: &mut State,
+		offer_id: vector<u8>,
+		lend_chain_borrower: vector<u8>,
+		clock: &Clock,
+		ctx: &mut TxContext,
+	) {
+		version.assert_current_version();
+		let current_timestamp = clock.timestamp_ms();
+
+		let swap_holder_key = collateral_holder::new_swap_holder_key(offer_id.to_string(), lend_chain_borrower.to_string());
+		assert!(state.contain<SwapCollateralHolderKey, CollateralHolder<NewCollateralCoinType>>(swap_holder_key), ESwapCollateralHolderNotFound);
+		let swapping_holder = state.remove<SwapCollateralHolderKey, CollateralHolder<NewCollateralCoinType>>(swap_holder_key);
+		let borrower = swapping_holder.borrower();
+
+		assert!(swapping_holder.is_swapping_status(), EInvalidCollateralHolderStatus);
+		// Note: The lend chain no longer accepts the swap vaa, so the new collateral can go back to the borrower
+		assert!(
+			swapping_holder.start_timestamp() + (collateral_holder::holding_swap_collateral_duration() * 1000) < current_timestamp,
+			ESwapCollateralIsNotExpired
+		);
+
+		let refund_collateral_balance = swapping_holder.delete();
+		transfer::public_transfer(refund_collateral_balance.to_coin(ctx), borrower);
+	}
 }
\ No newline at end of file
@@ -39,6 +39,26 @@ module enso_lending::vaa_utils {
 		)
 	}
 
+	public fun parse_release_swapped_collateral_payload(
+		payload_body: vector<String>
+	): (String, String, String, u64) {
+		assert!(vector::length<String>(&payload_body) == 4, EMissingDataOnVaa);
+		let offer_id = *vector::borrow<String>(&payload_body, 0);
+		let lend_chain_borrower = *vector::borrow<String>(&payload_body, 1);
+		let collateral_token = *vector::borrow<String>(&payload_body, 2);
+		let collateral_amount = *vector::borrow<String>(&payload_body, 3);
+
+		let mut collateral_amount_u64_opt = string_to_u64(collateral_amount);
+		assert!(option::is_some<u64>(&collateral_amount_u64_opt), EInvalidDataOnVaa);
+
+		(
+			offer_id,
+			lend_chain_borrower,
+			collateral_token,
+			option::extract<u64>(&mut collateral_amount_u64_opt),
+		)
+	}
+
 	public fun parse_refund_collateral_to_repaid_borrower_payload(
 		payload_body: vector<String>
 	): (String, String) {